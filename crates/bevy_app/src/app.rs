@@ -1,16 +1,24 @@
-use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
+use crate::{
+    AppRegistrations, First, Main, MainSchedulePlugin, Plugin, Plugins, StateKind, StateTransition,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event::Events,
     prelude::*,
     schedule::{
-        apply_state_transition, common_conditions::run_once as run_once_condition,
-        run_enter_schedule, InternedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
-        ScheduleBuildSettings, ScheduleLabel, StateTransitionEvent,
+        add_computed_state_transition_systems, add_state_transition_systems,
+        add_sub_state_transition_systems, FreelyMutableState, InternedScheduleLabel,
+        IntoSystemConfigs, IntoSystemSetConfigs, NodeId, ScheduleBuildError, ScheduleBuildSettings,
+        ScheduleLabel, StateTransitionEvent,
     },
 };
-use bevy_utils::{intern::Interned, thiserror::Error, tracing::debug, HashMap, HashSet};
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+use bevy_utils::{
+    intern::Interned, thiserror::Error, tracing::debug, Duration, HashMap, HashSet, Instant,
+};
 use std::{
     fmt::Debug,
+    hash::Hash,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
 };
 
@@ -82,6 +90,8 @@ pub struct App {
     plugin_name_added: HashSet<String>,
     /// A private counter to prevent incorrect calls to `App::run()` from `Plugin::build()`
     building_plugin_depth: usize,
+    /// The indices in [`AppRegistrations::plugins`] of the plugins being built, innermost last
+    building_plugins: Vec<usize>,
     plugins_state: PluginsState,
 }
 
@@ -97,6 +107,11 @@ impl Debug for App {
 /// This is useful for situations where data and data processing should be kept completely separate
 /// from the main application. The primary use of this feature in bevy is to enable pipelined rendering.
 ///
+/// Sub apps can also run independent simulations side by side, such as a server and a client in
+/// the same process. A sub app can run [in parallel](SubApp::with_parallel) with the other
+/// parallel sub apps, and at its own [update rate](SubApp::with_update_rate).
+/// Sub apps can exchange data with [`message_channel`](crate::message_channel)s.
+///
 /// # Example
 ///
 /// ```
@@ -143,6 +158,33 @@ pub struct SubApp {
     /// A function that allows access to both the main [`App`] [`World`] and the [`SubApp`]. This is
     /// useful for moving data between the sub app and the main app.
     extract: Box<dyn Fn(&mut World, &mut App) + Send>,
+    update_rate: UpdateRate,
+    parallel: bool,
+    /// Whether each schedule of this sub app accesses non-send data, as of the last time it was
+    /// initialized by [`SubApp::can_run_in_parallel`]
+    non_send_schedules: HashMap<InternedScheduleLabel, bool>,
+    /// The number of updates of the parent app to skip before running, for
+    /// [`UpdateRate::EveryNthUpdate`]
+    updates_to_skip: u32,
+    /// When this sub app should next run, for [`UpdateRate::Interval`]
+    next_run: Option<Instant>,
+}
+
+/// How often a [`SubApp`] runs, relative to the [updates](App::update) of its parent [`App`].
+///
+/// The [extract](SubApp::extract) function of a sub app is only called when the sub app runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpdateRate {
+    /// Runs on every update.
+    #[default]
+    EveryUpdate,
+    /// Runs on the first update out of every `n` updates.
+    EveryNthUpdate(u32),
+    /// Runs on updates where at least the given time has passed since it should last have run.
+    ///
+    /// This runs at most once per update: if updates are slower than the interval, the runs
+    /// that were missed are skipped.
+    Interval(Duration),
 }
 
 impl SubApp {
@@ -156,6 +198,112 @@ impl SubApp {
         Self {
             app,
             extract: Box::new(extract),
+            update_rate: UpdateRate::EveryUpdate,
+            parallel: false,
+            non_send_schedules: HashMap::default(),
+            updates_to_skip: 0,
+            next_run: None,
+        }
+    }
+
+    /// Sets how often this sub app runs, see [`UpdateRate`].
+    pub fn with_update_rate(mut self, update_rate: UpdateRate) -> Self {
+        self.update_rate = update_rate;
+        self
+    }
+
+    /// Sets whether this sub app runs in parallel with the other parallel sub apps.
+    ///
+    /// The [extract](SubApp::extract) functions of all sub apps are called one after the other on
+    /// the thread updating the parent [`App`]. Then parallel sub apps run on the
+    /// [`AsyncComputeTaskPool`], leaving the [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool) to
+    /// the systems of their schedules, while the other sub apps run one after the other on the
+    /// updating thread.
+    ///
+    /// As the thread a parallel sub app runs on isn't the one owning its [`World`], it can't
+    /// access non-send resources. If any of its schedules has a system or run condition accessing
+    /// non-send data, such as a [`NonSend`](bevy_ecs::system::NonSend) parameter, the sub app
+    /// falls back to running on the updating thread, like a sequential sub app. Exclusive systems
+    /// aren't checked, and panic if they access a non-send resource of a parallel sub app.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Returns whether this sub app can run away from the thread owning its [`World`], which
+    /// requires it to be [parallel](SubApp::with_parallel) and to have no system or run condition
+    /// accessing non-send data.
+    ///
+    /// This initializes the schedules of the sub app, as systems only know which data they access
+    /// once initialized. The result is cached per schedule, and only recomputed for schedules that
+    /// were added or [changed](Schedule::is_changed) since the last call.
+    fn can_run_in_parallel(&mut self) -> Result<bool, ScheduleBuildError> {
+        if !self.parallel {
+            return Ok(false);
+        }
+        let world = &mut self.app.world;
+        let Some(schedules) = world.get_resource::<Schedules>() else {
+            self.non_send_schedules.clear();
+            return Ok(true);
+        };
+        self.non_send_schedules
+            .retain(|label, _| schedules.contains(*label));
+        let changed: Vec<_> = schedules
+            .iter()
+            .map(|(_, schedule)| (schedule.label(), schedule.is_changed()))
+            .filter(|(label, changed)| *changed || !self.non_send_schedules.contains_key(label))
+            .map(|(label, _)| label)
+            .collect();
+        for label in changed {
+            if let Ok(non_send) = world.try_schedule_scope(label, |world, schedule| {
+                schedule
+                    .initialize(world)
+                    .map(|()| matches!(schedule.has_non_send_systems(), Ok(true)))
+            }) {
+                self.non_send_schedules.insert(label, non_send?);
+            }
+        }
+        Ok(!self.non_send_schedules.values().any(|non_send| *non_send))
+    }
+
+    /// Returns how often this sub app runs.
+    pub fn update_rate(&self) -> UpdateRate {
+        self.update_rate
+    }
+
+    /// Returns whether this sub app runs in parallel with the other parallel sub apps.
+    pub fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// Returns whether this sub app should run during the current update of its parent, according
+    /// to its [`UpdateRate`].
+    fn should_run(&mut self) -> bool {
+        match self.update_rate {
+            UpdateRate::EveryUpdate => true,
+            UpdateRate::EveryNthUpdate(n) => {
+                if self.updates_to_skip > 0 {
+                    self.updates_to_skip -= 1;
+                    return false;
+                }
+                self.updates_to_skip = n.saturating_sub(1);
+                true
+            }
+            UpdateRate::Interval(interval) => {
+                let now = Instant::now();
+                match self.next_run {
+                    Some(next_run) if now < next_run => false,
+                    next_run => {
+                        let next_run = next_run.unwrap_or(now) + interval;
+                        self.next_run = Some(if next_run <= now {
+                            now + interval
+                        } else {
+                            next_run
+                        });
+                        true
+                    }
+                }
+            }
         }
     }
 
@@ -230,6 +378,7 @@ impl App {
     pub fn empty() -> App {
         let mut world = World::new();
         world.init_resource::<Schedules>();
+        world.init_resource::<AppRegistrations>();
         Self {
             world,
             runner: Box::new(run_once),
@@ -238,13 +387,14 @@ impl App {
             plugin_name_added: Default::default(),
             main_schedule_label: Main.intern(),
             building_plugin_depth: 0,
+            building_plugins: Vec::new(),
             plugins_state: PluginsState::Adding,
         }
     }
 
     /// Advances the execution of the [`Schedule`] by one cycle.
     ///
-    /// This method also updates sub apps.
+    /// This method also updates sub apps, according to their [`UpdateRate`].
     /// See [`insert_sub_app`](Self::insert_sub_app) for more details.
     ///
     /// The schedule run by this method is determined by the [`main_schedule_label`](App) field.
@@ -261,11 +411,35 @@ impl App {
             let _bevy_main_update_span = info_span!("main app").entered();
             self.world.run_schedule(self.main_schedule_label);
         }
-        for (_label, sub_app) in &mut self.sub_apps {
+        let mut parallel = Vec::new();
+        let mut sequential = Vec::new();
+        for (label, sub_app) in &mut self.sub_apps {
+            if !sub_app.should_run() {
+                continue;
+            }
             #[cfg(feature = "trace")]
-            let _sub_app_span = info_span!("sub app", name = ?_label).entered();
+            let _sub_app_span = info_span!("sub app extract", name = ?label).entered();
             sub_app.extract(&mut self.world);
+            match sub_app.can_run_in_parallel() {
+                Ok(true) => parallel.push((*label, sub_app)),
+                Ok(false) => sequential.push((*label, sub_app)),
+                Err(e) => panic!("Error when initializing the schedules of sub app {label:?}: {e}"),
+            }
+        }
+        let run = |(_label, sub_app): (InternedAppLabel, &mut SubApp)| {
+            #[cfg(feature = "trace")]
+            let _sub_app_span = info_span!("sub app", name = ?_label).entered();
             sub_app.run();
+        };
+        if parallel.is_empty() {
+            sequential.into_iter().for_each(run);
+        } else {
+            AsyncComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+                for sub_app in parallel {
+                    scope.spawn(async move { run(sub_app) });
+                }
+                sequential.into_iter().for_each(run);
+            });
         }
 
         self.world.clear_trackers();
@@ -352,72 +526,98 @@ impl App {
     ///
     /// If the [`State`] already exists, nothing happens.
     ///
-    /// Adds [`State<S>`] and [`NextState<S>`] resources, [`OnEnter`] and [`OnExit`] schedules
-    /// for each state variant (if they don't already exist), an instance of [`apply_state_transition::<S>`] in
-    /// [`StateTransition`] so that transitions happen before [`Update`](crate::Update) and
-    /// a instance of [`run_enter_schedule::<S>`] in [`StateTransition`] with a
-    /// [`run_once`](`run_once_condition`) condition to run the on enter schedule of the
-    /// initial state.
+    /// Adds [`State<S>`] and [`NextState<S>`] resources, and the systems applying transitions of `S`
+    /// and running its [`OnEnter`], [`OnExit`] and [`OnTransition`] schedules to
+    /// [`StateTransition`], so that transitions happen before [`Update`](crate::Update).
+    /// The [`OnEnter`] schedule of the initial state runs the first time [`StateTransition`] runs.
     ///
     /// If you would like to control how other systems run based on the current state,
     /// you can emulate this behavior using the [`in_state`] [`Condition`].
-    ///
-    /// Note that you can also apply state transitions at other points in the schedule
-    /// by adding the [`apply_state_transition`] system manually.
-    pub fn init_state<S: States + FromWorld>(&mut self) -> &mut Self {
+    pub fn init_state<S: FreelyMutableState + FromWorld>(&mut self) -> &mut Self {
         if !self.world.contains_resource::<State<S>>() {
-            self.init_resource::<State<S>>()
-                .init_resource::<NextState<S>>()
-                .add_event::<StateTransitionEvent<S>>()
-                .add_systems(
-                    StateTransition,
-                    (
-                        run_enter_schedule::<S>.run_if(run_once_condition()),
-                        apply_state_transition::<S>,
-                    )
-                        .chain(),
-                );
+            self.init_resource::<State<S>>();
+            let initial = self.world.resource::<State<S>>().get().clone();
+            self.register_state(initial);
+            self.record_state::<S>(StateKind::FreelyMutable);
         }
-
-        // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
-        // (i.e. when the first system is added to them), and World::try_run_schedule is used to fail
-        // gracefully if they aren't present.
-
         self
     }
 
     /// Inserts a specific [`State`] to the current [`App`] and
     /// overrides any [`State`] previously added of the same type.
     ///
-    /// Adds [`State<S>`] and [`NextState<S>`] resources, [`OnEnter`] and [`OnExit`] schedules
-    /// for each state variant (if they don't already exist), an instance of [`apply_state_transition::<S>`] in
-    /// [`StateTransition`] so that transitions happen before [`Update`](crate::Update) and
-    /// a instance of [`run_enter_schedule::<S>`] in [`StateTransition`] with a
-    /// [`run_once`](`run_once_condition`) condition to run the on enter schedule of the
-    /// initial state.
+    /// Adds [`State<S>`] and [`NextState<S>`] resources, and the systems applying transitions of `S`
+    /// and running its [`OnEnter`], [`OnExit`] and [`OnTransition`] schedules to
+    /// [`StateTransition`], so that transitions happen before [`Update`](crate::Update).
+    /// The [`OnEnter`] schedule of the initial state runs the first time [`StateTransition`] runs.
     ///
     /// If you would like to control how other systems run based on the current state,
     /// you can emulate this behavior using the [`in_state`] [`Condition`].
-    ///
-    /// Note that you can also apply state transitions at other points in the schedule
-    /// by adding the [`apply_state_transition`] system manually.
-    pub fn insert_state<S: States>(&mut self, state: S) -> &mut Self {
-        self.insert_resource(State::new(state))
-            .init_resource::<NextState<S>>()
+    pub fn insert_state<S: FreelyMutableState>(&mut self, state: S) -> &mut Self {
+        let registered = self.world.contains_resource::<NextState<S>>();
+        self.insert_resource(State::new(state.clone()));
+        if !registered {
+            self.register_state(state);
+            self.record_state::<S>(StateKind::FreelyMutable);
+        }
+        self
+    }
+
+    fn register_state<S: FreelyMutableState>(&mut self, initial: S) {
+        self.init_resource::<NextState<S>>()
             .add_event::<StateTransitionEvent<S>>()
-            .add_systems(
-                StateTransition,
-                (
-                    run_enter_schedule::<S>.run_if(run_once_condition()),
-                    apply_state_transition::<S>,
-                )
-                    .chain(),
-            );
+            .edit_schedule(StateTransition, add_state_transition_systems::<S>);
+        self.world.send_event(StateTransitionEvent {
+            before: None,
+            after: Some(initial),
+        });
 
         // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
         // (i.e. when the first system is added to them), and World::try_run_schedule is used to fail
         // gracefully if they aren't present.
+    }
+
+    fn record_state<S>(&mut self, kind: StateKind) {
+        let plugin = self.building_plugins.last().copied();
+        self.registrations().add_state::<S>(kind, plugin);
+    }
+
+    /// Adds a [`ComputedStates`] to the current [`App`].
+    ///
+    /// If the state was already added, nothing happens.
+    ///
+    /// The [`State<S>`] resource is updated in [`StateTransition`] whenever the source states of `S`
+    /// change, after their own transitions, and the [`OnExit`] schedules of `S` run before those
+    /// of its sources while its [`OnEnter`] schedules run after them.
+    /// The source states should be added to the app as well.
+    pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
+        if !self
+            .world
+            .contains_resource::<Events<StateTransitionEvent<S>>>()
+        {
+            self.add_event::<StateTransitionEvent<S>>()
+                .edit_schedule(StateTransition, add_computed_state_transition_systems::<S>);
+            self.record_state::<S>(StateKind::Computed);
+        }
+        self
+    }
 
+    /// Adds a [`SubStates`] to the current [`App`].
+    ///
+    /// If the state was already added, nothing happens.
+    ///
+    /// The [`State<S>`] resource only exists while [`SubStates::should_exist`] returns `Some`
+    /// for the current source states, and can be changed with [`NextState<S>`] in the meantime.
+    /// Transitions are ordered after those of the source states, like for
+    /// [`add_computed_state`](Self::add_computed_state). The source states should be added to
+    /// the app as well.
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<NextState<S>>() {
+            self.init_resource::<NextState<S>>()
+                .add_event::<StateTransitionEvent<S>>()
+                .edit_schedule(StateTransition, add_sub_state_transition_systems::<S>);
+            self.record_state::<S>(StateKind::Sub);
+        }
         self
     }
 
@@ -443,18 +643,9 @@ impl App {
         schedule: impl ScheduleLabel,
         systems: impl IntoSystemConfigs<M>,
     ) -> &mut Self {
-        let schedule = schedule.intern();
-        let mut schedules = self.world.resource_mut::<Schedules>();
-
-        if let Some(schedule) = schedules.get_mut(schedule) {
+        self.edit_schedule(schedule, |schedule| {
             schedule.add_systems(systems);
-        } else {
-            let mut new_schedule = Schedule::new(schedule);
-            new_schedule.add_systems(systems);
-            schedules.insert(new_schedule);
-        }
-
-        self
+        })
     }
 
     /// Configures a collection of system sets in the provided schedule, adding any sets that do not exist.
@@ -465,14 +656,12 @@ impl App {
         sets: impl IntoSystemSetConfigs,
     ) -> &mut Self {
         let schedule = schedule.intern();
-        let mut schedules = self.world.resource_mut::<Schedules>();
-        if let Some(schedule) = schedules.get_mut(schedule) {
-            schedule.configure_sets(sets);
-        } else {
-            let mut new_schedule = Schedule::new(schedule);
-            new_schedule.configure_sets(sets);
-            schedules.insert(new_schedule);
-        }
+        self.init_schedule(schedule);
+        self.world
+            .resource_mut::<Schedules>()
+            .get_mut(schedule)
+            .unwrap()
+            .configure_sets(sets);
         self
     }
 
@@ -507,6 +696,8 @@ impl App {
                 bevy_ecs::event::event_update_system::<T>
                     .run_if(bevy_ecs::event::event_update_condition::<T>),
             );
+            let plugin = self.building_plugins.last().copied();
+            self.registrations().add_event::<T>(plugin);
         }
         self
     }
@@ -533,6 +724,9 @@ impl App {
     ///    .insert_resource(MyCounter { counter: 0 });
     /// ```
     pub fn insert_resource<R: Resource>(&mut self, resource: R) -> &mut Self {
+        if !self.world.contains_resource::<R>() {
+            self.record_resource::<R>(false);
+        }
         self.world.insert_resource(resource);
         self
     }
@@ -556,6 +750,9 @@ impl App {
     ///     .insert_non_send_resource(MyCounter { counter: 0 });
     /// ```
     pub fn insert_non_send_resource<R: 'static>(&mut self, resource: R) -> &mut Self {
+        if !self.world.contains_non_send::<R>() {
+            self.record_resource::<R>(true);
+        }
         self.world.insert_non_send_resource(resource);
         self
     }
@@ -591,6 +788,9 @@ impl App {
     ///     .init_resource::<MyCounter>();
     /// ```
     pub fn init_resource<R: Resource + FromWorld>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<R>() {
+            self.record_resource::<R>(false);
+        }
         self.world.init_resource::<R>();
         self
     }
@@ -601,10 +801,52 @@ impl App {
     /// If the [`Default`] trait is implemented, the [`FromWorld`] trait will use
     /// the [`Default::default`] method to initialize the [`Resource`].
     pub fn init_non_send_resource<R: 'static + FromWorld>(&mut self) -> &mut Self {
+        if !self.world.contains_non_send::<R>() {
+            self.record_resource::<R>(true);
+        }
         self.world.init_non_send_resource::<R>();
         self
     }
 
+    fn record_resource<R: 'static>(&mut self, non_send: bool) {
+        let plugin = self.building_plugins.last().copied();
+        self.registrations().add_resource::<R>(non_send, plugin);
+    }
+
+    /// Returns the [`AppRegistrations`] of this app, inserting it if it was removed.
+    fn registrations(&mut self) -> Mut<'_, AppRegistrations> {
+        self.world
+            .get_resource_or_insert_with(AppRegistrations::default)
+    }
+
+    /// Starts indexing entities by the value of their `T` component, so that they can be looked up
+    /// with the [`Indexed`](bevy_ecs::index::Indexed) system parameter.
+    ///
+    /// See [`World::init_index`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::index::Indexed;
+    /// #
+    /// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+    /// struct Team(u32);
+    ///
+    /// fn count_team_zero(teams: Indexed<Team>) {
+    ///     println!("{} players in team 0", teams.get(&Team(0)).count());
+    /// }
+    ///
+    /// App::new()
+    ///     .init_index::<Team>()
+    ///     .add_systems(Update, count_team_zero);
+    /// ```
+    pub fn init_index<T: Component + Eq + Hash + Clone>(&mut self) -> &mut Self {
+        self.world.init_index::<T>();
+        self
+    }
+
     /// Sets the function that will be called when the app is run.
     ///
     /// The runner function `run_fn` is called only once by [`App::run`]. If the
@@ -651,8 +893,15 @@ impl App {
         let plugin_position_in_registry = self.plugin_registry.len();
         self.plugin_registry.push(Box::new(PlaceholderPlugin));
 
+        let parent = self.building_plugins.last().copied();
+        let index = self
+            .registrations()
+            .add_plugin(plugin.name().to_string(), parent);
+
         self.building_plugin_depth += 1;
+        self.building_plugins.push(index);
         let result = catch_unwind(AssertUnwindSafe(|| plugin.build(self)));
+        self.building_plugins.pop();
         self.building_plugin_depth -= 1;
         if let Err(payload) = result {
             resume_unwind(payload);
@@ -859,9 +1108,15 @@ impl App {
     /// This method will overwrite any existing schedule at that label.
     /// To avoid this behavior, use the `init_schedule` method instead.
     pub fn add_schedule(&mut self, schedule: Schedule) -> &mut Self {
+        let label = schedule.label();
         let mut schedules = self.world.resource_mut::<Schedules>();
+        let created = !schedules.contains(label);
         schedules.insert(schedule);
 
+        if created {
+            let plugin = self.building_plugins.last().copied();
+            self.registrations().add_schedule(label, plugin);
+        }
         self
     }
 
@@ -873,6 +1128,8 @@ impl App {
         let mut schedules = self.world.resource_mut::<Schedules>();
         if !schedules.contains(label) {
             schedules.insert(Schedule::new(label));
+            let plugin = self.building_plugins.last().copied();
+            self.registrations().add_schedule(label, plugin);
         }
         self
     }
@@ -900,16 +1157,26 @@ impl App {
         f: impl FnOnce(&mut Schedule),
     ) -> &mut Self {
         let label = label.intern();
-        let mut schedules = self.world.resource_mut::<Schedules>();
-
-        if schedules.get(label).is_none() {
-            schedules.insert(Schedule::new(label));
-        }
+        self.init_schedule(label);
 
+        let mut schedules = self.world.resource_mut::<Schedules>();
         let schedule = schedules.get_mut(label).unwrap();
+        // Systems that haven't been built yet are still in the graph, so the new ones are
+        // those that weren't there before
+        let pending: HashSet<NodeId> = schedule.graph().systems().map(|(id, ..)| id).collect();
         // Call the function f, passing in the schedule retrieved
         f(schedule);
-
+        let added: Vec<_> = schedule
+            .graph()
+            .systems()
+            .filter(|(id, ..)| !pending.contains(id))
+            .map(|(id, system, _)| (id, system.name().into_owned()))
+            .collect();
+
+        if !added.is_empty() {
+            let plugin = self.building_plugins.last().copied();
+            self.registrations().add_systems(label, added, plugin);
+        }
         self
     }
 
@@ -1063,11 +1330,14 @@ mod tests {
     use std::marker::PhantomData;
 
     use bevy_ecs::{
-        schedule::{OnEnter, States},
-        system::Commands,
+        schedule::{
+            ComputedStates, IntoSystemConfigs, NextState, OnEnter, State, States, SubStates,
+        },
+        system::{Commands, Local, NonSend, Res, ResMut, Resource},
     };
+    use bevy_utils::Duration;
 
-    use crate::{App, Plugin};
+    use crate::{self as bevy_app, App, AppLabel, Plugin, SubApp, UpdateRate};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -1159,6 +1429,82 @@ mod tests {
         assert_eq!(app.world.entities().len(), 2);
     }
 
+    #[test]
+    fn initial_state_is_entered_on_first_update() {
+        let mut app = App::new();
+        app.init_state::<AppState>()
+            .add_systems(OnEnter(AppState::MainMenu), (foo, bar));
+
+        app.update();
+        assert_eq!(app.world.entities().len(), 2);
+        app.update();
+        assert_eq!(app.world.entities().len(), 2);
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    enum GameState {
+        #[default]
+        Menu,
+        Playing,
+    }
+
+    #[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+    struct Playing;
+
+    impl ComputedStates for Playing {
+        type SourceStates = GameState;
+
+        fn compute(state: GameState) -> Option<Self> {
+            (state == GameState::Playing).then_some(Playing)
+        }
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Hash, Clone, Copy)]
+    enum Turn {
+        Player,
+        Enemy,
+    }
+
+    impl SubStates for Turn {
+        type SourceStates = Playing;
+
+        fn should_exist(_: Playing) -> Option<Self> {
+            Some(Turn::Player)
+        }
+    }
+
+    #[test]
+    fn computed_and_sub_states() {
+        let mut app = App::new();
+        app.init_state::<GameState>()
+            .add_computed_state::<Playing>()
+            .add_sub_state::<Turn>()
+            .add_systems(OnEnter(Turn::Enemy), foo);
+
+        app.update();
+        assert!(app.world.get_resource::<State<Playing>>().is_none());
+        assert!(app.world.get_resource::<State<Turn>>().is_none());
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+        assert!(app.world.get_resource::<State<Playing>>().is_some());
+        assert_eq!(*app.world.resource::<State<Turn>>().get(), Turn::Player);
+
+        app.world.resource_mut::<NextState<Turn>>().set(Turn::Enemy);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Turn>>().get(), Turn::Enemy);
+        assert_eq!(app.world.entities().len(), 1);
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.update();
+        assert!(app.world.get_resource::<State<Playing>>().is_none());
+        assert!(app.world.get_resource::<State<Turn>>().is_none());
+    }
+
     #[test]
     fn test_derive_app_label() {
         use super::AppLabel;
@@ -1293,4 +1639,157 @@ mod tests {
             .add_systems(PreUpdate, my_system)
             .run();
     }
+
+    #[derive(Resource, Default)]
+    struct Runs {
+        extracted: u32,
+        updated: u32,
+    }
+
+    fn counting_sub_app(update_rate: UpdateRate) -> SubApp {
+        let mut app = App::new();
+        app.init_resource::<Runs>()
+            .add_systems(crate::Update, |mut runs: ResMut<Runs>| runs.updated += 1);
+        SubApp::new(app, |_, sub_app| {
+            sub_app.world.resource_mut::<Runs>().extracted += 1;
+        })
+        .with_update_rate(update_rate)
+    }
+
+    #[test]
+    fn sub_app_update_rates() {
+        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+        enum Rate {
+            Every,
+            Third,
+            Hourly,
+        }
+
+        let mut app = App::new();
+        app.insert_sub_app(Rate::Every, counting_sub_app(UpdateRate::EveryUpdate));
+        app.insert_sub_app(Rate::Third, counting_sub_app(UpdateRate::EveryNthUpdate(3)));
+        app.insert_sub_app(
+            Rate::Hourly,
+            counting_sub_app(UpdateRate::Interval(Duration::from_secs(3600))),
+        );
+        for _ in 0..7 {
+            app.update();
+        }
+
+        for (label, expected) in [(Rate::Every, 7), (Rate::Third, 3), (Rate::Hourly, 1)] {
+            let runs = app.sub_app(label).world.resource::<Runs>();
+            assert_eq!((runs.extracted, runs.updated), (expected, expected));
+        }
+    }
+
+    #[test]
+    fn parallel_sub_apps_exchange_messages() {
+        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+        enum Side {
+            Server,
+            Client,
+        }
+
+        struct Tick(u32);
+
+        #[derive(Resource, Default)]
+        struct Ticks(Vec<u32>);
+
+        let (sender, receiver) = crate::message_channel::<Tick>();
+        let mut server = App::new();
+        server.insert_resource(sender).add_systems(
+            crate::Update,
+            |sender: Res<crate::MessageSender<Tick>>, mut tick: Local<u32>| {
+                *tick += 1;
+                assert!(sender.send(Tick(*tick)).is_ok());
+            },
+        );
+        let mut client = App::new();
+        client
+            .insert_resource(receiver)
+            .init_resource::<Ticks>()
+            .add_systems(
+                crate::Update,
+                |receiver: Res<crate::MessageReceiver<Tick>>, mut ticks: ResMut<Ticks>| {
+                    ticks.0.extend(receiver.try_iter().map(|tick| tick.0));
+                },
+            );
+
+        let mut app = App::new();
+        let server = SubApp::new(server, |_, _| {})
+            .with_parallel(true)
+            .with_update_rate(UpdateRate::EveryNthUpdate(2));
+        assert!(server.is_parallel());
+        app.insert_sub_app(Side::Server, server);
+        app.insert_sub_app(
+            Side::Client,
+            SubApp::new(client, |_, _| {}).with_parallel(true),
+        );
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let client = app.sub_app(Side::Client);
+        let mut ticks = client.world.resource::<Ticks>().0.clone();
+        let receiver = client.world.resource::<crate::MessageReceiver<Tick>>();
+        ticks.extend(receiver.try_iter().map(|tick| tick.0));
+        assert_eq!(ticks, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn parallel_sub_apps_with_non_send_systems_run_sequentially() {
+        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+        enum Kind {
+            NonSend,
+            Send,
+        }
+
+        struct NotSend;
+
+        let mut non_send = App::new();
+        non_send
+            .insert_non_send_resource(NotSend)
+            .init_resource::<Runs>()
+            .add_systems(
+                crate::Update,
+                |_: NonSend<NotSend>, mut runs: ResMut<Runs>| runs.updated += 1,
+            );
+
+        let mut non_send = SubApp::new(non_send, |_, _| {}).with_parallel(true);
+        let mut send = counting_sub_app(UpdateRate::EveryUpdate).with_parallel(true);
+        assert!(!non_send.can_run_in_parallel().unwrap());
+        assert!(send.can_run_in_parallel().unwrap());
+
+        let mut app = App::new();
+        app.insert_sub_app(Kind::NonSend, non_send);
+        app.insert_sub_app(Kind::Send, send);
+        for _ in 0..3 {
+            app.update();
+        }
+
+        for kind in [Kind::NonSend, Kind::Send] {
+            assert_eq!(app.sub_app(kind).world.resource::<Runs>().updated, 3);
+        }
+    }
+
+    #[test]
+    fn parallel_sub_apps_recheck_changed_schedules() {
+        struct NotSend;
+
+        let mut sub_app = counting_sub_app(UpdateRate::EveryUpdate).with_parallel(true);
+        assert!(sub_app.can_run_in_parallel().unwrap());
+
+        sub_app
+            .app
+            .insert_non_send_resource(NotSend)
+            .add_systems(crate::Update, |_: NonSend<NotSend>| {});
+        assert!(!sub_app.can_run_in_parallel().unwrap());
+
+        fn a() {}
+        fn b() {}
+        sub_app
+            .app
+            .add_systems(crate::Update, (a.after(b), b.after(a)));
+        assert!(sub_app.can_run_in_parallel().is_err());
+    }
 }
@@ -4,9 +4,12 @@
 
 mod app;
 mod main_schedule;
+mod messages;
 mod plugin;
 mod plugin_group;
+mod registrations;
 mod schedule_runner;
+mod test_app;
 
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
@@ -14,9 +17,12 @@ pub mod ci_testing;
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
 pub use main_schedule::*;
+pub use messages::*;
 pub use plugin::*;
 pub use plugin_group::*;
+pub use registrations::*;
 pub use schedule_runner::*;
+pub use test_app::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
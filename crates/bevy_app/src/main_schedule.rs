@@ -1,6 +1,8 @@
 use crate::{App, Plugin};
 use bevy_ecs::{
-    schedule::{ExecutorKind, InternedScheduleLabel, Schedule, ScheduleLabel},
+    schedule::{
+        ExecutorKind, InternedScheduleLabel, IntoSystemConfigs, Schedule, ScheduleLabel, Stepping,
+    },
     system::{Local, Resource},
     world::{Mut, World},
 };
@@ -254,7 +256,7 @@ impl Plugin for MainSchedulePlugin {
             .add_schedule(fixed_main_loop_schedule)
             .init_resource::<MainScheduleOrder>()
             .init_resource::<FixedMainScheduleOrder>()
-            .add_systems(Main, Main::run_main)
+            .add_systems(Main, (Stepping::begin_frame, Main::run_main).chain())
             .add_systems(FixedMain, FixedMain::run_fixed_main);
     }
 }
@@ -0,0 +1,101 @@
+use async_channel::{Receiver, Sender};
+use bevy_ecs::system::Resource;
+
+/// Creates a channel passing messages of type `T` between apps, for example between
+/// [`SubApp`](crate::SubApp)s running in parallel.
+///
+/// Both ends are resources: the [`MessageSender`] is inserted in the world of the app sending
+/// messages and the [`MessageReceiver`] in the world of the app receiving them. Messages are
+/// buffered until they are received.
+///
+/// ```
+/// # use bevy_app::{prelude::*, message_channel, AppLabel, MessageReceiver, MessageSender, SubApp};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+/// struct Server;
+///
+/// #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+/// struct Client;
+///
+/// struct Ping(u32);
+///
+/// #[derive(Resource, Default)]
+/// struct Received(u32);
+///
+/// let (sender, receiver) = message_channel::<Ping>();
+///
+/// let mut server = App::new();
+/// server
+///     .insert_resource(sender)
+///     .add_systems(Update, |sender: Res<MessageSender<Ping>>| {
+///         sender.send(Ping(1)).ok();
+///     });
+///
+/// let mut client = App::new();
+/// client
+///     .insert_resource(receiver)
+///     .init_resource::<Received>()
+///     .add_systems(Update, |receiver: Res<MessageReceiver<Ping>>, mut received: ResMut<Received>| {
+///         received.0 += receiver.try_iter().map(|ping| ping.0).sum::<u32>();
+///     });
+///
+/// let mut app = App::new();
+/// app.insert_sub_app(Server, SubApp::new(server, |_, _| {}).with_parallel(true));
+/// app.insert_sub_app(Client, SubApp::new(client, |_, _| {}).with_parallel(true));
+/// for _ in 0..3 {
+///     app.update();
+/// }
+///
+/// // the client receives each ping during the same update or the next one
+/// let received = app.sub_app(Client).world.resource::<Received>().0;
+/// assert!((2..=3).contains(&received));
+/// ```
+pub fn message_channel<T: Send + 'static>() -> (MessageSender<T>, MessageReceiver<T>) {
+    let (sender, receiver) = async_channel::unbounded();
+    (MessageSender(sender), MessageReceiver(receiver))
+}
+
+/// Resource sending messages of type `T` through a [`message_channel`].
+///
+/// The sender can be cloned to send messages from several apps.
+#[derive(Resource)]
+pub struct MessageSender<T: Send + 'static>(Sender<T>);
+
+impl<T: Send + 'static> MessageSender<T> {
+    /// Sends `message`, or returns it if the [`MessageReceiver`] was dropped.
+    pub fn send(&self, message: T) -> Result<(), T> {
+        self.0.try_send(message).map_err(|error| error.into_inner())
+    }
+}
+
+impl<T: Send + 'static> Clone for MessageSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Resource receiving the messages of type `T` sent through a [`message_channel`].
+#[derive(Resource)]
+pub struct MessageReceiver<T: Send + 'static>(Receiver<T>);
+
+impl<T: Send + 'static> MessageReceiver<T> {
+    /// Receives the oldest message that hasn't been received yet, if any.
+    pub fn try_recv(&self) -> Option<T> {
+        self.0.try_recv().ok()
+    }
+
+    /// Returns an iterator receiving the messages that have been sent so far, oldest first.
+    pub fn try_iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(|| self.try_recv())
+    }
+
+    /// Returns the number of messages waiting to be received.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no messages waiting to be received.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
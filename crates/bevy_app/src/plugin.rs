@@ -66,6 +66,47 @@ impl_downcast!(Plugin);
 /// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
 
+/// A type representing the function that returns the [`DYNAMIC_PLUGIN_ABI`] a dynamic plugin was
+/// compiled with.
+///
+/// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
+pub type PluginAbi = unsafe extern "C" fn() -> u64;
+
+/// A fingerprint of the Bevy version and of the layout of the types shared between an [`App`] and
+/// the dynamic plugins it loads.
+///
+/// Being a constant, it is baked into a dynamic plugin when the plugin is compiled. Comparing the
+/// value exported by a plugin with the one of the loading program detects plugins built against a
+/// different version of Bevy before any of their code runs. It can't detect every incompatibility,
+/// such as a different compiler version that happens to keep these layouts unchanged.
+pub const DYNAMIC_PLUGIN_ABI: u64 = {
+    const fn hash(mut hash: u64, bytes: &[u8]) -> u64 {
+        // FNV-1a
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+            i += 1;
+        }
+        hash
+    }
+
+    let layouts = [
+        std::mem::size_of::<App>(),
+        std::mem::align_of::<App>(),
+        std::mem::size_of::<bevy_ecs::world::World>(),
+        std::mem::size_of::<bevy_ecs::schedule::Schedule>(),
+        std::mem::size_of::<Box<dyn Plugin>>(),
+    ];
+    let mut abi = hash(0xcbf2_9ce4_8422_2325, env!("CARGO_PKG_VERSION").as_bytes());
+    let mut i = 0;
+    while i < layouts.len() {
+        abi = hash(abi, &(layouts[i] as u64).to_le_bytes());
+        i += 1;
+    }
+    abi
+};
+
 /// Types that represent a set of [`Plugin`]s.
 ///
 /// This is implemented for all types which implement [`Plugin`],
@@ -0,0 +1,432 @@
+use bevy_ecs::{
+    schedule::{NodeId, ScheduleLabel},
+    system::Resource,
+};
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Resource recording what each [`Plugin`](crate::Plugin) added to an [`App`](crate::App), so that
+/// tools like editors and inspectors can show where systems, resources, events and states come
+/// from.
+///
+/// Registrations are recorded by the methods of [`App`](crate::App), such as
+/// [`App::add_systems`](crate::App::add_systems) or [`App::add_event`](crate::App::add_event),
+/// and attributed to the plugin being built at the time, if any. Plugins are listed in the order
+/// they were added, and each registration refers to its plugin by index in
+/// [`AppRegistrations::plugins`]. Additions made directly through the [`World`] or a [`Schedule`]
+/// are not recorded, and neither are those made to the app of a [`SubApp`], which has its own
+/// [`AppRegistrations`].
+///
+/// With the `serialize` feature, this resource can be serialized to be sent to external tools.
+///
+/// ```
+/// # use bevy_app::{prelude::*, AppRegistrations};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Event)]
+/// struct Jump;
+///
+/// fn jump() {}
+///
+/// struct JumpPlugin;
+///
+/// impl Plugin for JumpPlugin {
+///     fn build(&self, app: &mut App) {
+///         app.add_event::<Jump>().add_systems(Update, jump);
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(JumpPlugin);
+///
+/// let registrations = app.world.resource::<AppRegistrations>();
+/// let origin = registrations.system_origin(Update, "jump").unwrap();
+/// assert!(origin.name.ends_with("JumpPlugin"));
+/// assert!(registrations
+///     .events()
+///     .iter()
+///     .any(|event| event.type_name.ends_with("Jump")));
+/// ```
+///
+/// [`World`]: bevy_ecs::world::World
+/// [`Schedule`]: bevy_ecs::schedule::Schedule
+/// [`SubApp`]: crate::SubApp
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AppRegistrations {
+    plugins: Vec<RegisteredPlugin>,
+    schedules: Vec<RegisteredSchedule>,
+    systems: Vec<RegisteredSystem>,
+    resources: Vec<RegisteredResource>,
+    events: Vec<RegisteredEvent>,
+    states: Vec<RegisteredState>,
+}
+
+/// A [`Plugin`](crate::Plugin) recorded in [`AppRegistrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegisteredPlugin {
+    /// The [name](crate::Plugin::name) of the plugin.
+    pub name: String,
+    /// The index of the plugin that added this one while being built, if any.
+    pub parent: Option<usize>,
+}
+
+/// A [`Schedule`](bevy_ecs::schedule::Schedule) created by an [`App`](crate::App) method, recorded
+/// in [`AppRegistrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegisteredSchedule {
+    /// The label of the schedule.
+    pub label: String,
+    /// The index of the plugin that created the schedule, if any.
+    pub plugin: Option<usize>,
+}
+
+/// A system recorded in [`AppRegistrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegisteredSystem {
+    /// The label of the schedule the system was added to.
+    pub schedule: String,
+    /// The id of the system in its schedule.
+    pub id: NodeId,
+    /// The name of the system.
+    pub name: String,
+    /// The index of the plugin that added the system, if any.
+    pub plugin: Option<usize>,
+}
+
+/// A resource recorded in [`AppRegistrations`], when it is first inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegisteredResource {
+    /// The type name of the resource.
+    pub type_name: String,
+    /// Whether this is a non-send resource.
+    pub non_send: bool,
+    /// The index of the plugin that first inserted the resource, if any.
+    pub plugin: Option<usize>,
+}
+
+/// An event type added with [`App::add_event`](crate::App::add_event), recorded in
+/// [`AppRegistrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegisteredEvent {
+    /// The type name of the event.
+    pub type_name: String,
+    /// The index of the plugin that added the event, if any.
+    pub plugin: Option<usize>,
+}
+
+/// A state type recorded in [`AppRegistrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct RegisteredState {
+    /// The type name of the state.
+    pub type_name: String,
+    /// How the state was added.
+    pub kind: StateKind,
+    /// The index of the plugin that added the state, if any.
+    pub plugin: Option<usize>,
+}
+
+/// The kind of a [`RegisteredState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum StateKind {
+    /// A state added with [`App::init_state`](crate::App::init_state) or
+    /// [`App::insert_state`](crate::App::insert_state).
+    FreelyMutable,
+    /// A state added with [`App::add_computed_state`](crate::App::add_computed_state).
+    Computed,
+    /// A state added with [`App::add_sub_state`](crate::App::add_sub_state).
+    Sub,
+}
+
+impl AppRegistrations {
+    /// Returns the plugins, in the order they were added.
+    pub fn plugins(&self) -> &[RegisteredPlugin] {
+        &self.plugins
+    }
+
+    /// Returns the plugin at `index` in [`AppRegistrations::plugins`].
+    pub fn plugin(&self, index: usize) -> Option<&RegisteredPlugin> {
+        self.plugins.get(index)
+    }
+
+    /// Returns the schedules created through the [`App`](crate::App).
+    pub fn schedules(&self) -> &[RegisteredSchedule] {
+        &self.schedules
+    }
+
+    /// Returns the systems, in the order they were added.
+    pub fn systems(&self) -> &[RegisteredSystem] {
+        &self.systems
+    }
+
+    /// Returns the resources, in the order they were first inserted.
+    pub fn resources(&self) -> &[RegisteredResource] {
+        &self.resources
+    }
+
+    /// Returns the event types, in the order they were added.
+    pub fn events(&self) -> &[RegisteredEvent] {
+        &self.events
+    }
+
+    /// Returns the state types, in the order they were added.
+    pub fn states(&self) -> &[RegisteredState] {
+        &self.states
+    }
+
+    /// Returns the first system named `name` added to the schedule labelled `schedule`.
+    ///
+    /// `name` may be either the full name of the system or its short name, without module paths.
+    pub fn find_system(
+        &self,
+        schedule: impl ScheduleLabel,
+        name: &str,
+    ) -> Option<&RegisteredSystem> {
+        let schedule = format!("{:?}", schedule.intern());
+        self.systems.iter().find(|system| {
+            system.schedule == schedule
+                && (system.name == name || bevy_utils::get_short_name(&system.name) == name)
+        })
+    }
+
+    /// Returns the plugin that added the first system named `name` to the schedule labelled
+    /// `schedule`, see [`AppRegistrations::find_system`].
+    pub fn system_origin(
+        &self,
+        schedule: impl ScheduleLabel,
+        name: &str,
+    ) -> Option<&RegisteredPlugin> {
+        self.plugin(self.find_system(schedule, name)?.plugin?)
+    }
+
+    /// Returns the registrations attributed to the plugin at `index` in
+    /// [`AppRegistrations::plugins`], excluding those of the plugins it added.
+    pub fn added_by(&self, index: usize) -> PluginRegistrations<'_> {
+        let plugin = Some(index);
+        PluginRegistrations {
+            schedules: self
+                .schedules
+                .iter()
+                .filter(|s| s.plugin == plugin)
+                .collect(),
+            systems: self.systems.iter().filter(|s| s.plugin == plugin).collect(),
+            resources: self
+                .resources
+                .iter()
+                .filter(|r| r.plugin == plugin)
+                .collect(),
+            events: self.events.iter().filter(|e| e.plugin == plugin).collect(),
+            states: self.states.iter().filter(|s| s.plugin == plugin).collect(),
+        }
+    }
+
+    pub(crate) fn add_plugin(&mut self, name: String, parent: Option<usize>) -> usize {
+        self.plugins.push(RegisteredPlugin { name, parent });
+        self.plugins.len() - 1
+    }
+
+    pub(crate) fn add_schedule(&mut self, label: impl ScheduleLabel, plugin: Option<usize>) {
+        self.schedules.push(RegisteredSchedule {
+            label: format!("{:?}", label.intern()),
+            plugin,
+        });
+    }
+
+    pub(crate) fn add_systems(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        systems: impl IntoIterator<Item = (NodeId, String)>,
+        plugin: Option<usize>,
+    ) {
+        let schedule = format!("{:?}", schedule.intern());
+        self.systems
+            .extend(systems.into_iter().map(|(id, name)| RegisteredSystem {
+                schedule: schedule.clone(),
+                id,
+                name,
+                plugin,
+            }));
+    }
+
+    pub(crate) fn add_resource<R: ?Sized>(&mut self, non_send: bool, plugin: Option<usize>) {
+        self.resources.push(RegisteredResource {
+            type_name: std::any::type_name::<R>().to_string(),
+            non_send,
+            plugin,
+        });
+    }
+
+    pub(crate) fn add_event<E>(&mut self, plugin: Option<usize>) {
+        self.events.push(RegisteredEvent {
+            type_name: std::any::type_name::<E>().to_string(),
+            plugin,
+        });
+    }
+
+    pub(crate) fn add_state<S>(&mut self, kind: StateKind, plugin: Option<usize>) {
+        self.states.push(RegisteredState {
+            type_name: std::any::type_name::<S>().to_string(),
+            kind,
+            plugin,
+        });
+    }
+}
+
+/// The registrations of a single plugin, returned by [`AppRegistrations::added_by`].
+#[derive(Debug, Clone, Default)]
+pub struct PluginRegistrations<'a> {
+    /// The schedules created by the plugin.
+    pub schedules: Vec<&'a RegisteredSchedule>,
+    /// The systems added by the plugin.
+    pub systems: Vec<&'a RegisteredSystem>,
+    /// The resources first inserted by the plugin.
+    pub resources: Vec<&'a RegisteredResource>,
+    /// The event types added by the plugin.
+    pub events: Vec<&'a RegisteredEvent>,
+    /// The state types added by the plugin.
+    pub states: Vec<&'a RegisteredState>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, StateTransition};
+    use bevy_ecs::prelude::*;
+
+    #[derive(Event)]
+    struct Jump;
+
+    #[derive(Resource, Default)]
+    struct Score;
+
+    #[derive(States, Default, Clone, Debug, PartialEq, Eq, Hash)]
+    enum GameState {
+        #[default]
+        Menu,
+        Playing,
+    }
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    fn jump() {}
+    fn score() {}
+
+    struct GamePlugin;
+
+    impl Plugin for GamePlugin {
+        fn build(&self, app: &mut App) {
+            app.add_plugins(JumpPlugin)
+                .init_resource::<Score>()
+                .init_state::<GameState>()
+                .add_systems(Update, score);
+        }
+    }
+
+    struct JumpPlugin;
+
+    impl Plugin for JumpPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_event::<Jump>()
+                .init_schedule(Physics)
+                .add_systems(Physics, jump);
+        }
+    }
+
+    fn plugin_index(registrations: &AppRegistrations, name: &str) -> usize {
+        registrations
+            .plugins()
+            .iter()
+            .position(|plugin| plugin.name.ends_with(name))
+            .unwrap()
+    }
+
+    #[test]
+    fn attributes_registrations_to_plugins() {
+        let mut app = App::new();
+        app.add_plugins(GamePlugin);
+
+        let registrations = app.world.resource::<AppRegistrations>();
+        let game = plugin_index(registrations, "GamePlugin");
+        let jump_plugin = plugin_index(registrations, "JumpPlugin");
+        assert_eq!(registrations.plugin(game).unwrap().parent, None);
+        assert_eq!(
+            registrations.plugin(jump_plugin).unwrap().parent,
+            Some(game)
+        );
+
+        let origin = registrations.system_origin(Physics, "jump").unwrap();
+        assert!(origin.name.ends_with("JumpPlugin"));
+        assert!(registrations.system_origin(Update, "jump").is_none());
+        let score = registrations
+            .find_system(Update, std::any::type_name_of_val(&score))
+            .unwrap();
+        assert_eq!(score.plugin, Some(game));
+
+        let added = registrations.added_by(jump_plugin);
+        assert_eq!(added.schedules.len(), 1);
+        assert_eq!(added.schedules[0].label, "Physics");
+        assert_eq!(added.events.len(), 1);
+        assert!(added.events[0].type_name.ends_with("Jump"));
+        // `add_event` adds an update system to `First`
+        assert!(added
+            .systems
+            .iter()
+            .any(|system| system.schedule == "First"));
+        assert!(added.states.is_empty());
+
+        let added = registrations.added_by(game);
+        // states add an event for their transitions
+        assert!(added
+            .events
+            .iter()
+            .all(|event| event.type_name.contains("StateTransitionEvent")));
+        assert_eq!(added.states.len(), 1);
+        assert_eq!(added.states[0].kind, StateKind::FreelyMutable);
+        assert!(added.states[0].type_name.ends_with("GameState"));
+        assert!(added
+            .resources
+            .iter()
+            .any(|resource| resource.type_name.ends_with("Score") && !resource.non_send));
+        // the state transition systems are attributed to the plugin adding the state
+        assert!(added
+            .systems
+            .iter()
+            .any(|system| system.schedule == format!("{:?}", StateTransition.intern())));
+    }
+
+    #[test]
+    fn records_first_insertion_only() {
+        let mut app = App::new();
+        app.insert_resource(Score)
+            .insert_resource(Score)
+            .init_resource::<Score>()
+            .insert_non_send_resource(Score)
+            .add_event::<Jump>()
+            .add_event::<Jump>()
+            .insert_state(GameState::Playing)
+            .insert_state(GameState::Menu);
+
+        let registrations = app.world.resource::<AppRegistrations>();
+        let scores: Vec<_> = registrations
+            .resources()
+            .iter()
+            .filter(|resource| resource.type_name.ends_with("Score"))
+            .map(|resource| (resource.non_send, resource.plugin))
+            .collect();
+        assert_eq!(scores, [(false, None), (true, None)]);
+        let jumps = registrations
+            .events()
+            .iter()
+            .filter(|event| event.type_name.ends_with("Jump"));
+        assert_eq!(jumps.count(), 1);
+        assert_eq!(registrations.states().len(), 1);
+    }
+}
@@ -0,0 +1,541 @@
+use std::{
+    any::{Any, TypeId},
+    fmt::Write,
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+use bevy_ecs::{
+    event::{Event, Events, ManualEventReader},
+    query::{QueryFilter, ROQueryItem, ReadOnlyQueryData},
+    world::World,
+};
+use bevy_utils::HashMap;
+
+use crate::{App, PluginsState};
+
+/// The environment variable that makes [`TestApp::assert_golden`] overwrite golden files with the
+/// current recording instead of comparing against them.
+pub const UPDATE_GOLDEN_FILES_VAR: &str = "BEVY_UPDATE_GOLDEN_FILES";
+
+/// A headless [`App`] wrapper for writing integration tests.
+///
+/// A `TestApp` derefs to its [`App`], so plugins, systems and resources are added the same way as
+/// usual. On top of that it:
+/// - runs [`Plugin::finish`](crate::Plugin::finish) and [`Plugin::cleanup`](crate::Plugin::cleanup)
+///   before the first frame, like the app runners do
+/// - steps frames with [`TestApp::run_frames`] and [`TestApp::run_until`]
+/// - injects events, such as input events, with [`TestApp::send_event`]
+/// - collects the events sent during every frame, see [`TestApp::capture_events`]
+/// - records the state of the world after every frame for comparison against a golden file, see
+///   [`TestApp::record_frames`]
+///
+/// Time isn't managed by `TestApp`, as it only exists once `TimePlugin` is added. To make time
+/// advance by a fixed amount every frame, use `TestAppTimeExt::with_frame_time` from `bevy_time`.
+///
+/// ```
+/// # use bevy_app::{prelude::*, TestApp};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Event, Clone, Debug, PartialEq)]
+/// struct Jump;
+///
+/// #[derive(Event, Clone, Debug, PartialEq)]
+/// struct Landed(u32);
+///
+/// #[derive(Component)]
+/// struct Airborne(u32);
+///
+/// fn jump(mut commands: Commands, mut jumps: EventReader<Jump>) {
+///     for _ in jumps.read() {
+///         commands.spawn(Airborne(3));
+///     }
+/// }
+///
+/// fn fall(
+///     mut commands: Commands,
+///     mut query: Query<(Entity, &mut Airborne)>,
+///     mut landed: EventWriter<Landed>,
+/// ) {
+///     for (entity, mut airborne) in &mut query {
+///         airborne.0 -= 1;
+///         if airborne.0 == 0 {
+///             commands.entity(entity).despawn();
+///             landed.send(Landed(entity.index()));
+///         }
+///     }
+/// }
+///
+/// let mut app = TestApp::new();
+/// app.add_event::<Jump>()
+///     .add_event::<Landed>()
+///     .add_systems(Update, (jump, fall).chain());
+/// app.capture_events::<Landed>();
+///
+/// app.send_event(Jump);
+/// app.run_frames(1);
+/// assert_eq!(app.query::<&Airborne>().len(), 1);
+///
+/// let frames = app.run_until(10, |world| !world.resource::<Events<Landed>>().is_empty());
+/// assert_eq!(frames, 2);
+/// assert_eq!(app.events::<Landed>().len(), 1);
+/// assert!(app.query::<&Airborne>().is_empty());
+/// ```
+pub struct TestApp {
+    app: App,
+    frame: u32,
+    captures: HashMap<TypeId, Box<dyn EventCapture>>,
+    recorder: Option<Box<dyn FnMut(&mut World) -> String>>,
+    recording: Vec<String>,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// Creates a new [`TestApp`] around [`App::new`].
+    pub fn new() -> Self {
+        Self::from_app(App::new())
+    }
+
+    /// Wraps an existing [`App`].
+    pub fn from_app(app: App) -> Self {
+        Self {
+            app,
+            frame: 0,
+            captures: HashMap::default(),
+            recorder: None,
+            recording: Vec::new(),
+        }
+    }
+
+    /// Returns the number of frames that have run.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Runs a single frame.
+    ///
+    /// Before the first frame, this waits for all plugins to be ready and finishes them.
+    pub fn update(&mut self) {
+        if self.app.plugins_state() != PluginsState::Cleaned {
+            while self.app.plugins_state() == PluginsState::Adding {
+                #[cfg(not(target_arch = "wasm32"))]
+                bevy_tasks::tick_global_task_pools_on_main_thread();
+            }
+            self.app.finish();
+            self.app.cleanup();
+        }
+
+        self.app.update();
+        self.frame += 1;
+
+        for capture in self.captures.values_mut() {
+            capture.update(&self.app.world);
+        }
+        if let Some(recorder) = &mut self.recorder {
+            self.recording.push(recorder(&mut self.app.world));
+        }
+    }
+
+    /// Runs `frames` frames.
+    pub fn run_frames(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Runs frames until `condition` returns `true` after a frame, and returns the number of frames
+    /// that were run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the condition is still not met after `max_frames` frames.
+    #[track_caller]
+    pub fn run_until(
+        &mut self,
+        max_frames: u32,
+        mut condition: impl FnMut(&mut World) -> bool,
+    ) -> u32 {
+        for frames in 1..=max_frames {
+            self.update();
+            if condition(&mut self.app.world) {
+                return frames;
+            }
+        }
+        panic!("condition was not met within {max_frames} frames");
+    }
+
+    /// Sends `event`, to be read by systems during the next frame.
+    ///
+    /// This can be used to simulate input, by sending the events that input plugins normally
+    /// receive from the windowing backend.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event type has not been added with [`App::add_event`].
+    #[track_caller]
+    pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+        if self.app.world.send_event(event).is_none() {
+            panic!(
+                "unable to send event `{}`, it has not been added to the app",
+                std::any::type_name::<E>()
+            );
+        }
+        self
+    }
+
+    /// Starts collecting all events of type `E` sent from now on, including those sent with
+    /// [`TestApp::send_event`].
+    ///
+    /// Unlike an [`EventReader`](bevy_ecs::event::EventReader), the collected events are kept
+    /// across any number of frames, until [`TestApp::take_events`] is called.
+    pub fn capture_events<E: Event + Clone>(&mut self) -> &mut Self {
+        let events = self.app.world.get_resource::<Events<E>>();
+        let reader = events.map(Events::get_reader_current).unwrap_or_default();
+        self.captures.entry(TypeId::of::<E>()).or_insert_with(|| {
+            Box::new(Capture::<E> {
+                reader,
+                events: Vec::new(),
+            })
+        });
+        self
+    }
+
+    /// Returns the events of type `E` collected so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`TestApp::capture_events`] was not called for `E`.
+    #[track_caller]
+    pub fn events<E: Event + Clone>(&self) -> &[E] {
+        &self.capture::<E>().events
+    }
+
+    /// Returns and clears the events of type `E` collected so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`TestApp::capture_events`] was not called for `E`.
+    #[track_caller]
+    pub fn take_events<E: Event + Clone>(&mut self) -> Vec<E> {
+        std::mem::take(&mut self.capture_mut::<E>().events)
+    }
+
+    #[track_caller]
+    fn capture<E: Event + Clone>(&self) -> &Capture<E> {
+        self.captures
+            .get(&TypeId::of::<E>())
+            .and_then(|capture| capture.as_any().downcast_ref())
+            .unwrap_or_else(|| {
+                panic!(
+                    "events of type `{}` are not captured, call `TestApp::capture_events` first",
+                    std::any::type_name::<E>()
+                )
+            })
+    }
+
+    #[track_caller]
+    fn capture_mut<E: Event + Clone>(&mut self) -> &mut Capture<E> {
+        self.captures
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|capture| capture.as_any_mut().downcast_mut())
+            .unwrap_or_else(|| {
+                panic!(
+                    "events of type `{}` are not captured, call `TestApp::capture_events` first",
+                    std::any::type_name::<E>()
+                )
+            })
+    }
+
+    /// Returns the results of the query `D` for every matching entity.
+    pub fn query<D: ReadOnlyQueryData>(&mut self) -> Vec<ROQueryItem<'_, D>> {
+        self.query_filtered::<D, ()>()
+    }
+
+    /// Returns the results of the query `D`, filtered by `F`, for every matching entity.
+    pub fn query_filtered<D: ReadOnlyQueryData, F: QueryFilter>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, D>> {
+        let mut state = self.app.world.query_filtered::<D, F>();
+        state.iter(&self.app.world).collect()
+    }
+
+    /// Records the state of the world after every frame from now on, as returned by `recorder`.
+    ///
+    /// The recording can be compared against a golden file with [`TestApp::assert_golden`].
+    pub fn record_frames(
+        &mut self,
+        recorder: impl FnMut(&mut World) -> String + 'static,
+    ) -> &mut Self {
+        self.recorder = Some(Box::new(recorder));
+        self
+    }
+
+    /// Returns the states recorded so far, one per frame, see [`TestApp::record_frames`].
+    pub fn recording(&self) -> &[String] {
+        &self.recording
+    }
+
+    /// Returns the recording as a single string, with a header before each frame.
+    pub fn recording_to_string(&self) -> String {
+        let mut output = String::new();
+        let first_frame = self.frame as usize - self.recording.len() + 1;
+        for (frame, state) in (first_frame..).zip(&self.recording) {
+            writeln!(output, "--- frame {frame} ---").unwrap();
+            output.push_str(state);
+            if !state.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    /// Compares the recording against the contents of the golden file at `path`.
+    ///
+    /// If the [`UPDATE_GOLDEN_FILES_VAR`] environment variable is set, the file is written with the
+    /// current recording instead, which is also how missing golden files are created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the recording differs from the golden file, or the file doesn't exist or can't be
+    /// read or written.
+    #[track_caller]
+    pub fn assert_golden(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.recording_to_string();
+        if std::env::var_os(UPDATE_GOLDEN_FILES_VAR).is_some() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).unwrap_or_else(|error| {
+                    panic!("unable to create directory {}: {error}", parent.display())
+                });
+            }
+            std::fs::write(path, actual).unwrap_or_else(|error| {
+                panic!("unable to write golden file {}: {error}", path.display())
+            });
+            return;
+        }
+
+        if !path.exists() {
+            panic!(
+                "golden file {} doesn't exist\nset {UPDATE_GOLDEN_FILES_VAR}=1 to create it",
+                path.display(),
+            );
+        }
+        let expected = std::fs::read_to_string(path).unwrap_or_else(|error| {
+            panic!("unable to read golden file {}: {error}", path.display())
+        });
+        if let Some((line, (expected_line, actual_line))) = expected
+            .lines()
+            .zip(actual.lines())
+            .enumerate()
+            .find(|(_, (expected, actual))| expected != actual)
+        {
+            panic!(
+                "recording differs from golden file {} at line {}:\n  expected: {expected_line}\n    actual: {actual_line}\nset {UPDATE_GOLDEN_FILES_VAR}=1 to update it",
+                path.display(),
+                line + 1,
+            );
+        }
+        if expected.lines().count() != actual.lines().count() {
+            panic!(
+                "recording has {} lines but golden file {} has {}\nset {UPDATE_GOLDEN_FILES_VAR}=1 to update it",
+                actual.lines().count(),
+                path.display(),
+                expected.lines().count(),
+            );
+        }
+    }
+
+    /// Returns the wrapped [`App`].
+    pub fn into_app(self) -> App {
+        self.app
+    }
+}
+
+impl Deref for TestApp {
+    type Target = App;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.app
+    }
+}
+
+/// Type-erased [`Capture`].
+trait EventCapture {
+    fn update(&mut self, world: &World);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// The events of type `E` collected by a [`TestApp`].
+struct Capture<E: Event> {
+    reader: ManualEventReader<E>,
+    events: Vec<E>,
+}
+
+impl<E: Event + Clone> EventCapture for Capture<E> {
+    fn update(&mut self, world: &World) {
+        if let Some(events) = world.get_resource::<Events<E>>() {
+            self.events.extend(self.reader.read(events).cloned());
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+
+    use super::*;
+    use crate::{Plugin, Update};
+
+    #[derive(Event, Clone, Debug, PartialEq)]
+    struct Input(u32);
+
+    #[derive(Event, Clone, Debug, PartialEq)]
+    struct Output(u32);
+
+    #[derive(Component, Debug)]
+    struct Counter(u32);
+
+    fn echo(mut inputs: EventReader<Input>, mut outputs: EventWriter<Output>) {
+        for input in inputs.read() {
+            outputs.send(Output(input.0 * 2));
+        }
+    }
+
+    fn count(mut query: Query<&mut Counter>) {
+        for mut counter in &mut query {
+            counter.0 += 1;
+        }
+    }
+
+    fn test_app() -> TestApp {
+        let mut app = TestApp::new();
+        app.add_event::<Input>()
+            .add_event::<Output>()
+            .add_systems(Update, (echo, count));
+        app
+    }
+
+    #[test]
+    fn captures_events_across_frames() {
+        let mut app = test_app();
+        app.capture_events::<Input>().capture_events::<Output>();
+
+        app.send_event(Input(1));
+        app.run_frames(3);
+        app.send_event(Input(2));
+        app.update();
+
+        assert_eq!(app.frame(), 4);
+        assert_eq!(app.events::<Input>(), [Input(1), Input(2)]);
+        assert_eq!(app.take_events::<Output>(), [Output(2), Output(4)]);
+        assert!(app.events::<Output>().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "has not been added to the app")]
+    fn send_unregistered_event() {
+        TestApp::new().send_event(Input(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "are not captured")]
+    fn events_not_captured() {
+        test_app().events::<Output>();
+    }
+
+    #[test]
+    fn run_until_condition() {
+        let mut app = test_app();
+        app.world.spawn(Counter(0));
+
+        let frames = app.run_until(10, |world| world.query::<&Counter>().single(world).0 == 4);
+        assert_eq!(frames, 4);
+        assert_eq!(app.query::<&Counter>()[0].0, 4);
+        assert!(app
+            .query_filtered::<&Counter, Without<Counter>>()
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "condition was not met within 3 frames")]
+    fn run_until_timeout() {
+        test_app().run_until(3, |_| false);
+    }
+
+    #[test]
+    fn finishes_plugins_before_first_frame() {
+        #[derive(Resource)]
+        struct Finished;
+
+        struct FinishPlugin;
+        impl Plugin for FinishPlugin {
+            fn build(&self, _app: &mut App) {}
+            fn finish(&self, app: &mut App) {
+                app.insert_resource(Finished);
+            }
+        }
+
+        let mut app = TestApp::new();
+        app.add_plugins(FinishPlugin);
+        app.update();
+        assert!(app.world.contains_resource::<Finished>());
+    }
+
+    #[test]
+    fn golden_file() {
+        let mut app = test_app();
+        app.world.spawn(Counter(0));
+        app.update();
+        app.record_frames(|world| {
+            let counter = world.query::<&Counter>().single(world);
+            format!("{counter:?}")
+        });
+        app.run_frames(2);
+
+        assert_eq!(app.recording(), ["Counter(2)", "Counter(3)"]);
+        assert_eq!(
+            app.recording_to_string(),
+            "--- frame 2 ---\nCounter(2)\n--- frame 3 ---\nCounter(3)\n"
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!("bevy_app_golden_{}", std::process::id()))
+            .join("counter.txt");
+        let _ = std::fs::remove_file(&path);
+        let assert_golden = |app: &TestApp| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| app.assert_golden(&path)))
+        };
+        // missing golden files are only written when updating them
+        assert!(assert_golden(&app).is_err());
+        assert!(!path.exists());
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, app.recording_to_string()).unwrap();
+        app.assert_golden(&path);
+
+        app.update();
+        let result = assert_golden(&app);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        assert!(result.is_err());
+    }
+}
@@ -16,12 +16,18 @@ pub mod prelude {
     //! The Bevy Core Prelude.
     #[doc(hidden)]
     pub use crate::{
-        DebugName, FrameCountPlugin, Name, TaskPoolOptions, TaskPoolPlugin, TypeRegistrationPlugin,
+        AsyncWorldPlugin, DebugName, FrameCountPlugin, Name, TaskPoolOptions, TaskPoolPlugin,
+        TypeRegistrationPlugin,
     };
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{
+    entity::StableId,
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    world::AsyncTasks,
+};
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use bevy_utils::{Duration, HashSet, Instant, Uuid};
 use std::borrow::Cow;
@@ -40,7 +46,9 @@ pub struct TypeRegistrationPlugin;
 
 impl Plugin for TypeRegistrationPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Entity>().register_type::<Name>();
+        app.register_type::<Entity>()
+            .register_type::<StableId>()
+            .register_type::<Name>();
 
         register_rust_types(app);
         register_math_types(app);
@@ -130,6 +138,50 @@ fn tick_global_task_pools(_main_thread_marker: Option<NonSend<NonSendMarker>>) {
     tick_global_task_pools_on_main_thread();
 }
 
+/// Runs the async tasks of the [`AsyncTasks`] resource, which access the [`World`] through an
+/// [`AsyncWorld`](bevy_ecs::world::AsyncWorld) and are spawned with
+/// [`AsyncCommands`](bevy_ecs::world::AsyncCommands).
+///
+/// [`AsyncTasks::apply`] runs in each of the [`schedules`](Self::schedules), so a task's
+/// [`run_in`](bevy_ecs::world::AsyncWorld::run_in) can target any of them, while its
+/// [`run`](bevy_ecs::world::AsyncWorld::run) runs in the first one reached. As these systems are
+/// exclusive, they only run while [`AsyncTasks`] isn't [idle](AsyncTasks::is_idle).
+///
+/// This plugin isn't part of the default plugins, and should be added by apps using async tasks.
+pub struct AsyncWorldPlugin {
+    /// The schedules in which tasks are polled and access the world.
+    ///
+    /// Defaults to [`First`], [`PreUpdate`], [`Update`], [`PostUpdate`] and [`Last`].
+    pub schedules: Vec<InternedScheduleLabel>,
+}
+
+impl Default for AsyncWorldPlugin {
+    fn default() -> Self {
+        Self {
+            schedules: vec![
+                First.intern(),
+                PreUpdate.intern(),
+                Update.intern(),
+                PostUpdate.intern(),
+                Last.intern(),
+            ],
+        }
+    }
+}
+
+impl Plugin for AsyncWorldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AsyncTasks>();
+        for &schedule in &self.schedules {
+            app.add_systems(
+                schedule,
+                (move |world: &mut World| AsyncTasks::apply(world, schedule))
+                    .run_if(|tasks: Res<AsyncTasks>| !tasks.is_idle()),
+            );
+        }
+    }
+}
+
 /// Maintains a count of frames rendered since the start of the application.
 ///
 /// [`FrameCount`] is incremented during [`Last`], providing predictable
@@ -199,6 +251,36 @@ mod tests {
         io_rx.try_recv().unwrap();
     }
 
+    #[test]
+    fn async_world_tasks() {
+        #[derive(Resource, Default)]
+        struct Steps(Vec<&'static str>);
+
+        fn step(name: &'static str) -> impl FnOnce(&mut World) + Send {
+            move |world| world.resource_mut::<Steps>().0.push(name)
+        }
+
+        let mut app = App::new();
+        app.add_plugins(AsyncWorldPlugin::default())
+            .init_resource::<Steps>()
+            .add_systems(Startup, |tasks: AsyncCommands| {
+                tasks.spawn(|world| async move {
+                    world.run_in(Update, step("update")).await;
+                    world.run_in(First, step("first")).await;
+                });
+            });
+
+        for _ in 0..1000 {
+            app.update();
+            if app.world.resource::<AsyncTasks>().is_idle() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(app.world.resource::<Steps>().0, ["update", "first"]);
+        assert!(app.world.resource::<AsyncTasks>().is_empty());
+    }
+
     #[test]
     fn frame_counter_update() {
         let mut app = App::new();
@@ -14,5 +14,10 @@ pub fn derive_dynamic_plugin(input: TokenStream) -> TokenStream {
             let boxed = Box::new(object);
             Box::into_raw(boxed)
         }
+
+        #[no_mangle]
+        pub extern "C" fn _bevy_plugin_abi() -> u64 {
+            bevy::app::DYNAMIC_PLUGIN_ABI
+        }
     })
 }
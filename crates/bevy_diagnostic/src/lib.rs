@@ -7,6 +7,7 @@ mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
 mod system_information_diagnostics_plugin;
+mod system_stats_diagnostics_plugin;
 
 use bevy_app::prelude::*;
 pub use diagnostic::*;
@@ -14,6 +15,7 @@ pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
 pub use system_information_diagnostics_plugin::SystemInformationDiagnosticsPlugin;
+pub use system_stats_diagnostics_plugin::SystemStatsDiagnosticsPlugin;
 
 /// Adds core diagnostics resources to an App.
 #[derive(Default)]
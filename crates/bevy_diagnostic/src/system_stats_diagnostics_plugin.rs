@@ -0,0 +1,123 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, NodeId, ScheduleLabel, SystemStats},
+};
+use bevy_utils::{Duration, HashMap, Instant};
+
+use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+
+/// Adds per-system diagnostics for every system of every schedule to an App, using the
+/// [`SystemStats`] resource.
+///
+/// Once per frame, each system that has been seen by the executor gets the following
+/// measurements, under the paths returned by [`SystemStatsDiagnosticsPlugin::path`]:
+/// - `duration`: the mean time the system took to run during the frame, in milliseconds
+/// - `wait`: the mean time the system waited on systems with conflicting data access before it
+///   could start during the frame, in milliseconds
+/// - `run_count`: how many times the system ran during the frame
+/// - `skipped_count`: how many times the system was skipped by run conditions during the frame
+///
+/// `duration` and `wait` are only measured in frames where the system ran.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct SystemStatsDiagnosticsPlugin;
+
+impl Plugin for SystemStatsDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SystemStats>()
+            .init_resource::<DiagnosticsStore>()
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+/// The statistics of a system when its diagnostics were last measured.
+struct Sample {
+    paths: [DiagnosticPath; 4],
+    run_count: u64,
+    skipped_count: u64,
+    total_duration: Duration,
+    total_wait: Duration,
+}
+
+impl SystemStatsDiagnosticsPlugin {
+    /// The measurements recorded for each system, see [`SystemStatsDiagnosticsPlugin`].
+    pub const MEASUREMENTS: [&'static str; 4] = ["duration", "wait", "run_count", "skipped_count"];
+
+    /// Returns the [`DiagnosticPath`] of `measurement` for the system named `system` in the
+    /// schedule labelled `schedule`, in the form `systems/<schedule>/<system>/<measurement>`.
+    ///
+    /// `measurement` is one of [`SystemStatsDiagnosticsPlugin::MEASUREMENTS`].
+    pub fn path(schedule: impl ScheduleLabel, system: &str, measurement: &str) -> DiagnosticPath {
+        Self::path_inner(schedule.intern(), system, measurement)
+    }
+
+    fn path_inner(
+        schedule: InternedScheduleLabel,
+        system: &str,
+        measurement: &str,
+    ) -> DiagnosticPath {
+        let schedule = format!("{schedule:?}");
+        DiagnosticPath::from_components(["systems", &schedule, system, measurement])
+    }
+
+    fn diagnostic_system(
+        mut store: ResMut<DiagnosticsStore>,
+        stats: Res<SystemStats>,
+        mut samples: Local<HashMap<(InternedScheduleLabel, NodeId), Sample>>,
+    ) {
+        let time = Instant::now();
+        for (schedule, id, system) in stats.iter() {
+            let sample = samples.entry((schedule, id)).or_insert_with(|| {
+                let paths = Self::MEASUREMENTS
+                    .map(|measurement| Self::path_inner(schedule, system.name(), measurement));
+                for (path, measurement) in paths.iter().zip(Self::MEASUREMENTS) {
+                    let diagnostic = Diagnostic::new(path.clone());
+                    store.add(match measurement {
+                        "duration" | "wait" => diagnostic.with_suffix("ms"),
+                        _ => diagnostic,
+                    });
+                }
+                Sample {
+                    paths,
+                    run_count: 0,
+                    skipped_count: 0,
+                    total_duration: Duration::ZERO,
+                    total_wait: Duration::ZERO,
+                }
+            });
+
+            // `SystemStats` may have been cleared since the last frame
+            let runs = system.run_count().saturating_sub(sample.run_count);
+            let skips = system.skipped_count().saturating_sub(sample.skipped_count);
+            let mut measure = |path: &DiagnosticPath, value: f64| {
+                if let Some(diagnostic) = store
+                    .get_mut(path)
+                    .filter(|diagnostic| diagnostic.is_enabled)
+                {
+                    diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+                }
+            };
+
+            let [duration_path, wait_path, run_count_path, skipped_count_path] = &sample.paths;
+            if runs > 0 {
+                let duration = system
+                    .total_duration()
+                    .saturating_sub(sample.total_duration);
+                let wait = system.total_wait().saturating_sub(sample.total_wait);
+                measure(duration_path, duration.as_secs_f64() * 1000.0 / runs as f64);
+                measure(wait_path, wait.as_secs_f64() * 1000.0 / runs as f64);
+            }
+            measure(run_count_path, runs as f64);
+            measure(skipped_count_path, skips as f64);
+
+            sample.run_count = system.run_count();
+            sample.skipped_count = system.skipped_count();
+            sample.total_duration = system.total_duration();
+            sample.total_wait = system.total_wait();
+        }
+    }
+}
@@ -1,8 +1,13 @@
 use libloading::{Library, Symbol};
-use std::ffi::OsStr;
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
-use bevy_app::{App, CreatePlugin, Plugin};
+use bevy_app::{App, CreatePlugin, Plugin, PluginAbi, DYNAMIC_PLUGIN_ABI};
+
+use crate::{reload, DynamicPluginReloadError};
 
 /// Errors that can occur when loading a dynamic plugin
 #[derive(Debug, Error)]
@@ -11,20 +16,30 @@ pub enum DynamicPluginLoadError {
     Library(libloading::Error),
     #[error("dynamic library does not contain a valid Bevy dynamic plugin")]
     Plugin(libloading::Error),
+    #[error("dynamic plugin was built against an incompatible version of Bevy")]
+    Abi,
 }
 
 /// Dynamically links a plugin at the given path. The plugin must export a function with the
-/// [`CreatePlugin`] signature named `_bevy_create_plugin`.
+/// [`CreatePlugin`] signature named `_bevy_create_plugin`, and a function with the [`PluginAbi`]
+/// signature named `_bevy_plugin_abi` returning the same [`DYNAMIC_PLUGIN_ABI`] as this program.
 ///
 /// # Safety
 ///
 /// The specified plugin must be linked against the exact same libbevy.so as this program.
-/// In addition the `_bevy_create_plugin` symbol must not be manually created, but instead created
-/// by deriving `DynamicPlugin` on a unit struct implementing [`Plugin`].
+/// In addition the `_bevy_create_plugin` and `_bevy_plugin_abi` symbols must not be manually
+/// created, but instead created by deriving `DynamicPlugin` on a unit struct implementing
+/// [`Plugin`].
 pub unsafe fn dynamically_load_plugin<P: AsRef<OsStr>>(
     path: P,
 ) -> Result<(Library, Box<dyn Plugin>), DynamicPluginLoadError> {
     let lib = Library::new(path).map_err(DynamicPluginLoadError::Library)?;
+    let abi: Symbol<PluginAbi> = lib
+        .get(b"_bevy_plugin_abi")
+        .map_err(DynamicPluginLoadError::Plugin)?;
+    if abi() != DYNAMIC_PLUGIN_ABI {
+        return Err(DynamicPluginLoadError::Abi);
+    }
     let func: Symbol<CreatePlugin> = lib
         .get(b"_bevy_create_plugin")
         .map_err(DynamicPluginLoadError::Plugin)?;
@@ -37,6 +52,47 @@ pub trait DynamicPluginExt {
     ///
     /// Same as [`dynamically_load_plugin`].
     unsafe fn load_plugin<P: AsRef<OsStr>>(&mut self, path: P) -> &mut Self;
+
+    /// Loads a plugin like [`DynamicPluginExt::load_plugin`], and keeps track of the systems,
+    /// system sets, schedules and reflected types it adds, so that it can be replaced with a
+    /// rebuilt version by [`DynamicPluginExt::reload_changed_plugins`].
+    ///
+    /// # Safety
+    ///
+    /// Same as [`dynamically_load_plugin`].
+    unsafe fn load_reloadable_plugin<P: AsRef<Path>>(&mut self, path: P) -> &mut Self;
+
+    /// Reloads the plugins loaded with [`DynamicPluginExt::load_reloadable_plugin`] whose library
+    /// has been modified since it was last loaded, and returns the result for each of them.
+    ///
+    /// This must be called between updates, for example from a custom runner. For each plugin, it:
+    /// - loads a copy of the new library, checking that it was built against the same version of
+    ///   Bevy
+    /// - builds the new version into an empty app, and checks that the reflected types it
+    ///   registers have kept the same size, alignment and fields, recursively, since existing
+    ///   values of a type are kept across reloads as long as its [`TypeId`] is unchanged
+    /// - removes the systems, system sets and schedules the previous version added, then builds
+    ///   the new version, and calls its [`Plugin::finish`] and [`Plugin::cleanup`]
+    /// - if the [`TypeId`] of a reflected component or resource changed, converts its values to
+    ///   the new type using [`FromReflect`](bevy_reflect::FromReflect)
+    ///
+    /// If any of this fails, the previous version of the plugin is built again and kept, and the
+    /// error is returned. Note that building a plugin again resets the resources it inserts, and
+    /// the [`Local`](bevy_ecs::system::Local) state of its systems.
+    ///
+    /// Old libraries are never unloaded.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`dynamically_load_plugin`]. In addition, if the layout of a type of the plugin
+    /// that is kept in the app, like a component, changed, the type must be reflected, as only the
+    /// layout of reflected types can be checked. As the new version is built into an empty app
+    /// to find its types, building it must not have effects outside of the app.
+    ///
+    /// [`TypeId`]: std::any::TypeId
+    unsafe fn reload_changed_plugins(
+        &mut self,
+    ) -> Vec<(PathBuf, Result<(), DynamicPluginReloadError>)>;
 }
 
 impl DynamicPluginExt for App {
@@ -46,4 +102,15 @@ impl DynamicPluginExt for App {
         plugin.build(self);
         self
     }
+
+    unsafe fn load_reloadable_plugin<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        reload::load_reloadable_plugin(self, path.as_ref());
+        self
+    }
+
+    unsafe fn reload_changed_plugins(
+        &mut self,
+    ) -> Vec<(PathBuf, Result<(), DynamicPluginReloadError>)> {
+        reload::reload_changed_plugins(self)
+    }
 }
@@ -0,0 +1,811 @@
+use std::{
+    any::{Any, TypeId},
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::SystemTime,
+};
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    schedule::{InternedScheduleLabel, NodeId, Schedules},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::{
+    NamedField, Reflect, ReflectFromReflect, TypeInfo, TypeRegistration, TypeRegistry,
+    UnnamedField, VariantInfo,
+};
+use bevy_utils::{HashMap, HashSet};
+use thiserror::Error;
+
+use crate::{dynamically_load_plugin, DynamicPluginLoadError};
+
+/// Errors that can occur when reloading a dynamic plugin.
+///
+/// When reloading fails, the previous version of the plugin is kept.
+#[derive(Debug, Error)]
+pub enum DynamicPluginReloadError {
+    #[error("cannot copy library for dynamic plugin: {0}")]
+    Copy(std::io::Error),
+    #[error(transparent)]
+    Load(#[from] DynamicPluginLoadError),
+    #[error("dynamic plugin panicked while being built: {0}")]
+    Build(String),
+    #[error(
+        "the layout of `{type_path}` changed from `{old}` to `{new}`, restart the app to use it"
+    )]
+    LayoutChanged {
+        type_path: String,
+        old: String,
+        new: String,
+    },
+    #[error("cannot migrate the values of `{type_path}` to its new version: {reason}")]
+    Migration {
+        type_path: String,
+        reason: &'static str,
+    },
+}
+
+/// The dynamic plugins loaded with
+/// [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+#[derive(Resource, Default)]
+pub(crate) struct ReloadablePlugins(Vec<ReloadablePlugin>);
+
+struct ReloadablePlugin {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    plugin: Box<dyn Plugin>,
+    footprint: Footprint,
+}
+
+/// What a plugin added to an [`App`] when it was built.
+#[derive(Default)]
+struct Footprint {
+    /// Systems and system sets added to schedules that already existed.
+    nodes: Vec<(InternedScheduleLabel, Vec<NodeId>)>,
+    /// Schedules that didn't exist before.
+    schedules: Vec<InternedScheduleLabel>,
+    /// Types registered in the [`AppTypeRegistry`].
+    types: Vec<TypeId>,
+}
+
+pub(crate) unsafe fn load_reloadable_plugin(app: &mut App, path: &Path) {
+    let modified = modified(path);
+    let plugin = load_copy(path).unwrap();
+    let footprint =
+        build_tracked(app, &*plugin).unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+
+    app.world
+        .get_resource_or_insert_with(ReloadablePlugins::default)
+        .0
+        .push(ReloadablePlugin {
+            path: path.to_owned(),
+            modified,
+            plugin,
+            footprint,
+        });
+}
+
+pub(crate) unsafe fn reload_changed_plugins(
+    app: &mut App,
+) -> Vec<(PathBuf, Result<(), DynamicPluginReloadError>)> {
+    let Some(mut plugins) = app.world.remove_resource::<ReloadablePlugins>() else {
+        return Vec::new();
+    };
+
+    let mut results = Vec::new();
+    for loaded in &mut plugins.0 {
+        let modified = modified(&loaded.path);
+        if modified == loaded.modified {
+            continue;
+        }
+        // a library that fails to load is retried once it is modified again
+        loaded.modified = modified;
+        results.push((loaded.path.clone(), reload(app, loaded)));
+    }
+
+    app.world.insert_resource(plugins);
+    results
+}
+
+unsafe fn reload(
+    app: &mut App,
+    loaded: &mut ReloadablePlugin,
+) -> Result<(), DynamicPluginReloadError> {
+    let plugin = load_copy(&loaded.path)?;
+    replace(app, loaded, plugin)
+}
+
+/// Replaces the current version of a plugin with `plugin`.
+fn replace(
+    app: &mut App,
+    loaded: &mut ReloadablePlugin,
+    plugin: Box<dyn Plugin>,
+) -> Result<(), DynamicPluginReloadError> {
+    // The types of the old version are unregistered while the new one is built, so that it
+    // registers them again with the type information of the new library. The registry as it was
+    // is kept to check the new types against, and to be restored if the new version fails.
+    let app_registry = app.world.get_resource::<AppTypeRegistry>().cloned();
+
+    // The layouts of the new types are checked before the new version is built into the app, as
+    // that may already create values of them.
+    if let Some(app_registry) = &app_registry {
+        let registry = app_registry.read();
+        let (registered, types) =
+            dry_run(&*plugin, without_types(&registry, &loaded.footprint.types));
+        check_layouts(&registry, &registered, &types)?;
+    }
+
+    let previous = app_registry.as_ref().map(|app_registry| {
+        let mut registry = app_registry.write();
+        let without_plugin = without_types(&registry, &loaded.footprint.types);
+        std::mem::replace(&mut *registry, without_plugin)
+    });
+
+    // the new version is built from scratch, so that it doesn't reuse the system sets and
+    // schedules of the old one
+    remove(app, &loaded.footprint);
+    let footprint = match build_tracked(app, &*plugin) {
+        Ok(footprint) => footprint,
+        Err(payload) => {
+            restore(app, loaded, previous);
+            return Err(DynamicPluginReloadError::Build(panic_message(&*payload)));
+        }
+    };
+
+    let migrations = match (&app_registry, &previous) {
+        (Some(app_registry), Some(previous)) => plan_migrations(
+            &app.world,
+            previous,
+            &app_registry.read(),
+            &loaded.footprint.types,
+        ),
+        _ => Ok(Vec::new()),
+    };
+    let migrations = match migrations {
+        Ok(migrations) => migrations,
+        Err(error) => {
+            remove(app, &footprint);
+            restore(app, loaded, previous);
+            return Err(error);
+        }
+    };
+
+    if let (Some(app_registry), Some(previous)) = (&app_registry, &previous) {
+        keep_type_data(&mut app_registry.write(), previous, &footprint.types);
+        let registry = app_registry.read();
+        for migration in migrations {
+            migration.apply(&mut app.world, previous, &registry);
+        }
+    }
+
+    plugin.finish(app);
+    plugin.cleanup(app);
+    loaded.plugin = plugin;
+    loaded.footprint = footprint;
+    Ok(())
+}
+
+/// Rebuilds the current version of a plugin after its new version failed to load, going through
+/// its whole lifecycle again.
+///
+/// `registry` is the content of the [`AppTypeRegistry`] before the new version was built.
+fn restore(app: &mut App, loaded: &mut ReloadablePlugin, registry: Option<TypeRegistry>) {
+    if let (Some(app_registry), Some(registry)) =
+        (app.world.get_resource::<AppTypeRegistry>(), registry)
+    {
+        *app_registry.write() = registry;
+    }
+
+    let mut types = std::mem::take(&mut loaded.footprint.types);
+    loaded.footprint = match build_tracked(app, &*loaded.plugin) {
+        Ok(footprint) => {
+            loaded.plugin.finish(app);
+            loaded.plugin.cleanup(app);
+            types.extend(footprint.types);
+            Footprint { types, ..footprint }
+        }
+        Err(_) => Footprint {
+            types,
+            ..Default::default()
+        },
+    };
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Loads a copy of the library at `path`, which allows loading a new version of the library once
+/// it is rebuilt, and rebuilding it while a copy is loaded.
+unsafe fn load_copy(path: &Path) -> Result<Box<dyn Plugin>, DynamicPluginReloadError> {
+    static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+    let directory = std::env::temp_dir().join("bevy_dynamic_plugin");
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let copy = directory.join(format!(
+        "{}-{}-{file_name}",
+        std::process::id(),
+        COPIES.fetch_add(1, Ordering::Relaxed),
+    ));
+    std::fs::create_dir_all(&directory)
+        .and_then(|()| std::fs::copy(path, &copy))
+        .map_err(DynamicPluginReloadError::Copy)?;
+
+    let (lib, plugin) = dynamically_load_plugin(copy)?;
+    // Libraries are never unloaded, as values created by their code may still be alive,
+    // like interned labels or components.
+    std::mem::forget(lib);
+    Ok(plugin)
+}
+
+/// Builds `plugin`, returning what it added to the app.
+///
+/// If the plugin panics, the systems it added are removed and the panic payload is returned.
+fn build_tracked(app: &mut App, plugin: &dyn Plugin) -> Result<Footprint, Box<dyn Any + Send>> {
+    let before = schedule_nodes(&app.world);
+    let registered = registered_types(&app.world);
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| plugin.build(app)));
+
+    let mut footprint = Footprint {
+        types: registered_types(&app.world)
+            .difference(&registered)
+            .copied()
+            .collect(),
+        ..Default::default()
+    };
+    for (label, nodes) in schedule_nodes(&app.world) {
+        match before.get(&label) {
+            Some(existing) => {
+                let added: Vec<_> = nodes.difference(existing).copied().collect();
+                if !added.is_empty() {
+                    footprint.nodes.push((label, added));
+                }
+            }
+            None => footprint.schedules.push(label),
+        }
+    }
+
+    match result {
+        Ok(()) => Ok(footprint),
+        Err(payload) => {
+            remove(app, &footprint);
+            Err(payload)
+        }
+    }
+}
+
+fn registered_types(world: &World) -> HashSet<TypeId> {
+    world
+        .get_resource::<AppTypeRegistry>()
+        .map(|registry| {
+            registry
+                .read()
+                .iter()
+                .map(|registration| registration.type_id())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn schedule_nodes(world: &World) -> HashMap<InternedScheduleLabel, HashSet<NodeId>> {
+    let Some(schedules) = world.get_resource::<Schedules>() else {
+        return HashMap::default();
+    };
+    schedules
+        .iter()
+        .map(|(_, schedule)| (schedule.label(), schedule.graph().node_ids().collect()))
+        .collect()
+}
+
+/// Removes the systems, system sets and schedules a plugin added.
+fn remove(app: &mut App, footprint: &Footprint) {
+    let Some(mut schedules) = app.world.get_resource_mut::<Schedules>() else {
+        return;
+    };
+    for &label in &footprint.schedules {
+        schedules.remove(label);
+    }
+    for (label, nodes) in &footprint.nodes {
+        if let Some(schedule) = schedules.get_mut(*label) {
+            schedule.remove_nodes(nodes.iter().copied());
+        }
+    }
+}
+
+/// Copies `registry`, leaving out the registrations of `types`.
+fn without_types(registry: &TypeRegistry, types: &[TypeId]) -> TypeRegistry {
+    let mut copy = TypeRegistry::empty();
+    for registration in registry.iter() {
+        if !types.contains(&registration.type_id()) {
+            copy.add_registration(registration.clone());
+        }
+    }
+    for function in registry.functions() {
+        copy.register_function(function.info().name().to_string(), function.clone());
+    }
+    copy
+}
+
+/// Adds the type data the `previous` registrations of `types` had, such as the type data other
+/// plugins registered for them, to their new registrations.
+fn keep_type_data(registry: &mut TypeRegistry, previous: &TypeRegistry, types: &[TypeId]) {
+    for &type_id in types {
+        if let (Some(registration), Some(previous)) =
+            (registry.get_mut(type_id), previous.get(type_id))
+        {
+            registration.insert_missing_data(previous);
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Builds `plugin` into an empty app whose [`AppTypeRegistry`] is `registry`, to find the types it
+/// registers, and their layouts, without changing the app it will be built into.
+///
+/// Returns the registry once the plugin is built, and the types the plugin added to it. The plugin
+/// may rely on the rest of the app and panic, in which case the types it registered until then are
+/// returned.
+fn dry_run(plugin: &dyn Plugin, registry: TypeRegistry) -> (TypeRegistry, Vec<TypeId>) {
+    let before: HashSet<TypeId> = registry.iter().map(TypeRegistration::type_id).collect();
+    let mut app = App::empty();
+    let app_registry = AppTypeRegistry::default();
+    *app_registry.write() = registry;
+    app.insert_resource(app_registry.clone());
+
+    let _ = std::panic::catch_unwind(AssertUnwindSafe(|| plugin.build(&mut app)));
+    drop(app);
+
+    let registry = std::mem::replace(&mut *app_registry.write(), TypeRegistry::empty());
+    let types = registry
+        .iter()
+        .map(TypeRegistration::type_id)
+        .filter(|type_id| !before.contains(type_id))
+        .collect();
+    (registry, types)
+}
+
+/// Checks that the `types` registered by the new version of a plugin in `registry` that were
+/// already known to the app, such as components kept in the world, still have the same layout.
+///
+/// As a type keeps its [`TypeId`] across rebuilds, existing values would otherwise be interpreted
+/// with the new layout.
+fn check_layouts(
+    previous: &TypeRegistry,
+    registry: &TypeRegistry,
+    types: &[TypeId],
+) -> Result<(), DynamicPluginReloadError> {
+    for &type_id in types {
+        let (Some(existing), Some(registration)) = (previous.get(type_id), registry.get(type_id))
+        else {
+            continue;
+        };
+        let type_path = registration.type_info().type_path();
+        let old = shape(previous, type_id, existing.type_info().type_path());
+        let new = shape(registry, type_id, type_path);
+        if old != new {
+            return Err(DynamicPluginReloadError::LayoutChanged {
+                type_path: type_path.to_string(),
+                old,
+                new,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Describes the size and alignment of a type, and its fields, recursively through the types
+/// registered in `registry`.
+///
+/// Types that aren't registered are only described by their path.
+fn shape(registry: &TypeRegistry, type_id: TypeId, type_path: &str) -> String {
+    fn describe(
+        registry: &TypeRegistry,
+        type_id: TypeId,
+        type_path: &str,
+        visiting: &mut Vec<TypeId>,
+    ) -> String {
+        let Some(registration) = registry.get(type_id) else {
+            return type_path.to_string();
+        };
+        // recursive types are only described once
+        if visiting.contains(&type_id) {
+            return type_path.to_string();
+        }
+        visiting.push(type_id);
+        let mut field = |type_id, type_path: &str| describe(registry, type_id, type_path, visiting);
+        let description = match registration.type_info() {
+            TypeInfo::Struct(info) => format!("struct {{ {} }}", named(info.iter(), &mut field)),
+            TypeInfo::TupleStruct(info) => {
+                format!("struct ({})", unnamed(info.iter(), &mut field))
+            }
+            TypeInfo::Tuple(info) => format!("({})", unnamed(info.iter(), &mut field)),
+            TypeInfo::List(info) => format!(
+                "[{}]",
+                field(info.item_type_id(), info.item_type_path_table().path())
+            ),
+            TypeInfo::Array(info) => format!(
+                "[{}; {}]",
+                field(info.item_type_id(), info.item_type_path_table().path()),
+                info.capacity()
+            ),
+            TypeInfo::Map(info) => format!(
+                "{{ {}: {} }}",
+                field(info.key_type_id(), info.key_type_path_table().path()),
+                field(info.value_type_id(), info.value_type_path_table().path())
+            ),
+            TypeInfo::Enum(info) => {
+                let variants: Vec<_> = info
+                    .iter()
+                    .map(|variant| match variant {
+                        VariantInfo::Struct(variant) => format!(
+                            "{} {{ {} }}",
+                            variant.name(),
+                            named(variant.iter(), &mut field)
+                        ),
+                        VariantInfo::Tuple(variant) => {
+                            format!(
+                                "{}({})",
+                                variant.name(),
+                                unnamed(variant.iter(), &mut field)
+                            )
+                        }
+                        VariantInfo::Unit(variant) => variant.name().to_string(),
+                    })
+                    .collect();
+                format!("enum {{ {} }}", variants.join(", "))
+            }
+            TypeInfo::Value(info) => info.type_path().to_string(),
+        };
+        visiting.pop();
+        let layout = registration.layout();
+        format!(
+            "{description} [size {}, align {}]",
+            layout.size(),
+            layout.align()
+        )
+    }
+
+    fn named<'a>(
+        fields: impl Iterator<Item = &'a NamedField>,
+        describe: &mut impl FnMut(TypeId, &str) -> String,
+    ) -> String {
+        let fields: Vec<_> = fields
+            .map(|field| {
+                let shape = describe(field.type_id(), field.type_path());
+                format!("{}: {shape}", field.name())
+            })
+            .collect();
+        fields.join(", ")
+    }
+
+    fn unnamed<'a>(
+        fields: impl Iterator<Item = &'a UnnamedField>,
+        describe: &mut impl FnMut(TypeId, &str) -> String,
+    ) -> String {
+        let fields: Vec<_> = fields
+            .map(|field| describe(field.type_id(), field.type_path()))
+            .collect();
+        fields.join(", ")
+    }
+
+    describe(registry, type_id, type_path, &mut Vec::new())
+}
+
+/// The values of a type registered by the old version of a plugin, converted to the type with the
+/// same path registered by the new version.
+///
+/// This happens when the new version of the type has a new [`TypeId`], for example because the
+/// library was built with different compiler flags.
+struct Migration {
+    old: TypeId,
+    new: TypeId,
+    components: Vec<(Entity, Box<dyn Reflect>)>,
+    resource: Option<Box<dyn Reflect>>,
+}
+
+fn plan_migrations(
+    world: &World,
+    previous: &TypeRegistry,
+    registry: &TypeRegistry,
+    old_types: &[TypeId],
+) -> Result<Vec<Migration>, DynamicPluginReloadError> {
+    let mut migrations = Vec::new();
+    for &old_type in old_types {
+        let Some(old) = previous.get(old_type) else {
+            continue;
+        };
+        let type_path = old.type_info().type_path();
+        let Some(new) = registry.get_with_type_path(type_path) else {
+            continue;
+        };
+        if new.type_id() == old_type {
+            continue;
+        }
+
+        let error = |reason| DynamicPluginReloadError::Migration {
+            type_path: type_path.to_string(),
+            reason,
+        };
+        let convert = |value: &dyn Reflect| {
+            new.data::<ReflectFromReflect>()
+                .ok_or_else(|| error("the new version doesn't implement `FromReflect`"))?
+                .from_reflect(value)
+                .ok_or_else(|| error("a value could not be converted"))
+        };
+
+        let mut migration = Migration {
+            old: old_type,
+            new: new.type_id(),
+            components: Vec::new(),
+            resource: None,
+        };
+        if let Some(reflect_component) = old.data::<ReflectComponent>() {
+            for entity in world.iter_entities() {
+                let Some(value) = reflect_component.reflect(entity) else {
+                    continue;
+                };
+                if new.data::<ReflectComponent>().is_none() {
+                    return Err(error("the new version isn't a reflected component"));
+                }
+                migration.components.push((entity.id(), convert(value)?));
+            }
+        }
+        if let Some(value) = old
+            .data::<ReflectResource>()
+            .and_then(|reflect_resource| reflect_resource.reflect(world))
+        {
+            if new.data::<ReflectResource>().is_none() {
+                return Err(error("the new version isn't a reflected resource"));
+            }
+            migration.resource = Some(convert(value)?);
+        }
+        migrations.push(migration);
+    }
+    Ok(migrations)
+}
+
+impl Migration {
+    /// Replaces the old values with the new ones. The old type must be in `previous`, and the new
+    /// one in `registry`.
+    fn apply(self, world: &mut World, previous: &TypeRegistry, registry: &TypeRegistry) {
+        let old = previous.get(self.old).unwrap();
+        let new = registry.get(self.new).unwrap();
+
+        if let (Some(old), Some(new)) = (
+            old.data::<ReflectComponent>(),
+            new.data::<ReflectComponent>(),
+        ) {
+            for (entity, value) in self.components {
+                let mut entity = world.entity_mut(entity);
+                old.remove(&mut entity);
+                new.insert(&mut entity, &*value, registry);
+            }
+        }
+        if let (Some(old), Some(new), Some(value)) = (
+            old.data::<ReflectResource>(),
+            new.data::<ReflectResource>(),
+            self.resource,
+        ) {
+            old.remove(world);
+            new.insert(world, &*value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Update;
+    use bevy_ecs::{component::Component, system::ResMut};
+    use bevy_reflect::std_traits::ReflectDefault;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<String>);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Owned(u32);
+
+    #[derive(Reflect, Default)]
+    struct Shared;
+
+    /// Type data added by another plugin.
+    #[derive(Clone)]
+    struct Extra;
+
+    mod old {
+        use super::*;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        #[type_path = "reload_tests"]
+        #[type_name = "Moved"]
+        pub struct Moved(pub u32);
+    }
+
+    mod new {
+        use super::*;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        #[type_path = "reload_tests"]
+        #[type_name = "Moved"]
+        pub struct Moved(pub u32);
+    }
+
+    /// A version of a plugin, logging its name when updated.
+    struct Version {
+        name: &'static str,
+        register: fn(&mut App),
+        panics: bool,
+    }
+
+    impl Version {
+        fn new(name: &'static str) -> Self {
+            Self {
+                name,
+                register: |app| {
+                    app.register_type::<Owned>();
+                },
+                panics: false,
+            }
+        }
+    }
+
+    impl Plugin for Version {
+        fn build(&self, app: &mut App) {
+            let name = self.name;
+            (self.register)(app);
+            app.register_type_data::<Shared, ReflectDefault>()
+                .add_systems(Update, move |mut log: ResMut<Log>| {
+                    log.0.push(name.to_string());
+                });
+            if self.panics {
+                panic!("{name} failed");
+            }
+        }
+
+        fn finish(&self, app: &mut App) {
+            app.world
+                .resource_mut::<Log>()
+                .0
+                .push(format!("finish {}", self.name));
+        }
+    }
+
+    fn load(app: &mut App, version: Version) -> ReloadablePlugin {
+        app.init_resource::<Log>().register_type::<Shared>();
+        let plugin: Box<dyn Plugin> = Box::new(version);
+        let footprint = build_tracked(app, &*plugin).unwrap();
+        if let Some(owned) = app
+            .world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .get_mut(TypeId::of::<Owned>())
+        {
+            owned.insert(Extra);
+        }
+        ReloadablePlugin {
+            path: PathBuf::new(),
+            modified: None,
+            plugin,
+            footprint,
+        }
+    }
+
+    fn update_log(app: &mut App) -> Vec<String> {
+        app.update();
+        std::mem::take(&mut app.world.resource_mut::<Log>().0)
+    }
+
+    #[test]
+    fn replace_plugin() {
+        let mut app = App::new();
+        let mut loaded = load(&mut app, Version::new("v1"));
+        assert_eq!(update_log(&mut app), ["v1"]);
+
+        replace(&mut app, &mut loaded, Box::new(Version::new("v2"))).unwrap();
+        assert_eq!(update_log(&mut app), ["finish v2", "v2"]);
+        assert_eq!(loaded.footprint.types, [TypeId::of::<Owned>()]);
+
+        let registry = app.world.resource::<AppTypeRegistry>().read();
+        let owned = registry.get(TypeId::of::<Owned>()).unwrap();
+        assert!(owned.data::<ReflectComponent>().is_some());
+        assert!(owned.data::<Extra>().is_some());
+        assert!(registry
+            .get_type_data::<ReflectDefault>(TypeId::of::<Shared>())
+            .is_some());
+    }
+
+    #[test]
+    fn restore_plugin() {
+        let mut app = App::new();
+        let mut loaded = load(&mut app, Version::new("v1"));
+
+        let result = replace(
+            &mut app,
+            &mut loaded,
+            Box::new(Version {
+                panics: true,
+                ..Version::new("v2")
+            }),
+        );
+        assert!(
+            matches!(result, Err(DynamicPluginReloadError::Build(message)) if message == "v2 failed")
+        );
+        assert_eq!(update_log(&mut app), ["finish v1", "v1"]);
+        assert_eq!(loaded.footprint.types, [TypeId::of::<Owned>()]);
+
+        let registry = app.world.resource::<AppTypeRegistry>().read();
+        let owned = registry.get(TypeId::of::<Owned>()).unwrap();
+        assert!(owned.data::<Extra>().is_some());
+    }
+
+    #[test]
+    fn shape_describes_nested_types() {
+        #[derive(Reflect)]
+        struct Outer {
+            inner: Owned,
+            items: Vec<Owned>,
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Outer>();
+        registry.register::<Vec<Owned>>();
+        registry.register::<Owned>();
+        let owned = "struct (u32 [size 4, align 4]) [size 4, align 4]";
+        assert_eq!(
+            shape(&registry, TypeId::of::<Outer>(), "Outer"),
+            format!(
+                "struct {{ inner: {owned}, items: [{owned}] [size {}, align {}] }} [size {}, align {}]",
+                std::mem::size_of::<Vec<Owned>>(),
+                std::mem::align_of::<Vec<Owned>>(),
+                std::mem::size_of::<Outer>(),
+                std::mem::align_of::<Outer>(),
+            )
+        );
+    }
+
+    #[test]
+    fn migrate_component() {
+        let mut app = App::new();
+        let mut loaded = load(
+            &mut app,
+            Version {
+                register: |app| {
+                    app.register_type::<old::Moved>();
+                },
+                ..Version::new("v1")
+            },
+        );
+        let entity = app.world.spawn(old::Moved(3)).id();
+
+        let version = Version {
+            register: |app| {
+                app.register_type::<new::Moved>();
+            },
+            ..Version::new("v2")
+        };
+        replace(&mut app, &mut loaded, Box::new(version)).unwrap();
+
+        let entity = app.world.entity(entity);
+        assert!(!entity.contains::<old::Moved>());
+        assert_eq!(entity.get::<new::Moved>().unwrap().0, 3);
+        let registry = app.world.resource::<AppTypeRegistry>().read();
+        assert!(registry.get(TypeId::of::<old::Moved>()).is_none());
+    }
+}
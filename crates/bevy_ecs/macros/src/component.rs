@@ -1,12 +1,30 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Data, DeriveInput, Error, Expr, ExprPath, Fields, Ident, LitStr, Member, Path, Result,
+    Token, Type,
+};
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
     let bevy_ecs_path: Path = crate::bevy_ecs_path();
 
+    let attrs = match parse_event_attr(&ast) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let traversal = attrs
+        .traversal
+        .unwrap_or_else(|| parse_quote! { #bevy_ecs_path::traversal::DefaultTraversal });
+    let auto_propagate = attrs.auto_propagate;
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -17,6 +35,12 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
 
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::event::Event for #struct_name #type_generics #where_clause {
+            type Traversal = #traversal;
+            const AUTO_PROPAGATE: bool = #auto_propagate;
+        }
+
+        impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
+            type Storage = #bevy_ecs_path::component::TableStorage;
         }
     })
 }
@@ -48,8 +72,99 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         Err(e) => return e.into_compile_error().into(),
     };
 
+    let relationship = match parse_relationship_attrs(&ast) {
+        Ok(relationship) => relationship,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let requires = match parse_require_attrs(&ast) {
+        Ok(requires) => requires,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let register_required = requires.iter().map(|Require { path, func }| {
+        let constructor = match func {
+            Some(func) => quote! { #func },
+            None => quote! { <#path as ::core::default::Default>::default },
+        };
+        quote! {
+            required_components.register::<#path>(components, storages, #constructor);
+        }
+    });
+
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
 
+    let on_add = hook_register_function_call(quote! {on_add}, attrs.on_add);
+    let mut on_insert = hook_register_function_call(quote! {on_insert}, attrs.on_insert);
+    let mut on_replace = hook_register_function_call(quote! {on_replace}, attrs.on_replace);
+    let mut on_remove = hook_register_function_call(quote! {on_remove}, attrs.on_remove);
+    let mut on_despawn = hook_register_function_call(quote! {on_despawn}, attrs.on_despawn);
+
+    // Relationships are maintained by hooks, which can't be combined with user-defined ones.
+    let relationship_impl = match &relationship {
+        Some(attrs @ RelationshipAttrs::Relationship { .. }) => {
+            if on_insert.is_some() || on_replace.is_some() {
+                return Error::new(
+                    ast.ident.span(),
+                    "Relationship components can't define their own `on_insert` or `on_replace` hooks",
+                )
+                .into_compile_error()
+                .into();
+            }
+            let relationship_trait =
+                quote! { <Self as #bevy_ecs_path::relationship::Relationship> };
+            on_insert = Some(quote! { hooks.on_insert(#relationship_trait::on_insert); });
+            on_replace = Some(quote! { hooks.on_replace(#relationship_trait::on_replace); });
+            derive_relationship(&ast, &bevy_ecs_path, attrs)
+        }
+        Some(attrs @ RelationshipAttrs::RelationshipTarget { .. }) => {
+            if on_remove.is_some() || on_despawn.is_some() {
+                return Error::new(
+                    ast.ident.span(),
+                    "Relationship target components can't define their own `on_remove` or `on_despawn` hooks",
+                )
+                .into_compile_error()
+                .into();
+            }
+            let target_trait =
+                quote! { <Self as #bevy_ecs_path::relationship::RelationshipTarget> };
+            on_remove = Some(quote! { hooks.on_remove(#target_trait::on_remove); });
+            on_despawn = Some(quote! { hooks.on_despawn(#target_trait::on_despawn); });
+            derive_relationship(&ast, &bevy_ecs_path, attrs)
+        }
+        None => Ok(TokenStream2::new()),
+    };
+    let relationship_impl = match relationship_impl {
+        Ok(relationship_impl) => relationship_impl,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let clone_behavior_path = quote! { #bevy_ecs_path::component::ComponentCloneBehavior };
+    let clone_behavior = match (attrs.clone_behavior, &relationship) {
+        (Some(behavior), _) => quote! { #clone_behavior_path::#behavior },
+        (None, Some(RelationshipAttrs::Relationship { .. })) => quote! {
+            #clone_behavior_path::Custom(<Self as #bevy_ecs_path::relationship::Relationship>::clone_relationship)
+        },
+        (
+            None,
+            Some(RelationshipAttrs::RelationshipTarget {
+                clone_recursive: true,
+                ..
+            }),
+        ) => quote! {
+            #clone_behavior_path::Custom(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::clone_sources)
+        },
+        // The collection of sources is rebuilt by the hooks of the cloned sources.
+        (None, Some(RelationshipAttrs::RelationshipTarget { .. })) => {
+            quote! { #clone_behavior_path::Ignore }
+        }
+        // Use the `Clone` implementation of the component if it has one.
+        (None, None) => quote! {
+            use #bevy_ecs_path::component::{DefaultCloneBehaviorBase, DefaultCloneBehaviorViaClone};
+            (&&#bevy_ecs_path::component::DefaultCloneBehaviorSpecialization::<Self>::default())
+                .default_clone_behavior()
+        },
+    };
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -61,15 +176,83 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             type Storage = #storage;
+
+            #[allow(unused_variables)]
+            fn register_component_hooks(hooks: &mut #bevy_ecs_path::component::ComponentHooks) {
+                #on_add
+                #on_insert
+                #on_replace
+                #on_remove
+                #on_despawn
+            }
+
+            #[allow(unused_variables)]
+            fn register_required_components(
+                components: &mut #bevy_ecs_path::component::Components,
+                storages: &mut #bevy_ecs_path::storage::Storages,
+                required_components: &mut #bevy_ecs_path::component::RequiredComponents,
+            ) {
+                #(#register_required)*
+            }
+
+            fn clone_behavior() -> #clone_behavior_path {
+                #clone_behavior
+            }
         }
+
+        #relationship_impl
     })
 }
 
+pub const EVENT: &str = "event";
+pub const TRAVERSAL: &str = "traversal";
+pub const AUTO_PROPAGATE: &str = "auto_propagate";
+
+struct EventAttrs {
+    traversal: Option<Type>,
+    auto_propagate: bool,
+}
+
+fn parse_event_attr(ast: &DeriveInput) -> Result<EventAttrs> {
+    let mut attrs = EventAttrs {
+        traversal: None,
+        auto_propagate: false,
+    };
+
+    for meta in ast.attrs.iter().filter(|a| a.path().is_ident(EVENT)) {
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(TRAVERSAL) {
+                attrs.traversal = Some(nested.value()?.parse::<Type>()?);
+                Ok(())
+            } else if nested.path.is_ident(AUTO_PROPAGATE) {
+                attrs.auto_propagate = true;
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
+
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
+pub const ON_ADD: &str = "on_add";
+pub const ON_INSERT: &str = "on_insert";
+pub const ON_REPLACE: &str = "on_replace";
+pub const ON_REMOVE: &str = "on_remove";
+pub const ON_DESPAWN: &str = "on_despawn";
+pub const CLONE_BEHAVIOR: &str = "clone_behavior";
 
 struct Attrs {
     storage: StorageTy,
+    on_add: Option<ExprPath>,
+    on_insert: Option<ExprPath>,
+    on_replace: Option<ExprPath>,
+    on_remove: Option<ExprPath>,
+    on_despawn: Option<ExprPath>,
+    clone_behavior: Option<Expr>,
 }
 
 #[derive(Clone, Copy)]
@@ -85,6 +268,12 @@ const SPARSE_SET: &str = "SparseSet";
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        on_add: None,
+        on_insert: None,
+        on_replace: None,
+        on_remove: None,
+        on_despawn: None,
+        clone_behavior: None,
     };
 
     for meta in ast.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
@@ -100,6 +289,24 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
                 Ok(())
+            } else if nested.path.is_ident(ON_ADD) {
+                attrs.on_add = Some(nested.value()?.parse::<ExprPath>()?);
+                Ok(())
+            } else if nested.path.is_ident(ON_INSERT) {
+                attrs.on_insert = Some(nested.value()?.parse::<ExprPath>()?);
+                Ok(())
+            } else if nested.path.is_ident(ON_REPLACE) {
+                attrs.on_replace = Some(nested.value()?.parse::<ExprPath>()?);
+                Ok(())
+            } else if nested.path.is_ident(ON_REMOVE) {
+                attrs.on_remove = Some(nested.value()?.parse::<ExprPath>()?);
+                Ok(())
+            } else if nested.path.is_ident(ON_DESPAWN) {
+                attrs.on_despawn = Some(nested.value()?.parse::<ExprPath>()?);
+                Ok(())
+            } else if nested.path.is_ident(CLONE_BEHAVIOR) {
+                attrs.clone_behavior = Some(nested.value()?.parse::<Expr>()?);
+                Ok(())
             } else {
                 Err(nested.error("Unsupported attribute"))
             }
@@ -109,6 +316,201 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     Ok(attrs)
 }
 
+pub const REQUIRE: &str = "require";
+
+/// A component listed in a `#[require(...)]` attribute, with an optional constructor.
+struct Require {
+    path: Path,
+    func: Option<Expr>,
+}
+
+impl Parse for Require {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let path = input.parse::<Path>()?;
+        let func = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse::<Expr>()?)
+        } else {
+            None
+        };
+        Ok(Require { path, func })
+    }
+}
+
+fn parse_require_attrs(ast: &DeriveInput) -> Result<Vec<Require>> {
+    let mut requires = Vec::new();
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident(REQUIRE)) {
+        let list = attr.parse_args_with(Punctuated::<Require, Token![,]>::parse_terminated)?;
+        requires.extend(list);
+    }
+    Ok(requires)
+}
+
+pub const RELATIONSHIP: &str = "relationship";
+pub const RELATIONSHIP_TARGET: &str = "relationship_target";
+pub const DESPAWN_RECURSIVE: &str = "despawn_recursive";
+pub const CLONE_RECURSIVE: &str = "clone_recursive";
+
+enum RelationshipAttrs {
+    Relationship {
+        relationship_target: Type,
+    },
+    RelationshipTarget {
+        relationship: Type,
+        despawn_recursive: bool,
+        clone_recursive: bool,
+    },
+}
+
+fn parse_relationship_attrs(ast: &DeriveInput) -> Result<Option<RelationshipAttrs>> {
+    let mut result = None;
+
+    for meta in ast.attrs.iter() {
+        if meta.path().is_ident(RELATIONSHIP) {
+            if result.is_some() {
+                return Err(Error::new(
+                    meta.span(),
+                    "A component can only have a single `relationship` or `relationship_target` attribute",
+                ));
+            }
+            let mut relationship_target = None;
+            meta.parse_nested_meta(|nested| {
+                if nested.path.is_ident(RELATIONSHIP_TARGET) {
+                    relationship_target = Some(nested.value()?.parse::<Type>()?);
+                    Ok(())
+                } else {
+                    Err(nested.error("Unsupported attribute"))
+                }
+            })?;
+            let relationship_target = relationship_target.ok_or_else(|| {
+                Error::new(meta.span(), "Missing `relationship_target = ...` argument")
+            })?;
+            result = Some(RelationshipAttrs::Relationship {
+                relationship_target,
+            });
+        } else if meta.path().is_ident(RELATIONSHIP_TARGET) {
+            if result.is_some() {
+                return Err(Error::new(
+                    meta.span(),
+                    "A component can only have a single `relationship` or `relationship_target` attribute",
+                ));
+            }
+            let mut relationship = None;
+            let mut despawn_recursive = false;
+            let mut clone_recursive = false;
+            meta.parse_nested_meta(|nested| {
+                if nested.path.is_ident(RELATIONSHIP) {
+                    relationship = Some(nested.value()?.parse::<Type>()?);
+                    Ok(())
+                } else if nested.path.is_ident(DESPAWN_RECURSIVE) {
+                    despawn_recursive = true;
+                    Ok(())
+                } else if nested.path.is_ident(CLONE_RECURSIVE) {
+                    clone_recursive = true;
+                    Ok(())
+                } else {
+                    Err(nested.error("Unsupported attribute"))
+                }
+            })?;
+            let relationship = relationship
+                .ok_or_else(|| Error::new(meta.span(), "Missing `relationship = ...` argument"))?;
+            result = Some(RelationshipAttrs::RelationshipTarget {
+                relationship,
+                despawn_recursive,
+                clone_recursive,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+fn derive_relationship(
+    ast: &DeriveInput,
+    bevy_ecs_path: &Path,
+    attrs: &RelationshipAttrs,
+) -> Result<TokenStream2> {
+    let Data::Struct(data) = &ast.data else {
+        return Err(Error::new(
+            ast.ident.span(),
+            "Relationships can only be derived for structs",
+        ));
+    };
+    let field = match &data.fields {
+        Fields::Named(fields) if fields.named.len() == 1 => &fields.named[0],
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        _ => {
+            return Err(Error::new(
+                ast.ident.span(),
+                "Relationships must be structs with exactly one field",
+            ))
+        }
+    };
+    let member = field
+        .ident
+        .clone()
+        .map_or_else(|| Member::from(0), Member::Named);
+    let field_type = &field.ty;
+
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    Ok(match attrs {
+        RelationshipAttrs::Relationship {
+            relationship_target,
+        } => quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+
+                #[inline(always)]
+                fn get(&self) -> #bevy_ecs_path::entity::Entity {
+                    self.#member
+                }
+
+                #[inline]
+                fn from(entity: #bevy_ecs_path::entity::Entity) -> Self {
+                    Self { #member: entity }
+                }
+            }
+        },
+        RelationshipAttrs::RelationshipTarget {
+            relationship,
+            despawn_recursive,
+            ..
+        } => {
+            let despawn_policy = if *despawn_recursive {
+                quote! { #bevy_ecs_path::relationship::DespawnPolicy::Recursive }
+            } else {
+                quote! { #bevy_ecs_path::relationship::DespawnPolicy::Orphan }
+            };
+            quote! {
+                impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
+                    type Relationship = #relationship;
+                    type Collection = #field_type;
+
+                    const DESPAWN_POLICY: #bevy_ecs_path::relationship::DespawnPolicy = #despawn_policy;
+
+                    #[inline]
+                    fn collection(&self) -> &Self::Collection {
+                        &self.#member
+                    }
+
+                    #[inline]
+                    fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                        &mut self.#member
+                    }
+
+                    #[inline]
+                    fn from_collection_risky(collection: Self::Collection) -> Self {
+                        Self { #member: collection }
+                    }
+                }
+            }
+        }
+    })
+}
+
 fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     let typename = match ty {
         StorageTy::Table => Ident::new("TableStorage", Span::call_site()),
@@ -117,3 +519,10 @@ fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
 
     quote! { #bevy_ecs_path::component::#typename }
 }
+
+fn hook_register_function_call(
+    hook: TokenStream2,
+    function: Option<ExprPath>,
+) -> Option<TokenStream2> {
+    function.map(|meta| quote! { hooks. #hook (#meta); })
+}
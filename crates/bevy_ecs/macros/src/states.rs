@@ -11,11 +11,17 @@ pub fn derive_states(input: TokenStream) -> TokenStream {
 
     let mut trait_path = bevy_ecs_path();
     trait_path.segments.push(format_ident!("schedule").into());
+    let mut mutable_trait_path = trait_path.clone();
     trait_path.segments.push(format_ident!("States").into());
+    mutable_trait_path
+        .segments
+        .push(format_ident!("FreelyMutableState").into());
     let struct_name = &ast.ident;
 
     quote! {
         impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause {}
+
+        impl #impl_generics #mutable_trait_path for #struct_name #ty_generics #where_clause {}
     }
     .into()
 }
@@ -20,9 +20,10 @@
 //! [`World::archetypes`]: crate::world::World::archetypes
 
 use crate::{
-    bundle::BundleId,
-    component::{ComponentId, StorageType},
+    bundle::{BundleId, BundleInfo, BundleRequiredComponent},
+    component::{ComponentId, Components, StorageType},
     entity::{Entity, EntityLocation},
+    observer::Observers,
     storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
 };
 use std::{
@@ -114,8 +115,31 @@ pub(crate) enum ComponentStatus {
 }
 
 pub(crate) struct AddBundle {
+    /// The target archetype after the bundle is added to the source archetype
     pub archetype_id: ArchetypeId,
+    /// For each component iterated in the same order as the source [`Bundle`](crate::bundle::Bundle),
+    /// indicate if the component is newly added to the target archetype or if it already existed
     pub bundle_status: Vec<ComponentStatus>,
+    /// The components that are newly added to the target archetype by the bundle
+    pub added: Vec<ComponentId>,
+    /// The components of the bundle that were already present in the source archetype
+    pub existing: Vec<ComponentId>,
+    /// The components required by the bundle that are missing from the source archetype,
+    /// and are added to the target archetype alongside the bundle
+    pub required_components: Vec<BundleRequiredComponent>,
+}
+
+impl AddBundle {
+    /// Returns the components inserted by the bundle: the components of the bundle, followed by
+    /// the required components it added.
+    pub(crate) fn iter_inserted<'a>(
+        &'a self,
+        bundle_info: &'a BundleInfo,
+    ) -> impl Iterator<Item = ComponentId> + Clone + 'a {
+        bundle_info
+            .iter_components()
+            .chain(self.required_components.iter().map(|required| required.id))
+    }
 }
 
 /// This trait is used to report the status of [`Bundle`](crate::bundle::Bundle) components
@@ -199,12 +223,18 @@ impl Edges {
         bundle_id: BundleId,
         archetype_id: ArchetypeId,
         bundle_status: Vec<ComponentStatus>,
+        required_components: Vec<BundleRequiredComponent>,
+        added: Vec<ComponentId>,
+        existing: Vec<ComponentId>,
     ) {
         self.add_bundle.insert(
             bundle_id,
             AddBundle {
                 archetype_id,
                 bundle_status,
+                added,
+                existing,
+                required_components,
             },
         );
     }
@@ -298,6 +328,27 @@ struct ArchetypeComponentInfo {
     archetype_component_id: ArchetypeComponentId,
 }
 
+bitflags::bitflags! {
+    /// Flags used to keep track of metadata about the component in this [`Archetype`]
+    ///
+    /// Used primarily to early-out when there are no [`ComponentHook`] registered for any contained components,
+    /// or no [`Observer`] watching for any of them.
+    ///
+    /// [`ComponentHook`]: crate::component::ComponentHook
+    /// [`Observer`]: crate::observer::Observer
+    #[derive(Clone, Copy)]
+    pub(crate) struct ArchetypeFlags: u32 {
+        const ON_ADD_HOOK        = (1 << 0);
+        const ON_INSERT_HOOK     = (1 << 1);
+        const ON_REPLACE_HOOK    = (1 << 2);
+        const ON_REMOVE_HOOK     = (1 << 3);
+        const ON_DESPAWN_HOOK    = (1 << 4);
+        const ON_ADD_OBSERVER    = (1 << 5);
+        const ON_INSERT_OBSERVER = (1 << 6);
+        const ON_REMOVE_OBSERVER = (1 << 7);
+    }
+}
+
 /// Metadata for a single archetype within a [`World`].
 ///
 /// For more information, see the *[module level documentation]*.
@@ -310,10 +361,13 @@ pub struct Archetype {
     edges: Edges,
     entities: Vec<ArchetypeEntity>,
     components: ImmutableSparseSet<ComponentId, ArchetypeComponentInfo>,
+    flags: ArchetypeFlags,
 }
 
 impl Archetype {
     pub(crate) fn new(
+        components: &Components,
+        observers: &Observers,
         id: ArchetypeId,
         table_id: TableId,
         table_components: impl Iterator<Item = (ComponentId, ArchetypeComponentId)>,
@@ -321,9 +375,14 @@ impl Archetype {
     ) -> Self {
         let (min_table, _) = table_components.size_hint();
         let (min_sparse, _) = sparse_set_components.size_hint();
-        let mut components = SparseSet::with_capacity(min_table + min_sparse);
+        let mut flags = ArchetypeFlags::empty();
+        let mut archetype_components = SparseSet::with_capacity(min_table + min_sparse);
         for (component_id, archetype_component_id) in table_components {
-            components.insert(
+            // SAFETY: We are creating an archetype that includes this component so it must exist
+            let info = unsafe { components.get_info_unchecked(component_id) };
+            info.update_archetype_flags(&mut flags);
+            observers.update_archetype_flags(component_id, &mut flags);
+            archetype_components.insert(
                 component_id,
                 ArchetypeComponentInfo {
                     storage_type: StorageType::Table,
@@ -333,7 +392,11 @@ impl Archetype {
         }
 
         for (component_id, archetype_component_id) in sparse_set_components {
-            components.insert(
+            // SAFETY: We are creating an archetype that includes this component so it must exist
+            let info = unsafe { components.get_info_unchecked(component_id) };
+            info.update_archetype_flags(&mut flags);
+            observers.update_archetype_flags(component_id, &mut flags);
+            archetype_components.insert(
                 component_id,
                 ArchetypeComponentInfo {
                     storage_type: StorageType::SparseSet,
@@ -345,8 +408,9 @@ impl Archetype {
             id,
             table_id,
             entities: Vec::new(),
-            components: components.into_immutable(),
+            components: archetype_components.into_immutable(),
             edges: Default::default(),
+            flags,
         }
     }
 
@@ -536,6 +600,60 @@ impl Archetype {
     pub(crate) fn clear_entities(&mut self) {
         self.entities.clear();
     }
+
+    /// Returns true if any of the components in this archetype have `on_add` hooks
+    #[inline]
+    pub fn has_on_add(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_ADD_HOOK)
+    }
+
+    /// Returns true if any of the components in this archetype have `on_insert` hooks
+    #[inline]
+    pub fn has_on_insert(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_INSERT_HOOK)
+    }
+
+    /// Returns true if any of the components in this archetype have `on_replace` hooks
+    #[inline]
+    pub fn has_on_replace(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_REPLACE_HOOK)
+    }
+
+    /// Returns true if any of the components in this archetype have `on_remove` hooks
+    #[inline]
+    pub fn has_on_remove(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_REMOVE_HOOK)
+    }
+
+    /// Returns true if any of the components in this archetype have `on_despawn` hooks
+    #[inline]
+    pub fn has_on_despawn(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_DESPAWN_HOOK)
+    }
+
+    /// Returns true if any of the components in this archetype have at least one [`OnAdd`] observer
+    ///
+    /// [`OnAdd`]: crate::world::OnAdd
+    #[inline]
+    pub fn has_add_observer(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_ADD_OBSERVER)
+    }
+
+    /// Returns true if any of the components in this archetype have at least one [`OnInsert`] observer
+    ///
+    /// [`OnInsert`]: crate::world::OnInsert
+    #[inline]
+    pub fn has_insert_observer(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_INSERT_OBSERVER)
+    }
+
+    /// Returns true if any of the components in this archetype have at least one [`OnRemove`] observer
+    ///
+    /// [`OnRemove`]: crate::world::OnRemove
+    #[inline]
+    pub fn has_remove_observer(&self) -> bool {
+        self.flags.contains(ArchetypeFlags::ON_REMOVE_OBSERVER)
+    }
 }
 
 /// The next [`ArchetypeId`] in an [`Archetypes`] collection.
@@ -625,7 +743,16 @@ impl Archetypes {
             by_components: Default::default(),
             archetype_component_count: 0,
         };
-        archetypes.get_id_or_insert(TableId::empty(), Vec::new(), Vec::new());
+        // SAFETY: Empty archetype has no components
+        unsafe {
+            archetypes.get_id_or_insert(
+                &Components::default(),
+                &Observers::default(),
+                TableId::empty(),
+                Vec::new(),
+                Vec::new(),
+            );
+        }
         archetypes
     }
 
@@ -702,8 +829,11 @@ impl Archetypes {
     ///
     /// # Safety
     /// [`TableId`] must exist in tables
-    pub(crate) fn get_id_or_insert(
+    /// `table_components` and `sparse_set_components` must exist in `components`
+    pub(crate) unsafe fn get_id_or_insert(
         &mut self,
+        components: &Components,
+        observers: &Observers,
         table_id: TableId,
         table_components: Vec<ComponentId>,
         sparse_set_components: Vec<ComponentId>,
@@ -729,6 +859,8 @@ impl Archetypes {
                 let sparse_set_archetype_components =
                     (sparse_start..*archetype_component_count).map(ArchetypeComponentId);
                 archetypes.push(Archetype::new(
+                    components,
+                    observers,
                     id,
                     table_id,
                     table_components.into_iter().zip(table_archetype_components),
@@ -740,6 +872,26 @@ impl Archetypes {
             })
     }
 
+    /// Updates the given `flags` of every archetype containing `component_id`.
+    ///
+    /// When `set` is true the flags are inserted. Otherwise they are kept only on archetypes
+    /// that contain at least one component for which `keep` returns true.
+    pub(crate) fn update_flags(
+        &mut self,
+        component_id: ComponentId,
+        flags: ArchetypeFlags,
+        set: bool,
+        keep: impl Fn(ComponentId) -> bool,
+    ) {
+        for archetype in &mut self.archetypes {
+            if !archetype.contains(component_id) {
+                continue;
+            }
+            let value = set || archetype.components().any(&keep);
+            archetype.flags.set(flags, value);
+        }
+    }
+
     /// Returns the number of components that are stored in archetypes.
     /// Note that if some component `T` is stored in more than one archetype, it will be counted once for each archetype it's present in.
     #[inline]
@@ -7,18 +7,24 @@ use bevy_utils::{HashMap, HashSet};
 
 use crate::{
     archetype::{
-        Archetype, ArchetypeId, Archetypes, BundleComponentStatus, ComponentStatus,
+        AddBundle, Archetype, ArchetypeId, Archetypes, BundleComponentStatus, ComponentStatus,
         SpawnBundleStatus,
     },
-    component::{Component, ComponentId, ComponentStorage, Components, StorageType, Tick},
+    component::{
+        Component, ComponentId, ComponentStorage, Components, RequiredComponentConstructor,
+        StorageType, Tick,
+    },
     entity::{Entities, Entity, EntityLocation},
+    observer::Observers,
     query::DebugCheckedUnwrap,
     storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World, ON_ADD, ON_INSERT},
     TypeIdMap,
 };
 use bevy_ptr::OwningPtr;
 use bevy_utils::all_tuples;
 use std::any::TypeId;
+use std::ptr::NonNull;
 
 /// The `Bundle` trait enables insertion and removal of [`Component`]s from an entity.
 ///
@@ -284,6 +290,17 @@ pub struct BundleInfo {
     // must have its storage initialized (i.e. columns created in tables, sparse set created),
     // and must be in the same order as the source bundle type writes its components in.
     component_ids: Vec<ComponentId>,
+    // SAFETY: Every ID in this list must be valid within the World that owns the BundleInfo,
+    // must have its storage initialized, and must not be in `component_ids`.
+    required_components: Vec<BundleRequiredComponent>,
+}
+
+/// A component that isn't part of a bundle, but is required by one of its components.
+#[derive(Clone)]
+pub(crate) struct BundleRequiredComponent {
+    pub(crate) id: ComponentId,
+    pub(crate) storage_type: StorageType,
+    pub(crate) constructor: RequiredComponentConstructor,
 }
 
 impl BundleInfo {
@@ -326,11 +343,38 @@ impl BundleInfo {
             panic!("Bundle {bundle_type_name} has duplicate components: {names}");
         }
 
+        let mut required_components: Vec<BundleRequiredComponent> = Vec::new();
+        for &component_id in &component_ids {
+            // SAFETY: the caller ensures component_id is valid.
+            let info = unsafe { components.get_info_unchecked(component_id) };
+            for (required_id, constructor) in info.required_components().iter() {
+                if component_ids.contains(&required_id)
+                    || required_components.iter().any(|r| r.id == required_id)
+                {
+                    continue;
+                }
+                required_components.push(BundleRequiredComponent {
+                    id: required_id,
+                    // SAFETY: required components are initialized when registering the component requiring them.
+                    storage_type: unsafe {
+                        components.get_info_unchecked(required_id).storage_type()
+                    },
+                    constructor: constructor.clone(),
+                });
+            }
+        }
+
         // SAFETY: The caller ensures that component_ids:
         // - is valid for the associated world
         // - has had its storage initialized
         // - is in the same order as the source bundle type
-        BundleInfo { id, component_ids }
+        // Required components were initialized, along with their storage, when the component
+        // requiring them was, and were filtered to exclude `component_ids`.
+        BundleInfo {
+            id,
+            component_ids,
+            required_components,
+        }
     }
 
     /// Returns a value identifying the associated [`Bundle`] type.
@@ -345,86 +389,27 @@ impl BundleInfo {
         &self.component_ids
     }
 
-    pub(crate) fn get_bundle_inserter<'a, 'b>(
-        &'b self,
-        entities: &'a mut Entities,
-        archetypes: &'a mut Archetypes,
-        components: &Components,
-        storages: &'a mut Storages,
-        archetype_id: ArchetypeId,
-        change_tick: Tick,
-    ) -> BundleInserter<'a, 'b> {
-        let new_archetype_id =
-            self.add_bundle_to_archetype(archetypes, storages, components, archetype_id);
-        let archetypes_ptr = archetypes.archetypes.as_mut_ptr();
-        if new_archetype_id == archetype_id {
-            let archetype = &mut archetypes[archetype_id];
-            let table_id = archetype.table_id();
-            BundleInserter {
-                bundle_info: self,
-                archetype,
-                entities,
-                sparse_sets: &mut storages.sparse_sets,
-                table: &mut storages.tables[table_id],
-                archetypes_ptr,
-                change_tick,
-                result: InsertBundleResult::SameArchetype,
-            }
-        } else {
-            let (archetype, new_archetype) = archetypes.get_2_mut(archetype_id, new_archetype_id);
-            let table_id = archetype.table_id();
-            if table_id == new_archetype.table_id() {
-                BundleInserter {
-                    bundle_info: self,
-                    archetype,
-                    archetypes_ptr,
-                    entities,
-                    sparse_sets: &mut storages.sparse_sets,
-                    table: &mut storages.tables[table_id],
-                    change_tick,
-                    result: InsertBundleResult::NewArchetypeSameTable { new_archetype },
-                }
-            } else {
-                let (table, new_table) = storages
-                    .tables
-                    .get_2_mut(table_id, new_archetype.table_id());
-                BundleInserter {
-                    bundle_info: self,
-                    archetype,
-                    sparse_sets: &mut storages.sparse_sets,
-                    entities,
-                    archetypes_ptr,
-                    table,
-                    change_tick,
-                    result: InsertBundleResult::NewArchetypeNewTable {
-                        new_archetype,
-                        new_table,
-                    },
-                }
-            }
-        }
+    /// Returns an iterator over the [ID](ComponentId) of each component stored in this bundle.
+    #[inline]
+    pub fn iter_components(&self) -> impl Iterator<Item = ComponentId> + Clone + '_ {
+        self.component_ids.iter().cloned()
     }
 
-    pub(crate) fn get_bundle_spawner<'a, 'b>(
-        &'b self,
-        entities: &'a mut Entities,
-        archetypes: &'a mut Archetypes,
-        components: &Components,
-        storages: &'a mut Storages,
-        change_tick: Tick,
-    ) -> BundleSpawner<'a, 'b> {
-        let new_archetype_id =
-            self.add_bundle_to_archetype(archetypes, storages, components, ArchetypeId::EMPTY);
-        let archetype = &mut archetypes[new_archetype_id];
-        let table = &mut storages.tables[archetype.table_id()];
-        BundleSpawner {
-            archetype,
-            bundle_info: self,
-            table,
-            entities,
-            sparse_sets: &mut storages.sparse_sets,
-            change_tick,
-        }
+    /// Returns an iterator over the [ID](ComponentId) of each component required by the components
+    /// of this bundle, but not stored in it.
+    ///
+    /// These are inserted alongside the bundle when they are missing from the entity.
+    #[inline]
+    pub fn iter_required_components(&self) -> impl Iterator<Item = ComponentId> + Clone + '_ {
+        self.required_components.iter().map(|required| required.id)
+    }
+
+    /// Returns an iterator over the [ID](ComponentId) of each component stored in this bundle,
+    /// followed by the components they require.
+    #[inline]
+    pub fn iter_contributed_components(&self) -> impl Iterator<Item = ComponentId> + Clone + '_ {
+        self.iter_components()
+            .chain(self.iter_required_components())
     }
 
     /// This writes components from a given [`Bundle`] to the given entity.
@@ -441,6 +426,9 @@ impl BundleInfo {
     /// to look up the [`AddBundle`](crate::archetype::AddBundle) in the archetype graph, which requires
     /// ownership of the entity's current archetype.
     ///
+    /// `required_components` must be the components required by this bundle that are missing from
+    /// the entity's original archetype, which are constructed and initialized after the bundle is written.
+    ///
     /// `table` must be the "new" table for `entity`. `table_row` must have space allocated for the
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
     #[inline]
@@ -450,6 +438,7 @@ impl BundleInfo {
         table: &mut Table,
         sparse_sets: &mut SparseSets,
         bundle_component_status: &S,
+        required_components: &[BundleRequiredComponent],
         entity: Entity,
         table_row: TableRow,
         change_tick: Tick,
@@ -486,6 +475,27 @@ impl BundleInfo {
             }
             bundle_component += 1;
         });
+
+        for required in required_components {
+            required
+                .constructor
+                .construct(&mut |component_ptr| match required.storage_type {
+                    StorageType::Table => {
+                        let column =
+                        // SAFETY: Required components missing from the original archetype are part of
+                        // the new archetype, so the target table contains the component.
+                        unsafe { table.get_column_mut(required.id).debug_checked_unwrap() };
+                        column.initialize(table_row, component_ptr, change_tick);
+                    }
+                    StorageType::SparseSet => {
+                        let sparse_set =
+                        // SAFETY: Required components have their storage initialized when registered,
+                        // so a sparse set exists for the component.
+                        unsafe { sparse_sets.get_mut(required.id).debug_checked_unwrap() };
+                        sparse_set.insert(entity, component_ptr, change_tick);
+                    }
+                });
+        }
     }
 
     /// Adds a bundle to the given archetype and returns the resulting archetype. This could be the
@@ -496,6 +506,7 @@ impl BundleInfo {
         archetypes: &mut Archetypes,
         storages: &mut Storages,
         components: &Components,
+        observers: &Observers,
         archetype_id: ArchetypeId,
     ) -> ArchetypeId {
         if let Some(add_bundle_id) = archetypes[archetype_id].edges().get_add_bundle(self.id) {
@@ -504,13 +515,17 @@ impl BundleInfo {
         let mut new_table_components = Vec::new();
         let mut new_sparse_set_components = Vec::new();
         let mut bundle_status = Vec::with_capacity(self.component_ids.len());
+        let mut added = Vec::new();
+        let mut existing = Vec::new();
 
         let current_archetype = &mut archetypes[archetype_id];
         for component_id in self.component_ids.iter().cloned() {
             if current_archetype.contains(component_id) {
                 bundle_status.push(ComponentStatus::Mutated);
+                existing.push(component_id);
             } else {
                 bundle_status.push(ComponentStatus::Added);
+                added.push(component_id);
                 // SAFETY: component_id exists
                 let component_info = unsafe { components.get_info_unchecked(component_id) };
                 match component_info.storage_type() {
@@ -520,10 +535,29 @@ impl BundleInfo {
             }
         }
 
+        let mut required_components = Vec::new();
+        for required in &self.required_components {
+            if !current_archetype.contains(required.id) {
+                added.push(required.id);
+                required_components.push(required.clone());
+                match required.storage_type {
+                    StorageType::Table => new_table_components.push(required.id),
+                    StorageType::SparseSet => new_sparse_set_components.push(required.id),
+                }
+            }
+        }
+
         if new_table_components.is_empty() && new_sparse_set_components.is_empty() {
             let edges = current_archetype.edges_mut();
             // the archetype does not change when we add this bundle
-            edges.insert_add_bundle(self.id, archetype_id, bundle_status);
+            edges.insert_add_bundle(
+                self.id,
+                archetype_id,
+                bundle_status,
+                required_components,
+                added,
+                existing,
+            );
             archetype_id
         } else {
             let table_id;
@@ -559,80 +593,215 @@ impl BundleInfo {
                     new_sparse_set_components
                 };
             };
-            let new_archetype_id =
-                archetypes.get_id_or_insert(table_id, table_components, sparse_set_components);
+            // SAFETY: ids in self must be valid
+            let new_archetype_id = unsafe {
+                archetypes.get_id_or_insert(
+                    components,
+                    observers,
+                    table_id,
+                    table_components,
+                    sparse_set_components,
+                )
+            };
             // add an edge from the old archetype to the new archetype
             archetypes[archetype_id].edges_mut().insert_add_bundle(
                 self.id,
                 new_archetype_id,
                 bundle_status,
+                required_components,
+                added,
+                existing,
             );
             new_archetype_id
         }
     }
 }
 
-pub(crate) struct BundleInserter<'a, 'b> {
-    pub(crate) archetype: &'a mut Archetype,
-    pub(crate) entities: &'a mut Entities,
-    bundle_info: &'b BundleInfo,
-    table: &'a mut Table,
-    sparse_sets: &'a mut SparseSets,
-    result: InsertBundleResult<'a>,
-    archetypes_ptr: *mut Archetype,
+// SAFETY: We have exclusive world access so our pointers can't be invalidated externally
+pub(crate) struct BundleInserter<'w> {
+    world: UnsafeWorldCell<'w>,
+    bundle_info: NonNull<BundleInfo>,
+    add_bundle: NonNull<AddBundle>,
+    table: NonNull<Table>,
+    archetype: NonNull<Archetype>,
+    result: InsertBundleResult,
     change_tick: Tick,
 }
 
-pub(crate) enum InsertBundleResult<'a> {
+pub(crate) enum InsertBundleResult {
     SameArchetype,
     NewArchetypeSameTable {
-        new_archetype: &'a mut Archetype,
+        new_archetype: NonNull<Archetype>,
     },
     NewArchetypeNewTable {
-        new_archetype: &'a mut Archetype,
-        new_table: &'a mut Table,
+        new_archetype: NonNull<Archetype>,
+        new_table: NonNull<Table>,
     },
 }
 
-impl<'a, 'b> BundleInserter<'a, 'b> {
+impl<'w> BundleInserter<'w> {
+    #[inline]
+    pub(crate) fn new<T: Bundle>(
+        world: &'w mut World,
+        archetype_id: ArchetypeId,
+        change_tick: Tick,
+    ) -> Self {
+        let bundle_id = world
+            .bundles
+            .init_info::<T>(&mut world.components, &mut world.storages);
+        // SAFETY: We just ensured this bundle exists
+        unsafe { Self::new_with_id(world, archetype_id, bundle_id, change_tick) }
+    }
+
+    /// Creates a new [`BundleInserter`].
+    ///
+    /// # Safety
+    /// - Caller must ensure that `bundle_id` exists in `world.bundles`
+    #[inline]
+    pub(crate) unsafe fn new_with_id(
+        world: &'w mut World,
+        archetype_id: ArchetypeId,
+        bundle_id: BundleId,
+        change_tick: Tick,
+    ) -> Self {
+        // SAFETY: We will not make any accesses to the command queue, component or resource data of this world
+        let bundle_info = world.bundles.get_unchecked(bundle_id);
+        let bundle_id = bundle_info.id();
+        let new_archetype_id = bundle_info.add_bundle_to_archetype(
+            &mut world.archetypes,
+            &mut world.storages,
+            &world.components,
+            &world.observers,
+            archetype_id,
+        );
+        if new_archetype_id == archetype_id {
+            let archetype = &mut world.archetypes[archetype_id];
+            // SAFETY: The edge is assured to be initialized when we called add_bundle_to_archetype
+            let add_bundle = unsafe {
+                archetype
+                    .edges()
+                    .get_add_bundle_internal(bundle_id)
+                    .debug_checked_unwrap()
+            };
+            let table_id = archetype.table_id();
+            let table = &mut world.storages.tables[table_id];
+            Self {
+                add_bundle: add_bundle.into(),
+                archetype: archetype.into(),
+                bundle_info: bundle_info.into(),
+                table: table.into(),
+                result: InsertBundleResult::SameArchetype,
+                change_tick,
+                world: world.as_unsafe_world_cell(),
+            }
+        } else {
+            let (archetype, new_archetype) =
+                world.archetypes.get_2_mut(archetype_id, new_archetype_id);
+            // SAFETY: The edge is assured to be initialized when we called add_bundle_to_archetype
+            let add_bundle = unsafe {
+                archetype
+                    .edges()
+                    .get_add_bundle_internal(bundle_id)
+                    .debug_checked_unwrap()
+            };
+            let table_id = archetype.table_id();
+            let new_table_id = new_archetype.table_id();
+            if table_id == new_table_id {
+                let table = &mut world.storages.tables[table_id];
+                Self {
+                    add_bundle: add_bundle.into(),
+                    archetype: archetype.into(),
+                    bundle_info: bundle_info.into(),
+                    table: table.into(),
+                    result: InsertBundleResult::NewArchetypeSameTable {
+                        new_archetype: new_archetype.into(),
+                    },
+                    change_tick,
+                    world: world.as_unsafe_world_cell(),
+                }
+            } else {
+                let (table, new_table) = world.storages.tables.get_2_mut(table_id, new_table_id);
+                Self {
+                    add_bundle: add_bundle.into(),
+                    archetype: archetype.into(),
+                    bundle_info: bundle_info.into(),
+                    table: table.into(),
+                    result: InsertBundleResult::NewArchetypeNewTable {
+                        new_archetype: new_archetype.into(),
+                        new_table: new_table.into(),
+                    },
+                    change_tick,
+                    world: world.as_unsafe_world_cell(),
+                }
+            }
+        }
+    }
+
     /// # Safety
-    /// `entity` must currently exist in the source archetype for this inserter. `archetype_row`
+    /// `entity` must currently exist in the source archetype for this inserter. `location`
     /// must be `entity`'s location in the archetype. `T` must match this [`BundleInfo`]'s type
     #[inline]
-    pub unsafe fn insert<T: DynamicBundle>(
+    pub(crate) unsafe fn insert<T: DynamicBundle>(
         &mut self,
         entity: Entity,
         location: EntityLocation,
         bundle: T,
     ) -> EntityLocation {
-        match &mut self.result {
+        let bundle_info = self.bundle_info.as_ref();
+        let add_bundle = self.add_bundle.as_ref();
+
+        if self.archetype.as_ref().has_on_replace() {
+            // SAFETY: There are no outstanding references to world, the components of the bundle
+            // that are being replaced still hold their old values and exist in the World
+            unsafe {
+                let archetype = self.archetype.as_ref();
+                self.world.into_deferred().trigger_on_replace(
+                    archetype,
+                    entity,
+                    add_bundle.existing.iter().cloned(),
+                );
+            }
+        }
+
+        let table = self.table.as_mut();
+        let archetype = self.archetype.as_mut();
+
+        let (new_archetype, new_location) = match &mut self.result {
             InsertBundleResult::SameArchetype => {
-                // PERF: this could be looked up during Inserter construction and stored (but borrowing makes this nasty)
-                // SAFETY: The edge is assured to be initialized when creating the BundleInserter
-                let add_bundle = unsafe {
-                    self.archetype
-                        .edges()
-                        .get_add_bundle_internal(self.bundle_info.id)
-                        .debug_checked_unwrap()
+                // SAFETY: Mutable references do not alias and will be dropped after this block
+                let sparse_sets = {
+                    let world = self.world.world_mut();
+                    &mut world.storages.sparse_sets
                 };
-                self.bundle_info.write_components(
-                    self.table,
-                    self.sparse_sets,
+
+                bundle_info.write_components(
+                    table,
+                    sparse_sets,
                     add_bundle,
+                    &add_bundle.required_components,
                     entity,
                     location.table_row,
                     self.change_tick,
                     bundle,
                 );
-                location
+
+                (archetype, location)
             }
             InsertBundleResult::NewArchetypeSameTable { new_archetype } => {
-                let result = self.archetype.swap_remove(location.archetype_row);
+                let new_archetype = new_archetype.as_mut();
+
+                // SAFETY: Mutable references do not alias and will be dropped after this block
+                let (sparse_sets, entities) = {
+                    let world = self.world.world_mut();
+                    (&mut world.storages.sparse_sets, &mut world.entities)
+                };
+
+                let result = archetype.swap_remove(location.archetype_row);
                 if let Some(swapped_entity) = result.swapped_entity {
                     let swapped_location =
                         // SAFETY: If the swap was successful, swapped_entity must be valid.
-                        unsafe { self.entities.get(swapped_entity).debug_checked_unwrap() };
-                    self.entities.set(
+                        unsafe { entities.get(swapped_entity).debug_checked_unwrap() };
+                    entities.set(
                         swapped_entity.index(),
                         EntityLocation {
                             archetype_id: swapped_location.archetype_id,
@@ -643,37 +812,43 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
                     );
                 }
                 let new_location = new_archetype.allocate(entity, result.table_row);
-                self.entities.set(entity.index(), new_location);
-
-                // PERF: this could be looked up during Inserter construction and stored (but borrowing makes this nasty)
-                // SAFETY: The edge is assured to be initialized when creating the BundleInserter
-                let add_bundle = unsafe {
-                    self.archetype
-                        .edges()
-                        .get_add_bundle_internal(self.bundle_info.id)
-                        .debug_checked_unwrap()
-                };
-                self.bundle_info.write_components(
-                    self.table,
-                    self.sparse_sets,
+                entities.set(entity.index(), new_location);
+                bundle_info.write_components(
+                    table,
+                    sparse_sets,
                     add_bundle,
+                    &add_bundle.required_components,
                     entity,
                     result.table_row,
                     self.change_tick,
                     bundle,
                 );
-                new_location
+
+                (new_archetype, new_location)
             }
             InsertBundleResult::NewArchetypeNewTable {
                 new_archetype,
                 new_table,
             } => {
-                let result = self.archetype.swap_remove(location.archetype_row);
+                let new_table = new_table.as_mut();
+                let new_archetype = new_archetype.as_mut();
+
+                // SAFETY: Mutable references do not alias and will be dropped after this block
+                let (archetypes_ptr, sparse_sets, entities) = {
+                    let world = self.world.world_mut();
+                    let archetype_ptr: *mut Archetype = world.archetypes.archetypes.as_mut_ptr();
+                    (
+                        archetype_ptr,
+                        &mut world.storages.sparse_sets,
+                        &mut world.entities,
+                    )
+                };
+                let result = archetype.swap_remove(location.archetype_row);
                 if let Some(swapped_entity) = result.swapped_entity {
                     let swapped_location =
                         // SAFETY: If the swap was successful, swapped_entity must be valid.
-                        unsafe { self.entities.get(swapped_entity).debug_checked_unwrap() };
-                    self.entities.set(
+                        unsafe { entities.get(swapped_entity).debug_checked_unwrap() };
+                    entities.set(
                         swapped_entity.index(),
                         EntityLocation {
                             archetype_id: swapped_location.archetype_id,
@@ -685,30 +860,17 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
                 }
                 // PERF: store "non bundle" components in edge, then just move those to avoid
                 // redundant copies
-                let move_result = self
-                    .table
-                    .move_to_superset_unchecked(result.table_row, new_table);
+                let move_result = table.move_to_superset_unchecked(result.table_row, new_table);
                 let new_location = new_archetype.allocate(entity, move_result.new_row);
-                self.entities.set(entity.index(), new_location);
+                entities.set(entity.index(), new_location);
 
                 // if an entity was moved into this entity's table spot, update its table row
                 if let Some(swapped_entity) = move_result.swapped_entity {
                     let swapped_location =
                         // SAFETY: If the swap was successful, swapped_entity must be valid.
-                        unsafe { self.entities.get(swapped_entity).debug_checked_unwrap() };
-                    let swapped_archetype = if self.archetype.id() == swapped_location.archetype_id
-                    {
-                        &mut *self.archetype
-                    } else if new_archetype.id() == swapped_location.archetype_id {
-                        new_archetype
-                    } else {
-                        // SAFETY: the only two borrowed archetypes are above and we just did collision checks
-                        &mut *self
-                            .archetypes_ptr
-                            .add(swapped_location.archetype_id.index())
-                    };
+                        unsafe { entities.get(swapped_entity).debug_checked_unwrap() };
 
-                    self.entities.set(
+                    entities.set(
                         swapped_entity.index(),
                         EntityLocation {
                             archetype_id: swapped_location.archetype_id,
@@ -717,47 +879,134 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
                             table_row: result.table_row,
                         },
                     );
-                    swapped_archetype
-                        .set_entity_table_row(swapped_location.archetype_row, result.table_row);
+
+                    if archetype.id() == swapped_location.archetype_id {
+                        archetype
+                            .set_entity_table_row(swapped_location.archetype_row, result.table_row);
+                    } else if new_archetype.id() == swapped_location.archetype_id {
+                        new_archetype
+                            .set_entity_table_row(swapped_location.archetype_row, result.table_row);
+                    } else {
+                        // SAFETY: the only two borrowed archetypes are above and we just did collision checks
+                        (*archetypes_ptr.add(swapped_location.archetype_id.index()))
+                            .set_entity_table_row(swapped_location.archetype_row, result.table_row);
+                    }
                 }
 
-                // PERF: this could be looked up during Inserter construction and stored (but borrowing makes this nasty)
-                // SAFETY: The edge is assured to be initialized when creating the BundleInserter
-                let add_bundle = unsafe {
-                    self.archetype
-                        .edges()
-                        .get_add_bundle_internal(self.bundle_info.id)
-                        .debug_checked_unwrap()
-                };
-                self.bundle_info.write_components(
+                bundle_info.write_components(
                     new_table,
-                    self.sparse_sets,
+                    sparse_sets,
                     add_bundle,
+                    &add_bundle.required_components,
                     entity,
                     move_result.new_row,
                     self.change_tick,
                     bundle,
                 );
-                new_location
+
+                (new_archetype, new_location)
+            }
+        };
+
+        let new_archetype = &*new_archetype;
+        // SAFETY: We have no outstanding mutable references to world as they were dropped
+        let mut deferred_world = unsafe { self.world.into_deferred() };
+
+        // SAFETY: All components in the bundle are guaranteed to exist in the World
+        // as they must be initialized before creating the BundleInfo.
+        unsafe {
+            deferred_world.trigger_on_add(new_archetype, entity, add_bundle.added.iter().cloned());
+            if new_archetype.has_add_observer() {
+                deferred_world.trigger_observers(ON_ADD, entity, add_bundle.added.iter().cloned());
+            }
+            deferred_world.trigger_on_insert(
+                new_archetype,
+                entity,
+                add_bundle.iter_inserted(bundle_info),
+            );
+            if new_archetype.has_insert_observer() {
+                deferred_world.trigger_observers(
+                    ON_INSERT,
+                    entity,
+                    add_bundle.iter_inserted(bundle_info),
+                );
             }
         }
+
+        new_location
+    }
+
+    #[inline]
+    pub(crate) fn entities(&mut self) -> &mut Entities {
+        // SAFETY: No outstanding references to self.world, changes to entities cannot invalidate our internal pointers
+        unsafe { &mut self.world.world_mut().entities }
+    }
+
+    /// Flushes any entities reserved since the last flush, e.g. by hooks spawning through [`Commands`](crate::system::Commands).
+    #[inline]
+    pub(crate) fn flush_entities(&mut self) {
+        // SAFETY: No outstanding references to self.world, flushing only allocates entities into the
+        // empty archetype, which does not move any archetype or table
+        unsafe { self.world.world_mut().flush() };
     }
 }
 
-pub(crate) struct BundleSpawner<'a, 'b> {
-    pub(crate) archetype: &'a mut Archetype,
-    pub(crate) entities: &'a mut Entities,
-    bundle_info: &'b BundleInfo,
-    table: &'a mut Table,
-    sparse_sets: &'a mut SparseSets,
+// SAFETY: We have exclusive world access so our pointers can't be invalidated externally
+pub(crate) struct BundleSpawner<'w> {
+    world: UnsafeWorldCell<'w>,
+    bundle_info: NonNull<BundleInfo>,
+    table: NonNull<Table>,
+    archetype: NonNull<Archetype>,
     change_tick: Tick,
 }
 
-impl<'a, 'b> BundleSpawner<'a, 'b> {
+impl<'w> BundleSpawner<'w> {
+    #[inline]
+    pub fn new<T: Bundle>(world: &'w mut World, change_tick: Tick) -> Self {
+        let bundle_id = world
+            .bundles
+            .init_info::<T>(&mut world.components, &mut world.storages);
+        // SAFETY: we initialized this bundle_id in `init_info`
+        unsafe { Self::new_with_id(world, bundle_id, change_tick) }
+    }
+
+    /// Creates a new [`BundleSpawner`].
+    ///
+    /// # Safety
+    /// - `bundle_id` must be a valid id in `world.bundles`
+    #[inline]
+    pub(crate) unsafe fn new_with_id(
+        world: &'w mut World,
+        bundle_id: BundleId,
+        change_tick: Tick,
+    ) -> Self {
+        let bundle_info = world.bundles.get_unchecked(bundle_id);
+        let new_archetype_id = bundle_info.add_bundle_to_archetype(
+            &mut world.archetypes,
+            &mut world.storages,
+            &world.components,
+            &world.observers,
+            ArchetypeId::EMPTY,
+        );
+        let archetype = &mut world.archetypes[new_archetype_id];
+        let table = &mut world.storages.tables[archetype.table_id()];
+        Self {
+            bundle_info: bundle_info.into(),
+            table: table.into(),
+            archetype: archetype.into(),
+            change_tick,
+            world: world.as_unsafe_world_cell(),
+        }
+    }
+
+    #[inline]
     pub fn reserve_storage(&mut self, additional: usize) {
-        self.archetype.reserve(additional);
-        self.table.reserve(additional);
+        // SAFETY: There are no outstanding world references
+        let (archetype, table) = unsafe { (self.archetype.as_mut(), self.table.as_mut()) };
+        archetype.reserve(additional);
+        table.reserve(additional);
     }
+
     /// # Safety
     /// `entity` must be allocated (but non-existent), `T` must match this [`BundleInfo`]'s type
     #[inline]
@@ -766,18 +1015,66 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
         entity: Entity,
         bundle: T,
     ) -> EntityLocation {
-        let table_row = self.table.allocate(entity);
-        let location = self.archetype.allocate(entity, table_row);
-        self.bundle_info.write_components(
-            self.table,
-            self.sparse_sets,
-            &SpawnBundleStatus,
-            entity,
-            table_row,
-            self.change_tick,
-            bundle,
-        );
-        self.entities.set(entity.index(), location);
+        // SAFETY: We do not make any structural changes to the archetype graph, archetype, or table
+        // so this reference can only be promoted from shared to &mut down here, after they have been ensured to be valid
+        let bundle_info = self.bundle_info.as_ref();
+        let location = {
+            let table = self.table.as_mut();
+            let archetype = self.archetype.as_mut();
+
+            // SAFETY: Mutable references do not alias and will be dropped after this block
+            let (sparse_sets, entities) = {
+                let world = self.world.world_mut();
+                (&mut world.storages.sparse_sets, &mut world.entities)
+            };
+            let table_row = table.allocate(entity);
+            let location = archetype.allocate(entity, table_row);
+            bundle_info.write_components(
+                table,
+                sparse_sets,
+                &SpawnBundleStatus,
+                &bundle_info.required_components,
+                entity,
+                table_row,
+                self.change_tick,
+                bundle,
+            );
+            entities.set(entity.index(), location);
+            location
+        };
+
+        // SAFETY: We have no outstanding mutable references to world as they were dropped
+        let mut deferred_world = unsafe { self.world.into_deferred() };
+        // SAFETY: `DeferredWorld` cannot provide mutable access to `Archetypes`.
+        let archetype = self.archetype.as_ref();
+        // SAFETY: All components in the bundle are guaranteed to exist in the World
+        // as they must be initialized before creating the BundleInfo.
+        unsafe {
+            deferred_world.trigger_on_add(
+                archetype,
+                entity,
+                bundle_info.iter_contributed_components(),
+            );
+            if archetype.has_add_observer() {
+                deferred_world.trigger_observers(
+                    ON_ADD,
+                    entity,
+                    bundle_info.iter_contributed_components(),
+                );
+            }
+            deferred_world.trigger_on_insert(
+                archetype,
+                entity,
+                bundle_info.iter_contributed_components(),
+            );
+            if archetype.has_insert_observer() {
+                deferred_world.trigger_observers(
+                    ON_INSERT,
+                    entity,
+                    bundle_info.iter_contributed_components(),
+                );
+            }
+        }
 
         location
     }
@@ -786,11 +1083,35 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
     /// `T` must match this [`BundleInfo`]'s type
     #[inline]
     pub unsafe fn spawn<T: Bundle>(&mut self, bundle: T) -> Entity {
-        let entity = self.entities.alloc();
+        // Hooks run by previous spawns may have reserved entities, which must be flushed before allocating.
+        self.flush_entities();
+        let entity = self.entities().alloc();
         // SAFETY: entity is allocated (but non-existent), `T` matches this BundleInfo's type
         self.spawn_non_existent(entity, bundle);
         entity
     }
+
+    #[inline]
+    pub(crate) fn entities(&mut self) -> &mut Entities {
+        // SAFETY: No outstanding references to self.world, changes to entities cannot invalidate our internal pointers
+        unsafe { &mut self.world.world_mut().entities }
+    }
+
+    /// Flushes any entities reserved since the last flush, e.g. by hooks spawning through [`Commands`](crate::system::Commands).
+    #[inline]
+    pub(crate) fn flush_entities(&mut self) {
+        // SAFETY: No outstanding references to self.world, flushing only allocates entities into the
+        // empty archetype, which does not move any archetype or table
+        unsafe { self.world.world_mut().flush() };
+    }
+
+    /// # Safety
+    /// - Must not be called while any references into the world are held
+    #[inline]
+    pub(crate) unsafe fn flush_commands(&mut self) {
+        // SAFETY: pointers on self can be invalidated,
+        self.world.world_mut().flush_commands();
+    }
 }
 
 /// Metadata for bundles. Stores a [`BundleInfo`] for each type of [`Bundle`] in a given world.
@@ -800,9 +1121,11 @@ pub struct Bundles {
     /// Cache static [`BundleId`]
     bundle_ids: TypeIdMap<BundleId>,
     /// Cache dynamic [`BundleId`] with multiple components
-    dynamic_bundle_ids: HashMap<Vec<ComponentId>, (BundleId, Vec<StorageType>)>,
+    dynamic_bundle_ids: HashMap<Vec<ComponentId>, BundleId>,
+    dynamic_bundle_storages: HashMap<BundleId, Vec<StorageType>>,
     /// Cache optimized dynamic [`BundleId`] with single component
-    dynamic_component_bundle_ids: HashMap<ComponentId, (BundleId, StorageType)>,
+    dynamic_component_bundle_ids: HashMap<ComponentId, BundleId>,
+    dynamic_component_storages: HashMap<BundleId, StorageType>,
 }
 
 impl Bundles {
@@ -822,13 +1145,13 @@ impl Bundles {
     }
 
     /// Initializes a new [`BundleInfo`] for a statically known type.
-    pub(crate) fn init_info<'a, T: Bundle>(
-        &'a mut self,
+    pub(crate) fn init_info<T: Bundle>(
+        &mut self,
         components: &mut Components,
         storages: &mut Storages,
-    ) -> &'a BundleInfo {
+    ) -> BundleId {
         let bundle_infos = &mut self.bundle_infos;
-        let id = self.bundle_ids.entry(TypeId::of::<T>()).or_insert_with(|| {
+        let id = *self.bundle_ids.entry(TypeId::of::<T>()).or_insert_with(|| {
             let mut component_ids = Vec::new();
             T::component_ids(components, storages, &mut |id| component_ids.push(id));
             let id = BundleId(bundle_infos.len());
@@ -841,8 +1164,30 @@ impl Bundles {
             bundle_infos.push(bundle_info);
             id
         });
-        // SAFETY: index either exists, or was initialized
-        unsafe { self.bundle_infos.get_unchecked(id.0) }
+        id
+    }
+
+    /// # Safety
+    /// A [`BundleInfo`] with the given [`BundleId`] must have been initialized for this instance of `Bundles`.
+    pub(crate) unsafe fn get_unchecked(&self, id: BundleId) -> &BundleInfo {
+        self.bundle_infos.get_unchecked(id.0)
+    }
+
+    /// # Safety
+    /// This [`BundleId`] must have been initialized with a single [`Component`] (via [`init_component_info`](Self::init_component_info))
+    pub(crate) unsafe fn get_storage_unchecked(&self, id: BundleId) -> StorageType {
+        *self
+            .dynamic_component_storages
+            .get(&id)
+            .debug_checked_unwrap()
+    }
+
+    /// # Safety
+    /// This [`BundleId`] must have been initialized with multiple [`Component`]s (via [`init_dynamic_info`](Self::init_dynamic_info))
+    pub(crate) unsafe fn get_storages_unchecked(&mut self, id: BundleId) -> &mut Vec<StorageType> {
+        self.dynamic_bundle_storages
+            .get_mut(&id)
+            .debug_checked_unwrap()
     }
 
     /// Initializes a new [`BundleInfo`] for a dynamic [`Bundle`].
@@ -855,25 +1200,21 @@ impl Bundles {
         &mut self,
         components: &Components,
         component_ids: &[ComponentId],
-    ) -> (&BundleInfo, &Vec<StorageType>) {
+    ) -> BundleId {
         let bundle_infos = &mut self.bundle_infos;
 
         // Use `raw_entry_mut` to avoid cloning `component_ids` to access `Entry`
-        let (_, (bundle_id, storage_types)) = self
+        let (_, bundle_id) = self
             .dynamic_bundle_ids
             .raw_entry_mut()
             .from_key(component_ids)
             .or_insert_with(|| {
-                (
-                    Vec::from(component_ids),
-                    initialize_dynamic_bundle(bundle_infos, components, Vec::from(component_ids)),
-                )
+                let (id, storages) =
+                    initialize_dynamic_bundle(bundle_infos, components, Vec::from(component_ids));
+                self.dynamic_bundle_storages.insert(id, storages);
+                (Vec::from(component_ids), id)
             });
-
-        // SAFETY: index either exists, or was initialized
-        let bundle_info = unsafe { bundle_infos.get_unchecked(bundle_id.0) };
-
-        (bundle_info, storage_types)
+        *bundle_id
     }
 
     /// Initializes a new [`BundleInfo`] for a dynamic [`Bundle`] with single component.
@@ -885,22 +1226,19 @@ impl Bundles {
         &mut self,
         components: &Components,
         component_id: ComponentId,
-    ) -> (&BundleInfo, StorageType) {
+    ) -> BundleId {
         let bundle_infos = &mut self.bundle_infos;
-        let (bundle_id, storage_types) = self
+        let bundle_id = self
             .dynamic_component_bundle_ids
             .entry(component_id)
             .or_insert_with(|| {
                 let (id, storage_type) =
                     initialize_dynamic_bundle(bundle_infos, components, vec![component_id]);
                 // SAFETY: `storage_type` guaranteed to have length 1
-                (id, storage_type[0])
+                self.dynamic_component_storages.insert(id, storage_type[0]);
+                id
             });
-
-        // SAFETY: index either exists, or was initialized
-        let bundle_info = unsafe { bundle_infos.get_unchecked(bundle_id.0) };
-
-        (bundle_info, *storage_types)
+        *bundle_id
     }
 }
 
@@ -928,3 +1266,326 @@ fn initialize_dynamic_bundle(
 
     (id, storage_types)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{component::ComponentId, prelude::*, world::DeferredWorld};
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    #[component(on_add = a_on_add, on_insert = a_on_insert, on_remove = a_on_remove)]
+    struct AMacroHooks;
+
+    fn a_on_add(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+        world.resource_mut::<R>().assert_order(0);
+    }
+
+    fn a_on_insert(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+        world.resource_mut::<R>().assert_order(1);
+    }
+
+    fn a_on_remove(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+        world.resource_mut::<R>().assert_order(2);
+    }
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[derive(Component)]
+    struct D;
+
+    #[derive(Resource, Default)]
+    struct R(usize);
+
+    impl R {
+        #[track_caller]
+        fn assert_order(&mut self, count: usize) {
+            assert_eq!(count, self.0);
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn component_hook_order_spawn_despawn() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| world.resource_mut::<R>().assert_order(1))
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(2));
+
+        let entity = world.spawn(A).id();
+        world.despawn(entity);
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_spawn_despawn_with_macro_hooks() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+
+        let entity = world.spawn(AMacroHooks).id();
+        world.despawn(entity);
+
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_insert_remove() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| world.resource_mut::<R>().assert_order(1))
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(2));
+
+        let mut entity = world.spawn_empty();
+        entity.insert(A);
+        entity.remove::<A>();
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_reinsert() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_insert(|mut world, _, _| {
+                let r = world.resource::<R>().0;
+                assert!(r == 1 || r == 2);
+                world.resource_mut::<R>().0 += 1;
+            });
+
+        let mut entity = world.spawn(A);
+        // Inserting a component the entity already has runs `on_insert` but not `on_add`.
+        entity.insert(A);
+        assert_eq!(3, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_replace() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_insert(|mut world, _, _| {
+                let r = world.resource::<R>().0;
+                assert!(r == 0 || r == 2);
+                world.resource_mut::<R>().0 += 1;
+            })
+            .on_replace(|mut world, _, _| {
+                let r = world.resource::<R>().0;
+                assert!(r == 1 || r == 3);
+                world.resource_mut::<R>().0 += 1;
+            })
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(4));
+
+        let mut entity = world.spawn(A);
+        // Inserting a component the entity already has runs `on_replace` on the old value first.
+        entity.insert(A);
+        entity.remove::<A>();
+        assert_eq!(5, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_despawn() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .on_despawn(|mut world, _, _| world.resource_mut::<R>().assert_order(1))
+            .on_replace(|mut world, _, _| world.resource_mut::<R>().assert_order(2))
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(3));
+
+        let entity = world.spawn(A).id();
+        world.despawn(entity);
+        assert_eq!(4, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_recursive() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, entity, _| {
+                world.resource_mut::<R>().assert_order(0);
+                world.commands().entity(entity).insert(B);
+            })
+            .on_remove(|mut world, entity, _| {
+                world.resource_mut::<R>().assert_order(2);
+                world.commands().entity(entity).remove::<B>();
+            });
+
+        world
+            .register_component_hooks::<B>()
+            .on_add(|mut world, entity, _| {
+                world.resource_mut::<R>().assert_order(1);
+                world.commands().entity(entity).remove::<A>();
+            })
+            .on_remove(|mut world, _, _| {
+                world.resource_mut::<R>().assert_order(3);
+            });
+
+        let entity = world.spawn(A).id();
+        let entity = world.get_entity(entity).unwrap();
+        assert!(!entity.contains::<A>());
+        assert!(!entity.contains::<B>());
+        assert_eq!(4, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_recursive_multiple() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, entity, _| {
+                world.resource_mut::<R>().assert_order(0);
+                world.commands().entity(entity).insert(B).insert(C);
+            });
+
+        world
+            .register_component_hooks::<B>()
+            .on_add(|mut world, entity, _| {
+                world.resource_mut::<R>().assert_order(1);
+                world.commands().entity(entity).insert(D);
+            });
+
+        world
+            .register_component_hooks::<C>()
+            .on_add(|mut world, _, _| {
+                world.resource_mut::<R>().assert_order(3);
+            });
+
+        world
+            .register_component_hooks::<D>()
+            .on_add(|mut world, _, _| {
+                world.resource_mut::<R>().assert_order(2);
+            });
+
+        world.spawn(A);
+        assert_eq!(4, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hooks_spawn_batch() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().0 += 1)
+            .on_insert(|mut world, entity, _| {
+                world.commands().entity(entity).insert(B);
+            });
+
+        let entities = world.spawn_batch((0..10).map(|_| A)).collect::<Vec<_>>();
+        assert_eq!(10, world.resource::<R>().0);
+        for entity in entities {
+            assert!(world.entity(entity).contains::<B>());
+        }
+    }
+
+    #[test]
+    fn component_hooks_insert_or_spawn_batch() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<R>().0 += 1)
+            .on_insert(|mut world, _, _| {
+                // Reserves an entity which must be flushed before the next one is spawned.
+                world.commands().spawn(C);
+            });
+
+        let existing = world.spawn_empty().id();
+        let reserved = world.entities().reserve_entity();
+        let unallocated = Entity::from_raw(100);
+        world
+            .insert_or_spawn_batch([(existing, A), (reserved, A), (unallocated, A)])
+            .unwrap();
+        assert_eq!(3, world.resource::<R>().0);
+        assert!(world.entity(existing).contains::<A>());
+        assert!(world.entity(reserved).contains::<A>());
+        assert!(world.entity(unallocated).contains::<A>());
+        assert_eq!(3, world.query::<&C>().iter(&world).count());
+    }
+
+    #[test]
+    fn component_hooks_take() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_remove(|mut world, entity, _| {
+                // The component is still accessible while the hook runs.
+                assert!(world.entity(entity).contains::<A>());
+                world.resource_mut::<R>().0 += 1;
+            });
+
+        let mut entity = world.spawn((A, B));
+        assert!(entity.take::<(A, C)>().is_none());
+        assert!(entity.take::<A>().is_some());
+        assert!(entity.contains::<B>());
+        assert_eq!(1, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hooks_despawn() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_remove(|mut world, entity, _| {
+                world.resource_mut::<R>().0 += 1;
+                world.commands().spawn(B).insert(A);
+                // The entity is still alive while its hooks run.
+                assert!(world.get_entity(entity).is_some());
+            });
+
+        let entities = world
+            .spawn_batch((0..5).map(|_| (A, C)))
+            .collect::<Vec<_>>();
+        for entity in entities {
+            world.despawn(entity);
+            assert!(world.get_entity(entity).is_none());
+        }
+        assert_eq!(5, world.resource::<R>().0);
+        assert_eq!(5, world.query::<(&A, &B)>().iter(&world).count());
+    }
+
+    #[test]
+    fn component_hooks_despawn_from_hook() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<A>()
+            .on_insert(|mut world, entity, _| {
+                world.commands().entity(entity).despawn();
+            });
+
+        let mut entity = world.spawn_empty();
+        entity.insert(A);
+        assert!(entity.is_despawned());
+        let id = entity.id();
+        assert!(world.get_entity(id).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn component_hooks_cannot_be_registered_after_use() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.register_component_hooks::<A>();
+    }
+}
@@ -2,15 +2,18 @@
 
 use crate::{
     self as bevy_ecs,
+    archetype::ArchetypeFlags,
     change_detection::MAX_CHANGE_AGE,
+    entity::{ComponentCloneCtx, Entity},
     storage::{SparseSetIndex, Storages},
     system::{Local, Resource, SystemParam},
-    world::{FromWorld, World},
+    world::{DeferredWorld, FromWorld, World},
     TypeIdMap,
 };
 pub use bevy_ecs_macros::Component;
 use bevy_ptr::{OwningPtr, UnsafeCellDeref};
 use std::cell::UnsafeCell;
+use std::sync::Arc;
 use std::{
     alloc::Layout,
     any::{Any, TypeId},
@@ -146,10 +149,111 @@ use std::{
 ///
 /// [`SyncCell`]: bevy_utils::synccell::SyncCell
 /// [`Exclusive`]: https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html
+///
+/// # Component hooks
+///
+/// Components can run [`ComponentHooks`] whenever they are added to, inserted into, replaced on or removed from an entity,
+/// and when the entity holding them is despawned.
+/// Hooks run immediately as part of the structural change, so unlike [`Added`](crate::query::Added) and
+/// [`RemovedComponents`](crate::removal_detection::RemovedComponents) they never miss a component
+/// that only existed for part of a frame.
+///
+/// Hooks can be declared in the derive attribute:
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, component::ComponentId, world::DeferredWorld};
+/// #[derive(Resource, Default)]
+/// struct PlayerCount(usize);
+///
+/// #[derive(Component)]
+/// #[component(on_add = count_player, on_remove = uncount_player)]
+/// struct Player;
+///
+/// fn count_player(mut world: DeferredWorld, _entity: Entity, _component_id: ComponentId) {
+///     world.resource_mut::<PlayerCount>().0 += 1;
+/// }
+///
+/// fn uncount_player(mut world: DeferredWorld, _entity: Entity, _component_id: ComponentId) {
+///     world.resource_mut::<PlayerCount>().0 -= 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<PlayerCount>();
+/// let player = world.spawn(Player).id();
+/// assert_eq!(world.resource::<PlayerCount>().0, 1);
+/// world.despawn(player);
+/// assert_eq!(world.resource::<PlayerCount>().0, 0);
+/// ```
+///
+/// They can also be registered by implementing [`Component::register_component_hooks`],
+/// or at runtime through [`World::register_component_hooks`].
+///
+/// # Relationships
+///
+/// The `#[relationship(relationship_target = T)]` and `#[relationship_target(relationship = R)]`
+/// attributes implement [`Relationship`](crate::relationship::Relationship) and
+/// [`RelationshipTarget`](crate::relationship::RelationshipTarget) for a component, and register
+/// the hooks that keep both sides in sync. See the [`relationship`](crate::relationship) module for more.
+///
+/// # Required components
+///
+/// A component can require other components to be present on the same entity. Whenever it is
+/// inserted, any required component that the entity doesn't have yet is inserted alongside it,
+/// using either its [`Default`] implementation or a given constructor:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// #[require(Velocity, Health(full_health))]
+/// struct Player;
+///
+/// #[derive(Component, Default, PartialEq, Debug)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component, PartialEq, Debug)]
+/// struct Health(u32);
+///
+/// fn full_health() -> Health {
+///     Health(100)
+/// }
+///
+/// let mut world = World::new();
+/// let player = world.spawn(Player).id();
+/// assert_eq!(world.get::<Velocity>(player), Some(&Velocity(0.0)));
+/// assert_eq!(world.get::<Health>(player), Some(&Health(100)));
+///
+/// // Components that are already present, or inserted in the same bundle, are left untouched.
+/// let wounded = world.spawn((Player, Health(10))).id();
+/// assert_eq!(world.get::<Health>(wounded), Some(&Health(10)));
+/// ```
+///
+/// Requirements are transitive: the components required by a required component are required as well.
+/// They can be inspected through [`ComponentInfo::required_components`], and declared without the derive
+/// by implementing [`Component::register_required_components`].
 pub trait Component: Send + Sync + 'static {
     /// A marker type indicating the storage type used for this component.
     /// This must be either [`TableStorage`] or [`SparseStorage`].
     type Storage: ComponentStorage;
+
+    /// Called when registering this component, allowing mutable access to its [`ComponentHooks`].
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {}
+
+    /// Called when registering this component, allowing it to declare the components it requires
+    /// by adding them to `required_components`.
+    fn register_required_components(
+        _components: &mut Components,
+        _storages: &mut Storages,
+        _required_components: &mut RequiredComponents,
+    ) {
+    }
+
+    /// Returns how this component is cloned when its entity is cloned, see [`ComponentCloneBehavior`].
+    ///
+    /// `#[derive(Component)]` uses the [`Clone`] implementation of the component if it has one, and
+    /// reflection otherwise. This can be overridden with the `#[component(clone_behavior = ...)]` attribute.
+    fn clone_behavior() -> ComponentCloneBehavior {
+        ComponentCloneBehavior::Default
+    }
 }
 
 /// Marker type for components stored in a [`Table`](crate::storage::Table).
@@ -201,11 +305,265 @@ pub enum StorageType {
     SparseSet,
 }
 
+/// The type used for [`Component`] lifecycle hooks such as `on_add`, `on_insert`, `on_replace`, `on_remove` or `on_despawn`.
+///
+/// Hooks receive a [`DeferredWorld`], which can be used to read and write component and resource data,
+/// and to queue [`Commands`](crate::system::Commands) for any structural changes.
+pub type ComponentHook = for<'w> fn(DeferredWorld<'w>, Entity, ComponentId);
+
+/// Lifecycle hooks for a given [`Component`], stored in its [`ComponentInfo`].
+///
+/// Hooks are functions that run when a component is added, inserted, replaced or removed from an entity,
+/// or when the entity holding it is despawned.
+/// They are run synchronously as part of the structural change, before control is returned to the caller,
+/// and are intended for enforcing structural invariants that must always hold:
+/// cleaning up an index, keeping a counter up to date or reacting to a component being replaced.
+///
+/// Each hook can only be registered once per component type, and hooks must be registered before the
+/// component is first added to an entity. See [`World::register_component_hooks`].
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_utils::HashSet;
+///
+/// #[derive(Component)]
+/// struct MyTrackedComponent;
+///
+/// #[derive(Resource, Default)]
+/// struct TrackedEntities(HashSet<Entity>);
+///
+/// let mut world = World::new();
+/// world.init_resource::<TrackedEntities>();
+///
+/// // No entities with `MyTrackedComponent` have been added yet, so we can safely add component hooks
+/// world
+///     .register_component_hooks::<MyTrackedComponent>()
+///     .on_add(|mut world, entity, _component_id| {
+///         world.resource_mut::<TrackedEntities>().0.insert(entity);
+///     })
+///     .on_remove(|mut world, entity, _component_id| {
+///         world.resource_mut::<TrackedEntities>().0.remove(&entity);
+///     });
+///
+/// let entity = world.spawn(MyTrackedComponent).id();
+/// assert!(world.resource::<TrackedEntities>().0.contains(&entity));
+///
+/// world.despawn(entity);
+/// assert!(world.resource::<TrackedEntities>().0.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<ComponentHook>,
+    pub(crate) on_insert: Option<ComponentHook>,
+    pub(crate) on_replace: Option<ComponentHook>,
+    pub(crate) on_remove: Option<ComponentHook>,
+    pub(crate) on_despawn: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Register a [`ComponentHook`] that will be run when this component is added to an entity.
+    /// An `on_add` hook will always run before `on_insert` hooks. Spawning an entity counts as
+    /// adding all of its components.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_add` hook
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_add(hook)
+            .expect("Component already has an on_add hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is added (with `.insert`)
+    /// or replaced. The hook won't run if the component is already present and is only mutated.
+    /// An `on_insert` hook always runs after any `on_add` hooks (if the entity didn't already have the component).
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_insert` hook
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_insert(hook)
+            .expect("Component already has an on_insert hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is about to be replaced by an insert,
+    /// or removed from an entity. The hook runs before the old value is dropped, so it can still be read.
+    ///
+    /// Unlike `on_insert` and `on_remove`, this sees the previous value of a component whenever a new
+    /// value is inserted over it, which makes it the place to undo bookkeeping done by `on_insert`.
+    /// An `on_replace` hook always runs before `on_remove` hooks.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_replace` hook
+    pub fn on_replace(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_replace(hook)
+            .expect("Component already has an on_replace hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is removed from an entity.
+    /// Despawning an entity counts as removing all of its components.
+    /// The hook runs before the component is removed, so its value can still be read.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_remove` hook
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_remove(hook)
+            .expect("Component already has an on_remove hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when an entity holding this component is despawned.
+    /// It runs before the `on_replace` and `on_remove` hooks of the despawned entity.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_despawn` hook
+    pub fn on_despawn(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_despawn(hook)
+            .expect("Component already has an on_despawn hook")
+    }
+
+    /// Fallible version of [`Self::on_add`].
+    /// Returns `None` if the component already has an `on_add` hook.
+    pub fn try_on_add(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_add.is_some() {
+            return None;
+        }
+        self.on_add = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_insert`].
+    /// Returns `None` if the component already has an `on_insert` hook.
+    pub fn try_on_insert(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_insert.is_some() {
+            return None;
+        }
+        self.on_insert = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_replace`].
+    /// Returns `None` if the component already has an `on_replace` hook.
+    pub fn try_on_replace(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_replace.is_some() {
+            return None;
+        }
+        self.on_replace = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_remove`].
+    /// Returns `None` if the component already has an `on_remove` hook.
+    pub fn try_on_remove(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_remove.is_some() {
+            return None;
+        }
+        self.on_remove = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_despawn`].
+    /// Returns `None` if the component already has an `on_despawn` hook.
+    pub fn try_on_despawn(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_despawn.is_some() {
+            return None;
+        }
+        self.on_despawn = Some(hook);
+        Some(self)
+    }
+}
+
+/// The function used to clone a component, see [`ComponentCloneBehavior::Custom`].
+///
+/// It receives the [`World`] and a [`ComponentCloneCtx`] describing the component to clone, which
+/// is read from [`ComponentCloneCtx::source`] and should be written to the clone with
+/// [`ComponentCloneCtx::write_target_component`].
+pub type ComponentCloneFn = fn(&mut World, &mut ComponentCloneCtx);
+
+/// How a component is cloned when the entity holding it is cloned with an
+/// [`EntityCloner`](crate::entity::EntityCloner), stored in its [`ComponentInfo`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component, Clone)]
+/// struct Health(u32);
+///
+/// // Handles to external resources shouldn't be shared between clones.
+/// #[derive(Component, Clone)]
+/// #[component(clone_behavior = Ignore)]
+/// struct SoundHandle(u32);
+///
+/// let mut world = World::new();
+/// let clone = world.spawn((Health(10), SoundHandle(3))).clone_entity();
+/// assert_eq!(world.get::<Health>(clone).unwrap().0, 10);
+/// assert!(world.get::<SoundHandle>(clone).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ComponentCloneBehavior {
+    /// Clones the component using reflection, if its type is registered in the
+    /// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry) with `ReflectComponent`.
+    /// Otherwise, the component is not cloned.
+    ///
+    /// This is the behavior of components which don't implement [`Clone`].
+    #[default]
+    Default,
+    /// The component is not cloned.
+    Ignore,
+    /// The component is cloned by the given function.
+    Custom(ComponentCloneFn),
+}
+
+impl ComponentCloneBehavior {
+    /// Clones the component with its [`Clone`] implementation.
+    ///
+    /// This is the behavior of components which implement [`Clone`].
+    pub fn via_clone<C: Component + Clone>() -> Self {
+        Self::Custom(crate::entity::component_clone_via_clone::<C>)
+    }
+}
+
+/// Used by `#[derive(Component)]` to default to [`ComponentCloneBehavior::via_clone`] for components
+/// implementing [`Clone`], and to [`ComponentCloneBehavior::Default`] for the others.
+#[doc(hidden)]
+pub struct DefaultCloneBehaviorSpecialization<T>(PhantomData<T>);
+
+impl<T> Default for DefaultCloneBehaviorSpecialization<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait DefaultCloneBehaviorBase {
+    fn default_clone_behavior(&self) -> ComponentCloneBehavior;
+}
+
+impl<T> DefaultCloneBehaviorBase for DefaultCloneBehaviorSpecialization<T> {
+    fn default_clone_behavior(&self) -> ComponentCloneBehavior {
+        ComponentCloneBehavior::Default
+    }
+}
+
+#[doc(hidden)]
+pub trait DefaultCloneBehaviorViaClone {
+    fn default_clone_behavior(&self) -> ComponentCloneBehavior;
+}
+
+impl<C: Component + Clone> DefaultCloneBehaviorViaClone for &DefaultCloneBehaviorSpecialization<C> {
+    fn default_clone_behavior(&self) -> ComponentCloneBehavior {
+        ComponentCloneBehavior::via_clone::<C>()
+    }
+}
+
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
 #[derive(Debug, Clone)]
 pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
+    hooks: ComponentHooks,
+    required_components: RequiredComponents,
+    clone_behavior: ComponentCloneBehavior,
 }
 
 impl ComponentInfo {
@@ -261,7 +619,140 @@ impl ComponentInfo {
 
     /// Create a new [`ComponentInfo`].
     pub(crate) fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
-        ComponentInfo { id, descriptor }
+        ComponentInfo {
+            id,
+            descriptor,
+            hooks: ComponentHooks::default(),
+            required_components: RequiredComponents::default(),
+            clone_behavior: ComponentCloneBehavior::Default,
+        }
+    }
+
+    /// Update the given flags to include any [`ComponentHook`] registered to self
+    #[inline]
+    pub(crate) fn update_archetype_flags(&self, flags: &mut ArchetypeFlags) {
+        if self.hooks().on_add.is_some() {
+            flags.insert(ArchetypeFlags::ON_ADD_HOOK);
+        }
+        if self.hooks().on_insert.is_some() {
+            flags.insert(ArchetypeFlags::ON_INSERT_HOOK);
+        }
+        if self.hooks().on_replace.is_some() {
+            flags.insert(ArchetypeFlags::ON_REPLACE_HOOK);
+        }
+        if self.hooks().on_remove.is_some() {
+            flags.insert(ArchetypeFlags::ON_REMOVE_HOOK);
+        }
+        if self.hooks().on_despawn.is_some() {
+            flags.insert(ArchetypeFlags::ON_DESPAWN_HOOK);
+        }
+    }
+
+    /// Provides a reference to the collection of hooks associated with this [`Component`]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    /// Returns the components required by this [`Component`], including transitive requirements.
+    pub fn required_components(&self) -> &RequiredComponents {
+        &self.required_components
+    }
+
+    /// Returns how this component is cloned when its entity is cloned.
+    pub fn clone_behavior(&self) -> ComponentCloneBehavior {
+        self.clone_behavior
+    }
+}
+
+/// A type-erased constructor for a required component, see [`RequiredComponents`].
+#[derive(Clone)]
+pub struct RequiredComponentConstructor(Arc<dyn Fn(&mut dyn FnMut(OwningPtr<'_>)) + Send + Sync>);
+
+impl RequiredComponentConstructor {
+    fn new<C: Component>(constructor: impl Fn() -> C + Send + Sync + 'static) -> Self {
+        Self(Arc::new(move |write| {
+            OwningPtr::make(constructor(), write);
+        }))
+    }
+
+    /// Constructs a new value of the required component and passes it to `write`,
+    /// which must take ownership of it.
+    pub(crate) fn construct(&self, write: &mut dyn FnMut(OwningPtr<'_>)) {
+        (self.0)(write);
+    }
+}
+
+impl std::fmt::Debug for RequiredComponentConstructor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequiredComponentConstructor")
+            .finish_non_exhaustive()
+    }
+}
+
+/// The components required by a [`Component`], along with the constructors used to insert them
+/// when they are missing. See the [`Component`] documentation for how to declare requirements.
+///
+/// Requirements are stored in the order in which they were registered.
+#[derive(Debug, Clone, Default)]
+pub struct RequiredComponents(Vec<(ComponentId, RequiredComponentConstructor)>);
+
+impl RequiredComponents {
+    /// Registers `C` as a required component, which will be constructed with `constructor` when missing.
+    ///
+    /// If `C` was already registered as a requirement, the existing constructor is kept.
+    pub fn register<C: Component>(
+        &mut self,
+        components: &mut Components,
+        storages: &mut Storages,
+        constructor: impl Fn() -> C + Send + Sync + 'static,
+    ) {
+        let component_id = components.init_component::<C>(storages);
+        self.register_by_id(component_id, RequiredComponentConstructor::new(constructor));
+    }
+
+    /// Registers the component with the given id as a required component, unless it already is one.
+    ///
+    /// `constructor` must construct values of the component identified by `component_id`.
+    pub(crate) fn register_by_id(
+        &mut self,
+        component_id: ComponentId,
+        constructor: RequiredComponentConstructor,
+    ) {
+        if !self.contains(component_id) {
+            self.0.push((component_id, constructor));
+        }
+    }
+
+    /// Adds the requirements of `other` that aren't already registered on `self`.
+    pub(crate) fn merge(&mut self, other: &RequiredComponents) {
+        for (component_id, constructor) in other.iter() {
+            self.register_by_id(component_id, constructor.clone());
+        }
+    }
+
+    /// Returns `true` if the component with the given id is required.
+    pub fn contains(&self, component_id: ComponentId) -> bool {
+        self.0.iter().any(|(id, _)| *id == component_id)
+    }
+
+    /// Returns an iterator over the ids of the required components.
+    pub fn ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.0.iter().map(|(id, _)| *id)
+    }
+
+    /// Returns an iterator over the ids of the required components and their constructors.
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &RequiredComponentConstructor)> + '_ {
+        self.0.iter().map(|(id, constructor)| (*id, constructor))
+    }
+
+    /// Returns the number of required components.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no components are required.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
     }
 }
 
@@ -461,9 +952,38 @@ impl Components {
             components,
             ..
         } = self;
-        *indices.entry(type_id).or_insert_with(|| {
-            Components::init_component_inner(components, storages, ComponentDescriptor::new::<T>())
-        })
+        let mut is_new_registration = false;
+        let id = *indices.entry(type_id).or_insert_with(|| {
+            is_new_registration = true;
+            let index = Components::init_component_inner(
+                components,
+                storages,
+                ComponentDescriptor::new::<T>(),
+            );
+            let info = &mut components[index.index()];
+            T::register_component_hooks(&mut info.hooks);
+            info.clone_behavior = T::clone_behavior();
+            index
+        });
+        if is_new_registration {
+            let mut required_components = RequiredComponents::default();
+            T::register_required_components(self, storages, &mut required_components);
+            // Requirements are transitive. The components required by a direct requirement are
+            // merged after all direct requirements, so that those take precedence.
+            let direct = required_components.ids().collect::<Vec<_>>();
+            for required_id in direct {
+                let inherited = self.components[required_id.index()]
+                    .required_components
+                    .clone();
+                required_components.merge(&inherited);
+            }
+            // A component can end up requiring itself through a cycle of requirements.
+            required_components
+                .0
+                .retain(|(required_id, _)| *required_id != id);
+            self.components[id.index()].required_components = required_components;
+        }
+        id
     }
 
     /// Initializes a component described by `descriptor`.
@@ -538,6 +1058,21 @@ impl Components {
         self.components.get_unchecked(id.0)
     }
 
+    #[inline]
+    pub(crate) fn get_clone_behavior_mut(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentCloneBehavior> {
+        self.components
+            .get_mut(id.0)
+            .map(|info| &mut info.clone_behavior)
+    }
+
+    #[inline]
+    pub(crate) fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        self.components.get_mut(id.0).map(|info| &mut info.hooks)
+    }
+
     /// Type-erased equivalent of [`Components::component_id()`].
     #[inline]
     pub fn get_id(&self, type_id: TypeId) -> Option<ComponentId> {
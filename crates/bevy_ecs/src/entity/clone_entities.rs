@@ -0,0 +1,767 @@
+use crate::{
+    component::{Component, ComponentCloneBehavior, ComponentId},
+    entity::Entity,
+    world::World,
+};
+use bevy_ptr::OwningPtr;
+use bevy_utils::{EntityHashMap, HashSet};
+use std::{
+    alloc::Layout,
+    any::{Any, TypeId},
+    collections::VecDeque,
+    ptr::NonNull,
+};
+
+/// Clones entities, along with their components.
+///
+/// Each component is cloned according to the [`ComponentCloneBehavior`] registered in its
+/// [`ComponentInfo`](crate::component::ComponentInfo): with its [`Clone`] implementation, with
+/// reflection, with a custom function, or not at all.
+///
+/// When cloning recursively, the sources of the relationship targets declared with
+/// `#[relationship_target(relationship = R, clone_recursive)]`, such as the children of an entity,
+/// are cloned as well. Their [`Relationship`](crate::relationship::Relationship) points at the clone
+/// of their target rather than at the original.
+///
+/// If the `bevy_reflect` feature is enabled and the world has an
+/// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry), references to the cloned entities held by
+/// components registered with [`ReflectMapEntities`](crate::reflect::ReflectMapEntities) are remapped
+/// to their clones. References to other entities are kept as they are.
+///
+/// The components of every clone are gathered first, and remapped once all the entities to clone
+/// are known. The components of each clone are then inserted at once, like a bundle, so component
+/// hooks observe the clone with all of its components and with its references already remapped.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity::EntityCloner;
+/// #[derive(Component, Clone)]
+/// struct Health(u32);
+///
+/// #[derive(Component, Clone)]
+/// struct Selected;
+///
+/// let mut world = World::new();
+/// let entity = world.spawn((Health(10), Selected)).id();
+///
+/// let clone = world
+///     .entity_mut(entity)
+///     .clone_entity_with(&EntityCloner::default().deny::<Selected>());
+/// assert_eq!(world.get::<Health>(clone).unwrap().0, 10);
+/// assert!(!world.entity(clone).contains::<Selected>());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EntityCloner {
+    recursive: bool,
+    denied: HashSet<TypeId>,
+}
+
+impl EntityCloner {
+    /// Sets whether the sources of relationship targets declared with `clone_recursive` are cloned
+    /// along with their target.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Prevents components of type `T` from being cloned.
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.denied.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Returns `true` if this cloner clones recursively, see [`EntityCloner::recursive`].
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Clones `source` into a new entity, and returns the id of the clone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` doesn't exist.
+    pub fn clone_entity(&self, world: &mut World, source: Entity) -> Entity {
+        let target = world.spawn_empty().id();
+        self.clone_entity_into(world, source, target);
+        target
+    }
+
+    /// Clones the components of `source` into `target`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` doesn't exist.
+    pub fn clone_entity_into(&self, world: &mut World, source: Entity, target: Entity) {
+        let mut entity_map = EntityHashMap::default();
+        let mut queue = VecDeque::new();
+        entity_map.insert(source, target);
+        queue.push_back((source, target));
+
+        let mut clones = Vec::new();
+        while let Some((source, target)) = queue.pop_front() {
+            let components =
+                self.clone_components(world, source, target, &mut entity_map, &mut queue);
+            clones.push((target, components));
+        }
+
+        map_cloned_entities(world, entity_map, &mut clones);
+        for (target, components) in clones {
+            insert_cloned_components(world, target, components);
+        }
+    }
+
+    /// Clones the components of `source`, to be inserted on `target`.
+    fn clone_components(
+        &self,
+        world: &mut World,
+        source: Entity,
+        target: Entity,
+        entity_map: &mut EntityHashMap<Entity, Entity>,
+        queue: &mut VecDeque<(Entity, Entity)>,
+    ) -> Vec<ClonedComponent> {
+        let mut cloned = Vec::new();
+        let components = world
+            .entity(source)
+            .archetype()
+            .components()
+            .collect::<Vec<_>>();
+        for component_id in components {
+            let info = world.components().get_info(component_id).unwrap();
+            let type_id = info.type_id();
+            if type_id.is_some_and(|type_id| self.denied.contains(&type_id)) {
+                continue;
+            }
+            let behavior = info.clone_behavior();
+            let mut ctx = ComponentCloneCtx {
+                component_id,
+                type_id,
+                source,
+                target,
+                cloner: self,
+                entity_map,
+                queue,
+                cloned: &mut cloned,
+            };
+            match behavior {
+                ComponentCloneBehavior::Default => component_clone_via_reflect(world, &mut ctx),
+                ComponentCloneBehavior::Ignore => {}
+                ComponentCloneBehavior::Custom(clone) => clone(world, &mut ctx),
+            }
+        }
+        cloned
+    }
+}
+
+/// A component written by a [`ComponentCloneFn`](crate::component::ComponentCloneFn), waiting to be
+/// inserted on its clone.
+struct ClonedComponent {
+    component_id: ComponentId,
+    /// The value of the component, of the type of the component.
+    value: Box<dyn Any>,
+    /// Maps the entities referenced by `value`, see [`ComponentCloneCtx::write_target_component_mapped`].
+    map_entities: Option<EntityMapFn>,
+}
+
+type EntityMapFn = Box<dyn Fn(&mut dyn Any, &mut dyn FnMut(Entity) -> Entity)>;
+
+/// The context of a [`ComponentCloneFn`](crate::component::ComponentCloneFn), describing the
+/// component being cloned.
+pub struct ComponentCloneCtx<'a> {
+    component_id: ComponentId,
+    type_id: Option<TypeId>,
+    source: Entity,
+    target: Entity,
+    cloner: &'a EntityCloner,
+    entity_map: &'a mut EntityHashMap<Entity, Entity>,
+    queue: &'a mut VecDeque<(Entity, Entity)>,
+    cloned: &'a mut Vec<ClonedComponent>,
+}
+
+impl ComponentCloneCtx<'_> {
+    /// Returns the id of the component being cloned.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Returns the entity being cloned.
+    pub fn source(&self) -> Entity {
+        self.source
+    }
+
+    /// Returns the clone of [`ComponentCloneCtx::source`], which the component is written to.
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// Returns `true` if the entity is being cloned recursively, see [`EntityCloner::recursive`].
+    pub fn is_recursive(&self) -> bool {
+        self.cloner.recursive
+    }
+
+    /// Returns the clone of `entity` if it has been cloned by the current operation so far,
+    /// or `entity` itself otherwise.
+    ///
+    /// Entities cloned later on by the operation aren't known yet, so references to entities should
+    /// rather be mapped with [`ComponentCloneCtx::write_target_component_mapped`].
+    pub fn map_entity(&self, entity: Entity) -> Entity {
+        self.entity_map.get(&entity).copied().unwrap_or(entity)
+    }
+
+    /// Writes `component` to [`ComponentCloneCtx::target`].
+    ///
+    /// The component is inserted along with the other components of the clone once all entities
+    /// have been cloned, after its references to cloned entities are remapped if its type is
+    /// registered with [`ReflectMapEntities`](crate::reflect::ReflectMapEntities).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` isn't the type of the component being cloned.
+    pub fn write_target_component<C: Component>(&mut self, component: C) {
+        self.write(Box::new(component), None);
+    }
+
+    /// Writes `component` to [`ComponentCloneCtx::target`], like
+    /// [`ComponentCloneCtx::write_target_component`], but maps its references to cloned entities
+    /// with `map_entities` instead of reflection.
+    ///
+    /// `map_entities` is called once all entities have been cloned, with a function returning the
+    /// clone of an entity, or the entity itself if it wasn't cloned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` isn't the type of the component being cloned.
+    pub fn write_target_component_mapped<C: Component>(
+        &mut self,
+        component: C,
+        map_entities: fn(&mut C, &mut dyn FnMut(Entity) -> Entity),
+    ) {
+        self.write(
+            Box::new(component),
+            Some(Box::new(move |component, map| {
+                map_entities(component.downcast_mut::<C>().unwrap(), map);
+            })),
+        );
+    }
+
+    /// Writes the reflected `component` to [`ComponentCloneCtx::target`], like
+    /// [`ComponentCloneCtx::write_target_component`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component` isn't of the concrete type of the component being cloned, such as a
+    /// dynamic type. Use [`ReflectFromReflect`](bevy_reflect::ReflectFromReflect) to convert it.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn write_target_component_reflect(&mut self, component: Box<dyn bevy_reflect::Reflect>) {
+        self.write(component.into_any(), None);
+    }
+
+    #[track_caller]
+    fn write(&mut self, value: Box<dyn Any>, map_entities: Option<EntityMapFn>) {
+        assert_eq!(
+            Some(Any::type_id(&*value)),
+            self.type_id,
+            "the value written to a clone must be of the type of the component being cloned"
+        );
+        self.cloned
+            .retain(|cloned| cloned.component_id != self.component_id);
+        self.cloned.push(ClonedComponent {
+            component_id: self.component_id,
+            value,
+            map_entities,
+        });
+    }
+
+    /// Reserves a new entity as the clone of `entity`, and returns its id.
+    ///
+    /// `entity` is cloned as part of the current operation, after the entities already queued.
+    /// This is used to clone the sources of a relationship along with its target: components of
+    /// `entity` referencing entities cloned by the current operation are mapped to their clones.
+    pub fn clone_entity(&mut self, world: &mut World, entity: Entity) -> Entity {
+        let clone = world.spawn_empty().id();
+        self.entity_map.insert(entity, clone);
+        self.queue.push_back((entity, clone));
+        clone
+    }
+}
+
+/// Clones a component with its [`Clone`] implementation, see [`ComponentCloneBehavior::via_clone`].
+pub fn component_clone_via_clone<C: Component + Clone>(
+    world: &mut World,
+    ctx: &mut ComponentCloneCtx,
+) {
+    if let Some(component) = world.get::<C>(ctx.source()).cloned() {
+        ctx.write_target_component(component);
+    }
+}
+
+/// Clones a component using reflection, see [`ComponentCloneBehavior::Default`].
+///
+/// Does nothing if the `bevy_reflect` feature is disabled, if the world has no
+/// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry), or if the component isn't registered in it
+/// with `ReflectComponent` and `ReflectFromReflect`.
+pub fn component_clone_via_reflect(world: &mut World, ctx: &mut ComponentCloneCtx) {
+    #[cfg(feature = "bevy_reflect")]
+    {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent};
+        use bevy_reflect::ReflectFromReflect;
+
+        let Some(type_id) = ctx.type_id else {
+            return;
+        };
+        let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+            return;
+        };
+        let registry = registry.read();
+        let (Some(reflect_component), Some(from_reflect)) = (
+            registry.get_type_data::<ReflectComponent>(type_id),
+            registry.get_type_data::<ReflectFromReflect>(type_id),
+        ) else {
+            return;
+        };
+        let Some(component) = reflect_component
+            .reflect(world.entity(ctx.source()))
+            .and_then(|component| from_reflect.from_reflect(component))
+        else {
+            return;
+        };
+        ctx.write_target_component_reflect(component);
+    }
+    #[cfg(not(feature = "bevy_reflect"))]
+    let _ = (world, ctx);
+}
+
+/// Remaps the references to cloned entities held by the components written to their clones.
+fn map_cloned_entities(
+    world: &mut World,
+    mut entity_map: EntityHashMap<Entity, Entity>,
+    clones: &mut [(Entity, Vec<ClonedComponent>)],
+) {
+    #[cfg(feature = "bevy_reflect")]
+    let registry = world
+        .get_resource::<crate::reflect::AppTypeRegistry>()
+        .cloned();
+    #[cfg(feature = "bevy_reflect")]
+    let registry = registry.as_ref().map(|registry| registry.read());
+    #[cfg(not(feature = "bevy_reflect"))]
+    let _ = world;
+
+    for cloned in clones.iter_mut().flat_map(|(_, components)| components) {
+        if let Some(map_entities) = &cloned.map_entities {
+            map_entities(&mut *cloned.value, &mut |entity| {
+                entity_map.get(&entity).copied().unwrap_or(entity)
+            });
+            continue;
+        }
+        #[cfg(feature = "bevy_reflect")]
+        if let Some(registry) = &registry {
+            map_reflected_entities(world, registry, &mut entity_map, cloned);
+        }
+    }
+}
+
+/// Remaps the references to cloned entities held by `cloned` with its
+/// [`ReflectMapEntities`](crate::reflect::ReflectMapEntities), if it is registered.
+#[cfg(feature = "bevy_reflect")]
+fn map_reflected_entities(
+    world: &mut World,
+    registry: &bevy_reflect::TypeRegistry,
+    entity_map: &mut EntityHashMap<Entity, Entity>,
+    cloned: &mut ClonedComponent,
+) {
+    use crate::reflect::ReflectMapEntities;
+    use bevy_ptr::Ptr;
+    use bevy_reflect::ReflectFromPtr;
+
+    let type_id = Any::type_id(&*cloned.value);
+    let (Some(map_entities), Some(from_ptr)) = (
+        registry.get_type_data::<ReflectMapEntities>(type_id),
+        registry.get_type_data::<ReflectFromPtr>(type_id),
+    ) else {
+        return;
+    };
+    // SAFETY: `from_ptr` was registered for the type of `cloned.value`, which the pointer points to.
+    let component = unsafe { from_ptr.as_reflect(Ptr::new(NonNull::from(&*cloned.value).cast())) };
+    // References to entities outside of the cloned ones are kept as they are.
+    for referenced in map_entities
+        .referenced_entities(component, registry)
+        .unwrap_or_default()
+    {
+        entity_map.entry(referenced).or_insert(referenced);
+    }
+    if let Some(mapped) = map_entities.map_reflect(world, entity_map, component, registry) {
+        cloned.value = mapped.into_any();
+    }
+}
+
+/// Inserts the components written to `target` at once.
+fn insert_cloned_components(world: &mut World, target: Entity, components: Vec<ClonedComponent>) {
+    if components.is_empty() {
+        return;
+    }
+    let component_ids = components
+        .iter()
+        .map(|cloned| cloned.component_id)
+        .collect::<Vec<_>>();
+    let values = components
+        .into_iter()
+        .map(|cloned| {
+            let layout = Layout::for_value(&*cloned.value);
+            (Box::into_raw(cloned.value).cast::<u8>(), layout)
+        })
+        .collect::<Vec<_>>();
+    // SAFETY: each value was checked to be of the type of its component when it was written, the
+    // values are moved out of their boxes by the insertion and the boxes are deallocated below.
+    unsafe {
+        world.entity_mut(target).insert_by_ids(
+            &component_ids,
+            values
+                .iter()
+                .map(|&(ptr, _)| OwningPtr::new(NonNull::new_unchecked(ptr))),
+        );
+    }
+    for (ptr, layout) in values {
+        if layout.size() != 0 {
+            // SAFETY: `ptr` was allocated by a `Box` with this layout, and its value was moved out.
+            unsafe { std::alloc::dealloc(ptr, layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as bevy_ecs,
+        component::ComponentCloneBehavior,
+        relationship::RelationshipTarget,
+        system::{CommandQueue, Commands, Resource},
+        world::DeferredWorld,
+    };
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct NotClone;
+
+    #[derive(Component, Clone)]
+    #[component(clone_behavior = Ignore)]
+    struct Ignored;
+
+    #[derive(Component)]
+    #[component(clone_behavior = Custom(clone_doubled))]
+    struct Doubled(u32);
+
+    fn clone_doubled(world: &mut World, ctx: &mut ComponentCloneCtx) {
+        let value = world.get::<Doubled>(ctx.source()).unwrap().0;
+        ctx.write_target_component(Doubled(value * 2));
+    }
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Parts)]
+    struct PartOf(Entity);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = PartOf, clone_recursive)]
+    struct Parts(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Likers)]
+    struct Likes(Entity);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = Likes)]
+    struct Likers(Vec<Entity>);
+
+    #[test]
+    fn clone_components() {
+        let mut world = World::new();
+        let entity = world.spawn((A(3), NotClone, Ignored, Doubled(5))).id();
+        let clone = world.entity_mut(entity).clone_entity();
+
+        assert_ne!(entity, clone);
+        assert_eq!(world.get::<A>(clone), Some(&A(3)));
+        // without a type registry, components which don't implement `Clone` aren't cloned
+        assert!(!world.entity(clone).contains::<NotClone>());
+        assert!(!world.entity(clone).contains::<Ignored>());
+        assert_eq!(world.get::<Doubled>(clone).unwrap().0, 10);
+        // the original is left untouched
+        assert_eq!(world.get::<Doubled>(entity).unwrap().0, 5);
+    }
+
+    #[test]
+    fn deny_and_set_clone_behavior() {
+        let mut world = World::new();
+        world.set_component_clone_behavior::<Doubled>(ComponentCloneBehavior::Ignore);
+        let entity = world.spawn((A(3), Doubled(5))).id();
+
+        let clone = world.entity_mut(entity).clone_entity();
+        assert!(world.entity(clone).contains::<A>());
+        assert!(!world.entity(clone).contains::<Doubled>());
+
+        let clone = world
+            .entity_mut(entity)
+            .clone_entity_with(&EntityCloner::default().deny::<A>());
+        assert!(!world.entity(clone).contains::<A>());
+    }
+
+    #[test]
+    fn clone_relationship_source() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let part = world.spawn((A(1), PartOf(target))).id();
+
+        // a clone of a source points at the same target
+        let clone = world.entity_mut(part).clone_entity();
+        assert_eq!(world.get::<PartOf>(clone).unwrap().0, target);
+        assert_eq!(
+            world.get::<Parts>(target).unwrap().as_slice(),
+            &[part, clone]
+        );
+
+        // relationship targets aren't cloned unless recursive
+        let target_clone = world.entity_mut(target).clone_entity();
+        assert!(!world.entity(target_clone).contains::<Parts>());
+    }
+
+    #[test]
+    fn clone_recursive() {
+        let mut world = World::new();
+        let liked = world.spawn_empty().id();
+        let root = world.spawn(A(0)).id();
+        let part = world.spawn((A(1), PartOf(root))).id();
+        let nested = world.spawn((A(2), PartOf(part), Likes(liked))).id();
+        let liker = world.spawn(Likes(root)).id();
+
+        let root_clone = world
+            .entity_mut(root)
+            .clone_entity_with(&EntityCloner::default().recursive(true));
+
+        let parts = world.get::<Parts>(root_clone).unwrap().as_slice().to_vec();
+        assert_eq!(parts.len(), 1);
+        let part_clone = parts[0];
+        assert_ne!(part_clone, part);
+        assert_eq!(world.get::<A>(part_clone), Some(&A(1)));
+        assert_eq!(world.get::<PartOf>(part_clone).unwrap().0, root_clone);
+
+        let nested_clone = world.get::<Parts>(part_clone).unwrap().as_slice()[0];
+        assert_ne!(nested_clone, nested);
+        assert_eq!(world.get::<A>(nested_clone), Some(&A(2)));
+        assert_eq!(world.get::<Likes>(nested_clone).unwrap().0, liked);
+
+        // the original hierarchy is untouched
+        assert_eq!(world.get::<Parts>(root).unwrap().as_slice(), &[part]);
+        assert_eq!(world.get::<Parts>(part).unwrap().as_slice(), &[nested]);
+        // sources of relationships without `clone_recursive` aren't cloned
+        assert_eq!(world.get::<Likers>(root).unwrap().as_slice(), &[liker]);
+        assert!(!world.entity(root_clone).contains::<Likers>());
+        assert_eq!(
+            world.get::<Likers>(liked).unwrap().as_slice(),
+            &[nested, nested_clone]
+        );
+    }
+
+    #[test]
+    fn insert_cloned_components_at_once() {
+        #[derive(Component, Clone)]
+        #[component(on_add = check_siblings)]
+        struct Checked;
+
+        #[derive(Resource, Default)]
+        struct Checks(Vec<bool>);
+
+        fn check_siblings(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+            let complete =
+                world.entity(entity).contains::<A>() && world.entity(entity).contains::<Doubled>();
+            world.resource_mut::<Checks>().0.push(complete);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Checks>();
+        let entity = world.spawn((A(1), Doubled(1), Checked)).id();
+        world.resource_mut::<Checks>().0.clear();
+
+        world.entity_mut(entity).clone_entity();
+        assert_eq!(world.resource::<Checks>().0, [true]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be of the type of the component being cloned")]
+    fn write_wrong_component_type() {
+        #[derive(Component)]
+        #[component(clone_behavior = Custom(clone_as_a))]
+        struct Wrong;
+
+        fn clone_as_a(_: &mut World, ctx: &mut ComponentCloneCtx) {
+            ctx.write_target_component(A(0));
+        }
+
+        let mut world = World::new();
+        world.spawn(Wrong).clone_entity();
+    }
+
+    #[test]
+    fn relationship_hooks_see_mapped_targets() {
+        #[derive(Resource, Default)]
+        struct Targets(Vec<Entity>);
+
+        #[derive(Component)]
+        #[relationship(relationship_target = Watchers)]
+        #[component(on_add = record_target)]
+        struct Watches(Entity);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = Watches)]
+        struct Watchers(Vec<Entity>);
+
+        fn record_target(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+            let target = world.get::<Watches>(entity).unwrap().0;
+            world.resource_mut::<Targets>().0.push(target);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Targets>();
+        let root = world.spawn_empty().id();
+        let first = world.spawn(PartOf(root)).id();
+        // `second` is cloned after `first`, which watches it
+        let second = world.spawn(PartOf(root)).id();
+        world.entity_mut(first).insert(Watches(second));
+        world.resource_mut::<Targets>().0.clear();
+
+        let root_clone = world
+            .entity_mut(root)
+            .clone_entity_with(&EntityCloner::default().recursive(true));
+        let parts = world.get::<Parts>(root_clone).unwrap().as_slice().to_vec();
+        let [first_clone, second_clone] = parts[..] else {
+            panic!("expected two parts, got {parts:?}");
+        };
+
+        assert_eq!(world.get::<Watches>(first_clone).unwrap().0, second_clone);
+        assert_eq!(world.resource::<Targets>().0, [second_clone]);
+        assert_eq!(
+            world.get::<Watchers>(second_clone).unwrap().as_slice(),
+            &[first_clone]
+        );
+        assert_eq!(world.get::<Watchers>(second).unwrap().as_slice(), &[first]);
+    }
+
+    #[test]
+    fn clone_and_spawn_command() {
+        let mut world = World::new();
+        let entity = world.spawn(A(7)).id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let clone = commands
+            .entity(entity)
+            .clone_and_spawn()
+            .insert(Doubled(1))
+            .id();
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<A>(clone), Some(&A(7)));
+        assert_eq!(world.get::<Doubled>(clone).unwrap().0, 1);
+        assert!(!world.entity(entity).contains::<Doubled>());
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn clone_via_reflect_and_map_entities() {
+        use crate::{
+            entity::{EntityMapper, MapEntities},
+            reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+        };
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        struct Reflected(u32);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component, MapEntities)]
+        struct Target(Entity);
+
+        impl MapEntities for Target {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.0 = entity_mapper.get_or_reserve(self.0);
+            }
+        }
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Reflected>();
+            registry.register::<Target>();
+        }
+        world.insert_resource(registry);
+
+        let outside = world.spawn_empty().id();
+        let root = world.spawn(Reflected(4)).id();
+        let part = world.spawn((PartOf(root), Target(outside))).id();
+        world.entity_mut(root).insert(Target(part));
+
+        let root_clone = world
+            .entity_mut(root)
+            .clone_entity_with(&EntityCloner::default().recursive(true));
+        let part_clone = world.get::<Parts>(root_clone).unwrap().as_slice()[0];
+
+        assert_eq!(world.get::<Reflected>(root_clone).unwrap().0, 4);
+        // references to cloned entities are mapped to their clones
+        assert_eq!(world.get::<Target>(root_clone).unwrap().0, part_clone);
+        // references to other entities are kept
+        assert_eq!(world.get::<Target>(part_clone).unwrap().0, outside);
+        assert_eq!(world.get::<Target>(root).unwrap().0, part);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn reflect_map_entities_without_from_reflect() {
+        use crate::{
+            entity::{EntityMapper, MapEntities},
+            reflect::ReflectMapEntities,
+        };
+        use bevy_reflect::{Reflect, TypeRegistry};
+
+        #[derive(Component, Reflect)]
+        #[reflect(MapEntities)]
+        struct Target(Entity);
+
+        #[derive(Component, Reflect)]
+        #[reflect(MapEntities, from_reflect = false)]
+        struct Opaque(Entity);
+
+        impl MapEntities for Target {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.0 = entity_mapper.get_or_reserve(self.0);
+            }
+        }
+
+        impl MapEntities for Opaque {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.0 = entity_mapper.get_or_reserve(self.0);
+            }
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Target>();
+        registry.register::<Opaque>();
+        let entity = Entity::from_raw(3);
+
+        let map_entities = registry
+            .get_type_data::<ReflectMapEntities>(TypeId::of::<Target>())
+            .unwrap();
+        assert_eq!(
+            map_entities.referenced_entities(&Target(entity), &registry),
+            Some(vec![entity])
+        );
+        // Without `FromReflect`, only the world-based mapping is available.
+        let map_entities = registry
+            .get_type_data::<ReflectMapEntities>(TypeId::of::<Opaque>())
+            .unwrap();
+        assert_eq!(
+            map_entities.referenced_entities(&Opaque(entity), &registry),
+            None
+        );
+    }
+}
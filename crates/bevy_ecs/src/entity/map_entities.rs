@@ -105,6 +105,18 @@ impl<'m> EntityMapper<'m> {
         }
     }
 
+    /// Creates an [`EntityMapper`] that isn't tied to any [`World`].
+    ///
+    /// The entities it reserves are meaningless, so it must only be used to discover the entities
+    /// a [`MapEntities`] implementation references, which end up as the keys of `map`.
+    pub(crate) fn detached(map: &'m mut EntityHashMap<Entity, Entity>) -> Self {
+        Self {
+            map,
+            dead_start: Entity::PLACEHOLDER,
+            generations: 0,
+        }
+    }
+
     /// Reserves the allocated references to dead entities within the world. This frees the temporary base
     /// [`Entity`] while reserving extra generations via [`crate::entity::Entities::reserve_generations`]. Because this
     /// renders the [`EntityMapper`] unable to safely allocate any more references, this method takes ownership of
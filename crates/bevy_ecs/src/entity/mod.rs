@@ -23,6 +23,7 @@
 //! |Despawn an entity|[`EntityCommands::despawn`]|[`World::despawn`]|
 //! |Insert a component, bundle, or tuple of components and bundles to an entity|[`EntityCommands::insert`]|[`EntityWorldMut::insert`]|
 //! |Remove a component, bundle, or tuple of components and bundles from an entity|[`EntityCommands::remove`]|[`EntityWorldMut::remove`]|
+//! |Clone an entity and its components|[`EntityCommands::clone_and_spawn`]|[`EntityWorldMut::clone_entity`]|
 //!
 //! [`World`]: crate::world::World
 //! [`Commands::spawn`]: crate::system::Commands::spawn
@@ -30,15 +31,21 @@
 //! [`EntityCommands::despawn`]: crate::system::EntityCommands::despawn
 //! [`EntityCommands::insert`]: crate::system::EntityCommands::insert
 //! [`EntityCommands::remove`]: crate::system::EntityCommands::remove
+//! [`EntityCommands::clone_and_spawn`]: crate::system::EntityCommands::clone_and_spawn
 //! [`World::spawn`]: crate::world::World::spawn
 //! [`World::spawn_empty`]: crate::world::World::spawn_empty
 //! [`World::despawn`]: crate::world::World::despawn
 //! [`EntityWorldMut::insert`]: crate::world::EntityWorldMut::insert
 //! [`EntityWorldMut::remove`]: crate::world::EntityWorldMut::remove
+//! [`EntityWorldMut::clone_entity`]: crate::world::EntityWorldMut::clone_entity
+mod clone_entities;
 mod map_entities;
+mod stable_id;
 
 use bevy_utils::tracing::warn;
+pub use clone_entities::*;
 pub use map_entities::*;
+pub use stable_id::*;
 
 use crate::{
     archetype::{ArchetypeId, ArchetypeRow},
@@ -50,6 +57,7 @@ use crate::{
     },
     storage::{SparseSetIndex, TableId, TableRow},
 };
+use fixedbitset::FixedBitSet;
 use serde::{Deserialize, Serialize};
 use std::{convert::TryFrom, fmt, hash::Hash, mem, num::NonZeroU32, sync::atomic::Ordering};
 
@@ -845,6 +853,67 @@ impl Entities {
         self.len = count as u32;
     }
 
+    /// Captures which entities are alive, along with the state of the allocator, so that it can be
+    /// restored with [`Entities::restore`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserved entities are awaiting `flush()`.
+    pub fn snapshot(&mut self) -> EntitiesSnapshot {
+        assert!(
+            !self.needs_flush(),
+            "flush() needs to be called before taking a snapshot of the entities"
+        );
+        let mut alive = FixedBitSet::with_capacity(self.meta.len());
+        for (index, meta) in self.meta.iter().enumerate() {
+            alive.set(index, meta.location.archetype_id != ArchetypeId::INVALID);
+        }
+        EntitiesSnapshot {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            alive,
+            pending: self.pending.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Restores the state of the allocator captured by [`Entities::snapshot`]: the generation of
+    /// every index and the order in which freed indices will be reused, so that entities allocated
+    /// afterwards get the same ids as the ones allocated after the snapshot was taken.
+    ///
+    /// This doesn't spawn or despawn entities, which must be done beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserved entities are awaiting `flush()`, or if the entities currently alive aren't
+    /// the ones that were alive in `snapshot`.
+    pub fn restore(&mut self, snapshot: &EntitiesSnapshot) {
+        assert!(
+            !self.needs_flush(),
+            "flush() needs to be called before restoring a snapshot of the entities"
+        );
+        for (index, meta) in self.meta.iter().enumerate() {
+            let entity = Entity::from_raw_and_generation(index as u32, meta.generation);
+            if meta.location.archetype_id != ArchetypeId::INVALID {
+                assert!(
+                    snapshot.contains(entity),
+                    "{entity:?} must be despawned to restore the snapshot"
+                );
+            }
+        }
+        if let Some(entity) = snapshot.iter().find(|entity| !self.contains(*entity)) {
+            panic!("{entity:?} must be spawned to restore the snapshot");
+        }
+
+        self.meta
+            .resize(snapshot.generations.len(), EntityMeta::EMPTY);
+        for (meta, &generation) in self.meta.iter_mut().zip(&snapshot.generations) {
+            meta.generation = generation;
+        }
+        self.pending.clone_from(&snapshot.pending);
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        self.len = snapshot.len;
+    }
+
     /// The count of all entities in the [`World`] that have ever been allocated
     /// including the entities that are currently freed.
     ///
@@ -870,6 +939,42 @@ impl Entities {
     }
 }
 
+/// The entities alive in [`Entities`] and the state of its allocator at some point in time,
+/// see [`Entities::snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntitiesSnapshot {
+    generations: Vec<NonZeroU32>,
+    alive: FixedBitSet,
+    pending: Vec<u32>,
+    len: u32,
+}
+
+impl EntitiesSnapshot {
+    /// Returns `true` if `entity` was alive when the snapshot was taken.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.alive.contains(entity.index() as usize)
+            && self.generations[entity.index() as usize] == entity.generation
+    }
+
+    /// Returns an iterator over the entities that were alive when the snapshot was taken,
+    /// in the order of their index.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive
+            .ones()
+            .map(|index| Entity::from_raw_and_generation(index as u32, self.generations[index]))
+    }
+
+    /// Returns the number of entities that were alive when the snapshot was taken.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if no entities were alive when the snapshot was taken.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
 // This type is repr(C) to ensure that the layout and values within it can be safe to fully fill
 // with u8::MAX, as required by [`Entities::flush_and_reserve_invalid_assuming_no_entities`].
 // Safety:
@@ -923,7 +1028,7 @@ pub struct EntityLocation {
 
 impl EntityLocation {
     /// location for **pending entity** and **invalid entity**
-    const INVALID: EntityLocation = EntityLocation {
+    pub(crate) const INVALID: EntityLocation = EntityLocation {
         archetype_id: ArchetypeId::INVALID,
         archetype_row: ArchetypeRow::INVALID,
         table_id: TableId::INVALID,
@@ -0,0 +1,238 @@
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::ReflectComponent;
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId},
+    entity::Entity,
+    system::Resource,
+    world::{DeferredWorld, FromWorld, World},
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use bevy_utils::{tracing::warn, HashMap, Uuid};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A persistent identity for an entity, which survives despawning and respawning it,
+/// and saving and loading it, unlike its [`Entity`] id.
+///
+/// While [`Entity`] ids are only valid in the [`World`](crate::world::World) they were allocated in,
+/// and are reused once despawned, a [`StableId`] is a random UUID chosen once and kept by the entity
+/// wherever it goes. The [`StableIds`] resource, once added to a world, indexes its entities by their
+/// [`StableId`], and is kept up to date by the component's hooks.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity::{StableId, StableIds};
+/// let mut world = World::new();
+/// world.init_resource::<StableIds>();
+/// let id = StableId::new();
+/// let entity = world.spawn(id).id();
+/// assert_eq!(world.resource::<StableIds>().get(id), Some(entity));
+///
+/// // The same identity can be given to another entity, for example when loading a save.
+/// world.despawn(entity);
+/// let respawned = world.spawn(id).id();
+/// assert_eq!(world.resource::<StableIds>().get(id), Some(respawned));
+/// ```
+///
+/// The id of an entity should be changed by inserting a new [`StableId`] rather than by mutating the
+/// component in place, which would bypass the hooks and leave [`StableIds`] out of date.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[component(
+    on_insert = register_stable_id,
+    on_replace = unregister_stable_id,
+    clone_behavior = Ignore
+)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect_value(Component, PartialEq, Hash, Debug, Serialize, Deserialize)
+)]
+pub struct StableId(Uuid);
+
+impl StableId {
+    /// Creates a new, random, [`StableId`].
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Creates a [`StableId`] from an existing [`Uuid`].
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Returns the [`Uuid`] of this id.
+    pub const fn uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for StableId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for StableId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StableId({})", self.0)
+    }
+}
+
+impl fmt::Display for StableId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for StableId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StableId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Uuid::deserialize(deserializer).map(Self)
+    }
+}
+
+impl From<Uuid> for StableId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+/// Index of the entities of a [`World`] by their [`StableId`].
+///
+/// This resource is opt-in: it must be added to a world with [`World::init_resource`], which indexes
+/// the entities that already have a [`StableId`]. From then on it is kept up to date whenever a
+/// [`StableId`] is inserted, replaced or removed.
+#[derive(Resource, Debug)]
+pub struct StableIds {
+    entities: HashMap<StableId, Entity>,
+}
+
+impl FromWorld for StableIds {
+    fn from_world(world: &mut World) -> Self {
+        let entities = world
+            .query::<(Entity, &StableId)>()
+            .iter(world)
+            .map(|(entity, &id)| (id, entity))
+            .collect();
+        Self { entities }
+    }
+}
+
+impl StableIds {
+    /// Returns the entity with the given [`StableId`], if any.
+    pub fn get(&self, id: StableId) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    /// Returns `true` if an entity has the given [`StableId`].
+    pub fn contains(&self, id: StableId) -> bool {
+        self.entities.contains_key(&id)
+    }
+
+    /// Returns an iterator over all [`StableId`]s and the entities they identify.
+    pub fn iter(&self) -> impl Iterator<Item = (StableId, Entity)> + '_ {
+        self.entities.iter().map(|(&id, &entity)| (id, entity))
+    }
+
+    /// Returns the number of entities with a [`StableId`].
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity has a [`StableId`].
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+fn register_stable_id(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let id = *world.get::<StableId>(entity).unwrap();
+    let Some(mut stable_ids) = world.get_resource_mut::<StableIds>() else {
+        return;
+    };
+    if let Some(previous) = stable_ids.entities.insert(id, entity) {
+        if previous != entity {
+            warn!("{id} was inserted on {entity:?} while already used by {previous:?}. It now refers to {entity:?}.");
+        }
+    }
+}
+
+fn unregister_stable_id(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let id = *world.get::<StableId>(entity).unwrap();
+    let Some(mut stable_ids) = world.get_resource_mut::<StableIds>() else {
+        return;
+    };
+    // another entity may have been given this id since
+    if stable_ids.entities.get(&id) == Some(&entity) {
+        stable_ids.entities.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::World;
+
+    #[test]
+    fn stable_ids_are_indexed() {
+        let mut world = World::new();
+        world.init_resource::<StableIds>();
+        let a = StableId::new();
+        let b = StableId::new();
+
+        let entity = world.spawn(a).id();
+        assert_eq!(world.resource::<StableIds>().get(a), Some(entity));
+
+        // replacing the id updates the index
+        world.entity_mut(entity).insert(b);
+        assert_eq!(world.resource::<StableIds>().get(a), None);
+        assert_eq!(world.resource::<StableIds>().get(b), Some(entity));
+
+        world.entity_mut(entity).remove::<StableId>();
+        assert!(world.resource::<StableIds>().is_empty());
+
+        world.entity_mut(entity).insert(a);
+        world.despawn(entity);
+        assert!(world.resource::<StableIds>().is_empty());
+    }
+
+    #[test]
+    fn duplicate_stable_id_refers_to_latest_entity() {
+        let mut world = World::new();
+        world.init_resource::<StableIds>();
+        let id = StableId::new();
+
+        let first = world.spawn(id).id();
+        let second = world.spawn(id).id();
+        assert_eq!(world.resource::<StableIds>().get(id), Some(second));
+
+        // removing the id from the stale entity keeps the index pointing at the latest one
+        world.despawn(first);
+        assert_eq!(world.resource::<StableIds>().get(id), Some(second));
+        assert_eq!(world.resource::<StableIds>().len(), 1);
+    }
+
+    #[test]
+    fn stable_ids_index_existing_entities() {
+        let mut world = World::new();
+        let id = StableId::new();
+        let entity = world.spawn(id).id();
+        assert!(!world.contains_resource::<StableIds>());
+
+        world.init_resource::<StableIds>();
+        assert_eq!(world.resource::<StableIds>().get(id), Some(entity));
+    }
+}
@@ -0,0 +1,304 @@
+//! Error handling for fallible systems and commands.
+//!
+//! Systems returning a [`Result`] can be added to schedules like any other system, which lets them
+//! use the `?` operator. When such a system returns an error, or when a command such as
+//! [`EntityCommands::insert`] fails, the error is passed to the [`ErrorHandler`] resource of the
+//! world, along with an [`ErrorContext`] describing where it came from.
+//!
+//! The default handler panics, as failing commands always did, except for the errors of commands
+//! such as [`EntityCommands::despawn`] targeting an entity that no longer exists, which only log a
+//! warning. It can be replaced to log errors instead, or to handle them in a custom way:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::error::{ErrorHandler, Result};
+//!
+//! #[derive(Resource)]
+//! struct Config(String);
+//!
+//! fn parse_config(config: Res<Config>) -> Result {
+//!     let value: u32 = config.0.parse()?;
+//!     println!("parsed {value}");
+//!     Ok(())
+//! }
+//!
+//! let mut world = World::new();
+//! world.insert_resource(Config("not a number".to_string()));
+//! world.insert_resource(ErrorHandler::warn());
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(parse_config);
+//! // logs a warning instead of panicking
+//! schedule.run(&mut world);
+//! ```
+//!
+//! [`EntityCommands::insert`]: crate::system::EntityCommands::insert
+//! [`EntityCommands::despawn`]: crate::system::EntityCommands::despawn
+
+use crate::{self as bevy_ecs, system::Resource, world::World};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashSet,
+};
+use std::{
+    borrow::Cow,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// A type-erased error, which any type implementing [`std::error::Error`] can be converted into
+/// with the `?` operator.
+pub type BoxedError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A [`Result`](std::result::Result) whose error defaults to [`BoxedError`], convenient as the
+/// return type of fallible systems.
+pub type Result<T = (), E = BoxedError> = std::result::Result<T, E>;
+
+/// Describes where an error passed to an [`ErrorHandler`] came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorContext {
+    /// The error was returned by a system.
+    System {
+        /// The name of the system.
+        name: Cow<'static, str>,
+    },
+    /// The error happened while applying a command.
+    Command {
+        /// The name of the command.
+        name: Cow<'static, str>,
+    },
+}
+
+impl ErrorContext {
+    /// Returns the name of the system or command the error came from.
+    pub fn name(&self) -> &str {
+        match self {
+            ErrorContext::System { name } | ErrorContext::Command { name } => name,
+        }
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorContext::System { name } => write!(f, "system `{name}`"),
+            ErrorContext::Command { name } => write!(f, "command `{name}`"),
+        }
+    }
+}
+
+/// The [`Resource`] deciding what happens to the errors of fallible systems and commands.
+///
+/// If this resource isn't present in the world, errors are handled by [`ErrorHandler::panic`], or by
+/// [`ErrorHandler::warn`] for those reported with [`World::handle_warning`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::error::{ErrorContext, ErrorHandler};
+/// let mut world = World::new();
+/// world.insert_resource(ErrorHandler::new(|error, context: ErrorContext| {
+///     eprintln!("{} failed: {error}", context.name());
+/// }));
+/// ```
+#[derive(Resource, Clone)]
+pub struct ErrorHandler(Arc<dyn Fn(BoxedError, ErrorContext) + Send + Sync>);
+
+impl ErrorHandler {
+    /// Creates a handler calling `handler` for every error.
+    pub fn new(handler: impl Fn(BoxedError, ErrorContext) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    /// Creates a handler that panics on every error. This is the default.
+    pub fn panic() -> Self {
+        Self::new(|error, context| panic!("Encountered an error in {context}: {error}"))
+    }
+
+    /// Creates a handler that logs every error at the error level.
+    pub fn error() -> Self {
+        Self::new(|error, context| error!("Encountered an error in {context}: {error}"))
+    }
+
+    /// Creates a handler that logs every error at the warn level.
+    pub fn warn() -> Self {
+        Self::new(|error, context| warn!("Encountered an error in {context}: {error}"))
+    }
+
+    /// Creates a handler that logs the first error of each system or command at the warn level,
+    /// and ignores the following ones.
+    pub fn warn_once() -> Self {
+        let warned = Mutex::new(HashSet::<ErrorContext>::default());
+        Self::new(move |error, context| {
+            let mut warned = warned.lock().unwrap_or_else(|e| e.into_inner());
+            if !warned.contains(&context) {
+                warn!("Encountered an error in {context}: {error}. Further errors in {context} will be ignored.");
+                warned.insert(context);
+            }
+        })
+    }
+
+    /// Creates a handler that ignores every error.
+    pub fn ignore() -> Self {
+        Self::new(|_, _| {})
+    }
+
+    /// Handles `error`, which came from `context`.
+    pub fn handle(&self, error: BoxedError, context: ErrorContext) {
+        (self.0)(error, context);
+    }
+}
+
+impl Default for ErrorHandler {
+    fn default() -> Self {
+        Self::panic()
+    }
+}
+
+impl fmt::Debug for ErrorHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ErrorHandler").finish_non_exhaustive()
+    }
+}
+
+/// Passes `error` to `handler`, or panics if there is no handler.
+pub(crate) fn handle_error(
+    handler: Option<&ErrorHandler>,
+    error: BoxedError,
+    context: ErrorContext,
+) {
+    match handler {
+        Some(handler) => handler.handle(error, context),
+        None => ErrorHandler::panic().handle(error, context),
+    }
+}
+
+impl World {
+    /// Passes `error` to the world's [`ErrorHandler`], or panics if it doesn't have one.
+    ///
+    /// This is how commands report their failures.
+    pub fn handle_error(&self, error: impl Into<BoxedError>, context: ErrorContext) {
+        handle_error(self.get_resource::<ErrorHandler>(), error.into(), context);
+    }
+
+    /// Passes `error` to the world's [`ErrorHandler`], or logs it at the warn level if it doesn't
+    /// have one.
+    ///
+    /// This is how commands whose failure is usually harmless, like despawning an entity twice,
+    /// report it.
+    pub fn handle_warning(&self, error: impl Into<BoxedError>, context: ErrorContext) {
+        match self.get_resource::<ErrorHandler>() {
+            Some(handler) => handler.handle(error.into(), context),
+            None => ErrorHandler::warn().handle(error.into(), context),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::Component,
+        schedule::Schedule,
+        system::{Commands, Resource},
+    };
+
+    #[derive(Resource, Default)]
+    struct Ran;
+
+    #[derive(Component)]
+    struct A;
+
+    fn collect_errors(world: &mut World) -> Arc<Mutex<Vec<String>>> {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let handler_errors = errors.clone();
+        world.insert_resource(ErrorHandler::new(move |error, context| {
+            handler_errors
+                .lock()
+                .unwrap()
+                .push(format!("{context}: {error}"));
+        }));
+        errors
+    }
+
+    #[test]
+    fn fallible_system_errors_are_handled() {
+        let mut world = World::new();
+        let errors = collect_errors(&mut world);
+
+        fn parse(_: Commands) -> Result {
+            "nope".parse::<u32>()?;
+            Ok(())
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((parse, || -> Result<(), String> { Ok(()) }));
+        schedule.run(&mut world);
+
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("system `bevy_ecs::error::tests::"));
+        assert!(errors[0].ends_with("parse`: invalid digit found in string"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Encountered an error in system")]
+    fn default_handler_panics() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|| -> Result { Err("failure".into()) });
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn exclusive_fallible_system() {
+        let mut world = World::new();
+        let errors = collect_errors(&mut world);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|world: &mut World| -> Result {
+            world.init_resource::<Ran>();
+            Err("exclusive failure".into())
+        });
+        schedule.run(&mut world);
+
+        assert!(world.contains_resource::<Ran>());
+        assert_eq!(errors.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn failed_commands_are_handled() {
+        let mut world = World::new();
+        let errors = collect_errors(&mut world);
+        let entity = world.spawn_empty().id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(move |mut commands: Commands| {
+            commands.entity(entity).despawn();
+            commands.entity(entity).insert(A).remove::<A>().despawn();
+        });
+        schedule.run(&mut world);
+
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("command `insert`"));
+        assert!(errors[1].starts_with("command `remove`"));
+        assert!(errors[2].starts_with("command `despawn`"));
+    }
+
+    #[test]
+    fn missing_entities_only_warn_by_default() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(move |mut commands: Commands| {
+            commands.entity(entity).despawn();
+            commands
+                .entity(entity)
+                .remove::<A>()
+                .retain::<A>()
+                .despawn();
+        });
+        schedule.run(&mut world);
+    }
+}
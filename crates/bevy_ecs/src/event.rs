@@ -1,7 +1,11 @@
 //! Event handling types.
 
 use crate as bevy_ecs;
-use crate::system::{Local, Res, ResMut, Resource, SystemParam};
+use crate::{
+    component::Component,
+    system::{Local, Res, ResMut, Resource, SystemParam},
+    traversal::Traversal,
+};
 pub use bevy_ecs_macros::Event;
 use bevy_utils::detailed_trace;
 use std::ops::{Deref, DerefMut};
@@ -16,8 +20,22 @@ use std::{
 /// A type that can be stored in an [`Events<E>`] resource
 /// You can conveniently access events using the [`EventReader`] and [`EventWriter`] system parameter.
 ///
+/// Events can also be triggered directly at observers, see [`World::trigger`](crate::world::World::trigger).
+/// Triggered events targeting an entity can propagate along the event's [`Traversal`], which is
+/// configured with `#[event(traversal = MyTraversal)]` when deriving [`Event`] and defaults to
+/// [`DefaultTraversal`](crate::traversal::DefaultTraversal). Add `#[event(auto_propagate)]` to
+/// propagate without observers having to call [`Trigger::propagate`](crate::observer::Trigger::propagate).
+///
 /// Events must be thread-safe.
-pub trait Event: Send + Sync + 'static {}
+pub trait Event: Component {
+    /// The [`Traversal`] followed when this event propagates to other entities.
+    type Traversal: Traversal;
+
+    /// Whether triggering this event at an entity propagates it along [`Event::Traversal`] by default.
+    ///
+    /// Observers can still override this with [`Trigger::propagate`](crate::observer::Trigger::propagate).
+    const AUTO_PROPAGATE: bool = false;
+}
 
 /// An `EventId` uniquely identifies an event stored in a specific [`World`].
 ///
@@ -0,0 +1,517 @@
+//! Indexes of entities by the value of one of their components.
+//!
+//! Finding the entities whose component has a given value would otherwise require a scan of every
+//! entity with that component. An index, opted into with [`World::init_index`], keeps a map from each
+//! value of a [`Component`] implementing [`Hash`] and [`Eq`] to the entities that have it, so that these
+//! lookups are done in constant time with the [`Indexed`] system parameter, or with
+//! [`World::component_index`].
+//!
+//! Insertions and removals of the component update the index right away. Values mutated in place are
+//! not tracked as they happen: reading the index first re-indexes them, which like a
+//! [`Changed`] query visits every entity with the component. Keeping the index current thus costs
+//! time linear in the number of indexed entities each time it is read, only the lookups themselves
+//! are constant time.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::system::RunSystemOnce;
+//! use bevy_ecs::index::Indexed;
+//!
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! struct Team(u32);
+//!
+//! fn count_team_two(teams: Indexed<Team>) -> usize {
+//!     teams.get(&Team(2)).count()
+//! }
+//!
+//! let mut world = World::new();
+//! world.init_index::<Team>();
+//! world.spawn(Team(1));
+//! let entity = world.spawn(Team(2)).id();
+//! assert_eq!(world.run_system_once(count_team_two), 1);
+//!
+//! // Mutations are picked up as well.
+//! world.get_mut::<Team>(entity).unwrap().0 = 1;
+//! assert_eq!(world.run_system_once(count_team_two), 0);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    archetype::Archetype,
+    change_detection::{DetectChangesMut, Mut},
+    component::{Component, ComponentId, Tick},
+    entity::Entity,
+    query::{Changed, QueryState},
+    system::{Query, Res, Resource, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+use bevy_utils::{EntityHashMap, HashMap};
+use std::{
+    hash::Hash,
+    ops::Deref,
+    sync::{PoisonError, RwLock, RwLockReadGuard},
+};
+
+/// A map from each value of the component `T` to the entities that have it.
+///
+/// The index is created by [`World::init_index`], and read through the [`Indexed`] system parameter
+/// or [`World::component_index`]. Insertions and removals of `T` are applied to it immediately by
+/// component hooks, while the values of `T` mutated in place since it was last read are found with
+/// change detection when it is read again, which visits every entity with a `T` component.
+pub struct ComponentIndex<T: Component + Eq + Hash + Clone> {
+    entities: HashMap<T, Vec<Entity>>,
+    values: EntityHashMap<Entity, T>,
+    last_sync: Tick,
+}
+
+impl<T: Component + Eq + Hash + Clone> ComponentIndex<T> {
+    fn new(last_sync: Tick) -> Self {
+        Self {
+            entities: HashMap::default(),
+            values: EntityHashMap::default(),
+            last_sync,
+        }
+    }
+
+    /// Returns an iterator over the entities whose `T` component equals `value`.
+    pub fn get(&self, value: &T) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.get(value).into_iter().flatten().copied()
+    }
+
+    /// Returns the single entity whose `T` component equals `value`, or `None` if there are none or
+    /// several of them.
+    pub fn get_single(&self, value: &T) -> Option<Entity> {
+        match self.entities.get(value).map(Vec::as_slice) {
+            Some(&[entity]) => Some(entity),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if at least one entity has a `T` component equal to `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the value of the `T` component of `entity` as it was last indexed.
+    pub fn value(&self, entity: Entity) -> Option<&T> {
+        self.values.get(&entity)
+    }
+
+    /// Returns an iterator over every distinct indexed value, along with the entities that have it.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &[Entity])> + '_ {
+        self.entities
+            .iter()
+            .map(|(value, entities)| (value, entities.as_slice()))
+    }
+
+    /// Returns the number of entities in the index.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no entity is in the index.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn insert(&mut self, entity: Entity, value: T) {
+        self.remove(entity);
+        self.entities.entry(value.clone()).or_default().push(entity);
+        self.values.insert(entity, value);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(value) = self.values.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&value) {
+            entities.retain(|&e| e != entity);
+            if entities.is_empty() {
+                self.entities.remove(&value);
+            }
+        }
+    }
+
+    /// Re-indexes the entities whose `T` component was `changed` since the last sync.
+    fn sync<'a>(&mut self, changed: impl Iterator<Item = (Entity, &'a T)>, this_run: Tick) {
+        for (entity, value) in changed {
+            if self.values.get(&entity) != Some(value) {
+                self.insert(entity, value.clone());
+            }
+        }
+        self.last_sync = this_run;
+    }
+}
+
+/// The resource holding the [`ComponentIndex`] of `T`.
+///
+/// It is private so that the index is only read once synced. The index is behind a lock so that
+/// systems reading it, which only need shared access to this resource, can sync it while running in
+/// parallel.
+#[derive(Resource)]
+struct IndexStorage<T: Component + Eq + Hash + Clone> {
+    index: RwLock<ComponentIndex<T>>,
+    /// The query used by [`World::component_index`] to find the changed values.
+    changed: ChangedValues<T>,
+}
+
+/// The entities whose `T` component changed since the index was synced.
+type ChangedValues<T> = QueryState<(Entity, &'static T), Changed<T>>;
+
+/// The functions clamping the last sync tick of each index of a world, called by
+/// [`World::check_change_ticks`] so that the ticks never get old enough to wrap around.
+#[derive(Resource, Default)]
+struct IndexTickChecks(Vec<fn(&mut World, Tick)>);
+
+pub(crate) fn check_index_ticks(world: &mut World, change_tick: Tick) {
+    let Some(checks) = world.get_resource::<IndexTickChecks>() else {
+        return;
+    };
+    for check in checks.0.clone() {
+        check(world, change_tick);
+    }
+}
+
+fn check_index_tick<T: Component + Eq + Hash + Clone>(world: &mut World, change_tick: Tick) {
+    if let Some(mut storage) = world.get_resource_mut::<IndexStorage<T>>() {
+        let index = storage.bypass_change_detection().index.get_mut();
+        index
+            .unwrap_or_else(PoisonError::into_inner)
+            .last_sync
+            .check_tick(change_tick);
+    }
+}
+
+fn index_on_insert<T: Component + Eq + Hash + Clone>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    let value = world.get::<T>(entity).unwrap().clone();
+    if let Some(mut storage) = world.get_resource_mut::<IndexStorage<T>>() {
+        let index = storage.index.get_mut();
+        index
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(entity, value);
+    }
+}
+
+fn index_on_replace<T: Component + Eq + Hash + Clone>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    if let Some(mut storage) = world.get_resource_mut::<IndexStorage<T>>() {
+        let index = storage.index.get_mut();
+        index.unwrap_or_else(PoisonError::into_inner).remove(entity);
+    }
+}
+
+impl World {
+    /// Starts indexing the entities of this world by the value of their `T` component, making them
+    /// available to the [`Indexed`] system parameter and [`World::component_index`].
+    ///
+    /// Does nothing if `T` is already indexed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is already used by an entity, or if it has its own `on_insert` or `on_replace`
+    /// [hooks](crate::component::ComponentHooks), which the index needs to register.
+    pub fn init_index<T: Component + Eq + Hash + Clone>(&mut self) {
+        if self.contains_resource::<IndexStorage<T>>() {
+            return;
+        }
+        let hooks = self.register_component_hooks::<T>();
+        assert!(
+            hooks.on_insert.is_none() && hooks.on_replace.is_none(),
+            "{} cannot be indexed as it already has on_insert or on_replace hooks",
+            std::any::type_name::<T>()
+        );
+        hooks
+            .on_insert(index_on_insert::<T>)
+            .on_replace(index_on_replace::<T>);
+        let index = ComponentIndex::<T>::new(self.change_tick().relative_to(Tick::MAX));
+        let changed = ChangedValues::<T>::new(self);
+        self.insert_resource(IndexStorage {
+            index: RwLock::new(index),
+            changed,
+        });
+        self.get_resource_or_insert_with(IndexTickChecks::default)
+            .0
+            .push(check_index_tick::<T>);
+    }
+
+    /// Returns the index of the entities of this world by the value of their `T` component, after
+    /// re-indexing the ones whose `T` component was mutated in place since it was last read.
+    ///
+    /// Finding these visits every entity with a `T` component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't indexed with [`World::init_index`].
+    pub fn component_index<T: Component + Eq + Hash + Clone>(&mut self) -> &ComponentIndex<T> {
+        assert!(
+            self.contains_resource::<IndexStorage<T>>(),
+            "{} must be indexed with `World::init_index` before its index is read",
+            std::any::type_name::<T>()
+        );
+        let this_run = self.increment_change_tick();
+        self.resource_scope(|world, mut storage: Mut<IndexStorage<T>>| {
+            let IndexStorage { index, changed } = &mut *storage;
+            let index = index.get_mut().unwrap_or_else(PoisonError::into_inner);
+            changed.update_archetypes(world);
+            // SAFETY: the query is read-only, and was created from this world.
+            let changed = unsafe {
+                changed.iter_unchecked_manual(
+                    world.as_unsafe_world_cell_readonly(),
+                    index.last_sync,
+                    this_run,
+                )
+            };
+            index.sync(changed, this_run);
+        });
+        let storage = self.resource_mut::<IndexStorage<T>>().into_inner();
+        storage
+            .index
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A [`SystemParam`] to look up entities by the value of their `T` component, in constant time.
+///
+/// The index must have been created with [`World::init_index`]. Values of `T` mutated in place since
+/// the index was last read are re-indexed when the system runs, so lookups always reflect the current
+/// values. Finding them visits every `T` component like a [`Changed<T>`] query would.
+///
+/// This parameter has read access to every `T` component, and to the resource holding the index,
+/// so systems reading the same index may run in parallel.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::index::Indexed;
+/// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+/// struct GridPos(i32, i32);
+///
+/// fn find_occupant(grid: Indexed<GridPos>) {
+///     if let Some(entity) = grid.get_single(&GridPos(3, 4)) {
+///         println!("{entity:?} is at (3, 4)");
+///     }
+/// }
+/// # let mut world = World::new();
+/// # world.init_index::<GridPos>();
+/// # world.spawn(GridPos(3, 4));
+/// # let mut schedule = Schedule::default();
+/// # schedule.add_systems(find_occupant);
+/// # schedule.run(&mut world);
+/// ```
+pub struct Indexed<'w, T: Component + Eq + Hash + Clone> {
+    index: RwLockReadGuard<'w, ComponentIndex<T>>,
+}
+
+impl<'w, T: Component + Eq + Hash + Clone> Deref for Indexed<'w, T> {
+    type Target = ComponentIndex<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+// SAFETY: this impl defers to `Res` and `Query`, which initialize and validate the correct world access.
+unsafe impl<T: Component + Eq + Hash + Clone> SystemParam for Indexed<'_, T> {
+    type State = (ComponentId, ChangedValues<T>);
+    type Item<'w, 's> = Indexed<'w, T>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        assert!(
+            world.contains_resource::<IndexStorage<T>>(),
+            "Indexed<{0}> in system {1} requires {0} to be indexed with `World::init_index`",
+            std::any::type_name::<T>(),
+            system_meta.name
+        );
+        (
+            Res::<IndexStorage<T>>::init_state(world, system_meta),
+            Query::<(Entity, &T), Changed<T>>::init_state(world, system_meta),
+        )
+    }
+
+    fn new_archetype(
+        (_, query_state): &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        Query::<(Entity, &T), Changed<T>>::new_archetype(query_state, archetype, system_meta);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        (resource_state, query_state): &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: the caller ensures that `world` has permission to access the resource and the
+        // components registered in `init_state`.
+        let storage = unsafe {
+            Res::<IndexStorage<T>>::get_param(resource_state, system_meta, world, change_tick)
+        }
+        .into_inner();
+        let mut index = storage
+            .index
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Another system reading the index in parallel may have synced it already, past the
+        // changes this system can see.
+        if change_tick.is_newer_than(index.last_sync, world.change_tick()) {
+            // SAFETY: same as above. The changes are looked up since the index was last synced,
+            // which may be by another system.
+            let changed =
+                unsafe { query_state.iter_unchecked_manual(world, index.last_sync, change_tick) };
+            index.sync(changed, change_tick);
+        }
+        drop(index);
+        Indexed {
+            index: storage.index.read().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        change_detection::CHECK_TICK_THRESHOLD, schedule::Schedule, system::RunSystemOnce,
+    };
+
+    #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+    struct Team(u32);
+
+    fn team_members(world: &mut World, team: u32) -> Vec<Entity> {
+        world.run_system_once(move |index: Indexed<Team>| {
+            let mut entities: Vec<_> = index.get(&Team(team)).collect();
+            entities.sort();
+            entities
+        })
+    }
+
+    #[test]
+    fn index_follows_insertions_and_removals() {
+        let mut world = World::new();
+        world.init_index::<Team>();
+
+        let a = world.spawn(Team(1)).id();
+        let b = world.spawn(Team(1)).id();
+        let c = world.spawn(Team(2)).id();
+        assert_eq!(team_members(&mut world, 1), vec![a, b]);
+        assert_eq!(team_members(&mut world, 2), vec![c]);
+
+        world.entity_mut(a).insert(Team(2));
+        assert_eq!(team_members(&mut world, 1), vec![b]);
+        assert_eq!(team_members(&mut world, 2), vec![a, c]);
+
+        world.entity_mut(b).remove::<Team>();
+        world.despawn(c);
+        assert_eq!(team_members(&mut world, 1), vec![]);
+        assert_eq!(team_members(&mut world, 2), vec![a]);
+        assert!(!world.component_index::<Team>().contains(&Team(1)));
+        assert_eq!(world.component_index::<Team>().len(), 1);
+    }
+
+    #[test]
+    fn index_follows_mutations() {
+        let mut world = World::new();
+        world.init_index::<Team>();
+
+        let a = world.spawn(Team(1)).id();
+        let b = world.spawn(Team(1)).id();
+
+        world.get_mut::<Team>(a).unwrap().0 = 3;
+        assert_eq!(team_members(&mut world, 1), vec![b]);
+        assert_eq!(team_members(&mut world, 3), vec![a]);
+
+        world.run_system_once(|mut teams: Query<&mut Team>| {
+            for mut team in &mut teams {
+                team.0 += 1;
+            }
+        });
+        assert_eq!(team_members(&mut world, 2), vec![b]);
+        assert_eq!(team_members(&mut world, 4), vec![a]);
+        assert_eq!(world.component_index::<Team>().value(a), Some(&Team(4)));
+    }
+
+    #[test]
+    fn index_read_from_world() {
+        let mut world = World::new();
+        world.init_index::<Team>();
+
+        let a = world.spawn(Team(1)).id();
+        world.get_mut::<Team>(a).unwrap().0 = 2;
+        assert_eq!(
+            world.component_index::<Team>().get_single(&Team(2)),
+            Some(a)
+        );
+
+        world.get_mut::<Team>(a).unwrap().0 = 3;
+        assert_eq!(team_members(&mut world, 3), vec![a]);
+
+        world.get_mut::<Team>(a).unwrap().0 = 4;
+        assert_eq!(world.component_index::<Team>().value(a), Some(&Team(4)));
+        assert!(!world.component_index::<Team>().contains(&Team(3)));
+    }
+
+    #[test]
+    fn get_single() {
+        let mut world = World::new();
+        world.init_index::<Team>();
+
+        let a = world.spawn(Team(1)).id();
+        world.spawn_batch([Team(2), Team(2)]);
+
+        let index = world.component_index::<Team>();
+        assert_eq!(index.get_single(&Team(1)), Some(a));
+        assert_eq!(index.get_single(&Team(2)), None);
+        assert_eq!(index.get_single(&Team(3)), None);
+    }
+
+    #[test]
+    fn indexed_readers_do_not_conflict() {
+        let mut world = World::new();
+        world.init_index::<Team>();
+        world.spawn(Team(1));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((|_: Indexed<Team>| {}, |_: Indexed<Team>| {}));
+        schedule.initialize(&mut world).unwrap();
+        assert_eq!(schedule.graph().conflicting_systems().len(), 0);
+    }
+
+    #[test]
+    fn last_sync_is_clamped() {
+        let mut world = World::new();
+        world.init_index::<Team>();
+        let a = world.spawn(Team(1)).id();
+        world.component_index::<Team>();
+
+        // Without clamping, the last sync would look only 100 ticks old once the change tick wraps
+        // around, hiding the mutation made 500 ticks ago.
+        let mut elapsed = 0u32;
+        while elapsed < u32::MAX - 400 - CHECK_TICK_THRESHOLD {
+            *world.change_tick.get_mut() += CHECK_TICK_THRESHOLD;
+            elapsed += CHECK_TICK_THRESHOLD;
+            world.check_change_ticks();
+        }
+        let remaining = u32::MAX - 399 - elapsed;
+        *world.change_tick.get_mut() = world.change_tick.get_mut().wrapping_add(remaining);
+        world.get_mut::<Team>(a).unwrap().0 = 2;
+        *world.change_tick.get_mut() = world.change_tick.get_mut().wrapping_add(500);
+
+        assert_eq!(world.component_index::<Team>().value(a), Some(&Team(2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexed_requires_init_index() {
+        let mut world = World::new();
+        world.run_system_once(|_: Indexed<Team>| {});
+    }
+}
@@ -9,15 +9,21 @@ pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
+pub mod error;
 pub mod event;
 pub mod identifier;
+pub mod index;
+pub mod observer;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relationship;
 pub mod removal_detection;
 pub mod schedule;
+pub mod snapshot;
 pub mod storage;
 pub mod system;
+pub mod traversal;
 pub mod world;
 
 use std::any::TypeId;
@@ -38,18 +44,23 @@ pub mod prelude {
         component::Component,
         entity::Entity,
         event::{Event, EventReader, EventWriter, Events},
+        observer::{Observer, Trigger},
         query::{Added, AnyOf, Changed, Has, Or, QueryBuilder, QueryState, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
-            apply_deferred, apply_state_transition, common_conditions::*, Condition,
-            IntoSystemConfigs, IntoSystemSet, IntoSystemSetConfigs, NextState, OnEnter, OnExit,
-            OnTransition, Schedule, Schedules, State, StateTransitionEvent, States, SystemSet,
+            apply_deferred, apply_state_transition, common_conditions::*, ComputedStates,
+            Condition, IntoSystemConfigs, IntoSystemSet, IntoSystemSetConfigs, NextState, OnEnter,
+            OnExit, OnTransition, Schedule, Schedules, State, StateTransitionEvent, States,
+            SubStates, SystemSet,
         },
         system::{
             Commands, Deferred, In, IntoSystem, Local, NonSend, NonSendMut, ParallelCommands,
             ParamSet, Query, ReadOnlySystem, Res, ResMut, Resource, System, SystemParamFunction,
         },
-        world::{EntityMut, EntityRef, EntityWorldMut, FromWorld, World},
+        world::{
+            AsyncCommands, AsyncWorld, DeferredWorld, EntityMut, EntityRef, EntityWorldMut,
+            FromWorld, OnAdd, OnInsert, OnRemove, World,
+        },
     };
 }
 
@@ -94,7 +105,7 @@ mod tests {
         entity::Entity,
         query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
         system::Resource,
-        world::{EntityRef, Mut, World},
+        world::{DeferredWorld, EntityRef, Mut, World},
     };
     use bevy_tasks::{ComputeTaskPool, TaskPool};
     use std::num::NonZeroU32;
@@ -1788,4 +1799,175 @@ mod tests {
         field0: Simple,
         field1: ComponentB,
     }
+
+    #[test]
+    fn required_components() {
+        #[derive(Component)]
+        #[require(Y)]
+        struct X;
+
+        #[derive(Component, Default, PartialEq, Debug)]
+        #[require(Z(new_z))]
+        struct Y(u32);
+
+        #[derive(Component, PartialEq, Debug)]
+        struct Z(u32);
+
+        fn new_z() -> Z {
+            Z(7)
+        }
+
+        let mut world = World::new();
+
+        let id = world.spawn(X).id();
+        assert_eq!(world.get::<Y>(id), Some(&Y(0)));
+        assert_eq!(world.get::<Z>(id), Some(&Z(7)));
+
+        let id = world.spawn_empty().insert(X).id();
+        assert_eq!(world.get::<Y>(id), Some(&Y(0)));
+        assert_eq!(world.get::<Z>(id), Some(&Z(7)));
+
+        let id = world.spawn((X, Y(1))).id();
+        assert_eq!(world.get::<Y>(id), Some(&Y(1)));
+        assert_eq!(world.get::<Z>(id), Some(&Z(7)));
+
+        let id = world.spawn(Y(2)).insert(X).id();
+        assert_eq!(world.get::<Y>(id), Some(&Y(2)));
+        assert_eq!(world.get::<Z>(id), Some(&Z(7)));
+    }
+
+    #[test]
+    fn required_components_direct_requirements_take_precedence() {
+        #[derive(Component)]
+        #[require(Y, Z(|| Z(1)))]
+        struct X;
+
+        #[derive(Component, Default)]
+        #[require(Z)]
+        struct Y;
+
+        #[derive(Component, Default, PartialEq, Debug)]
+        struct Z(u32);
+
+        let mut world = World::new();
+        let id = world.spawn(X).id();
+        assert_eq!(world.get::<Z>(id), Some(&Z(1)));
+
+        let id = world.spawn(Y).id();
+        assert_eq!(world.get::<Z>(id), Some(&Z(0)));
+    }
+
+    #[test]
+    fn required_components_sparse_set() {
+        #[derive(Component)]
+        #[require(Y)]
+        struct X;
+
+        #[derive(Component, Default, PartialEq, Debug)]
+        #[component(storage = "SparseSet")]
+        struct Y(u32);
+
+        let mut world = World::new();
+        let id = world.spawn(X).id();
+        assert_eq!(world.get::<Y>(id), Some(&Y(0)));
+        world.entity_mut(id).remove::<Y>();
+        world.entity_mut(id).insert(X);
+        assert_eq!(world.get::<Y>(id), Some(&Y(0)));
+    }
+
+    #[test]
+    fn required_components_cycle() {
+        #[derive(Component, Default)]
+        #[require(Y)]
+        struct X;
+
+        #[derive(Component, Default)]
+        #[require(X)]
+        struct Y;
+
+        let mut world = World::new();
+        let id = world.spawn(X).id();
+        assert!(world.entity(id).contains::<Y>());
+        let id = world.spawn(Y).id();
+        assert!(world.entity(id).contains::<X>());
+
+        let x = world.component_id::<X>().unwrap();
+        let y = world.component_id::<Y>().unwrap();
+        let info = world.components().get_info(x).unwrap();
+        assert_eq!(
+            info.required_components().ids().collect::<Vec<_>>(),
+            vec![y]
+        );
+    }
+
+    #[test]
+    fn required_components_are_introspectable() {
+        #[derive(Component)]
+        #[require(Y, Z)]
+        struct X;
+
+        #[derive(Component, Default)]
+        #[require(W)]
+        struct Y;
+
+        #[derive(Component, Default)]
+        struct Z;
+
+        #[derive(Component, Default)]
+        struct W;
+
+        let mut world = World::new();
+        let x = world.init_component::<X>();
+        let y = world.component_id::<Y>().unwrap();
+        let z = world.component_id::<Z>().unwrap();
+        let w = world.component_id::<W>().unwrap();
+
+        let info = world.components().get_info(x).unwrap();
+        assert_eq!(
+            info.required_components().ids().collect::<Vec<_>>(),
+            vec![y, z, w]
+        );
+        assert!(world
+            .components()
+            .get_info(z)
+            .unwrap()
+            .required_components()
+            .is_empty());
+    }
+
+    #[test]
+    fn required_components_trigger_hooks() {
+        #[derive(Component)]
+        #[require(Y)]
+        struct X;
+
+        #[derive(Component, Default)]
+        #[component(on_add = count_add, on_insert = count_insert)]
+        struct Y;
+
+        #[derive(Resource, Default)]
+        struct Counts {
+            add: usize,
+            insert: usize,
+        }
+
+        fn count_add(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+            world.resource_mut::<Counts>().add += 1;
+        }
+
+        fn count_insert(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+            world.resource_mut::<Counts>().insert += 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let id = world.spawn(X).id();
+        world.spawn_empty().insert(X);
+        // Already present, so it isn't inserted again
+        world.entity_mut(id).insert(X);
+
+        let counts = world.resource::<Counts>();
+        assert_eq!(counts.add, 2);
+        assert_eq!(counts.insert, 2);
+    }
 }
@@ -0,0 +1,149 @@
+use crate::{
+    component::ComponentId,
+    entity::Entity,
+    event::Event,
+    system::Command,
+    world::{DeferredWorld, World},
+};
+
+/// A [`Command`] that emits a given trigger for a given set of targets.
+pub struct TriggerEvent<E, Targets: TriggerTargets = ()> {
+    /// The event to trigger.
+    pub event: E,
+
+    /// The targets to trigger the event for.
+    pub targets: Targets,
+}
+
+impl<E: Event, Targets: TriggerTargets> TriggerEvent<E, Targets> {
+    pub(super) fn trigger(mut self, world: &mut World) {
+        let event_type = world.init_component::<E>();
+        trigger_event(world, event_type, &mut self.event, self.targets);
+    }
+}
+
+impl<E: Event, Targets: TriggerTargets + Send + Sync + 'static> Command
+    for TriggerEvent<E, Targets>
+{
+    fn apply(self, world: &mut World) {
+        self.trigger(world);
+    }
+}
+
+#[inline]
+fn trigger_event<E: Event, Targets: TriggerTargets>(
+    world: &mut World,
+    event_type: ComponentId,
+    event_data: &mut E,
+    targets: Targets,
+) {
+    let mut deferred_world = DeferredWorld::from(&mut *world);
+    if targets.entities().is_empty() {
+        // SAFETY: `event_type` is the component id of `E`, initialized in `TriggerEvent::trigger`
+        unsafe {
+            deferred_world.trigger_observers_with_data::<_, E::Traversal>(
+                event_type,
+                Entity::PLACEHOLDER,
+                targets.components().iter().copied(),
+                event_data,
+                false,
+            );
+        };
+    } else {
+        for target in targets.entities() {
+            // SAFETY: `event_type` is the component id of `E`, initialized in `TriggerEvent::trigger`
+            unsafe {
+                deferred_world.trigger_observers_with_data::<_, E::Traversal>(
+                    event_type,
+                    *target,
+                    targets.components().iter().copied(),
+                    event_data,
+                    E::AUTO_PROPAGATE,
+                );
+            };
+        }
+    }
+    world.flush_commands();
+}
+
+/// Represents a collection of targets for a specific [`Trigger`](crate::observer::Trigger) of an [`Event`]. Targets can be of type [`Entity`] or [`ComponentId`].
+///
+/// When a trigger occurs for a given event and [`TriggerTargets`], any [`Observer`](crate::observer::Observer) that watches for that specific event-target combination
+/// will run.
+pub trait TriggerTargets: Send + Sync + 'static {
+    /// The components the trigger should target.
+    fn components(&self) -> &[ComponentId];
+
+    /// The entities the trigger should target.
+    fn entities(&self) -> &[Entity];
+}
+
+impl TriggerTargets for () {
+    fn components(&self) -> &[ComponentId] {
+        &[]
+    }
+
+    fn entities(&self) -> &[Entity] {
+        &[]
+    }
+}
+
+impl TriggerTargets for Entity {
+    fn components(&self) -> &[ComponentId] {
+        &[]
+    }
+
+    fn entities(&self) -> &[Entity] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl TriggerTargets for Vec<Entity> {
+    fn components(&self) -> &[ComponentId] {
+        &[]
+    }
+
+    fn entities(&self) -> &[Entity] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> TriggerTargets for [Entity; N] {
+    fn components(&self) -> &[ComponentId] {
+        &[]
+    }
+
+    fn entities(&self) -> &[Entity] {
+        self.as_slice()
+    }
+}
+
+impl TriggerTargets for ComponentId {
+    fn components(&self) -> &[ComponentId] {
+        std::slice::from_ref(self)
+    }
+
+    fn entities(&self) -> &[Entity] {
+        &[]
+    }
+}
+
+impl TriggerTargets for Vec<ComponentId> {
+    fn components(&self) -> &[ComponentId] {
+        self.as_slice()
+    }
+
+    fn entities(&self) -> &[Entity] {
+        &[]
+    }
+}
+
+impl<const N: usize> TriggerTargets for [ComponentId; N] {
+    fn components(&self) -> &[ComponentId] {
+        self.as_slice()
+    }
+
+    fn entities(&self) -> &[Entity] {
+        &[]
+    }
+}
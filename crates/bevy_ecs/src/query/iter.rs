@@ -403,6 +403,148 @@ where
 {
 }
 
+/// An iterator that joins the items of one query with the items of another query,
+/// through an [`Entity`] read from each item of the first.
+///
+/// For every item of the source query, the `key` closure returns the [`Entity`] that should be
+/// looked up in the joined query. Items whose key does not match the joined query are skipped,
+/// so only matched pairs are returned.
+///
+/// # Usage
+///
+/// This type is returned by calling [`QueryState::iter_join`], [`QueryState::iter_join_mut`],
+/// [`Query::iter_join`] or [`Query::iter_join_mut`].
+///
+/// It implements [`Iterator`] only if the joined query items are read-only ([learn more]),
+/// since several source items may point to the same joined entity.
+///
+/// In the case of mutable joined items, it can be iterated by calling [`fetch_next`] in a `while let` loop.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Target(Entity);
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn damage_targets(mut targets: Query<&Target>, mut healths: Query<&mut Health>) {
+///     let mut iter = targets.iter_join_mut(&mut healths, |target| target.0);
+///     while let Some((_target, mut health)) = iter.fetch_next() {
+///         health.0 = health.0.saturating_sub(1);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(damage_targets);
+/// ```
+///
+/// [`fetch_next`]: Self::fetch_next
+/// [learn more]: Self#impl-Iterator
+/// [`Query::iter_join`]: crate::system::Query::iter_join
+/// [`Query::iter_join_mut`]: crate::system::Query::iter_join_mut
+pub struct QueryJoinIter<'w, 's, D: QueryData, F: QueryFilter, J: QueryData, JF: QueryFilter, K> {
+    iter: QueryIter<'w, 's, D, F>,
+    world: UnsafeWorldCell<'w>,
+    joined_state: &'s QueryState<J, JF>,
+    last_run: Tick,
+    this_run: Tick,
+    key: K,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, J: QueryData, JF: QueryFilter, K>
+    QueryJoinIter<'w, 's, D, F, J, JF, K>
+where
+    K: FnMut(&D::Item<'w>) -> Entity,
+{
+    /// # Safety
+    /// - `world` must have permission to access any of the components registered in `query_state`
+    ///   and `joined_state`.
+    /// - `world` must be the same one used to initialize `query_state` and `joined_state`.
+    /// - The accesses of `query_state` and `joined_state` must be compatible unless both are read-only.
+    pub(crate) unsafe fn new(
+        world: UnsafeWorldCell<'w>,
+        query_state: &'s QueryState<D, F>,
+        joined_state: &'s QueryState<J, JF>,
+        key: K,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self {
+        QueryJoinIter {
+            iter: QueryIter::new(world, query_state, last_run, this_run),
+            world,
+            joined_state,
+            last_run,
+            this_run,
+            key,
+        }
+    }
+
+    /// Safety:
+    /// The lifetime here is not restrictive enough for Fetch with &mut access,
+    /// as calling `fetch_next_aliased_unchecked` multiple times can produce multiple
+    /// references to the same joined component, leading to unique reference aliasing.
+    ///
+    /// It is always safe for shared access to the joined items.
+    #[inline(always)]
+    unsafe fn fetch_next_aliased_unchecked(&mut self) -> Option<(D::Item<'w>, J::Item<'w>)> {
+        for item in self.iter.by_ref() {
+            let entity = (self.key)(&item);
+            // SAFETY: the caller of `new` guarantees `world` may access the joined components
+            // and that they do not conflict with the source query.
+            if let Ok(joined) = self.joined_state.get_unchecked_manual(
+                self.world,
+                entity,
+                self.last_run,
+                self.this_run,
+            ) {
+                return Some((item, joined));
+            }
+        }
+        None
+    }
+
+    /// Get next result from the join
+    #[inline(always)]
+    pub fn fetch_next(&mut self) -> Option<(D::Item<'_>, J::Item<'_>)> {
+        // SAFETY: we are limiting the returned reference to self,
+        // making sure this method cannot be called multiple times without getting rid
+        // of any previously returned unique references first, thus preventing aliasing.
+        unsafe {
+            self.fetch_next_aliased_unchecked()
+                .map(|(item, joined)| (D::shrink(item), J::shrink(joined)))
+        }
+    }
+}
+
+// Each source item is only returned once, so only the joined items need to be read-only.
+impl<'w, 's, D: QueryData, F: QueryFilter, J: ReadOnlyQueryData, JF: QueryFilter, K> Iterator
+    for QueryJoinIter<'w, 's, D, F, J, JF, K>
+where
+    K: FnMut(&D::Item<'w>) -> Entity,
+{
+    type Item = (D::Item<'w>, J::Item<'w>);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: It is safe to alias for ReadOnlyWorldQuery.
+        unsafe { self.fetch_next_aliased_unchecked() }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, max_size) = self.iter.size_hint();
+        (0, max_size)
+    }
+}
+
+// This is correct as [`QueryJoinIter`] always returns `None` once exhausted.
+impl<'w, 's, D: QueryData, F: QueryFilter, J: ReadOnlyQueryData, JF: QueryFilter, K> FusedIterator
+    for QueryJoinIter<'w, 's, D, F, J, JF, K>
+where
+    K: FnMut(&D::Item<'w>) -> Entity,
+{
+}
+
 /// An iterator over `K`-sized combinations of query items without repetition.
 ///
 /// A combination is an arrangement of a collection of items where order does not matter.
@@ -1,12 +1,12 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
     change_detection::Mut,
-    component::{ComponentId, Tick},
+    component::{ComponentId, Components, Tick},
     entity::Entity,
     prelude::{Component, FromWorld},
     query::{
         Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter,
-        QueryIter, QueryParIter,
+        QueryIter, QueryJoinIter, QueryParIter,
     },
     storage::{SparseSetIndex, TableId},
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
@@ -273,6 +273,47 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Checks that this query can be joined mutably with `joined`.
+    ///
+    /// # Panics
+    ///
+    /// If the accesses of the two queries conflict.
+    #[inline]
+    #[track_caller]
+    pub(crate) fn validate_join<J: QueryData, JF: QueryFilter>(
+        &self,
+        joined: &QueryState<J, JF>,
+        components: &Components,
+    ) {
+        if self
+            .component_access
+            .is_compatible(&joined.component_access)
+        {
+            return;
+        }
+        let conflicts = self
+            .component_access
+            .get_conflicts(&joined.component_access);
+        // Accesses to all components, like `EntityMut`, don't list their conflicts.
+        let accesses = if conflicts.is_empty() {
+            "all components".to_string()
+        } else {
+            let names = conflicts
+                .into_iter()
+                .map(|component_id| components.get_info(component_id).unwrap().name())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            format!("component(s) {names}")
+        };
+        panic!(
+            "Query<{}, {}> cannot be joined with Query<{}, {}>: both access {accesses} in a conflicting way. Consider using `Without<T>` to make the queries disjoint.",
+            std::any::type_name::<D>(),
+            std::any::type_name::<F>(),
+            std::any::type_name::<J>(),
+            std::any::type_name::<JF>(),
+        );
+    }
+
     /// Update the current [`QueryState`] with information from the provided [`Archetype`]
     /// (if applicable, i.e. if the archetype has any intersecting [`ComponentId`] with the current [`QueryState`]).
     pub fn new_archetype(&mut self, archetype: &Archetype) {
@@ -997,6 +1038,101 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the items of this query joined with the items of `joined`.
+    ///
+    /// For each item of this query, `key` returns the [`Entity`] to look up in `joined`.
+    /// Items whose key does not match `joined` are skipped.
+    ///
+    /// This can only be called for read-only queries, see [`Self::iter_join_mut`] for write-queries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// #[derive(Component, PartialEq, Debug)]
+    /// struct Name(&'static str);
+    ///
+    /// let mut world = World::new();
+    /// let bob = world.spawn(Name("Bob")).id();
+    /// world.spawn(Target(bob));
+    ///
+    /// let mut targets = world.query::<&Target>();
+    /// let mut names = world.query::<&Name>();
+    /// let joined: Vec<_> = targets
+    ///     .iter_join(&world, &mut names, |target| target.0)
+    ///     .map(|(_, name)| name)
+    ///     .collect();
+    /// assert_eq!(joined, vec![&Name("Bob")]);
+    /// ```
+    #[inline]
+    pub fn iter_join<'w, 's, J: QueryData, JF: QueryFilter, K>(
+        &'s mut self,
+        world: &'w World,
+        joined: &'s mut QueryState<J, JF>,
+        key: K,
+    ) -> QueryJoinIter<'w, 's, D::ReadOnly, F, J::ReadOnly, JF, K>
+    where
+        K: FnMut(&ROQueryItem<'w, D>) -> Entity,
+    {
+        self.update_archetypes(world);
+        joined.update_archetypes(world);
+        // SAFETY: both queries are read only
+        unsafe {
+            self.as_readonly().iter_join_unchecked_manual(
+                joined.as_readonly(),
+                key,
+                world.as_unsafe_world_cell_readonly(),
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an iterator over the items of this query joined with the items of `joined`.
+    ///
+    /// For each item of this query, `key` returns the [`Entity`] to look up in `joined`.
+    /// Items whose key does not match `joined` are skipped.
+    ///
+    /// If the joined items are mutable, the returned [`QueryJoinIter`] must be traversed with
+    /// [`QueryJoinIter::fetch_next`], since several items may share the same key.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the accesses of the two queries conflict, for example when both of them
+    /// access the same component and at least one of them does so mutably, unless their filters
+    /// guarantee that they match disjoint sets of entities.
+    #[inline]
+    #[track_caller]
+    pub fn iter_join_mut<'w, 's, J: QueryData, JF: QueryFilter, K>(
+        &'s mut self,
+        world: &'w mut World,
+        joined: &'s mut QueryState<J, JF>,
+        key: K,
+    ) -> QueryJoinIter<'w, 's, D, F, J, JF, K>
+    where
+        K: FnMut(&D::Item<'w>) -> Entity,
+    {
+        self.update_archetypes(world);
+        joined.update_archetypes(world);
+        self.validate_join(joined, world.components());
+        let change_tick = world.change_tick();
+        let last_change_tick = world.last_change_tick();
+        // SAFETY: Query has unique world access, and the accesses of both queries were checked
+        // to be compatible.
+        unsafe {
+            self.iter_join_unchecked_manual(
+                joined,
+                key,
+                world.as_unsafe_world_cell(),
+                last_change_tick,
+                change_tick,
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query results for the given [`World`].
     ///
     /// # Safety
@@ -1096,6 +1232,31 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         QueryCombinationIter::new(world, self, last_run, this_run)
     }
 
+    /// Returns an [`Iterator`] over the items of this query joined with the items of `joined`,
+    /// where the last change and the current change tick are given.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query, and that the accesses of `self` and
+    /// `joined` are compatible.
+    /// This does not validate that `world.id()` matches `self.world_id` or `joined.world_id`.
+    /// Calling this on a `world` with a mismatched [`WorldId`] is unsound.
+    #[inline]
+    pub(crate) unsafe fn iter_join_unchecked_manual<'w, 's, J: QueryData, JF: QueryFilter, K>(
+        &'s self,
+        joined: &'s QueryState<J, JF>,
+        key: K,
+        world: UnsafeWorldCell<'w>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> QueryJoinIter<'w, 's, D, F, J, JF, K>
+    where
+        K: FnMut(&D::Item<'w>) -> Entity,
+    {
+        QueryJoinIter::new(world, self, joined, key, last_run, this_run)
+    }
+
     /// Runs `func` on each query result for the given [`World`]. This is faster than the equivalent
     /// `iter()` method, but cannot be chained like a normal [`Iterator`].
     ///
@@ -1718,4 +1879,88 @@ mod tests {
 
         assert_eq!(entity_a, detection_query.single(&world));
     }
+
+    #[derive(Component)]
+    struct Target(Entity);
+
+    #[test]
+    fn join_through_entity_field() {
+        let mut world = World::new();
+        let target_a = world.spawn(A(1)).id();
+        let target_b = world.spawn(A(2)).id();
+        let no_a = world.spawn_empty().id();
+        world.spawn((Target(target_a), B(10)));
+        world.spawn((Target(target_b), B(20)));
+        world.spawn((Target(target_a), B(30)));
+        world.spawn((Target(no_a), B(40)));
+
+        let mut sources = world.query::<(&Target, &B)>();
+        let mut targets = world.query::<&A>();
+        let mut joined: Vec<_> = sources
+            .iter_join(&world, &mut targets, |(target, _)| target.0)
+            .map(|((_, b), a)| (b.0, a.0))
+            .collect();
+        joined.sort();
+        assert_eq!(joined, vec![(10, 1), (20, 2), (30, 1)]);
+    }
+
+    #[test]
+    fn join_mut_writes_joined_items() {
+        let mut world = World::new();
+        let target_a = world.spawn(A(0)).id();
+        let target_b = world.spawn(A(0)).id();
+        world.spawn((Target(target_a), B(1)));
+        world.spawn((Target(target_b), B(2)));
+        world.spawn((Target(target_a), B(3)));
+
+        let mut sources = world.query::<(&Target, &mut B)>();
+        let mut targets = world.query::<&mut A>();
+        let mut iter = sources.iter_join_mut(&mut world, &mut targets, |(target, _)| target.0);
+        while let Some(((_, mut b), mut a)) = iter.fetch_next() {
+            a.0 += b.0;
+            b.0 = 0;
+        }
+
+        assert_eq!(world.get::<A>(target_a).unwrap().0, 4);
+        assert_eq!(world.get::<A>(target_b).unwrap().0, 2);
+        assert!(world.query::<&B>().iter(&world).all(|b| b.0 == 0));
+    }
+
+    #[test]
+    fn join_mut_with_disjoint_filters() {
+        let mut world = World::new();
+        let target = world.spawn(A(1)).id();
+        world.spawn((Target(target), A(2), C(0)));
+
+        let mut sources = world.query_filtered::<(&Target, &mut A), With<C>>();
+        let mut targets = world.query_filtered::<&A, Without<C>>();
+        let joined: Vec<_> = sources
+            .iter_join_mut(&mut world, &mut targets, |(target, _)| target.0)
+            .map(|((_, a), target_a)| (a.0, target_a.0))
+            .collect();
+        assert_eq!(joined, vec![(2, 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn join_mut_with_conflicting_access() {
+        let mut world = World::new();
+        let target = world.spawn(A(1)).id();
+        world.spawn((Target(target), A(2)));
+
+        let mut sources = world.query::<(&Target, &mut A)>();
+        let mut targets = world.query::<&A>();
+        let _ = sources.iter_join_mut(&mut world, &mut targets, |(target, _)| target.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "both access all components")]
+    fn join_mut_entity_mut_with_entity_ref() {
+        let mut world = World::new();
+        world.spawn(A(1));
+
+        let mut sources = world.query::<EntityMut>();
+        let mut targets = world.query::<EntityRef>();
+        let _ = sources.iter_join_mut(&mut world, &mut targets, |entity| entity.id());
+    }
 }
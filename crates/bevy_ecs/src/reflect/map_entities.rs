@@ -3,8 +3,9 @@ use crate::{
     entity::{Entity, EntityMapper, MapEntities},
     world::World,
 };
-use bevy_reflect::FromType;
+use bevy_reflect::{FromType, Reflect, ReflectFromReflect, TypeRegistry};
 use bevy_utils::EntityHashMap;
+use std::any::TypeId;
 
 /// For a specific type of component, this maps any fields with values of type [`Entity`] to a new world.
 /// Since a given `Entity` ID is only valid for the world it came from, when performing deserialization
@@ -15,6 +16,7 @@ use bevy_utils::EntityHashMap;
 pub struct ReflectMapEntities {
     map_all_entities: fn(&mut World, &mut EntityMapper),
     map_entities: fn(&mut World, &mut EntityMapper, &[Entity]),
+    map_reflect: fn(&dyn Reflect, &TypeRegistry, &mut EntityMapper) -> Option<Box<dyn Reflect>>,
 }
 
 impl ReflectMapEntities {
@@ -51,6 +53,46 @@ impl ReflectMapEntities {
             (self.map_entities)(world, mapper, entities);
         });
     }
+
+    /// Returns a copy of the reflected `component` with [`MapEntities`] applied to it, ready to be
+    /// inserted into the [`World`] the `entity_map` maps to.
+    ///
+    /// Unlike [`map_entities`](Self::map_entities), this maps the entities before the component is
+    /// inserted, so component hooks such as those of a [`Relationship`](crate::relationship::Relationship)
+    /// never observe unmapped entities.
+    ///
+    /// Returns `None` if `component` could not be converted into the concrete component type,
+    /// which requires the [`ReflectFromReflect`] of the component to be registered in `registry`.
+    pub fn map_reflect(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity, Entity>,
+        component: &dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Option<Box<dyn Reflect>> {
+        EntityMapper::world_scope(entity_map, world, |_, mapper| {
+            (self.map_reflect)(component, registry, mapper)
+        })
+    }
+
+    /// Returns the entities referenced by the reflected `component`, as reported by its
+    /// [`MapEntities`] implementation.
+    ///
+    /// Returns `None` if `component` could not be converted into the concrete component type,
+    /// which requires the [`ReflectFromReflect`] of the component to be registered in `registry`.
+    pub fn referenced_entities(
+        &self,
+        component: &dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Option<Vec<Entity>> {
+        let mut entity_map = EntityHashMap::default();
+        (self.map_reflect)(
+            component,
+            registry,
+            &mut EntityMapper::detached(&mut entity_map),
+        )?;
+        Some(entity_map.into_keys().collect())
+    }
 }
 
 impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
@@ -63,6 +105,16 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
                     }
                 }
             },
+            map_reflect: |component, registry, entity_mapper| {
+                let mut component = registry
+                    .get_type_data::<ReflectFromReflect>(TypeId::of::<C>())?
+                    .from_reflect(component)?;
+                component
+                    .as_any_mut()
+                    .downcast_mut::<C>()?
+                    .map_entities(entity_mapper);
+                Some(component)
+            },
             map_all_entities: |world, entity_mapper| {
                 let entities = entity_mapper
                     .get_map()
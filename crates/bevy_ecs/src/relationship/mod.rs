@@ -0,0 +1,450 @@
+//! Types for declaring and maintaining relationships between [`Entity`]s.
+//!
+//! A relationship is a pair of components:
+//! - a [`Relationship`] component, stored on the _source_ entity, which points at a single _target_ entity.
+//! - a [`RelationshipTarget`] component, stored on the target entity, which collects all of the source
+//!   entities currently pointing at it.
+//!
+//! Only the [`Relationship`] side should ever be written to. The [`RelationshipTarget`] side is kept
+//! up to date by component hooks: inserting, replacing, removing or despawning the [`Relationship`]
+//! component updates the reverse index on the target, and removing or despawning the target applies
+//! its [`DespawnPolicy`] to the sources.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::relationship::RelationshipTarget;
+//!
+//! #[derive(Component)]
+//! #[relationship(relationship_target = Followers)]
+//! struct Following(Entity);
+//!
+//! #[derive(Component)]
+//! #[relationship_target(relationship = Following)]
+//! struct Followers(Vec<Entity>);
+//!
+//! let mut world = World::new();
+//! let leader = world.spawn_empty().id();
+//! let follower = world.spawn(Following(leader)).id();
+//! assert_eq!(world.get::<Followers>(leader).unwrap().as_slice(), &[follower]);
+//!
+//! world.despawn(follower);
+//! assert!(world.get::<Followers>(leader).is_none());
+//! ```
+
+mod relationship_query;
+mod relationship_source_collection;
+
+pub use relationship_query::*;
+pub use relationship_source_collection::*;
+
+use crate::{
+    component::{Component, ComponentId},
+    entity::{ComponentCloneCtx, Entity},
+    world::{DeferredWorld, World},
+};
+use bevy_utils::tracing::warn;
+
+/// A [`Component`] on a source [`Entity`] that points at a single target [`Entity`].
+///
+/// The target's [`RelationshipTarget`] component is inserted and updated automatically to contain
+/// every source pointing at it. Because this bookkeeping is done by component hooks, a [`Relationship`]
+/// component must not be mutated in place: insert a new value instead.
+///
+/// A relationship that points at its own entity, or at an entity that does not exist, is removed
+/// again and a warning is logged.
+///
+/// This trait is usually implemented with `#[derive(Component)]` and the
+/// `#[relationship(relationship_target = T)]` attribute on a struct with a single [`Entity`] field,
+/// which also registers the required hooks.
+pub trait Relationship: Component + Sized {
+    /// The [`Component`] added to the target of this relationship, collecting all of its sources.
+    type RelationshipTarget: RelationshipTarget<Relationship = Self>;
+
+    /// Returns the target [`Entity`] of this relationship.
+    fn get(&self) -> Entity;
+
+    /// Creates this relationship from the given target [`Entity`].
+    fn from(entity: Entity) -> Self;
+
+    /// The `on_insert` [`ComponentHook`](crate::component::ComponentHook) of a [`Relationship`],
+    /// which adds the source `entity` to the [`RelationshipTarget`] of its target.
+    fn on_insert(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let target = world.get::<Self>(entity).unwrap().get();
+        if target == entity {
+            warn!(
+                "{} on {entity:?} points to itself. The relationship has been removed.",
+                std::any::type_name::<Self>()
+            );
+            world.commands().entity(entity).remove::<Self>();
+            return;
+        }
+        if let Some(mut relationship_target) = world.get_mut::<Self::RelationshipTarget>(target) {
+            relationship_target.collection_mut_risky().add(entity);
+            return;
+        }
+        // The target either doesn't have its `RelationshipTarget` yet, or was only just reserved.
+        // Both require structural changes, so finish the bookkeeping in a command.
+        world.commands().add(move |world: &mut World| {
+            if world.get::<Self>(entity).map(Self::get) != Some(target) {
+                return;
+            }
+            if let Some(mut target_mut) = world.get_entity_mut(target) {
+                if let Some(mut relationship_target) =
+                    target_mut.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.collection_mut_risky().add(entity);
+                } else {
+                    let mut collection =
+                        <Self::RelationshipTarget as RelationshipTarget>::Collection::with_capacity(
+                            1,
+                        );
+                    collection.add(entity);
+                    target_mut.insert(Self::RelationshipTarget::from_collection_risky(collection));
+                }
+            } else {
+                warn!(
+                    "{} on {entity:?} points to {target:?}, which does not exist. The relationship has been removed.",
+                    std::any::type_name::<Self>()
+                );
+                world.entity_mut(entity).remove::<Self>();
+            }
+        });
+    }
+
+    /// The `on_replace` [`ComponentHook`](crate::component::ComponentHook) of a [`Relationship`],
+    /// which removes the source `entity` from the [`RelationshipTarget`] of its previous target.
+    ///
+    /// If that leaves the [`RelationshipTarget`] empty, it is removed from the target.
+    fn on_replace(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let target = world.get::<Self>(entity).unwrap().get();
+        let Some(mut relationship_target) = world.get_mut::<Self::RelationshipTarget>(target)
+        else {
+            return;
+        };
+        if !relationship_target.collection_mut_risky().remove(entity)
+            || !relationship_target.is_empty()
+        {
+            return;
+        }
+        world.commands().add(move |world: &mut World| {
+            let Some(mut target_mut) = world.get_entity_mut(target) else {
+                return;
+            };
+            if target_mut
+                .get::<Self::RelationshipTarget>()
+                .is_some_and(RelationshipTarget::is_empty)
+            {
+                target_mut.remove::<Self::RelationshipTarget>();
+            }
+        });
+    }
+
+    /// The [`ComponentCloneFn`](crate::component::ComponentCloneFn) of a [`Relationship`].
+    ///
+    /// The clone points at the same target as the original, unless that target is being cloned
+    /// as well, in which case it points at the clone of the target.
+    fn clone_relationship(world: &mut World, ctx: &mut ComponentCloneCtx) {
+        let Some(target) = world.get::<Self>(ctx.source()).map(Self::get) else {
+            return;
+        };
+        ctx.write_target_component_mapped(Self::from(target), |relationship, map| {
+            *relationship = Self::from(map(relationship.get()));
+        });
+    }
+}
+
+/// What happens to the sources of a relationship when its [`RelationshipTarget`] is despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DespawnPolicy {
+    /// The sources are kept, and their [`Relationship`] component is removed.
+    Orphan,
+    /// The sources are despawned as well, recursively applying their own despawn policies.
+    Recursive,
+}
+
+/// A [`Component`] on a target [`Entity`] that collects all of the source entities whose
+/// [`Relationship`] points at it.
+///
+/// This component is maintained automatically by the hooks of its [`Relationship`] and should
+/// not be inserted or modified manually, which is why the methods that allow it are suffixed with `_risky`.
+///
+/// Removing this component from an entity orphans all of its sources by removing their [`Relationship`].
+/// When the entity is despawned, its [`RelationshipTarget::DESPAWN_POLICY`] is applied to the sources.
+///
+/// This trait is usually implemented with `#[derive(Component)]` and the
+/// `#[relationship_target(relationship = R)]` attribute on a struct with a single field holding the
+/// [`RelationshipSourceCollection`]. Adding `despawn_recursive` to the attribute sets the
+/// despawn policy to [`DespawnPolicy::Recursive`], and adding `clone_recursive` clones the sources
+/// along with the target when it is cloned recursively with an [`EntityCloner`](crate::entity::EntityCloner).
+pub trait RelationshipTarget: Component + Sized {
+    /// The [`Relationship`] component stored on the sources of this relationship.
+    type Relationship: Relationship<RelationshipTarget = Self>;
+    /// The collection used to store the sources.
+    type Collection: RelationshipSourceCollection;
+
+    /// What happens to the sources when the entity holding this component is despawned.
+    const DESPAWN_POLICY: DespawnPolicy;
+
+    /// Returns the collection of sources.
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns the collection of sources mutably.
+    ///
+    /// Changing the contents of the collection does not update the [`Relationship`] of the sources,
+    /// and will leave the relationship in an inconsistent state unless done with care.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates this component from a collection of sources.
+    ///
+    /// This does not insert the [`Relationship`] on the sources, and will leave the relationship
+    /// in an inconsistent state unless done with care.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Returns the sources of this relationship as a slice.
+    fn as_slice(&self) -> &[Entity] {
+        self.collection().as_slice()
+    }
+
+    /// Iterates over the sources of this relationship.
+    fn iter(&self) -> std::iter::Copied<std::slice::Iter<'_, Entity>> {
+        self.as_slice().iter().copied()
+    }
+
+    /// Returns the number of sources of this relationship.
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns `true` if this relationship has no sources.
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_remove` [`ComponentHook`](crate::component::ComponentHook) of a [`RelationshipTarget`],
+    /// which orphans the sources that still point at `entity`.
+    fn on_remove(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let sources = world.get::<Self>(entity).unwrap().as_slice().to_vec();
+        let mut commands = world.commands();
+        for source in sources {
+            commands.add(move |world: &mut World| {
+                let Some(mut source_mut) = world.get_entity_mut(source) else {
+                    return;
+                };
+                if source_mut
+                    .get::<Self::Relationship>()
+                    .is_some_and(|relationship| relationship.get() == entity)
+                {
+                    source_mut.remove::<Self::Relationship>();
+                }
+            });
+        }
+    }
+
+    /// The `on_despawn` [`ComponentHook`](crate::component::ComponentHook) of a [`RelationshipTarget`],
+    /// which despawns the sources if the [`RelationshipTarget::DESPAWN_POLICY`] is [`DespawnPolicy::Recursive`].
+    fn on_despawn(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        if Self::DESPAWN_POLICY != DespawnPolicy::Recursive {
+            return;
+        }
+        let sources = world.get::<Self>(entity).unwrap().as_slice().to_vec();
+        let mut commands = world.commands();
+        for source in sources {
+            commands.add(move |world: &mut World| {
+                if world
+                    .get::<Self::Relationship>(source)
+                    .is_some_and(|relationship| relationship.get() == entity)
+                {
+                    world.despawn(source);
+                }
+            });
+        }
+    }
+
+    /// The [`ComponentCloneFn`](crate::component::ComponentCloneFn) of a [`RelationshipTarget`]
+    /// declared with `clone_recursive`, which clones the sources along with the target when
+    /// cloning recursively.
+    ///
+    /// The component itself is never cloned: it is built by the hooks of the cloned sources.
+    fn clone_sources(world: &mut World, ctx: &mut ComponentCloneCtx) {
+        if !ctx.is_recursive() {
+            return;
+        }
+        let Some(sources) = world
+            .get::<Self>(ctx.source())
+            .map(|target| target.as_slice().to_vec())
+        else {
+            return;
+        };
+        for source in sources {
+            ctx.clone_entity(world, source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::relationship::{Relationship, RelationshipTarget};
+    use crate::system::SystemState;
+
+    #[derive(Component, Debug, PartialEq)]
+    #[relationship(relationship_target = Likers)]
+    struct Likes(Entity);
+
+    #[derive(Component, Debug)]
+    #[relationship_target(relationship = Likes)]
+    struct Likers(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Owned)]
+    struct OwnedBy {
+        owner: Entity,
+    }
+
+    #[derive(Component)]
+    #[relationship_target(relationship = OwnedBy, despawn_recursive)]
+    struct Owned {
+        items: Vec<Entity>,
+    }
+
+    fn likers(world: &World, entity: Entity) -> Option<Vec<Entity>> {
+        world
+            .get::<Likers>(entity)
+            .map(|likers| likers.iter().collect())
+    }
+
+    #[test]
+    fn insert_maintains_target() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(Likes(a)).id();
+        let c = world.spawn(Likes(a)).id();
+        assert_eq!(likers(&world, a), Some(vec![b, c]));
+    }
+
+    #[test]
+    fn replace_moves_source() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(Likes(a)).id();
+        world.entity_mut(c).insert(Likes(b));
+        assert_eq!(likers(&world, a), None);
+        assert_eq!(likers(&world, b), Some(vec![c]));
+    }
+
+    #[test]
+    fn reinserting_same_target_keeps_single_entry() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(Likes(a)).id();
+        world.entity_mut(b).insert(Likes(a));
+        assert_eq!(likers(&world, a), Some(vec![b]));
+    }
+
+    #[test]
+    fn remove_and_despawn_source_update_target() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(Likes(a)).id();
+        let c = world.spawn(Likes(a)).id();
+        world.entity_mut(b).remove::<Likes>();
+        assert_eq!(likers(&world, a), Some(vec![c]));
+        world.despawn(c);
+        assert_eq!(likers(&world, a), None);
+    }
+
+    #[test]
+    fn self_and_missing_targets_are_rejected() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        world.entity_mut(a).insert(Likes(a));
+        assert!(world.get::<Likes>(a).is_none());
+        assert!(world.get::<Likers>(a).is_none());
+
+        let dead = world.spawn_empty().id();
+        world.despawn(dead);
+        let b = world.spawn(Likes(dead)).id();
+        assert!(world.get::<Likes>(b).is_none());
+    }
+
+    #[test]
+    fn despawning_target_orphans_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(Likes(a)).id();
+        world.despawn(a);
+        assert!(world.get_entity(b).is_some());
+        assert!(world.get::<Likes>(b).is_none());
+    }
+
+    #[test]
+    fn removing_target_orphans_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(OwnedBy { owner: a }).id();
+        world.entity_mut(a).remove::<Owned>();
+        assert!(world.get_entity(b).is_some());
+        assert!(world.get::<OwnedBy>(b).is_none());
+    }
+
+    #[test]
+    fn despawning_target_recursively_despawns_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(OwnedBy { owner: a }).id();
+        let c = world.spawn(OwnedBy { owner: b }).id();
+        let other = world.spawn_empty().id();
+        let d = world.spawn(OwnedBy { owner: other }).id();
+        world.despawn(a);
+        assert!(world.get_entity(b).is_none());
+        assert!(world.get_entity(c).is_none());
+        assert!(world.get_entity(d).is_some());
+        assert_eq!(world.get::<Owned>(other).unwrap().as_slice(), &[d]);
+    }
+
+    #[test]
+    fn relationships_through_commands() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let mut commands = world.commands();
+        let b = commands.spawn(Likes(a)).id();
+        let target = commands.spawn_empty().id();
+        let c = commands.spawn(Likes(target)).id();
+        world.flush_commands();
+        assert_eq!(likers(&world, a), Some(vec![b]));
+        assert_eq!(likers(&world, target), Some(vec![c]));
+        assert_eq!(world.get::<Likes>(c).unwrap().get(), target);
+    }
+
+    #[test]
+    fn relationship_queries() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let child = world.spawn(OwnedBy { owner: root }).id();
+        let grandchild_1 = world.spawn(OwnedBy { owner: child }).id();
+        let grandchild_2 = world.spawn(OwnedBy { owner: child }).id();
+
+        let mut system_state = SystemState::<(Query<&OwnedBy>, Query<&Owned>)>::new(&mut world);
+        let (owned_by, owned) = system_state.get(&world);
+        assert_eq!(owned_by.related(grandchild_1), Some(child));
+        assert_eq!(owned_by.related(root), None);
+        assert_eq!(
+            owned_by.iter_ancestors(grandchild_2).collect::<Vec<_>>(),
+            vec![child, root]
+        );
+        assert_eq!(owned_by.root_ancestor(grandchild_2), root);
+        assert_eq!(owned_by.root_ancestor(root), root);
+
+        assert_eq!(
+            owned.relationship_sources(child).collect::<Vec<_>>(),
+            vec![grandchild_1, grandchild_2]
+        );
+        assert_eq!(
+            owned.iter_descendants(root).collect::<Vec<_>>(),
+            vec![child, grandchild_1, grandchild_2]
+        );
+        assert_eq!(owned.iter_descendants(grandchild_1).count(), 0);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+
+use crate::{
+    entity::Entity,
+    query::{QueryData, QueryFilter, WorldQuery},
+    system::Query,
+};
+
+use super::{Relationship, RelationshipTarget};
+
+impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
+    /// Returns the target of the [`Relationship`] `R` on `entity`, if it has one.
+    ///
+    /// Can only be called on a [`Query`] of a [`Relationship`] (i.e. `Query<&Parent>`).
+    pub fn related<R: Relationship>(&'w self, entity: Entity) -> Option<Entity>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+    {
+        self.get(entity).map(R::get).ok()
+    }
+
+    /// Returns an [`Iterator`] over the sources of the [`RelationshipTarget`] `T` on `entity`.
+    ///
+    /// Can only be called on a [`Query`] of a [`RelationshipTarget`] (i.e. `Query<&Children>`).
+    pub fn relationship_sources<T: RelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w T>,
+    {
+        self.get(entity).into_iter().flat_map(T::iter)
+    }
+
+    /// Follows the [`Relationship`] `R` from `entity` until reaching an entity without one,
+    /// and returns that entity.
+    ///
+    /// Returns `entity` itself if it has no [`Relationship`].
+    /// Can only be called on a [`Query`] of a [`Relationship`] (i.e. `Query<&Parent>`).
+    pub fn root_ancestor<R: Relationship>(&'w self, entity: Entity) -> Entity
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+    {
+        self.iter_ancestors(entity).last().unwrap_or(entity)
+    }
+
+    /// Returns an [`Iterator`] of [`Entity`]s over all of `entity`s ancestors, following the
+    /// [`Relationship`] `R`.
+    ///
+    /// Can only be called on a [`Query`] of a [`Relationship`] (i.e. `Query<&Parent>`).
+    pub fn iter_ancestors<R: Relationship>(
+        &'w self,
+        entity: Entity,
+    ) -> AncestorIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+    {
+        AncestorIter::new(self, entity)
+    }
+
+    /// Returns an [`Iterator`] of [`Entity`]s over all of `entity`s descendants, following the
+    /// [`RelationshipTarget`] `T`.
+    ///
+    /// Can only be called on a [`Query`] of a [`RelationshipTarget`] (i.e. `Query<&Children>`).
+    ///
+    /// Traverses the relationship breadth-first.
+    pub fn iter_descendants<T: RelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> DescendantIter<'w, 's, D, F, T>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w T>,
+    {
+        DescendantIter::new(self, entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`], following a [`RelationshipTarget`].
+///
+/// Traverses the relationship breadth-first.
+pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter, T: RelationshipTarget>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w T>,
+{
+    children_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, T: RelationshipTarget> DescendantIter<'w, 's, D, F, T>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w T>,
+{
+    /// Returns a new [`DescendantIter`].
+    pub fn new(children_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        DescendantIter {
+            children_query,
+            vecdeque: children_query
+                .get(entity)
+                .into_iter()
+                .flat_map(T::iter)
+                .collect(),
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, T: RelationshipTarget> Iterator
+    for DescendantIter<'w, 's, D, F, T>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w T>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+
+        if let Ok(children) = self.children_query.get(entity) {
+            self.vecdeque.extend(children.iter());
+        }
+
+        Some(entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the ancestors of an [`Entity`], following a [`Relationship`].
+pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: Relationship>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+{
+    parent_query: &'w Query<'w, 's, D, F>,
+    next: Option<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> AncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+{
+    /// Returns a new [`AncestorIter`].
+    pub fn new(parent_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        AncestorIter {
+            parent_query,
+            next: Some(entity),
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> Iterator
+    for AncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w R>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next = self.parent_query.get(self.next?).ok().map(R::get);
+        self.next
+    }
+}
@@ -0,0 +1,84 @@
+use crate::entity::Entity;
+use bevy_utils::smallvec::SmallVec;
+
+/// The internal [`Entity`] collection used by a [`RelationshipTarget`](super::RelationshipTarget)
+/// to store the sources of a relationship.
+pub trait RelationshipSourceCollection {
+    /// Returns an empty collection with room for at least `capacity` entities.
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Adds `entity` to the collection, unless it is already present.
+    ///
+    /// Returns `true` if the entity was added.
+    fn add(&mut self, entity: Entity) -> bool;
+
+    /// Removes `entity` from the collection, preserving the order of the remaining entities.
+    ///
+    /// Returns `true` if the entity was present.
+    fn remove(&mut self, entity: Entity) -> bool;
+
+    /// Returns the entities in the collection as a slice.
+    fn as_slice(&self) -> &[Entity];
+
+    /// Returns the number of entities in the collection.
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns `true` if the collection contains no entities.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl RelationshipSourceCollection for Vec<Entity> {
+    fn with_capacity(capacity: usize) -> Self {
+        Vec::with_capacity(capacity)
+    }
+
+    fn add(&mut self, entity: Entity) -> bool {
+        if self.contains(&entity) {
+            return false;
+        }
+        self.push(entity);
+        true
+    }
+
+    fn remove(&mut self, entity: Entity) -> bool {
+        if let Some(index) = self.iter().position(|e| *e == entity) {
+            Vec::remove(self, index);
+            return true;
+        }
+        false
+    }
+
+    fn as_slice(&self) -> &[Entity] {
+        self
+    }
+}
+
+impl<const N: usize> RelationshipSourceCollection for SmallVec<[Entity; N]> {
+    fn with_capacity(capacity: usize) -> Self {
+        SmallVec::with_capacity(capacity)
+    }
+
+    fn add(&mut self, entity: Entity) -> bool {
+        if self.contains(&entity) {
+            return false;
+        }
+        self.push(entity);
+        true
+    }
+
+    fn remove(&mut self, entity: Entity) -> bool {
+        if let Some(index) = self.iter().position(|e| *e == entity) {
+            SmallVec::remove(self, index);
+            return true;
+        }
+        false
+    }
+
+    fn as_slice(&self) -> &[Entity] {
+        self
+    }
+}
@@ -1,13 +1,14 @@
 use bevy_utils::all_tuples;
 
 use crate::{
+    error::BoxedError,
     schedule::{
         condition::{BoxedCondition, Condition},
         graph_utils::{Ambiguity, Dependency, DependencyKind, GraphInfo},
         set::{InternedSystemSet, IntoSystemSet, SystemSet},
         Chain,
     },
-    system::{BoxedSystem, IntoSystem, System},
+    system::{BoxedSystem, FallibleSystem, IntoSystem, System},
 };
 
 fn new_condition<M>(condition: impl Condition<M>) -> BoxedCondition {
@@ -33,12 +34,56 @@ fn ambiguous_with(graph_info: &mut GraphInfo, set: InternedSystemSet) {
     }
 }
 
-impl<Marker, F> IntoSystemConfigs<Marker> for F
+/// The `!` type, which can't be named on stable Rust but is the output of diverging closures.
+type Never = <fn() -> ! as FnRet>::Output;
+
+#[doc(hidden)]
+pub trait FnRet {
+    type Output;
+}
+
+impl<R> FnRet for fn() -> R {
+    type Output = R;
+}
+
+/// The output of a system that can be added to a schedule.
+///
+/// Systems returning a [`Result`] pass their errors to the world's
+/// [`ErrorHandler`](crate::error::ErrorHandler). Systems returning `()`, or never returning like
+/// `|| panic!()`, are added as they are.
+#[doc(hidden)]
+pub trait ScheduleSystemOutput: Sized + 'static {
+    fn into_boxed_system(system: impl System<In = (), Out = Self>) -> BoxedSystem;
+}
+
+impl ScheduleSystemOutput for () {
+    fn into_boxed_system(system: impl System<In = (), Out = Self>) -> BoxedSystem {
+        Box::new(system)
+    }
+}
+
+impl<E: Into<BoxedError> + 'static> ScheduleSystemOutput for Result<(), E> {
+    fn into_boxed_system(system: impl System<In = (), Out = Self>) -> BoxedSystem {
+        Box::new(FallibleSystem::new(system))
+    }
+}
+
+impl ScheduleSystemOutput for Never {
+    fn into_boxed_system(system: impl System<In = (), Out = Self>) -> BoxedSystem {
+        Box::new(system.map(|never| -> () { never }))
+    }
+}
+
+#[doc(hidden)]
+pub struct ScheduleSystemMarker;
+
+impl<Marker, F, Out> IntoSystemConfigs<(ScheduleSystemMarker, Out, Marker)> for F
 where
-    F: IntoSystem<(), (), Marker>,
+    F: IntoSystem<(), Out, Marker>,
+    Out: ScheduleSystemOutput,
 {
     fn into_configs(self) -> SystemConfigs {
-        SystemConfigs::new_system(Box::new(IntoSystem::into_system(self)))
+        SystemConfigs::new_system(Out::into_boxed_system(IntoSystem::into_system(self)))
     }
 }
 
@@ -9,7 +9,7 @@ pub use self::single_threaded::SingleThreadedExecutor;
 use fixedbitset::FixedBitSet;
 
 use crate::{
-    schedule::{BoxedCondition, NodeId},
+    schedule::{BoxedCondition, NodeId, SystemTiming},
     system::BoxedSystem,
     world::World,
 };
@@ -18,7 +18,21 @@ use crate::{
 pub(super) trait SystemExecutor: Send + Sync {
     fn kind(&self) -> ExecutorKind;
     fn init(&mut self, schedule: &SystemSchedule);
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World);
+    /// Runs the systems of `schedule`, except for those in `skip_systems`, which are treated as
+    /// if they had already run.
+    ///
+    /// If `record_timings` is `true`, the executor measures the systems, see
+    /// [`SystemExecutor::timings`].
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        record_timings: bool,
+    );
+    /// Returns the [`SystemTiming`]s measured during the last run that recorded timings, in the
+    /// order of the systems of the schedule.
+    fn timings(&self) -> &[SystemTiming];
     fn set_apply_final_deferred(&mut self, value: bool);
 }
 
@@ -4,10 +4,10 @@ use std::{
 };
 
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
-use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Instrument, Span};
+use bevy_utils::{default, Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use async_channel::{Receiver, Sender};
@@ -17,7 +17,10 @@ use crate::{
     archetype::ArchetypeComponentId,
     prelude::Resource,
     query::Access,
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        SystemTiming,
+    },
     system::BoxedSystem,
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
@@ -71,6 +74,8 @@ struct SystemTaskMetadata {
 struct SystemResult {
     system_index: usize,
     success: bool,
+    /// How long the system ran for, if timings are being recorded.
+    duration: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -115,6 +120,13 @@ pub struct MultiThreadedExecutor {
     panic_payload: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
     /// When set, stops the executor from running any more systems.
     stop_spawning: bool,
+    /// Whether the current run measures its systems.
+    record_timings: bool,
+    /// Timings of the systems during the last run that recorded them.
+    timings: Vec<SystemTiming>,
+    /// Since when each system has been blocked by running systems with conflicting access, if
+    /// timings are being recorded.
+    blocked_since: Vec<Option<Instant>>,
 }
 
 impl Default for MultiThreadedExecutor {
@@ -163,7 +175,22 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        record_timings: bool,
+    ) {
+        self.record_timings = record_timings;
+        if record_timings {
+            self.timings.clear();
+            self.timings
+                .resize(schedule.systems.len(), SystemTiming::default());
+            self.blocked_since.clear();
+            self.blocked_since.resize(schedule.systems.len(), None);
+        }
+
         // reset counts
         self.num_systems = schedule.systems.len();
         if self.num_systems == 0 {
@@ -181,6 +208,17 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        // systems skipped by stepping are treated as already completed, and their dependents are
+        // signaled as though they had run
+        if let Some(skipped_systems) = skip_systems {
+            self.completed_systems |= skipped_systems;
+            self.num_completed_systems = self.completed_systems.count_ones(..);
+            for system_index in skipped_systems.ones() {
+                self.signal_dependents(system_index);
+            }
+            self.ready_systems.difference_with(skipped_systems);
+        }
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
@@ -258,6 +296,10 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.completed_systems.clear();
     }
 
+    fn timings(&self) -> &[SystemTiming] {
+        &self.timings
+    }
+
     fn set_apply_final_deferred(&mut self, value: bool) {
         self.apply_final_deferred = value;
     }
@@ -290,6 +332,9 @@ impl MultiThreadedExecutor {
             apply_final_deferred: true,
             panic_payload: Arc::new(Mutex::new(None)),
             stop_spawning: false,
+            record_timings: false,
+            timings: Vec::new(),
+            blocked_since: Vec::new(),
         }
     }
 
@@ -328,6 +373,7 @@ impl MultiThreadedExecutor {
                 continue;
             }
 
+            self.end_blocked(system_index);
             self.ready_systems.set(system_index, false);
 
             // SAFETY: `can_run` returned true, which means that:
@@ -375,10 +421,13 @@ impl MultiThreadedExecutor {
     ) -> bool {
         let system_meta = &self.system_task_metadata[system_index];
         if system_meta.is_exclusive && self.num_running_systems > 0 {
+            self.start_blocked(system_index);
             return false;
         }
 
+        // Waiting for the local thread isn't an access conflict.
         if !system_meta.is_send && self.local_thread_running {
+            self.end_blocked(system_index);
             return false;
         }
 
@@ -392,6 +441,7 @@ impl MultiThreadedExecutor {
                     .archetype_component_access()
                     .is_compatible(&self.active_access)
                 {
+                    self.start_blocked(system_index);
                     return false;
                 }
             }
@@ -403,6 +453,7 @@ impl MultiThreadedExecutor {
                 .archetype_component_access()
                 .is_compatible(&self.active_access)
             {
+                self.start_blocked(system_index);
                 return false;
             }
         }
@@ -413,6 +464,7 @@ impl MultiThreadedExecutor {
                 .archetype_component_access()
                 .is_compatible(&self.active_access)
             {
+                self.start_blocked(system_index);
                 return false;
             }
 
@@ -426,6 +478,25 @@ impl MultiThreadedExecutor {
         true
     }
 
+    /// Starts measuring how long the system is blocked by running systems with conflicting
+    /// access, unless it already is blocked.
+    fn start_blocked(&mut self, system_index: usize) {
+        if self.record_timings {
+            self.blocked_since[system_index].get_or_insert_with(Instant::now);
+        }
+    }
+
+    /// Stops measuring how long the system is blocked, adding the time to its timing.
+    fn end_blocked(&mut self, system_index: usize) {
+        if let Some(since) = self
+            .blocked_since
+            .get_mut(system_index)
+            .and_then(Option::take)
+        {
+            self.timings[system_index].waited += since.elapsed();
+        }
+    }
+
     /// # Safety
     /// * `world` must have permission to read any world data required by
     ///   the system's conditions: this includes conditions for the system
@@ -496,7 +567,9 @@ impl MultiThreadedExecutor {
         let system = unsafe { &mut *systems[system_index].get() };
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let record_timings = self.record_timings;
         let task = async move {
+            let start = record_timings.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -509,6 +582,7 @@ impl MultiThreadedExecutor {
                 .try_send(SystemResult {
                     system_index,
                     success: res.is_ok(),
+                    duration: start.map(|start| start.elapsed()),
                 })
                 .unwrap_or_else(|error| unreachable!("{}", error));
             if let Err(payload) = res {
@@ -554,17 +628,20 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let record_timings = self.record_timings;
         if is_apply_deferred(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
+                let start = record_timings.then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, systems, world);
                 // tell the executor that the system finished
                 sender
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        duration: start.map(|start| start.elapsed()),
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -583,6 +660,7 @@ impl MultiThreadedExecutor {
             scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let start = record_timings.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
@@ -591,6 +669,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        duration: start.map(|start| start.elapsed()),
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -621,8 +700,13 @@ impl MultiThreadedExecutor {
         let SystemResult {
             system_index,
             success,
+            duration,
         } = result;
 
+        if duration.is_some() {
+            self.timings[system_index].duration = duration;
+        }
+
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
         }
@@ -646,6 +730,9 @@ impl MultiThreadedExecutor {
     }
 
     fn skip_system_and_signal_dependents(&mut self, system_index: usize) {
+        if self.record_timings {
+            self.timings[system_index].skipped = true;
+        }
         self.num_completed_systems += 1;
         self.completed_systems.insert(system_index);
         self.signal_dependents(system_index);
@@ -1,10 +1,11 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
 use crate::{
-    schedule::{BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule, SystemTiming},
     world::World,
 };
 
@@ -16,6 +17,8 @@ pub struct SimpleExecutor {
     evaluated_sets: FixedBitSet,
     /// Systems that have run or been skipped.
     completed_systems: FixedBitSet,
+    /// Timings of the systems during the last run that recorded them.
+    timings: Vec<SystemTiming>,
 }
 
 impl SystemExecutor for SimpleExecutor {
@@ -30,7 +33,24 @@ impl SystemExecutor for SimpleExecutor {
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        record_timings: bool,
+    ) {
+        // systems skipped by stepping are treated as already completed
+        if let Some(skipped_systems) = skip_systems {
+            self.completed_systems |= skipped_systems;
+        }
+
+        if record_timings {
+            self.timings.clear();
+            self.timings
+                .resize(schedule.systems.len(), SystemTiming::default());
+        }
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -69,9 +89,15 @@ impl SystemExecutor for SimpleExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                let skipped_by_stepping =
+                    skip_systems.is_some_and(|skipped| skipped.contains(system_index));
+                if record_timings && !skipped_by_stepping {
+                    self.timings[system_index].skipped = true;
+                }
                 continue;
             }
 
+            let start = record_timings.then(Instant::now);
             let system = &mut schedule.systems[system_index];
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 system.run((), world);
@@ -81,6 +107,10 @@ impl SystemExecutor for SimpleExecutor {
                 std::panic::resume_unwind(payload);
             }
 
+            if let Some(start) = start {
+                self.timings[system_index].duration = Some(start.elapsed());
+            }
+
             system.apply_deferred(world);
         }
 
@@ -88,6 +118,10 @@ impl SystemExecutor for SimpleExecutor {
         self.completed_systems.clear();
     }
 
+    fn timings(&self) -> &[SystemTiming] {
+        &self.timings
+    }
+
     fn set_apply_final_deferred(&mut self, _: bool) {
         // do nothing. simple executor does not do a final sync
     }
@@ -100,6 +134,7 @@ impl SimpleExecutor {
         Self {
             evaluated_sets: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
+            timings: Vec::new(),
         }
     }
 }
@@ -1,10 +1,14 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
 use crate::{
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        SystemTiming,
+    },
     world::World,
 };
 
@@ -22,6 +26,8 @@ pub struct SingleThreadedExecutor {
     unapplied_systems: FixedBitSet,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
+    /// Timings of the systems during the last run that recorded them.
+    timings: Vec<SystemTiming>,
 }
 
 impl SystemExecutor for SingleThreadedExecutor {
@@ -38,7 +44,24 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        record_timings: bool,
+    ) {
+        // systems skipped by stepping are treated as already completed
+        if let Some(skipped_systems) = skip_systems {
+            self.completed_systems |= skipped_systems;
+        }
+
+        if record_timings {
+            self.timings.clear();
+            self.timings
+                .resize(schedule.systems.len(), SystemTiming::default());
+        }
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -77,9 +100,15 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                let skipped_by_stepping =
+                    skip_systems.is_some_and(|skipped| skipped.contains(system_index));
+                if record_timings && !skipped_by_stepping {
+                    self.timings[system_index].skipped = true;
+                }
                 continue;
             }
 
+            let start = record_timings.then(Instant::now);
             let system = &mut schedule.systems[system_index];
             if is_apply_deferred(system) {
                 self.apply_deferred(schedule, world);
@@ -93,6 +122,10 @@ impl SystemExecutor for SingleThreadedExecutor {
                 }
                 self.unapplied_systems.insert(system_index);
             }
+
+            if let Some(start) = start {
+                self.timings[system_index].duration = Some(start.elapsed());
+            }
         }
 
         if self.apply_final_deferred {
@@ -102,6 +135,10 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.completed_systems.clear();
     }
 
+    fn timings(&self) -> &[SystemTiming] {
+        &self.timings
+    }
+
     fn set_apply_final_deferred(&mut self, apply_final_deferred: bool) {
         self.apply_final_deferred = apply_final_deferred;
     }
@@ -117,6 +154,7 @@ impl SingleThreadedExecutor {
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
+            timings: Vec::new(),
         }
     }
 
@@ -0,0 +1,457 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schedule::NodeId;
+
+/// A serializable description of a built [`Schedule`](super::Schedule), returned by
+/// [`Schedule::export`](super::Schedule::export).
+///
+/// The export contains everything the schedule resolved when it was built: the system sets and
+/// their run conditions, the flattened topological order the executor uses, the
+/// [`apply_deferred`](super::apply_deferred) sync points and the pairs of systems with conflicting
+/// data access but no defined order.
+///
+/// All lists are sorted, so exporting the same schedule twice produces identical output. This makes
+/// the [`Serialize`] representation, for example as JSON, suitable for checking into a repository
+/// and diffing, while [`ScheduleExport::to_dot`] renders it for [Graphviz](https://graphviz.org/).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::ScheduleExport;
+/// fn spawn(mut commands: Commands) {}
+/// fn movement() {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems((spawn, movement).chain());
+/// schedule.initialize(&mut world).unwrap();
+///
+/// let export = schedule.export(world.components()).unwrap();
+/// // `spawn` has commands, so a sync point is inserted before `movement`.
+/// assert_eq!(export.order.len(), 3);
+/// assert_eq!(export.sync_points().count(), 1);
+///
+/// let dot = export.to_dot();
+/// assert!(dot.starts_with("digraph"));
+///
+/// let json = serde_json::to_string_pretty(&export).unwrap();
+/// assert_eq!(serde_json::from_str::<ScheduleExport>(&json).unwrap(), export);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleExport {
+    /// The name of the schedule's [`ScheduleLabel`](super::ScheduleLabel).
+    pub label: String,
+    /// Every system in the schedule, including sync points, sorted by [`NodeId`].
+    pub systems: Vec<ExportedSystem>,
+    /// Every system set in the schedule, sorted by [`NodeId`].
+    ///
+    /// The sets implicitly created for each system function are omitted.
+    pub sets: Vec<ExportedSystemSet>,
+    /// `(set, member)` pairs, where `member` is a system or set directly contained in `set`.
+    ///
+    /// Redundant (transitive) memberships are not included.
+    pub hierarchy: Vec<(NodeId, NodeId)>,
+    /// The systems in the order they were topologically sorted.
+    pub order: Vec<NodeId>,
+    /// `(before, after)` pairs of systems, after all set orderings have been flattened onto the
+    /// systems they contain and sync points have been inserted.
+    ///
+    /// Redundant (transitive) orderings are not included.
+    pub dependencies: Vec<(NodeId, NodeId)>,
+    /// Pairs of systems that have conflicting data access but no defined order between them.
+    ///
+    /// Ambiguities that were explicitly allowed are not included.
+    pub conflicts: Vec<ExportedConflict>,
+}
+
+/// A system in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedSystem {
+    /// The system's identifier in the [`ScheduleGraph`](super::ScheduleGraph).
+    pub id: NodeId,
+    /// The name of the system.
+    pub name: String,
+    /// The names of the run conditions attached directly to this system.
+    pub conditions: Vec<String>,
+    /// Whether the system requires exclusive [`World`](crate::world::World) access.
+    pub exclusive: bool,
+    /// Whether this system is an [`apply_deferred`](super::apply_deferred) sync point, and if so,
+    /// how it was added.
+    pub sync_point: Option<SyncPointKind>,
+}
+
+/// How an [`apply_deferred`](super::apply_deferred) sync point ended up in a schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SyncPointKind {
+    /// The sync point was inserted by the schedule, see
+    /// [`ScheduleBuildSettings::auto_insert_apply_deferred`](super::ScheduleBuildSettings::auto_insert_apply_deferred).
+    Automatic,
+    /// The sync point was added as a system by the user.
+    Explicit,
+}
+
+/// A system set in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedSystemSet {
+    /// The set's identifier in the [`ScheduleGraph`](super::ScheduleGraph).
+    pub id: NodeId,
+    /// The name of the set.
+    ///
+    /// Anonymous sets are named after their members.
+    pub name: String,
+    /// The names of the run conditions attached to this set.
+    pub conditions: Vec<String>,
+}
+
+/// A pair of systems in a [`ScheduleExport`] that have conflicting data access but no defined order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedConflict {
+    /// The first system.
+    pub system_a: NodeId,
+    /// The second system.
+    pub system_b: NodeId,
+    /// The names of the components and resources both systems access, at least one of them mutably.
+    ///
+    /// If one of the systems is exclusive, this contains the name of [`World`](crate::world::World).
+    pub access: Vec<String>,
+}
+
+impl ScheduleExport {
+    /// Returns the exported system with the given `id`, if it exists.
+    pub fn system(&self, id: NodeId) -> Option<&ExportedSystem> {
+        self.systems
+            .binary_search_by_key(&id, |system| system.id)
+            .ok()
+            .map(|index| &self.systems[index])
+    }
+
+    /// Returns the exported system set with the given `id`, if it exists.
+    pub fn set(&self, id: NodeId) -> Option<&ExportedSystemSet> {
+        self.sets
+            .binary_search_by_key(&id, |set| set.id)
+            .ok()
+            .map(|index| &self.sets[index])
+    }
+
+    /// Returns an iterator over the [`apply_deferred`](super::apply_deferred) sync points, in
+    /// topological order.
+    pub fn sync_points(&self) -> impl Iterator<Item = &ExportedSystem> + '_ {
+        self.order
+            .iter()
+            .filter_map(|&id| self.system(id))
+            .filter(|system| system.sync_point.is_some())
+    }
+
+    /// Renders the schedule in the [DOT language](https://graphviz.org/doc/info/lang.html).
+    ///
+    /// Systems are drawn as boxes (sync points as diamonds) connected by their flattened ordering,
+    /// system sets as ellipses connected to their members by dashed lines, and conflicting systems
+    /// by red, undirected edges labelled with the conflicting data.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph {} {{", quote(&self.label)).unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(dot, "    node [shape=box];").unwrap();
+
+        for set in &self.sets {
+            writeln!(
+                dot,
+                "    {} [label={}, shape=ellipse, style=dashed];",
+                node_key(set.id),
+                quote(&with_conditions(&set.name, &set.conditions)),
+            )
+            .unwrap();
+        }
+
+        for system in &self.systems {
+            let label = quote(&with_conditions(&system.name, &system.conditions));
+            let style = match system.sync_point {
+                Some(SyncPointKind::Automatic) => ", shape=diamond, style=dashed",
+                Some(SyncPointKind::Explicit) => ", shape=diamond",
+                None if system.exclusive => ", style=bold",
+                None => "",
+            };
+            writeln!(dot, "    {} [label={label}{style}];", node_key(system.id)).unwrap();
+        }
+
+        for &(set, member) in &self.hierarchy {
+            writeln!(
+                dot,
+                "    {} -> {} [style=dashed, arrowhead=none, color=gray];",
+                node_key(set),
+                node_key(member),
+            )
+            .unwrap();
+        }
+
+        for &(before, after) in &self.dependencies {
+            writeln!(dot, "    {} -> {};", node_key(before), node_key(after)).unwrap();
+        }
+
+        for conflict in &self.conflicts {
+            writeln!(
+                dot,
+                "    {} -> {} [dir=none, constraint=false, color=red, fontcolor=red, label={}];",
+                node_key(conflict.system_a),
+                node_key(conflict.system_b),
+                quote(&conflict.access.join("\n")),
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+fn with_conditions(name: &str, conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name}\nif {}", conditions.join(" && "))
+    }
+}
+
+/// Quotes `s` as a DOT string, escaping quotes, backslashes and newlines.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Component)]
+    struct Position;
+
+    #[derive(Resource)]
+    struct Paused(bool);
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    fn spawn(_commands: Commands) {}
+    fn read_position(_query: Query<&Position>) {}
+    fn write_position(_query: Query<&mut Position>) {}
+    fn exclusive(_world: &mut World) {}
+
+    fn not_paused(paused: Res<Paused>) -> bool {
+        !paused.0
+    }
+
+    fn build(schedule: &mut Schedule) -> ScheduleExport {
+        let mut world = World::new();
+        world.insert_resource(Paused(false));
+        schedule.initialize(&mut world).unwrap();
+        schedule.export(world.components()).unwrap()
+    }
+
+    fn id_of(export: &ScheduleExport, name: &str) -> NodeId {
+        export
+            .systems
+            .iter()
+            .find(|system| system.name == name)
+            .unwrap_or_else(|| panic!("no system named {name}"))
+            .id
+    }
+
+    #[test]
+    fn export_requires_initialized_schedule() {
+        let world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(spawn);
+        assert!(schedule.export(world.components()).is_err());
+    }
+
+    #[test]
+    fn exports_sets_conditions_and_hierarchy() {
+        let mut schedule = Schedule::default();
+        schedule
+            .configure_sets(Physics.run_if(not_paused))
+            .add_systems((read_position, write_position.run_if(not_paused)).in_set(Physics));
+        let export = build(&mut schedule);
+
+        assert_eq!(export.sets.len(), 1);
+        let physics = &export.sets[0];
+        assert_eq!(physics.name, "Physics");
+        assert_eq!(physics.conditions, vec!["not_paused".to_string()]);
+
+        let read = id_of(&export, "read_position");
+        let write = id_of(&export, "write_position");
+        assert!(export.system(read).unwrap().conditions.is_empty());
+        assert_eq!(
+            export.system(write).unwrap().conditions,
+            vec!["not_paused".to_string()]
+        );
+        assert_eq!(
+            export.hierarchy,
+            vec![(physics.id, read), (physics.id, write)]
+        );
+    }
+
+    #[test]
+    fn exports_order_and_sync_points() {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spawn, read_position, apply_deferred, write_position).chain());
+        let export = build(&mut schedule);
+
+        let names: Vec<_> = export
+            .order
+            .iter()
+            .map(|&id| export.system(id).unwrap().name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "spawn",
+                "apply_deferred",
+                "read_position",
+                "apply_deferred",
+                "write_position"
+            ]
+        );
+
+        let sync_points: Vec<_> = export
+            .sync_points()
+            .map(|system| system.sync_point.unwrap())
+            .collect();
+        assert_eq!(
+            sync_points,
+            [SyncPointKind::Automatic, SyncPointKind::Explicit]
+        );
+
+        let chain: Vec<_> = export.order.windows(2).map(|w| (w[0], w[1])).collect();
+        let mut expected = chain.clone();
+        expected.sort();
+        assert_eq!(export.dependencies, expected);
+        assert!(export.conflicts.is_empty());
+    }
+
+    #[test]
+    fn exports_conflicts_with_component_names() {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((read_position, write_position, exclusive));
+        let export = build(&mut schedule);
+
+        let read = id_of(&export, "read_position");
+        let write = id_of(&export, "write_position");
+        let exclusive = id_of(&export, "exclusive");
+        assert!(export.system(exclusive).unwrap().exclusive);
+
+        let position = export
+            .conflicts
+            .iter()
+            .find(|c| {
+                [c.system_a, c.system_b].contains(&read)
+                    && [c.system_a, c.system_b].contains(&write)
+            })
+            .unwrap();
+        assert_eq!(position.access, vec!["Position".to_string()]);
+
+        let world_conflicts = export
+            .conflicts
+            .iter()
+            .filter(|c| c.system_a == exclusive || c.system_b == exclusive)
+            .count();
+        assert_eq!(world_conflicts, 2);
+        assert!(export
+            .conflicts
+            .iter()
+            .filter(|c| c.system_a == exclusive || c.system_b == exclusive)
+            .all(|c| c.access == vec!["World".to_string()]));
+    }
+
+    #[test]
+    fn export_is_stable_and_round_trips() {
+        let make_schedule = || {
+            let mut schedule = Schedule::default();
+            schedule
+                .configure_sets(Physics.run_if(not_paused))
+                .add_systems((
+                    spawn.before(Physics),
+                    (read_position, write_position).in_set(Physics),
+                    exclusive.after(Physics),
+                ));
+            schedule
+        };
+        let first = build(&mut make_schedule());
+        let second = build(&mut make_schedule());
+
+        let serialized = ron::to_string(&first).unwrap();
+        assert_eq!(serialized, ron::to_string(&second).unwrap());
+        assert_eq!(ron::from_str::<ScheduleExport>(&serialized).unwrap(), first);
+        assert_eq!(first.to_dot(), second.to_dot());
+    }
+
+    #[test]
+    fn exports_unknown_components_by_id() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((read_position, write_position));
+        schedule.initialize(&mut world).unwrap();
+        let position = world.component_id::<Position>().unwrap();
+
+        let export = schedule.export(World::new().components()).unwrap();
+        assert_eq!(export.conflicts.len(), 1);
+        assert_eq!(export.conflicts[0].access, vec![format!("{position:?}")]);
+    }
+
+    #[test]
+    fn dot_output() {
+        let mut schedule = Schedule::default();
+        schedule
+            .configure_sets(Physics.run_if(not_paused))
+            .add_systems((spawn, write_position.in_set(Physics)).chain());
+        let export = build(&mut schedule);
+
+        let spawn = id_of(&export, "spawn");
+        let write = id_of(&export, "write_position");
+        let sync = export.sync_points().next().unwrap().id;
+        let physics = export.sets[0].id;
+        let key = node_key;
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph \"DefaultSchedule\" {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(&format!(
+            "{} [label=\"Physics\\nif not_paused\", shape=ellipse, style=dashed];",
+            key(physics)
+        )));
+        assert!(dot.contains(&format!(
+            "{} [label=\"apply_deferred\", shape=diamond, style=dashed];",
+            key(sync)
+        )));
+        assert!(dot.contains(&format!("{} -> {};", key(spawn), key(sync))));
+        assert!(dot.contains(&format!("{} -> {};", key(sync), key(write))));
+        assert!(dot.contains(&format!(
+            "{} -> {} [style=dashed, arrowhead=none, color=gray];",
+            key(physics),
+            key(write)
+        )));
+    }
+
+    #[test]
+    fn dot_quoting() {
+        assert_eq!(quote("a \"b\"\\c\nd"), "\"a \\\"b\\\"\\\\c\\nd\"");
+    }
+}
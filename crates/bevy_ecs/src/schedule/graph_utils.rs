@@ -5,13 +5,14 @@ use bevy_utils::{
     HashMap, HashSet,
 };
 use fixedbitset::FixedBitSet;
+use serde::{Deserialize, Serialize};
 
 use crate::schedule::set::*;
 
 /// Unique identifier for a system or system set stored in a [`ScheduleGraph`].
 ///
 /// [`ScheduleGraph`]: super::ScheduleGraph
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum NodeId {
     /// Identifier for a system.
     System(usize),
@@ -324,7 +324,25 @@ impl Schedule {
         world.check_change_ticks();
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.name));
-        self.executor.run(&mut self.executable, world);
+
+        let skip_systems = match world.get_resource_mut::<Stepping>() {
+            None => None,
+            Some(mut stepping) => stepping.skipped_systems(self),
+        };
+
+        let record_timings = world.contains_resource::<SystemStats>();
+        self.executor.run(
+            &mut self.executable,
+            world,
+            skip_systems.as_ref(),
+            record_timings,
+        );
+
+        if record_timings {
+            if let Some(mut stats) = world.get_resource_mut::<SystemStats>() {
+                stats.record(self.name, &self.executable, self.executor.timings());
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -356,6 +374,288 @@ impl Schedule {
         Ok(())
     }
 
+    /// Removes systems and system sets from this schedule, along with their run conditions and
+    /// all ordering, hierarchy and ambiguity relations that involve them.
+    ///
+    /// Removing a set does not remove the systems in it. Ids that don't belong to this schedule,
+    /// or that have already been removed, are ignored. The ids of the remaining nodes are left
+    /// unchanged, and the executable schedule is rebuilt the next time the schedule is
+    /// initialized.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource, Default)]
+    /// # struct Counter(u32);
+    /// fn count(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Counter>();
+    /// let mut schedule = Schedule::default();
+    /// let existing: Vec<_> = schedule.graph().node_ids().collect();
+    /// schedule.add_systems(count);
+    /// schedule.run(&mut world);
+    ///
+    /// let added: Vec<_> = schedule
+    ///     .graph()
+    ///     .node_ids()
+    ///     .filter(|id| !existing.contains(id))
+    ///     .collect();
+    /// schedule.remove_nodes(added);
+    /// schedule.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    /// ```
+    pub fn remove_nodes(&mut self, nodes: impl IntoIterator<Item = NodeId>) {
+        self.graph.reclaim(&mut self.executable);
+        // the executable schedule is empty until it is rebuilt
+        self.graph.changed = true;
+        for id in nodes {
+            self.graph.remove_node(id);
+        }
+    }
+
+    /// Returns `true` if systems, sets or dependencies have been added or removed since the last
+    /// call to [`Schedule::initialize`].
+    pub fn is_changed(&self) -> bool {
+        self.graph.changed
+    }
+
+    /// Returns the label of this schedule.
+    pub fn label(&self) -> InternedScheduleLabel {
+        self.name
+    }
+
+    /// Returns an iterator over the systems of this schedule, in the order the executor considers them.
+    ///
+    /// Systems are only moved into the executable schedule when the schedule is initialized, so this
+    /// returns an error if systems have been added since the last call to [`Schedule::initialize`].
+    pub fn systems(
+        &self,
+    ) -> Result<impl Iterator<Item = (NodeId, &BoxedSystem)>, ScheduleNotInitialized> {
+        if self.graph.changed {
+            return Err(ScheduleNotInitialized);
+        }
+
+        Ok(self
+            .executable
+            .system_ids
+            .iter()
+            .copied()
+            .zip(self.executable.systems.iter()))
+    }
+
+    /// Returns `true` if any system or run condition of this schedule accesses non-send data, and
+    /// so must run on the thread that owns the [`World`].
+    ///
+    /// Systems only know which data they access once initialized, so like [`Schedule::systems`],
+    /// this returns an error if systems have been added since the last call to
+    /// [`Schedule::initialize`]. Exclusive systems aren't counted, as they can only reach non-send
+    /// data through the `World`.
+    pub fn has_non_send_systems(&self) -> Result<bool, ScheduleNotInitialized> {
+        if self.graph.changed {
+            return Err(ScheduleNotInitialized);
+        }
+
+        let executable = &self.executable;
+        let mut conditions = executable
+            .system_conditions
+            .iter()
+            .chain(&executable.set_conditions)
+            .flatten();
+        Ok(executable
+            .systems
+            .iter()
+            .filter(|system| !system.is_exclusive())
+            .any(|system| !system.is_send())
+            || conditions.any(|condition| !condition.is_send()))
+    }
+
+    /// Exports the resolved structure of this schedule, e.g. to render it with
+    /// [`ScheduleExport::to_dot`] or serialize it for diffing.
+    ///
+    /// Names are shortened according to [`ScheduleBuildSettings::use_shortnames`], and the names of
+    /// conflicting components and resources are looked up in `components`, which should belong to
+    /// the [`World`] the schedule was initialized with. Components missing from `components` are
+    /// named after their [`ComponentId`](crate::component::ComponentId).
+    ///
+    /// Like [`Schedule::systems`], this returns an error if systems have been added since the last
+    /// call to [`Schedule::initialize`].
+    pub fn export(
+        &self,
+        components: &Components,
+    ) -> Result<ScheduleExport, ScheduleNotInitialized> {
+        if self.graph.changed {
+            return Err(ScheduleNotInitialized);
+        }
+
+        let graph = &self.graph;
+        let shorten = |name: &str| {
+            if graph.settings.use_shortnames {
+                bevy_utils::get_short_name(name)
+            } else {
+                name.to_string()
+            }
+        };
+        let auto_sync_points: HashSet<NodeId> =
+            graph.auto_sync_node_ids.values().copied().collect();
+
+        let mut systems: Vec<_> = self
+            .executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+            .zip(&self.executable.system_conditions)
+            .map(|((&id, system), conditions)| ExportedSystem {
+                id,
+                name: shorten(&system.name()),
+                conditions: conditions
+                    .iter()
+                    .map(|condition| shorten(&condition.name()))
+                    .collect(),
+                exclusive: system.is_exclusive(),
+                sync_point: is_apply_deferred(system).then(|| {
+                    if auto_sync_points.contains(&id) {
+                        SyncPointKind::Automatic
+                    } else {
+                        SyncPointKind::Explicit
+                    }
+                }),
+            })
+            .collect();
+        systems.sort_by_key(|system| system.id);
+
+        // conditions of sets are only moved into the executable schedule if there are any
+        let set_conditions: HashMap<NodeId, &Vec<BoxedCondition>> = self
+            .executable
+            .set_ids
+            .iter()
+            .copied()
+            .zip(&self.executable.set_conditions)
+            .collect();
+        let is_system_type_set =
+            |id: NodeId| id.is_set() && graph.system_sets[id.index()].is_system_type();
+
+        let mut sets = Vec::new();
+        for (index, set) in graph.system_sets.iter().enumerate() {
+            let id = NodeId::Set(index);
+            if set.is_system_type() || graph.removed_nodes.contains(&id) {
+                continue;
+            }
+            let conditions = set_conditions
+                .get(&id)
+                .copied()
+                .unwrap_or(&graph.system_set_conditions[index]);
+            sets.push(ExportedSystemSet {
+                id,
+                name: self.export_node_name(id, &systems, &shorten),
+                conditions: conditions
+                    .iter()
+                    .map(|condition| shorten(&condition.name()))
+                    .collect(),
+            });
+        }
+
+        // replace the sets created for system functions with the systems they contain
+        let mut hierarchy = Vec::new();
+        for (set, member, _) in graph.hierarchy.graph.all_edges() {
+            if is_system_type_set(set) {
+                continue;
+            }
+            if is_system_type_set(member) {
+                hierarchy.extend(
+                    graph
+                        .hierarchy
+                        .graph
+                        .neighbors_directed(member, Outgoing)
+                        .map(|system| (set, system)),
+                );
+            } else {
+                hierarchy.push((set, member));
+            }
+        }
+        hierarchy.sort();
+        hierarchy.dedup();
+
+        let system_ids = &self.executable.system_ids;
+        let mut dependencies: Vec<_> = self
+            .executable
+            .system_dependents
+            .iter()
+            .enumerate()
+            .flat_map(|(before, dependents)| {
+                dependents
+                    .iter()
+                    .map(move |&after| (system_ids[before], system_ids[after]))
+            })
+            .collect();
+        dependencies.sort();
+
+        let conflicts = graph
+            .conflicting_systems
+            .iter()
+            .map(|(system_a, system_b, conflicts)| ExportedConflict {
+                system_a: *system_a,
+                system_b: *system_b,
+                access: if conflicts.is_empty() {
+                    // one or both systems must be exclusive
+                    vec![shorten(std::any::type_name::<World>())]
+                } else {
+                    conflicts
+                        .iter()
+                        .map(|&id| match components.get_name(id) {
+                            Some(name) => shorten(name),
+                            None => format!("{id:?}"),
+                        })
+                        .collect()
+                },
+            })
+            .collect();
+
+        Ok(ScheduleExport {
+            label: format!("{:?}", self.name),
+            systems,
+            sets,
+            hierarchy,
+            order: system_ids.clone(),
+            dependencies,
+            conflicts,
+        })
+    }
+
+    /// Returns the name of a node for [`Schedule::export`].
+    ///
+    /// Built schedules no longer store their systems in the graph, so the names of systems are
+    /// taken from the already exported `systems`.
+    fn export_node_name(
+        &self,
+        id: NodeId,
+        systems: &[ExportedSystem],
+        shorten: &impl Fn(&str) -> String,
+    ) -> String {
+        match id {
+            NodeId::System(_) => systems
+                .binary_search_by_key(&id, |system| system.id)
+                .map(|index| systems[index].name.clone())
+                .unwrap_or_default(),
+            NodeId::Set(index) => {
+                let set = &self.graph.system_sets[index];
+                if set.is_anonymous() {
+                    let members: Vec<_> = self
+                        .graph
+                        .hierarchy
+                        .graph
+                        .neighbors_directed(id, Outgoing)
+                        .map(|member| self.export_node_name(member, systems, shorten))
+                        .collect();
+                    format!("({})", members.join(", "))
+                } else {
+                    shorten(&set.name())
+                }
+            }
+        }
+    }
+
     /// Returns the [`ScheduleGraph`].
     pub fn graph(&self) -> &ScheduleGraph {
         &self.graph
@@ -497,6 +797,7 @@ pub struct ScheduleGraph {
     settings: ScheduleBuildSettings,
     no_sync_edges: BTreeSet<(NodeId, NodeId)>,
     auto_sync_node_ids: HashMap<u32, NodeId>,
+    removed_nodes: HashSet<NodeId>,
 }
 
 impl ScheduleGraph {
@@ -519,6 +820,7 @@ impl ScheduleGraph {
             settings: default(),
             no_sync_edges: BTreeSet::new(),
             auto_sync_node_ids: HashMap::new(),
+            removed_nodes: HashSet::new(),
         }
     }
 
@@ -584,6 +886,18 @@ impl ScheduleGraph {
         })
     }
 
+    /// Returns the ids of all systems and system sets in this schedule that have not been removed,
+    /// wherever the systems are currently stored.
+    ///
+    /// Ids are never reused, so comparing the ids before and after adding to the schedule gives
+    /// the nodes that were added.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.systems.len())
+            .map(NodeId::System)
+            .chain((0..self.system_sets.len()).map(NodeId::Set))
+            .filter(|id| !self.removed_nodes.contains(id))
+    }
+
     /// Returns the [`Dag`] of the hierarchy.
     ///
     /// The hierarchy is a directed acyclic graph of the systems and sets,
@@ -1401,7 +1715,27 @@ impl ScheduleGraph {
             return Err(ScheduleBuildError::Uninitialized);
         }
 
-        // move systems out of old schedule
+        self.reclaim(schedule);
+        *schedule = self.build_schedule(components, schedule_label, ignored_ambiguities)?;
+
+        // move systems into new schedule
+        for &id in &schedule.system_ids {
+            let system = self.systems[id.index()].inner.take().unwrap();
+            let conditions = std::mem::take(&mut self.system_conditions[id.index()]);
+            schedule.systems.push(system);
+            schedule.system_conditions.push(conditions);
+        }
+
+        for &id in &schedule.set_ids {
+            let conditions = std::mem::take(&mut self.system_set_conditions[id.index()]);
+            schedule.set_conditions.push(conditions);
+        }
+
+        Ok(())
+    }
+
+    /// Moves all systems and run conditions out of the executable `schedule`, back into the graph.
+    fn reclaim(&mut self, schedule: &mut SystemSchedule) {
         for ((id, system), conditions) in schedule
             .system_ids
             .drain(..)
@@ -1419,23 +1753,45 @@ impl ScheduleGraph {
         {
             self.system_set_conditions[id.index()] = conditions;
         }
+    }
 
-        *schedule = self.build_schedule(components, schedule_label, ignored_ambiguities)?;
-
-        // move systems into new schedule
-        for &id in &schedule.system_ids {
-            let system = self.systems[id.index()].inner.take().unwrap();
-            let conditions = std::mem::take(&mut self.system_conditions[id.index()]);
-            schedule.systems.push(system);
-            schedule.system_conditions.push(conditions);
+    /// Removes a system or system set, dropping it and its run conditions and disconnecting it
+    /// from the rest of the graph.
+    ///
+    /// The node's systems must have been reclaimed from the executable schedule.
+    fn remove_node(&mut self, id: NodeId) {
+        let exists = match id {
+            NodeId::System(index) => index < self.systems.len(),
+            NodeId::Set(index) => index < self.system_sets.len(),
+        };
+        if !exists || !self.removed_nodes.insert(id) {
+            return;
         }
 
-        for &id in &schedule.set_ids {
-            let conditions = std::mem::take(&mut self.system_set_conditions[id.index()]);
-            schedule.set_conditions.push(conditions);
+        match id {
+            NodeId::System(index) => {
+                self.systems[index].inner = None;
+                self.system_conditions[index] = Vec::new();
+            }
+            NodeId::Set(index) => {
+                self.system_set_conditions[index] = Vec::new();
+                let set = self.system_sets[index].inner;
+                if self.system_set_ids.get(&set) == Some(&id) {
+                    self.system_set_ids.remove(&set);
+                }
+            }
         }
 
-        Ok(())
+        self.hierarchy.graph.remove_node(id);
+        self.dependency.graph.remove_node(id);
+        self.ambiguous_with.remove_node(id);
+        self.ambiguous_with_all.remove(&id);
+        self.no_sync_edges.retain(|&(a, b)| a != id && b != id);
+        self.auto_sync_node_ids.retain(|_, &mut node| node != id);
+        self.uninit.retain(|&(node, _)| node != id);
+        self.conflicting_systems
+            .retain(|&(a, b, _)| a != id && b != id);
+        self.changed = true;
     }
 }
 
@@ -1834,6 +2190,12 @@ impl ScheduleGraph {
     }
 }
 
+/// Error returned by [`Schedule::systems`] and [`Schedule::has_non_send_systems`] when the schedule
+/// has changed since it was last initialized.
+#[derive(Error, Debug)]
+#[error("executable schedule has not been built")]
+pub struct ScheduleNotInitialized;
+
 /// Category of errors encountered during schedule construction.
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -1943,7 +2305,7 @@ impl ScheduleBuildSettings {
 mod tests {
     use crate::{
         self as bevy_ecs,
-        prelude::{Res, Resource},
+        prelude::{NonSend, Res, ResMut, Resource},
         schedule::{
             IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleBuildSettings, SystemSet,
         },
@@ -2064,6 +2426,81 @@ mod tests {
         assert_eq!(schedule.executable.systems.len(), 2);
     }
 
+    #[test]
+    fn remove_nodes() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Set;
+
+        #[derive(Resource, Default)]
+        struct Runs(Vec<&'static str>);
+
+        let mut world = World::new();
+        world.init_resource::<Runs>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            (|mut runs: ResMut<Runs>| runs.0.push("a")).before(Set),
+            (|mut runs: ResMut<Runs>| runs.0.push("b")).in_set(Set),
+        ));
+        schedule.run(&mut world);
+        let existing: Vec<_> = schedule.graph().node_ids().collect();
+
+        schedule.configure_sets(Set.run_if(|| true)).add_systems((
+            (|mut commands: Commands| commands.insert_resource(Resource1)).in_set(Set),
+            (|_: Res<Resource1>, mut runs: ResMut<Runs>| runs.0.push("c")).after(Set),
+        ));
+        let added: Vec<_> = schedule
+            .graph()
+            .node_ids()
+            .filter(|id| !existing.contains(id))
+            .collect();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Runs>().0, ["a", "b", "a", "b", "c"]);
+
+        // `Set` existed before, so only the new systems are removed
+        schedule.remove_nodes(added.iter().copied());
+        assert!(!schedule.graph().node_ids().any(|id| added.contains(&id)));
+        world.resource_mut::<Runs>().0.clear();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Runs>().0, ["a", "b"]);
+        assert_eq!(schedule.executable.systems.len(), 2);
+
+        // removing a set keeps its systems, and the set can be configured again
+        let set = schedule
+            .graph()
+            .system_sets()
+            .find(|(_, set, _)| !set.is_anonymous() && set.system_type().is_none())
+            .map(|(id, _, _)| id)
+            .unwrap();
+        schedule.remove_nodes([set]);
+        schedule.run(&mut world);
+        assert_eq!(schedule.executable.systems.len(), 2);
+        // ... without the systems that were in it, or its ordering
+        schedule.configure_sets(Set.run_if(|| false));
+        world.resource_mut::<Runs>().0.clear();
+        schedule.run(&mut world);
+        let mut runs = world.resource_mut::<Runs>();
+        runs.0.sort();
+        assert_eq!(runs.0, ["a", "b"]);
+    }
+
+    #[test]
+    fn finds_non_send_systems() {
+        struct NotSend;
+
+        let mut world = World::new();
+        world.insert_non_send_resource(NotSend);
+        let mut schedule = Schedule::default();
+        schedule.add_systems((|_: Res<Resource1>| {}, |_: &mut World| {}));
+        schedule.initialize(&mut world).unwrap();
+        assert!(!schedule.has_non_send_systems().unwrap());
+
+        // run conditions count too
+        schedule.add_systems((|| {}).run_if(|_: NonSend<NotSend>| true));
+        assert!(schedule.has_non_send_systems().is_err());
+        schedule.initialize(&mut world).unwrap();
+        assert!(schedule.has_non_send_systems().unwrap());
+    }
+
     mod no_sync_edges {
         use super::*;
 
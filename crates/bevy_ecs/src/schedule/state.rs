@@ -1,19 +1,22 @@
 use std::fmt::Debug;
 use std::hash::Hash;
-use std::mem;
+use std::marker::PhantomData;
 use std::ops::Deref;
 
 use crate as bevy_ecs;
 use crate::change_detection::DetectChangesMut;
-use crate::event::Event;
+use crate::event::{Event, Events, ManualEventReader};
 use crate::prelude::FromWorld;
 #[cfg(feature = "bevy_reflect")]
 use crate::reflect::ReflectResource;
-use crate::schedule::ScheduleLabel;
-use crate::system::Resource;
+use crate::schedule::{
+    IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleLabel, SystemSet,
+};
+use crate::system::{Local, Resource};
 use crate::world::World;
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::std_traits::ReflectDefault;
+use bevy_utils::all_tuples;
 
 pub use bevy_ecs_macros::States;
 
@@ -26,7 +29,10 @@ pub use bevy_ecs_macros::States;
 /// and the queued state with the [`NextState<T>`] resource.
 ///
 /// State transitions typically occur in the [`OnEnter<T::Variant>`] and [`OnExit<T::Variant>`] schedules,
-/// which can be run via the [`apply_state_transition::<T>`] system.
+/// which are run by the systems added with [`add_state_transition_systems::<T>`].
+///
+/// Besides these freely mutable states, states can also be derived from other states:
+/// see [`ComputedStates`] and [`SubStates`].
 ///
 /// # Example
 ///
@@ -44,6 +50,199 @@ pub use bevy_ecs_macros::States;
 /// ```
 pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug {}
 
+/// [`States`] that can be changed directly through [`NextState<S>`].
+///
+/// This is implemented by `#[derive(States)]`. [`ComputedStates`] are not freely mutable,
+/// as their value is always derived from their source states.
+pub trait FreelyMutableState: States {}
+
+/// A state whose value is computed from one or more source [`States`].
+///
+/// Whenever the source states change, [`ComputedStates::compute`] is called with their new values.
+/// If it returns `None`, the [`State<Self>`] resource is removed; otherwise it is inserted or updated.
+/// Computed states can't be changed with [`NextState`], but they have their own
+/// [`OnEnter`], [`OnExit`] and [`OnTransition`] schedules and can be used in run conditions
+/// like any other state.
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     InGame,
+/// }
+///
+/// #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+/// enum PauseState {
+///     #[default]
+///     Running,
+///     Paused,
+/// }
+///
+/// /// Only exists while in game.
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// struct InGame {
+///     paused: bool,
+/// }
+///
+/// impl ComputedStates for InGame {
+///     // Any number of states can be used as a source, and `Option<S>` is
+///     // accepted for sources that may not exist.
+///     type SourceStates = (AppState, PauseState);
+///
+///     fn compute((app, pause): (AppState, PauseState)) -> Option<Self> {
+///         match app {
+///             AppState::InGame => Some(InGame {
+///                 paused: pause == PauseState::Paused,
+///             }),
+///             AppState::Menu => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait ComputedStates: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug {
+    /// The states this state is computed from.
+    type SourceStates: StateSet;
+
+    /// Computes the value of this state from the current values of its sources,
+    /// or `None` if it shouldn't exist.
+    ///
+    /// If a required source state doesn't exist, this isn't called and the state doesn't exist either.
+    fn compute(sources: Self::SourceStates) -> Option<Self>;
+}
+
+impl<S: ComputedStates> States for S {}
+
+/// A freely mutable state that only exists while its source [`States`] have certain values.
+///
+/// While [`SubStates::should_exist`] returns `Some`, the [`State<Self>`] resource exists and can be
+/// changed through [`NextState<Self>`]. When it starts to exist, it is initialized to the returned
+/// value. When it returns `None` again, the state is removed, along with any queued transition.
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     InGame,
+/// }
+///
+/// /// Only exists while in game.
+/// #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// enum GamePhase {
+///     Setup,
+///     Battle,
+/// }
+///
+/// impl SubStates for GamePhase {
+///     type SourceStates = AppState;
+///
+///     fn should_exist(app: AppState) -> Option<Self> {
+///         match app {
+///             AppState::InGame => Some(GamePhase::Setup),
+///             AppState::Menu => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait SubStates: FreelyMutableState {
+    /// The states that determine whether this state exists.
+    type SourceStates: StateSet;
+
+    /// Returns the value this state starts with, or `None` if it shouldn't exist for these sources.
+    ///
+    /// If a required source state doesn't exist, this isn't called and the state doesn't exist either.
+    fn should_exist(sources: Self::SourceStates) -> Option<Self>;
+}
+
+mod sealed {
+    /// Prevents implementing [`StateSet`](super::StateSet) and
+    /// [`InnerStateSet`](super::InnerStateSet) outside of `bevy_ecs`.
+    pub trait Sealed {}
+}
+
+/// A single source of a [`StateSet`]: either a state `S`, which must exist,
+/// or `Option<S>`, which is `None` when `S` doesn't exist.
+pub trait InnerStateSet: sealed::Sealed + Sized {
+    /// The state this source reads.
+    type RawState: States;
+
+    /// Converts the current state into this source, or `None` if a required state doesn't exist.
+    fn convert(state: Option<&State<Self::RawState>>) -> Option<Self>;
+}
+
+impl<S: States> sealed::Sealed for S {}
+
+impl<S: States> InnerStateSet for S {
+    type RawState = S;
+
+    fn convert(state: Option<&State<S>>) -> Option<Self> {
+        state.map(|state| state.get().clone())
+    }
+}
+
+impl<S: States> sealed::Sealed for Option<S> {}
+
+impl<S: States> InnerStateSet for Option<S> {
+    type RawState = S;
+
+    fn convert(state: Option<&State<S>>) -> Option<Self> {
+        Some(state.map(|state| state.get().clone()))
+    }
+}
+
+/// The source states of a [`ComputedStates`] or [`SubStates`]:
+/// a single [`InnerStateSet`], or a tuple of them.
+pub trait StateSet: sealed::Sealed + Sized {
+    /// Reads the current values of the source states,
+    /// or `None` if a required state doesn't exist.
+    fn values(world: &World) -> Option<Self>;
+
+    /// Orders the transition systems of the dependent state `T` relative to those of the source states,
+    /// so that `T` is updated after its sources, exits before them, and enters after them.
+    fn order_dependent_state<T: States>(schedule: &mut Schedule);
+}
+
+impl<S: InnerStateSet> StateSet for S {
+    fn values(world: &World) -> Option<Self> {
+        S::convert(world.get_resource::<State<S::RawState>>())
+    }
+
+    fn order_dependent_state<T: States>(schedule: &mut Schedule) {
+        order_after_source::<T, S::RawState>(schedule);
+    }
+}
+
+macro_rules! impl_state_set_tuple {
+    ($($param: ident),*) => {
+        impl<$($param: InnerStateSet),*> sealed::Sealed for ($($param,)*) {}
+
+        impl<$($param: InnerStateSet),*> StateSet for ($($param,)*) {
+            fn values(world: &World) -> Option<Self> {
+                Some(($($param::convert(world.get_resource::<State<$param::RawState>>())?,)*))
+            }
+
+            fn order_dependent_state<T: States>(schedule: &mut Schedule) {
+                $(order_after_source::<T, $param::RawState>(schedule);)*
+            }
+        }
+    };
+}
+
+all_tuples!(impl_state_set_tuple, 1, 15, S);
+
+fn order_after_source<T: States, Source: States>(schedule: &mut Schedule) {
+    schedule.configure_sets((
+        ApplyStateTransition::<T>::default().after(ApplyStateTransition::<Source>::default()),
+        ExitSchedules::<T>::default().before(ExitSchedules::<Source>::default()),
+        EnterSchedules::<T>::default().after(EnterSchedules::<Source>::default()),
+    ));
+}
+
 /// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`]
 /// enters this state.
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
@@ -139,6 +338,8 @@ impl<S: States> Deref for State<S> {
 /// Note that these transitions can be overridden by other systems:
 /// only the actual value of this resource at the time of [`apply_state_transition`] matters.
 ///
+/// Only [freely mutable](FreelyMutableState) states can be queued this way.
+///
 /// ```
 /// use bevy_ecs::prelude::*;
 ///
@@ -160,15 +361,15 @@ impl<S: States> Deref for State<S> {
     derive(bevy_reflect::Reflect),
     reflect(Resource, Default)
 )]
-pub struct NextState<S: States>(pub Option<S>);
+pub struct NextState<S: FreelyMutableState>(pub Option<S>);
 
-impl<S: States> Default for NextState<S> {
+impl<S: FreelyMutableState> Default for NextState<S> {
     fn default() -> Self {
         Self(None)
     }
 }
 
-impl<S: States> NextState<S> {
+impl<S: FreelyMutableState> NextState<S> {
     /// Tentatively set a planned state transition to `Some(state)`.
     pub fn set(&mut self, state: S) {
         self.0 = Some(state);
@@ -177,30 +378,92 @@ impl<S: States> NextState<S> {
 
 /// Event sent when any state transition of `S` happens.
 ///
+/// A state that starts existing, such as a newly inserted state, a [`SubStates`] or a [`ComputedStates`],
+/// has no `before` value; a state that stops existing has no `after` value.
+///
 /// If you know exactly what state you want to respond to ahead of time, consider [`OnEnter`], [`OnTransition`], or [`OnExit`]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Event)]
 pub struct StateTransitionEvent<S: States> {
     /// the state we were in before
-    pub before: S,
+    pub before: Option<S>,
     /// the state we're in now
-    pub after: S,
+    pub after: Option<S>,
 }
 
-/// Run the enter schedule (if it exists) for the current state.
-pub fn run_enter_schedule<S: States>(world: &mut World) {
-    let Some(state) = world.get_resource::<State<S>>() else {
-        return;
+/// The steps of a state transition, run in order in the schedule the transition systems are added to.
+///
+/// All states are updated before any [`OnExit`] schedule runs, and all [`OnExit`] schedules run
+/// before any [`OnEnter`] schedule.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StateTransitionSteps {
+    /// Freely mutable states that don't depend on other states apply their [`NextState`].
+    RootTransitions,
+    /// [`ComputedStates`] and [`SubStates`] are updated, after their sources.
+    DependentTransitions,
+    /// [`OnExit`] schedules run, dependent states before their sources.
+    ExitSchedules,
+    /// [`OnTransition`] schedules run.
+    TransitionSchedules,
+    /// [`OnEnter`] schedules run, dependent states after their sources.
+    EnterSchedules,
+}
+
+/// The system set containing the system that updates [`State<S>`].
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ApplyStateTransition<S: States>(PhantomData<S>);
+
+impl<S: States> Default for ApplyStateTransition<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The system set containing the system that runs the [`OnExit`] schedules of `S`.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExitSchedules<S: States>(PhantomData<S>);
+
+impl<S: States> Default for ExitSchedules<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The system set containing the system that runs the [`OnEnter`] schedules of `S`.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnterSchedules<S: States>(PhantomData<S>);
+
+impl<S: States> Default for EnterSchedules<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Sets [`State<S>`] to `new_state`, inserting or removing it as needed,
+/// and sends a [`StateTransitionEvent`] if it changed.
+fn set_state<S: States>(world: &mut World, new_state: Option<S>) {
+    let before = match (world.get_resource_mut::<State<S>>(), new_state.clone()) {
+        (Some(state), Some(entered)) if *state == entered => return,
+        (Some(mut state), Some(entered)) => Some(std::mem::replace(&mut state.0, entered)),
+        (Some(_), None) => world.remove_resource::<State<S>>().map(|state| state.0),
+        (None, Some(entered)) => {
+            world.insert_resource(State(entered));
+            None
+        }
+        (None, None) => return,
     };
-    world.try_run_schedule(OnEnter(state.0.clone())).ok();
+    world.send_event(StateTransitionEvent {
+        before,
+        after: new_state,
+    });
 }
 
 /// If a new state is queued in [`NextState<S>`], this system:
 /// - Takes the new state value from [`NextState<S>`] and updates [`State<S>`].
 /// - Sends a relevant [`StateTransitionEvent`]
-/// - Runs the [`OnExit(exited_state)`] schedule, if it exists.
-/// - Runs the [`OnTransition { from: exited_state, to: entered_state }`](OnTransition), if it exists.
-/// - Runs the [`OnEnter(entered_state)`] schedule, if it exists.
-pub fn apply_state_transition<S: States>(world: &mut World) {
+///
+/// The [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules are then run by [`run_exit_schedule`],
+/// [`run_transition_schedule`] and [`run_enter_schedule`].
+pub fn apply_state_transition<S: FreelyMutableState>(world: &mut World) {
     // We want to take the `NextState` resource,
     // but only mark it as changed if it wasn't empty.
     let Some(mut next_state_resource) = world.get_resource_mut::<NextState<S>>() else {
@@ -208,29 +471,410 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
     };
     if let Some(entered) = next_state_resource.bypass_change_detection().0.take() {
         next_state_resource.set_changed();
-        match world.get_resource_mut::<State<S>>() {
-            Some(mut state_resource) => {
-                if *state_resource != entered {
-                    let exited = mem::replace(&mut state_resource.0, entered.clone());
-                    world.send_event(StateTransitionEvent {
-                        before: exited.clone(),
-                        after: entered.clone(),
-                    });
-                    // Try to run the schedules if they exist.
-                    world.try_run_schedule(OnExit(exited.clone())).ok();
-                    world
-                        .try_run_schedule(OnTransition {
-                            from: exited,
-                            to: entered.clone(),
-                        })
-                        .ok();
-                    world.try_run_schedule(OnEnter(entered)).ok();
-                }
+        set_state(world, Some(entered));
+    }
+}
+
+/// Updates [`State<S>`] of a [`SubStates`] from its source states and [`NextState<S>`],
+/// and sends a relevant [`StateTransitionEvent`].
+///
+/// A transition queued while the state doesn't exist is discarded.
+pub fn apply_sub_state_transition<S: SubStates>(world: &mut World) {
+    let initial = S::SourceStates::values(world).and_then(S::should_exist);
+    let next = world
+        .get_resource_mut::<NextState<S>>()
+        .and_then(|mut next_state_resource| {
+            let next = next_state_resource.bypass_change_detection().0.take();
+            if next.is_some() {
+                next_state_resource.set_changed();
             }
-            None => {
-                world.insert_resource(State(entered.clone()));
-                world.try_run_schedule(OnEnter(entered)).ok();
+            next
+        });
+    let new_state = initial.map(|initial| {
+        next.or_else(|| {
+            world
+                .get_resource::<State<S>>()
+                .map(|state| state.0.clone())
+        })
+        .unwrap_or(initial)
+    });
+    set_state(world, new_state);
+}
+
+/// Updates [`State<S>`] of a [`ComputedStates`] from its source states,
+/// and sends a relevant [`StateTransitionEvent`].
+pub fn apply_computed_state_transition<S: ComputedStates>(world: &mut World) {
+    let new_state = S::SourceStates::values(world).and_then(S::compute);
+    set_state(world, new_state);
+}
+
+fn last_transition<S: States>(
+    world: &World,
+    reader: &mut ManualEventReader<StateTransitionEvent<S>>,
+) -> Option<StateTransitionEvent<S>> {
+    let events = world.get_resource::<Events<StateTransitionEvent<S>>>()?;
+    reader.read(events).last().cloned()
+}
+
+/// Runs the [`OnExit`] schedule (if it exists) of the state exited by the last transition of `S`.
+pub fn run_exit_schedule<S: States>(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<StateTransitionEvent<S>>>,
+) {
+    let Some(StateTransitionEvent {
+        before: Some(exited),
+        ..
+    }) = last_transition(world, &mut reader)
+    else {
+        return;
+    };
+    world.try_run_schedule(OnExit(exited)).ok();
+}
+
+/// Runs the [`OnTransition`] schedule (if it exists) of the last transition of `S`,
+/// if it went from one state to another.
+pub fn run_transition_schedule<S: States>(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<StateTransitionEvent<S>>>,
+) {
+    let Some(StateTransitionEvent {
+        before: Some(from),
+        after: Some(to),
+    }) = last_transition(world, &mut reader)
+    else {
+        return;
+    };
+    world.try_run_schedule(OnTransition { from, to }).ok();
+}
+
+/// Runs the [`OnEnter`] schedule (if it exists) of the state entered by the last transition of `S`.
+///
+/// To run the [`OnEnter`] schedule of the initial state, send a [`StateTransitionEvent`]
+/// without a `before` value when inserting it.
+pub fn run_enter_schedule<S: States>(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<StateTransitionEvent<S>>>,
+) {
+    let Some(StateTransitionEvent {
+        after: Some(entered),
+        ..
+    }) = last_transition(world, &mut reader)
+    else {
+        return;
+    };
+    world.try_run_schedule(OnEnter(entered)).ok();
+}
+
+fn add_transition_schedule_systems<S: States>(schedule: &mut Schedule) {
+    schedule
+        .configure_sets(
+            (
+                StateTransitionSteps::RootTransitions,
+                StateTransitionSteps::DependentTransitions,
+                StateTransitionSteps::ExitSchedules,
+                StateTransitionSteps::TransitionSchedules,
+                StateTransitionSteps::EnterSchedules,
+            )
+                .chain(),
+        )
+        .add_systems((
+            run_exit_schedule::<S>
+                .in_set(StateTransitionSteps::ExitSchedules)
+                .in_set(ExitSchedules::<S>::default()),
+            run_transition_schedule::<S>.in_set(StateTransitionSteps::TransitionSchedules),
+            run_enter_schedule::<S>
+                .in_set(StateTransitionSteps::EnterSchedules)
+                .in_set(EnterSchedules::<S>::default()),
+        ));
+}
+
+/// Adds the systems applying the transitions of the freely mutable state `S`,
+/// and running its [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules, to `schedule`.
+///
+/// The [`State<S>`], [`NextState<S>`] and [`Events<StateTransitionEvent<S>>`] resources
+/// must be added separately.
+pub fn add_state_transition_systems<S: FreelyMutableState>(schedule: &mut Schedule) {
+    add_transition_schedule_systems::<S>(schedule);
+    schedule.add_systems(
+        apply_state_transition::<S>
+            .in_set(StateTransitionSteps::RootTransitions)
+            .in_set(ApplyStateTransition::<S>::default()),
+    );
+}
+
+/// Adds the systems updating the sub-state `S` from its sources and [`NextState<S>`],
+/// and running its [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules, to `schedule`.
+///
+/// The transition systems of the source states should be added to the same schedule.
+/// The [`NextState<S>`] and [`Events<StateTransitionEvent<S>>`] resources must be added separately.
+pub fn add_sub_state_transition_systems<S: SubStates>(schedule: &mut Schedule) {
+    add_transition_schedule_systems::<S>(schedule);
+    S::SourceStates::order_dependent_state::<S>(schedule);
+    schedule.add_systems(
+        apply_sub_state_transition::<S>
+            .in_set(StateTransitionSteps::DependentTransitions)
+            .in_set(ApplyStateTransition::<S>::default()),
+    );
+}
+
+/// Adds the systems computing the state `S` from its sources,
+/// and running its [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules, to `schedule`.
+///
+/// The transition systems of the source states should be added to the same schedule.
+/// The [`Events<StateTransitionEvent<S>>`] resource must be added separately.
+pub fn add_computed_state_transition_systems<S: ComputedStates>(schedule: &mut Schedule) {
+    add_transition_schedule_systems::<S>(schedule);
+    S::SourceStates::order_dependent_state::<S>(schedule);
+    schedule.add_systems(
+        apply_computed_state_transition::<S>
+            .in_set(StateTransitionSteps::DependentTransitions)
+            .in_set(ApplyStateTransition::<S>::default()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    enum AppState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    enum PauseState {
+        #[default]
+        Running,
+        Paused,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct InGame {
+        paused: bool,
+    }
+
+    impl ComputedStates for InGame {
+        type SourceStates = (AppState, PauseState);
+
+        fn compute((app, pause): (AppState, PauseState)) -> Option<Self> {
+            match app {
+                AppState::InGame => Some(InGame {
+                    paused: pause == PauseState::Paused,
+                }),
+                AppState::Menu => None,
             }
-        };
+        }
+    }
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum GamePhase {
+        Setup,
+        Battle,
+    }
+
+    impl SubStates for GamePhase {
+        type SourceStates = AppState;
+
+        fn should_exist(app: AppState) -> Option<Self> {
+            match app {
+                AppState::InGame => Some(GamePhase::Setup),
+                AppState::Menu => None,
+            }
+        }
+    }
+
+    /// Computed from a sub-state, to check ordering across several levels.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct InBattle;
+
+    impl ComputedStates for InBattle {
+        type SourceStates = Option<GamePhase>;
+
+        fn compute(phase: Option<GamePhase>) -> Option<Self> {
+            (phase == Some(GamePhase::Battle)).then_some(InBattle)
+        }
+    }
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Transitions;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn init_state<S: FreelyMutableState + Default>(world: &mut World, schedule: &mut Schedule) {
+        world.init_resource::<State<S>>();
+        world.init_resource::<NextState<S>>();
+        world.init_resource::<Events<StateTransitionEvent<S>>>();
+        world.send_event(StateTransitionEvent {
+            before: None,
+            after: Some(S::default()),
+        });
+        add_state_transition_systems::<S>(schedule);
+    }
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(Transitions);
+        world.init_resource::<Log>();
+
+        init_state::<AppState>(&mut world, &mut schedule);
+        init_state::<PauseState>(&mut world, &mut schedule);
+
+        world.init_resource::<NextState<GamePhase>>();
+        world.init_resource::<Events<StateTransitionEvent<GamePhase>>>();
+        add_sub_state_transition_systems::<GamePhase>(&mut schedule);
+
+        world.init_resource::<Events<StateTransitionEvent<InGame>>>();
+        add_computed_state_transition_systems::<InGame>(&mut schedule);
+        world.init_resource::<Events<StateTransitionEvent<InBattle>>>();
+        add_computed_state_transition_systems::<InBattle>(&mut schedule);
+
+        (world, schedule)
+    }
+
+    fn set_next<S: FreelyMutableState>(world: &mut World, state: S) {
+        world.resource_mut::<NextState<S>>().set(state);
+    }
+
+    fn state<S: States>(world: &World) -> Option<S> {
+        world
+            .get_resource::<State<S>>()
+            .map(|state| state.get().clone())
+    }
+
+    #[test]
+    fn computed_state_follows_sources() {
+        let (mut world, mut schedule) = setup();
+
+        schedule.run(&mut world);
+        assert_eq!(state::<InGame>(&world), None);
+
+        set_next(&mut world, AppState::InGame);
+        schedule.run(&mut world);
+        assert_eq!(state::<InGame>(&world), Some(InGame { paused: false }));
+
+        set_next(&mut world, PauseState::Paused);
+        schedule.run(&mut world);
+        assert_eq!(state::<InGame>(&world), Some(InGame { paused: true }));
+
+        set_next(&mut world, AppState::Menu);
+        schedule.run(&mut world);
+        assert_eq!(state::<InGame>(&world), None);
+    }
+
+    #[test]
+    fn sub_state_exists_only_with_source() {
+        let (mut world, mut schedule) = setup();
+
+        // transitions queued while the sub-state doesn't exist are discarded
+        set_next(&mut world, GamePhase::Battle);
+        schedule.run(&mut world);
+        assert_eq!(state::<GamePhase>(&world), None);
+
+        set_next(&mut world, AppState::InGame);
+        schedule.run(&mut world);
+        assert_eq!(state::<GamePhase>(&world), Some(GamePhase::Setup));
+
+        set_next(&mut world, GamePhase::Battle);
+        schedule.run(&mut world);
+        assert_eq!(state::<GamePhase>(&world), Some(GamePhase::Battle));
+        assert_eq!(state::<InBattle>(&world), Some(InBattle));
+
+        // unrelated transitions keep the current value
+        set_next(&mut world, PauseState::Paused);
+        schedule.run(&mut world);
+        assert_eq!(state::<GamePhase>(&world), Some(GamePhase::Battle));
+
+        set_next(&mut world, AppState::Menu);
+        schedule.run(&mut world);
+        assert_eq!(state::<GamePhase>(&world), None);
+        assert_eq!(state::<InBattle>(&world), None);
+
+        // the sub-state is re-initialized when it starts existing again
+        set_next(&mut world, AppState::InGame);
+        schedule.run(&mut world);
+        assert_eq!(state::<GamePhase>(&world), Some(GamePhase::Setup));
+    }
+
+    #[test]
+    fn transition_schedules_are_ordered_by_dependency() {
+        let (mut world, mut schedule) = setup();
+
+        macro_rules! log {
+            ($label: expr, $message: literal) => {{
+                let mut log_schedule = Schedule::new($label);
+                log_schedule.add_systems(|mut log: ResMut<Log>| log.0.push($message));
+                world.add_schedule(log_schedule);
+            }};
+        }
+
+        log!(OnEnter(AppState::Menu), "enter menu");
+        log!(OnExit(AppState::Menu), "exit menu");
+        log!(OnEnter(AppState::InGame), "enter game");
+        log!(OnExit(AppState::InGame), "exit game");
+        log!(OnEnter(GamePhase::Battle), "enter battle phase");
+        log!(OnExit(GamePhase::Battle), "exit battle phase");
+        log!(OnEnter(InBattle), "enter in battle");
+        log!(OnExit(InBattle), "exit in battle");
+        log!(
+            OnTransition {
+                from: AppState::InGame,
+                to: AppState::Menu,
+            },
+            "game to menu"
+        );
+
+        // the initial state is entered
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["enter menu"]);
+
+        set_next(&mut world, AppState::InGame);
+        schedule.run(&mut world);
+        set_next(&mut world, GamePhase::Battle);
+        schedule.run(&mut world);
+        assert_eq!(
+            std::mem::take(&mut world.resource_mut::<Log>().0),
+            vec![
+                "enter menu",
+                "exit menu",
+                "enter game",
+                "enter battle phase",
+                "enter in battle"
+            ]
+        );
+
+        set_next(&mut world, AppState::Menu);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![
+                "exit in battle",
+                "exit battle phase",
+                "exit game",
+                "game to menu",
+                "enter menu"
+            ]
+        );
+    }
+
+    #[test]
+    fn dependent_states_work_with_run_conditions() {
+        let (mut world, mut schedule) = setup();
+        schedule.add_systems(
+            (|mut log: ResMut<Log>| log.0.push("paused"))
+                .run_if(in_state(InGame { paused: true }))
+                .after(StateTransitionSteps::EnterSchedules),
+        );
+
+        set_next(&mut world, PauseState::Paused);
+        schedule.run(&mut world);
+        assert!(world.resource::<Log>().0.is_empty());
+
+        set_next(&mut world, AppState::InGame);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["paused"]);
     }
 }
@@ -0,0 +1,405 @@
+use std::borrow::Cow;
+
+use bevy_utils::{Duration, HashMap};
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{InternedScheduleLabel, NodeId, ScheduleLabel, SystemSchedule},
+    system::Resource,
+};
+
+/// What happened to a single system during one run of a schedule, as recorded by the executor.
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct SystemTiming {
+    /// How long the system ran for, or [`None`] if it didn't run.
+    pub(super) duration: Option<Duration>,
+    /// Whether the system was skipped because its run conditions, or those of one of its sets,
+    /// were not met.
+    pub(super) skipped: bool,
+    /// How long the system was kept from starting by running systems with conflicting data
+    /// access, after all of its dependencies had completed.
+    pub(super) waited: Duration,
+}
+
+/// Accumulated statistics of a single system in a [`SystemStats`] resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemRunStats {
+    name: Cow<'static, str>,
+    run_count: u64,
+    skipped_count: u64,
+    total_duration: Duration,
+    max_duration: Duration,
+    last_duration: Duration,
+    total_wait: Duration,
+    max_wait: Duration,
+}
+
+impl SystemRunStats {
+    fn new(name: Cow<'static, str>) -> Self {
+        Self {
+            name,
+            run_count: 0,
+            skipped_count: 0,
+            total_duration: Duration::ZERO,
+            max_duration: Duration::ZERO,
+            last_duration: Duration::ZERO,
+            total_wait: Duration::ZERO,
+            max_wait: Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, timing: &SystemTiming) {
+        if let Some(duration) = timing.duration {
+            self.run_count += 1;
+            self.total_duration += duration;
+            self.max_duration = self.max_duration.max(duration);
+            self.last_duration = duration;
+            self.total_wait += timing.waited;
+            self.max_wait = self.max_wait.max(timing.waited);
+        } else if timing.skipped {
+            self.skipped_count += 1;
+        }
+    }
+
+    /// Returns the name of the system.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns how many times the system has run.
+    pub fn run_count(&self) -> u64 {
+        self.run_count
+    }
+
+    /// Returns how many times the system was skipped because its run conditions, or those of one
+    /// of its sets, were not met.
+    ///
+    /// Systems skipped by [`Stepping`](super::Stepping) are not counted.
+    pub fn skipped_count(&self) -> u64 {
+        self.skipped_count
+    }
+
+    /// Returns the total time spent running the system.
+    pub fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
+    /// Returns the mean time the system took to run, or [`Duration::ZERO`] if it hasn't run yet.
+    pub fn mean_duration(&self) -> Duration {
+        mean(self.total_duration, self.run_count)
+    }
+
+    /// Returns the longest time the system took to run.
+    pub fn max_duration(&self) -> Duration {
+        self.max_duration
+    }
+
+    /// Returns the time the system took to run the last time it ran.
+    pub fn last_duration(&self) -> Duration {
+        self.last_duration
+    }
+
+    /// Returns the total time the system spent blocked by other running systems with conflicting
+    /// data access, after all of its dependencies had completed.
+    ///
+    /// Time non-[`Send`] systems spent waiting for the local thread isn't counted. This is only
+    /// recorded by the [`MultiThreadedExecutor`](super::MultiThreadedExecutor).
+    pub fn total_wait(&self) -> Duration {
+        self.total_wait
+    }
+
+    /// Returns the mean time the system spent blocked by conflicting systems, see
+    /// [`SystemRunStats::total_wait`].
+    pub fn mean_wait(&self) -> Duration {
+        mean(self.total_wait, self.run_count)
+    }
+
+    /// Returns the longest time the system spent blocked by conflicting systems, see
+    /// [`SystemRunStats::total_wait`].
+    pub fn max_wait(&self) -> Duration {
+        self.max_wait
+    }
+}
+
+fn mean(total: Duration, count: u64) -> Duration {
+    if count == 0 {
+        Duration::ZERO
+    } else {
+        Duration::from_secs_f64(total.as_secs_f64() / count as f64)
+    }
+}
+
+/// Resource that records how often and for how long systems run.
+///
+/// While this resource exists, every [`Schedule`](super::Schedule) that runs in its world
+/// measures its systems and accumulates the results here, with any executor and without the
+/// `trace` feature. Removing the resource stops the measurements.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, SystemStats};
+/// #
+/// # #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// # struct Update;
+/// #
+/// fn movement() {}
+///
+/// let mut world = World::new();
+/// world.init_resource::<SystemStats>();
+///
+/// let mut schedule = Schedule::new(Update);
+/// schedule.add_systems(movement);
+/// schedule.run(&mut world);
+/// schedule.run(&mut world);
+///
+/// let stats = world.resource::<SystemStats>();
+/// let movement = stats.find(Update, "movement").unwrap();
+/// assert_eq!(movement.run_count(), 2);
+/// assert!(movement.max_duration() >= movement.mean_duration());
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct SystemStats {
+    schedules: HashMap<InternedScheduleLabel, HashMap<NodeId, SystemRunStats>>,
+}
+
+impl SystemStats {
+    /// Creates an empty [`SystemStats`] resource.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the statistics of the system `id` in the schedule labelled `schedule`.
+    pub fn get(&self, schedule: impl ScheduleLabel, id: NodeId) -> Option<&SystemRunStats> {
+        self.schedules.get(&schedule.intern())?.get(&id)
+    }
+
+    /// Returns the statistics of the first system named `name` in the schedule labelled
+    /// `schedule`.
+    ///
+    /// `name` may be either the full name of the system or its short name, without module paths.
+    pub fn find(&self, schedule: impl ScheduleLabel, name: &str) -> Option<&SystemRunStats> {
+        let mut systems: Vec<_> = self.schedules.get(&schedule.intern())?.iter().collect();
+        systems.sort_by_key(|(id, _)| **id);
+        systems
+            .into_iter()
+            .map(|(_, stats)| stats)
+            .find(|stats| stats.name == name || bevy_utils::get_short_name(&stats.name) == name)
+    }
+
+    /// Returns an iterator over the statistics of every system in the schedule labelled
+    /// `schedule`.
+    pub fn schedule(
+        &self,
+        schedule: impl ScheduleLabel,
+    ) -> impl Iterator<Item = (NodeId, &SystemRunStats)> + '_ {
+        self.schedules
+            .get(&schedule.intern())
+            .into_iter()
+            .flat_map(|systems| systems.iter().map(|(&id, stats)| (id, stats)))
+    }
+
+    /// Returns an iterator over the statistics of every system that has been recorded.
+    pub fn iter(&self) -> impl Iterator<Item = (InternedScheduleLabel, NodeId, &SystemRunStats)> {
+        self.schedules.iter().flat_map(|(&label, systems)| {
+            systems.iter().map(move |(&id, stats)| (label, id, stats))
+        })
+    }
+
+    /// Discards all statistics recorded so far.
+    pub fn clear(&mut self) {
+        self.schedules.clear();
+    }
+
+    /// Accumulates the timings of one run of the schedule labelled `label`.
+    pub(super) fn record(
+        &mut self,
+        label: InternedScheduleLabel,
+        schedule: &SystemSchedule,
+        timings: &[SystemTiming],
+    ) {
+        let systems = self.schedules.entry(label).or_default();
+        for ((&id, system), timing) in schedule
+            .system_ids
+            .iter()
+            .zip(&schedule.systems)
+            .zip(timings)
+        {
+            systems
+                .entry(id)
+                .or_insert_with(|| SystemRunStats::new(system.name()))
+                .record(timing);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleBuildSettings, ScheduleLabel, Stepping},
+        system::RunSystemOnce,
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    #[derive(Resource)]
+    struct Enabled(bool);
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Gated;
+
+    fn always() {}
+    fn conditional() {}
+    fn gated() {}
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+    fn after_spawn() {}
+
+    fn enabled(enabled: Res<Enabled>) -> bool {
+        enabled.0
+    }
+
+    fn run_twice(executor: ExecutorKind) -> World {
+        let mut world = World::new();
+        world.init_resource::<SystemStats>();
+        world.insert_resource(Enabled(false));
+
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule
+            .set_executor_kind(executor)
+            // the simple executor applies commands after every system instead of at sync points
+            .set_build_settings(ScheduleBuildSettings {
+                auto_insert_apply_deferred: executor != ExecutorKind::Simple,
+                ..Default::default()
+            })
+            .configure_sets(Gated.run_if(enabled))
+            .add_systems((
+                always,
+                conditional.run_if(enabled),
+                gated.in_set(Gated),
+                (spawn, after_spawn).chain(),
+            ));
+        schedule.run(&mut world);
+        world.resource_mut::<Enabled>().0 = true;
+        schedule.run(&mut world);
+        world
+    }
+
+    fn check_counts(executor: ExecutorKind) {
+        let world = run_twice(executor);
+        let stats = world.resource::<SystemStats>();
+        let counts = |name| {
+            let system = stats.find(TestSchedule, name).unwrap();
+            (system.run_count(), system.skipped_count())
+        };
+
+        assert_eq!(counts("always"), (2, 0));
+        assert_eq!(counts("conditional"), (1, 1));
+        assert_eq!(counts("gated"), (1, 1));
+        assert_eq!(counts("after_spawn"), (2, 0));
+        // the sync point inserted between `spawn` and `after_spawn` is recorded too
+        assert_eq!(counts("apply_deferred"), (2, 0));
+        assert_eq!(stats.schedule(TestSchedule).count(), 6);
+
+        let always = stats.find(TestSchedule, "always").unwrap();
+        assert!(always.max_duration() >= always.mean_duration());
+        assert!(always.total_duration() >= always.max_duration());
+    }
+
+    #[test]
+    fn single_threaded_stats() {
+        check_counts(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn simple_stats() {
+        let world = run_twice(ExecutorKind::Simple);
+        let stats = world.resource::<SystemStats>();
+        let counts = |name| {
+            let system = stats.find(TestSchedule, name).unwrap();
+            (system.run_count(), system.skipped_count())
+        };
+
+        assert_eq!(counts("always"), (2, 0));
+        assert_eq!(counts("conditional"), (1, 1));
+        assert_eq!(counts("gated"), (1, 1));
+        assert_eq!(counts("after_spawn"), (2, 0));
+        assert!(stats.find(TestSchedule, "apply_deferred").is_none());
+    }
+
+    #[test]
+    fn multi_threaded_stats() {
+        check_counts(ExecutorKind::MultiThreaded);
+    }
+
+    #[test]
+    fn multi_threaded_waits_count_only_conflicts() {
+        #[derive(Resource)]
+        struct Shared;
+
+        fn write_first(_: ResMut<Shared>) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        fn write_second(_: ResMut<Shared>) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut world = World::new();
+        world.init_resource::<SystemStats>();
+        world.insert_resource(Shared);
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule
+            .set_executor_kind(ExecutorKind::MultiThreaded)
+            .add_systems((always, write_first, write_second));
+        schedule.run(&mut world);
+
+        let stats = world.resource::<SystemStats>();
+        let wait = |name| stats.find(TestSchedule, name).unwrap().total_wait();
+        // one of the writers is blocked while the other runs
+        assert!(wait("write_first") + wait("write_second") >= Duration::from_millis(1));
+        assert_eq!(wait("always"), Duration::ZERO);
+    }
+
+    #[test]
+    fn records_only_while_resource_exists() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems(always);
+        schedule.run(&mut world);
+
+        world.init_resource::<SystemStats>();
+        schedule.run(&mut world);
+        let stats = world.resource::<SystemStats>();
+        assert_eq!(stats.find(TestSchedule, "always").unwrap().run_count(), 1);
+
+        let (id, _) = stats.schedule(TestSchedule).next().unwrap();
+        assert_eq!(
+            stats.get(TestSchedule, id).unwrap().name(),
+            stats.iter().next().unwrap().2.name()
+        );
+
+        world.resource_mut::<SystemStats>().clear();
+        assert_eq!(world.resource::<SystemStats>().iter().count(), 0);
+    }
+
+    #[test]
+    fn stepping_skips_are_not_counted() {
+        let mut world = World::new();
+        world.init_resource::<SystemStats>();
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(TestSchedule).enable();
+        world.insert_resource(stepping);
+
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems(always);
+        world.run_system_once(Stepping::begin_frame);
+        schedule.run(&mut world);
+
+        let stats = world.resource::<SystemStats>();
+        let always = stats.find(TestSchedule, "always").unwrap();
+        assert_eq!((always.run_count(), always.skipped_count()), (0, 0));
+    }
+}
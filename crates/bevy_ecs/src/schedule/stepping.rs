@@ -0,0 +1,731 @@
+use std::any::TypeId;
+
+use bevy_utils::HashMap;
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{InternedScheduleLabel, NodeId, Schedule, ScheduleLabel},
+    system::{IntoSystem, ResMut, Resource, System},
+};
+
+/// What [`Stepping`] does with the stepped schedules during the current frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    /// Stepping is disabled; all systems run.
+    #[default]
+    RunAll,
+    /// Stepping is enabled, but no system is being stepped.
+    Waiting,
+    /// Run the system at the cursor, then wait.
+    Step,
+    /// Run all systems from the cursor to the end of the frame, stopping at breakpoints.
+    Continue,
+}
+
+/// Per-system override of the stepping behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SystemBehavior {
+    /// The system runs every frame, whether or not it is being stepped.
+    AlwaysRun,
+    /// The system never runs while stepping is enabled.
+    NeverRun,
+    /// [`Stepping::continue_frame`] stops before running this system.
+    Break,
+}
+
+/// Identifies a system within a stepped schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SystemIdentifier {
+    /// All systems of the given type.
+    Type(TypeId),
+    /// A single system, by its node in the schedule.
+    Node(NodeId),
+}
+
+/// A change to the [`Stepping`] state, applied at the start of the next frame.
+#[derive(Debug)]
+enum Update {
+    SetAction(Action),
+    AddSchedule(InternedScheduleLabel),
+    RemoveSchedule(InternedScheduleLabel),
+    ClearSchedule(InternedScheduleLabel),
+    SetBehavior(InternedScheduleLabel, SystemIdentifier, SystemBehavior),
+    ClearBehavior(InternedScheduleLabel, SystemIdentifier),
+}
+
+/// Position of the next system to be stepped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Cursor {
+    /// Index into [`Stepping::schedule_order`].
+    schedule: usize,
+    /// Index of the system in the execution order of the schedule.
+    system: usize,
+}
+
+/// Stepping state of a single schedule.
+#[derive(Debug, Default)]
+struct ScheduleState {
+    behaviors: HashMap<SystemIdentifier, SystemBehavior>,
+    /// Node ids of the systems in the order they were last seen by the executor.
+    order: Vec<NodeId>,
+}
+
+impl ScheduleState {
+    fn behavior(&self, node_id: NodeId, type_id: TypeId) -> Option<SystemBehavior> {
+        self.behaviors
+            .get(&SystemIdentifier::Node(node_id))
+            .or_else(|| self.behaviors.get(&SystemIdentifier::Type(type_id)))
+            .copied()
+    }
+}
+
+/// Resource controlling the execution of systems one at a time, for debugging.
+///
+/// When stepping is enabled, the systems of every schedule added with [`Stepping::add_schedule`]
+/// are skipped by the executor, unless they are being stepped. [`Stepping::step_frame`] runs the
+/// next system, and [`Stepping::continue_frame`] runs all remaining systems up to the end of the
+/// frame. Individual systems can be configured to always or never run, or to stop
+/// [`Stepping::continue_frame`] before running.
+///
+/// The stepped schedules are treated as a single frame, in the order they were added, and a
+/// cursor tracks the next system to be stepped within that frame. Schedules that were not added
+/// run normally.
+///
+/// All changes take effect at the start of the next frame, when [`Stepping::begin_frame`] runs.
+/// This keeps the stepped systems consistent within a frame, and allows controlling stepping from
+/// any system, for example an in-game console.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, Stepping};
+/// #
+/// # #[derive(Resource, Default)]
+/// # struct Counter(usize);
+/// #
+/// # #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// # struct Update;
+/// #
+/// fn increment(mut counter: ResMut<Counter>) {
+///     counter.0 += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Counter>();
+///
+/// let mut schedule = Schedule::new(Update);
+/// schedule.add_systems(increment);
+///
+/// let mut stepping = Stepping::new();
+/// stepping.add_schedule(Update).enable();
+/// world.insert_resource(stepping);
+///
+/// let mut frame = |world: &mut World| {
+///     world.run_system_once(Stepping::begin_frame);
+///     schedule.run(world);
+/// };
+///
+/// // while stepping is enabled, nothing runs until a step is requested
+/// frame(&mut world);
+/// assert_eq!(world.resource::<Counter>().0, 0);
+///
+/// world.resource_mut::<Stepping>().step_frame();
+/// frame(&mut world);
+/// assert_eq!(world.resource::<Counter>().0, 1);
+/// # use bevy_ecs::system::RunSystemOnce;
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct Stepping {
+    /// Stepped schedules, in the order they run during a frame.
+    schedule_order: Vec<InternedScheduleLabel>,
+    schedule_states: HashMap<InternedScheduleLabel, ScheduleState>,
+    cursor: Cursor,
+    action: Action,
+    /// Changes to apply at the start of the next frame.
+    updates: Vec<Update>,
+}
+
+impl Stepping {
+    /// Creates a new, disabled, [`Stepping`] state without any stepped schedules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// System that applies the pending stepping changes at the start of a frame.
+    ///
+    /// This must run once per frame, before any stepped schedule. `bevy_app` runs it at the start
+    /// of the `Main` schedule.
+    pub fn begin_frame(stepping: Option<ResMut<Self>>) {
+        if let Some(mut stepping) = stepping {
+            stepping.next_frame();
+        }
+    }
+
+    /// Returns the stepped schedules, in the order they are expected to run.
+    pub fn schedules(&self) -> &[InternedScheduleLabel] {
+        &self.schedule_order
+    }
+
+    /// Returns the schedule and system that will run on the next step, if stepping is enabled.
+    ///
+    /// The system is only known once its schedule has run with stepping enabled.
+    pub fn cursor(&self) -> Option<(InternedScheduleLabel, NodeId)> {
+        if self.action == Action::RunAll {
+            return None;
+        }
+        let label = *self.schedule_order.get(self.cursor.schedule)?;
+        let node_id = *self
+            .schedule_states
+            .get(&label)?
+            .order
+            .get(self.cursor.system)?;
+        Some((label, node_id))
+    }
+
+    /// Returns `true` if stepping is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.action != Action::RunAll
+    }
+
+    /// Enables stepping, starting from the first system of the first stepped schedule.
+    pub fn enable(&mut self) -> &mut Self {
+        self.updates.push(Update::SetAction(Action::Waiting));
+        self
+    }
+
+    /// Disables stepping; all systems run normally again.
+    pub fn disable(&mut self) -> &mut Self {
+        self.updates.push(Update::SetAction(Action::RunAll));
+        self
+    }
+
+    /// Runs the system at the cursor during the next frame, then advances the cursor.
+    ///
+    /// Does nothing if stepping is disabled.
+    pub fn step_frame(&mut self) -> &mut Self {
+        self.updates.push(Update::SetAction(Action::Step));
+        self
+    }
+
+    /// Runs all systems from the cursor to the end of the frame during the next frame, stopping
+    /// before any system with a breakpoint.
+    ///
+    /// Does nothing if stepping is disabled.
+    pub fn continue_frame(&mut self) -> &mut Self {
+        self.updates.push(Update::SetAction(Action::Continue));
+        self
+    }
+
+    /// Adds a schedule to the stepped schedules.
+    ///
+    /// Schedules must be added in the order they run during a frame.
+    pub fn add_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.updates.push(Update::AddSchedule(schedule.intern()));
+        self
+    }
+
+    /// Removes a schedule from the stepped schedules, along with all its system behaviors.
+    pub fn remove_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.updates.push(Update::RemoveSchedule(schedule.intern()));
+        self
+    }
+
+    /// Clears all system behaviors of a schedule.
+    pub fn clear_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.updates.push(Update::ClearSchedule(schedule.intern()));
+        self
+    }
+
+    /// Runs all systems of the given type in `schedule` every frame, even while stepping.
+    pub fn always_run<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system_type(system), SystemBehavior::AlwaysRun)
+    }
+
+    /// Runs the system with the given node in `schedule` every frame, even while stepping.
+    pub fn always_run_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        self.set_behavior(
+            schedule,
+            SystemIdentifier::Node(node),
+            SystemBehavior::AlwaysRun,
+        )
+    }
+
+    /// Never runs systems of the given type in `schedule` while stepping is enabled.
+    pub fn never_run<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system_type(system), SystemBehavior::NeverRun)
+    }
+
+    /// Never runs the system with the given node in `schedule` while stepping is enabled.
+    pub fn never_run_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        self.set_behavior(
+            schedule,
+            SystemIdentifier::Node(node),
+            SystemBehavior::NeverRun,
+        )
+    }
+
+    /// Stops [`Stepping::continue_frame`] before running systems of the given type in `schedule`.
+    pub fn set_breakpoint<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system_type(system), SystemBehavior::Break)
+    }
+
+    /// Stops [`Stepping::continue_frame`] before running the system with the given node in `schedule`.
+    pub fn set_breakpoint_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        self.set_behavior(
+            schedule,
+            SystemIdentifier::Node(node),
+            SystemBehavior::Break,
+        )
+    }
+
+    /// Clears any behavior set for systems of the given type in `schedule`.
+    pub fn clear_system<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+    ) -> &mut Self {
+        self.updates.push(Update::ClearBehavior(
+            schedule.intern(),
+            system_type(system),
+        ));
+        self
+    }
+
+    /// Clears any behavior set for the system with the given node in `schedule`.
+    pub fn clear_node(&mut self, schedule: impl ScheduleLabel, node: NodeId) -> &mut Self {
+        self.updates.push(Update::ClearBehavior(
+            schedule.intern(),
+            SystemIdentifier::Node(node),
+        ));
+        self
+    }
+
+    fn set_behavior(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: SystemIdentifier,
+        behavior: SystemBehavior,
+    ) -> &mut Self {
+        self.updates
+            .push(Update::SetBehavior(schedule.intern(), system, behavior));
+        self
+    }
+
+    /// Applies pending updates, and ends any step or continue requested for the previous frame.
+    fn next_frame(&mut self) {
+        if matches!(self.action, Action::Step | Action::Continue) {
+            self.action = Action::Waiting;
+        }
+
+        for update in std::mem::take(&mut self.updates) {
+            match update {
+                Update::SetAction(Action::RunAll) => {
+                    self.action = Action::RunAll;
+                }
+                Update::SetAction(Action::Waiting) => {
+                    if self.action == Action::RunAll {
+                        self.cursor = Cursor::default();
+                        self.action = Action::Waiting;
+                    }
+                }
+                Update::SetAction(action) => {
+                    if self.action != Action::RunAll {
+                        self.action = action;
+                    }
+                }
+                Update::AddSchedule(label) => {
+                    if !self.schedule_order.contains(&label) {
+                        self.schedule_order.push(label);
+                    }
+                    self.schedule_states.entry(label).or_default();
+                }
+                Update::RemoveSchedule(label) => {
+                    if let Some(index) = self.schedule_order.iter().position(|l| *l == label) {
+                        self.schedule_order.remove(index);
+                        if index < self.cursor.schedule {
+                            self.cursor.schedule -= 1;
+                        } else if index == self.cursor.schedule {
+                            self.cursor.system = 0;
+                        }
+                        if self.cursor.schedule >= self.schedule_order.len() {
+                            self.cursor = Cursor::default();
+                        }
+                    }
+                    self.schedule_states.remove(&label);
+                }
+                Update::ClearSchedule(label) => {
+                    if let Some(state) = self.schedule_states.get_mut(&label) {
+                        state.behaviors.clear();
+                    }
+                }
+                Update::SetBehavior(label, system, behavior) => {
+                    self.schedule_states
+                        .entry(label)
+                        .or_default()
+                        .behaviors
+                        .insert(system, behavior);
+                }
+                Update::ClearBehavior(label, system) => {
+                    if let Some(state) = self.schedule_states.get_mut(&label) {
+                        state.behaviors.remove(&system);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the systems of `schedule` the executor should skip this frame, or `None` if all
+    /// systems should run.
+    ///
+    /// This is called by [`Schedule::run`], and advances the cursor past the stepped systems.
+    pub fn skipped_systems(&mut self, schedule: &Schedule) -> Option<FixedBitSet> {
+        if self.action == Action::RunAll {
+            return None;
+        }
+
+        let label = schedule.label();
+        let index = self.schedule_order.iter().position(|l| *l == label)?;
+        let systems = schedule.systems().ok()?;
+        let state = self.schedule_states.get_mut(&label)?;
+
+        // schedules before the cursor have already been stepped through in this stepping frame
+        let mut action = if index < self.cursor.schedule {
+            Action::Waiting
+        } else {
+            self.action
+        };
+        let start = if index == self.cursor.schedule {
+            self.cursor.system
+        } else {
+            0
+        };
+
+        state.order.clear();
+        let mut skip = FixedBitSet::new();
+        // position of the cursor within this schedule, if stepping stopped in it
+        let mut stopped_at = None;
+        for (system_index, (node_id, system)) in systems.enumerate() {
+            state.order.push(node_id);
+            skip.grow(system_index + 1);
+
+            let behavior = state.behavior(node_id, System::type_id(system.as_ref()));
+            match behavior {
+                Some(SystemBehavior::AlwaysRun) => continue,
+                Some(SystemBehavior::NeverRun) => {
+                    skip.insert(system_index);
+                    continue;
+                }
+                Some(SystemBehavior::Break) | None => {}
+            }
+
+            if system_index < start {
+                skip.insert(system_index);
+                continue;
+            }
+
+            match action {
+                Action::RunAll => unreachable!(),
+                Action::Waiting => {
+                    skip.insert(system_index);
+                }
+                Action::Step => {
+                    action = Action::Waiting;
+                    stopped_at = Some(system_index + 1);
+                }
+                Action::Continue => {
+                    let at_cursor = index == self.cursor.schedule && system_index == start;
+                    if behavior == Some(SystemBehavior::Break) && !at_cursor {
+                        action = Action::Waiting;
+                        stopped_at = Some(system_index);
+                        skip.insert(system_index);
+                    }
+                }
+            }
+        }
+
+        if index >= self.cursor.schedule && self.action != Action::Waiting {
+            match stopped_at {
+                Some(system_index) if system_index < state.order.len() => {
+                    self.cursor = Cursor {
+                        schedule: index,
+                        system: system_index,
+                    };
+                }
+                _ if index + 1 < self.schedule_order.len() => {
+                    self.cursor = Cursor {
+                        schedule: index + 1,
+                        system: 0,
+                    };
+                }
+                // the end of the stepping frame was reached
+                _ => {
+                    self.cursor = Cursor::default();
+                    action = Action::Waiting;
+                }
+            }
+            self.action = action;
+        }
+
+        Some(skip)
+    }
+}
+
+fn system_type<Marker>(system: impl IntoSystem<(), (), Marker>) -> SystemIdentifier {
+    let system = IntoSystem::into_system(system);
+    SystemIdentifier::Type(System::type_id(&system))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::ExecutorKind,
+        system::{RunSystemOnce, SystemState},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct First;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Second;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn a(mut log: ResMut<Log>) {
+        log.0.push("a");
+    }
+
+    fn b(mut log: ResMut<Log>) {
+        log.0.push("b");
+    }
+
+    fn c(mut log: ResMut<Log>) {
+        log.0.push("c");
+    }
+
+    fn d(mut log: ResMut<Log>) {
+        log.0.push("d");
+    }
+
+    const EXECUTORS: [ExecutorKind; 3] = [
+        ExecutorKind::Simple,
+        ExecutorKind::SingleThreaded,
+        ExecutorKind::MultiThreaded,
+    ];
+
+    struct Harness {
+        world: World,
+        first: Schedule,
+        second: Schedule,
+    }
+
+    impl Harness {
+        fn new(executor: ExecutorKind) -> Self {
+            let mut world = World::new();
+            world.init_resource::<Log>();
+            world.insert_resource(Stepping::new());
+
+            let mut first = Schedule::new(First);
+            first.set_executor_kind(executor);
+            first.add_systems((a, b, c).chain());
+
+            let mut second = Schedule::new(Second);
+            second.set_executor_kind(executor);
+            second.add_systems(d);
+
+            Self {
+                world,
+                first,
+                second,
+            }
+        }
+
+        fn stepping(&mut self) -> Mut<'_, Stepping> {
+            self.world.resource_mut::<Stepping>()
+        }
+
+        /// Runs a frame and returns the systems that ran.
+        fn frame(&mut self) -> Vec<&'static str> {
+            self.world.run_system_once(Stepping::begin_frame);
+            self.first.run(&mut self.world);
+            self.second.run(&mut self.world);
+            std::mem::take(&mut self.world.resource_mut::<Log>().0)
+        }
+    }
+
+    #[test]
+    fn disabled_runs_all_systems() {
+        for executor in EXECUTORS {
+            let mut harness = Harness::new(executor);
+            harness.stepping().add_schedule(First);
+
+            assert_eq!(harness.frame(), vec!["a", "b", "c", "d"]);
+            assert!(!harness.world.resource::<Stepping>().is_enabled());
+        }
+    }
+
+    #[test]
+    fn step_one_system_at_a_time() {
+        for executor in EXECUTORS {
+            let mut harness = Harness::new(executor);
+            harness.stepping().add_schedule(First).enable();
+
+            // unstepped schedules keep running
+            assert_eq!(harness.frame(), vec!["d"], "{executor:?}");
+            assert_eq!(harness.frame(), vec!["d"], "{executor:?}");
+
+            for expected in ["a", "b", "c", "a"] {
+                harness.stepping().step_frame();
+                assert_eq!(harness.frame(), vec![expected, "d"], "{executor:?}");
+            }
+
+            // the step only applies to one frame
+            assert_eq!(harness.frame(), vec!["d"], "{executor:?}");
+        }
+    }
+
+    #[test]
+    fn step_across_schedules() {
+        for executor in EXECUTORS {
+            let mut harness = Harness::new(executor);
+            harness
+                .stepping()
+                .add_schedule(First)
+                .add_schedule(Second)
+                .enable();
+
+            assert!(harness.frame().is_empty(), "{executor:?}");
+            for expected in ["a", "b", "c", "d", "a"] {
+                harness.stepping().step_frame();
+                assert_eq!(harness.frame(), vec![expected], "{executor:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn continue_to_end_of_frame() {
+        for executor in EXECUTORS {
+            let mut harness = Harness::new(executor);
+            harness
+                .stepping()
+                .add_schedule(First)
+                .add_schedule(Second)
+                .enable();
+            harness.frame();
+
+            harness.stepping().step_frame();
+            assert_eq!(harness.frame(), vec!["a"], "{executor:?}");
+
+            harness.stepping().continue_frame();
+            assert_eq!(harness.frame(), vec!["b", "c", "d"], "{executor:?}");
+
+            // the cursor is back at the start of the stepping frame
+            assert!(harness.frame().is_empty(), "{executor:?}");
+            harness.stepping().continue_frame();
+            assert_eq!(harness.frame(), vec!["a", "b", "c", "d"], "{executor:?}");
+        }
+    }
+
+    #[test]
+    fn continue_stops_at_breakpoint() {
+        for executor in EXECUTORS {
+            let mut harness = Harness::new(executor);
+            harness
+                .stepping()
+                .add_schedule(First)
+                .set_breakpoint(First, b)
+                .enable();
+            harness.frame();
+
+            harness.stepping().continue_frame();
+            assert_eq!(harness.frame(), vec!["a", "d"], "{executor:?}");
+
+            // continuing from a breakpoint runs the system it stopped at
+            harness.stepping().continue_frame();
+            assert_eq!(harness.frame(), vec!["b", "c", "d"], "{executor:?}");
+        }
+    }
+
+    #[test]
+    fn always_and_never_run() {
+        for executor in EXECUTORS {
+            let mut harness = Harness::new(executor);
+            harness
+                .stepping()
+                .add_schedule(First)
+                .always_run(First, c)
+                .never_run(First, a)
+                .enable();
+
+            assert_eq!(harness.frame(), vec!["c", "d"], "{executor:?}");
+
+            // `a` is never run, so the first step runs `b`
+            harness.stepping().step_frame();
+            assert_eq!(harness.frame(), vec!["b", "c", "d"], "{executor:?}");
+
+            harness.stepping().clear_system(First, a).continue_frame();
+            assert_eq!(harness.frame(), vec!["c", "d"], "{executor:?}");
+
+            harness.stepping().continue_frame();
+            assert_eq!(harness.frame(), vec!["a", "b", "c", "d"], "{executor:?}");
+
+            harness.stepping().disable();
+            assert_eq!(harness.frame(), vec!["a", "b", "c", "d"], "{executor:?}");
+        }
+    }
+
+    #[test]
+    fn behavior_by_node() {
+        let mut harness = Harness::new(ExecutorKind::SingleThreaded);
+        harness.stepping().add_schedule(First).enable();
+        harness.frame();
+
+        let (label, node) = harness.world.resource::<Stepping>().cursor().unwrap();
+        assert_eq!(label, First.intern());
+        assert_eq!(
+            harness.first.systems().unwrap().next().unwrap().0,
+            node,
+            "the cursor starts at the first system"
+        );
+
+        harness
+            .stepping()
+            .never_run_node(First, node)
+            .continue_frame();
+        assert_eq!(harness.frame(), vec!["b", "c", "d"]);
+
+        harness.stepping().clear_node(First, node).step_frame();
+        assert_eq!(harness.frame(), vec!["a", "d"]);
+        let (_, next) = harness.world.resource::<Stepping>().cursor().unwrap();
+        assert_eq!(harness.first.systems().unwrap().nth(1).unwrap().0, next);
+    }
+
+    #[test]
+    fn controlled_from_a_system() {
+        let mut harness = Harness::new(ExecutorKind::SingleThreaded);
+        harness.stepping().add_schedule(First).enable();
+        harness.frame();
+
+        let mut console = SystemState::<ResMut<Stepping>>::new(&mut harness.world);
+        console.get_mut(&mut harness.world).step_frame();
+        assert_eq!(harness.frame(), vec!["a", "d"]);
+
+        harness.stepping().remove_schedule(First);
+        assert_eq!(harness.frame(), vec!["a", "b", "c", "d"]);
+        assert!(harness.world.resource::<Stepping>().schedules().is_empty());
+    }
+}
@@ -0,0 +1,618 @@
+//! Snapshots of a subset of the components and resources of a [`World`], which can be restored
+//! later on or compared with each other.
+//!
+//! This is intended for rollback networking: a [`WorldSnapshot`] of the simulation state is taken
+//! every fixed tick, and restored when a misprediction is detected before resimulating. Restoring
+//! a snapshot also restores which entities are alive and the state of the entity allocator, so
+//! that resimulating spawns entities with the same ids as before.
+//!
+//! The components and resources included in snapshots are registered in a [`SnapshotRegistry`].
+//! They must implement [`Clone`], to be copied in and out of the snapshot, and [`PartialEq`], to only
+//! write back the values that changed and to compute a [`SnapshotDiff`] between two snapshots.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::snapshot::SnapshotRegistry;
+//!
+//! #[derive(Component, Clone, PartialEq)]
+//! struct Position(i32);
+//!
+//! let mut registry = SnapshotRegistry::default();
+//! registry.register_component::<Position>();
+//!
+//! let mut world = World::new();
+//! let player = world.spawn(Position(0)).id();
+//! let snapshot = registry.snapshot(&mut world);
+//!
+//! world.get_mut::<Position>(player).unwrap().0 = 5;
+//! let projectile = world.spawn(Position(6)).id();
+//!
+//! snapshot.restore(&mut world);
+//! assert_eq!(world.get::<Position>(player).unwrap().0, 0);
+//! assert!(world.get_entity(projectile).is_none());
+//!
+//! // The entity allocator was restored as well.
+//! assert_eq!(world.spawn_empty().id(), projectile);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId, ComponentStorage, StorageType},
+    entity::{EntitiesSnapshot, Entity},
+    system::Resource,
+    world::World,
+};
+use bevy_ptr::UnsafeCellDeref;
+use bevy_utils::{EntityHashMap, EntityHashSet};
+use std::any::{Any, TypeId};
+
+/// The components and resources to include in a [`WorldSnapshot`].
+///
+/// Components and resources that aren't registered are left untouched when restoring a snapshot,
+/// unless their entity is despawned or respawned.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SnapshotRegistry {
+    components: Vec<(TypeId, fn(&mut World) -> TypeSnapshot)>,
+    resources: Vec<(TypeId, fn(&mut World) -> TypeSnapshot)>,
+}
+
+impl SnapshotRegistry {
+    /// Includes the components of type `T` in snapshots.
+    pub fn register_component<T: Component + Clone + PartialEq>(&mut self) -> &mut Self {
+        let type_id = TypeId::of::<T>();
+        if !self.components.iter().any(|(id, _)| *id == type_id) {
+            self.components.push((type_id, snapshot_component::<T>));
+        }
+        self
+    }
+
+    /// Includes the resource `R` in snapshots.
+    pub fn register_resource<R: Resource + Clone + PartialEq>(&mut self) -> &mut Self {
+        let type_id = TypeId::of::<R>();
+        if !self.resources.iter().any(|(id, _)| *id == type_id) {
+            self.resources.push((type_id, snapshot_resource::<R>));
+        }
+        self
+    }
+
+    /// Takes a snapshot of the registered components and resources of `world`, along with its
+    /// entities.
+    ///
+    /// The values of table components are copied column by column.
+    pub fn snapshot(&self, world: &mut World) -> WorldSnapshot {
+        world.flush();
+        WorldSnapshot {
+            entities: world.entities.snapshot(),
+            components: self
+                .components
+                .iter()
+                .map(|(_, snapshot)| snapshot(world))
+                .collect(),
+            resources: self
+                .resources
+                .iter()
+                .map(|(_, snapshot)| snapshot(world))
+                .collect(),
+        }
+    }
+}
+
+/// A copy of the entities of a [`World`], and of the components and resources registered in a
+/// [`SnapshotRegistry`], see [`SnapshotRegistry::snapshot`].
+pub struct WorldSnapshot {
+    entities: EntitiesSnapshot,
+    components: Vec<TypeSnapshot>,
+    resources: Vec<TypeSnapshot>,
+}
+
+impl WorldSnapshot {
+    /// Returns the entities that were alive when this snapshot was taken, and the state of the
+    /// entity allocator.
+    pub fn entities(&self) -> &EntitiesSnapshot {
+        &self.entities
+    }
+
+    /// Restores `world` to the state captured by this snapshot.
+    ///
+    /// - Entities spawned since the snapshot was taken are despawned, and entities despawned since
+    ///   are spawned again with the same id. The entity allocator is restored, so that the next
+    ///   entities spawned get the same ids as the ones spawned after the snapshot was taken.
+    /// - Registered components and resources are inserted, removed or overwritten to match the
+    ///   snapshot. Values equal to the ones in the snapshot are left untouched, and don't trigger
+    ///   change detection.
+    ///
+    /// Components that have `on_insert` or `on_replace` [hooks](crate::component::ComponentHooks),
+    /// such as relationships, are restored by inserting them again so that their hooks run. Other
+    /// components are overwritten in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a hook or an observer triggered while restoring the snapshot despawns one of its entities.
+    pub fn restore(&self, world: &mut World) {
+        self.restore_entities(world);
+        for snapshot in &self.components {
+            (snapshot.restore)(world, snapshot.component_id, &*snapshot.data);
+        }
+        for snapshot in &self.resources {
+            (snapshot.restore)(world, snapshot.component_id, &*snapshot.data);
+        }
+        // Hooks and observers may have spawned entities while the components were restored.
+        self.restore_entities(world);
+        world.entities.restore(&self.entities);
+    }
+
+    fn restore_entities(&self, world: &mut World) {
+        world.flush();
+        loop {
+            let spawned = world
+                .archetypes
+                .iter()
+                .flat_map(|archetype| archetype.entities())
+                .map(|archetype_entity| archetype_entity.id())
+                .filter(|entity| !self.entities.contains(*entity))
+                .collect::<Vec<_>>();
+            if spawned.is_empty() {
+                break;
+            }
+            for entity in spawned {
+                world.despawn(entity);
+            }
+        }
+        for entity in self.entities.iter() {
+            if world.get_entity(entity).is_none() {
+                world.get_or_spawn(entity);
+            }
+        }
+    }
+
+    /// Returns the differences between this snapshot and a `newer` one, taken from the same world.
+    ///
+    /// Only the components and resources registered when taking both snapshots are compared.
+    /// Components of despawned entities are reported as removed, and components of spawned entities
+    /// as added.
+    pub fn diff(&self, newer: &WorldSnapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff {
+            spawned: newer
+                .entities
+                .iter()
+                .filter(|entity| !self.entities.contains(*entity))
+                .collect(),
+            despawned: self
+                .entities
+                .iter()
+                .filter(|entity| !newer.entities.contains(*entity))
+                .collect(),
+            ..Default::default()
+        };
+        for (old, new) in pair_snapshots(&self.components, &newer.components) {
+            (old.diff)(old.component_id, &*old.data, &*new.data, &mut diff);
+        }
+        for (old, new) in pair_snapshots(&self.resources, &newer.resources) {
+            (old.diff)(old.component_id, &*old.data, &*new.data, &mut diff);
+        }
+        diff
+    }
+}
+
+fn pair_snapshots<'a>(
+    old: &'a [TypeSnapshot],
+    new: &'a [TypeSnapshot],
+) -> impl Iterator<Item = (&'a TypeSnapshot, &'a TypeSnapshot)> {
+    old.iter().filter_map(|old| {
+        new.iter()
+            .find(|new| new.component_id == old.component_id)
+            .map(|new| (old, new))
+    })
+}
+
+/// The differences between two [`WorldSnapshot`]s, see [`WorldSnapshot::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    /// The entities alive in the newer snapshot but not in the older one.
+    pub spawned: Vec<Entity>,
+    /// The entities alive in the older snapshot but not in the newer one.
+    pub despawned: Vec<Entity>,
+    /// The components that were added, removed or changed.
+    pub components: Vec<ComponentChange>,
+    /// The resources that were added, removed or changed.
+    pub resources: Vec<ResourceChange>,
+}
+
+impl SnapshotDiff {
+    /// Returns `true` if both snapshots are identical.
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty()
+            && self.despawned.is_empty()
+            && self.components.is_empty()
+            && self.resources.is_empty()
+    }
+}
+
+/// How a component or resource differs between two [`WorldSnapshot`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// Only the newer snapshot has the value.
+    Added,
+    /// Only the older snapshot has the value.
+    Removed,
+    /// Both snapshots have the value, but they aren't equal.
+    Changed,
+}
+
+/// A component that differs between two [`WorldSnapshot`]s, see [`SnapshotDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentChange {
+    /// The entity holding the component.
+    pub entity: Entity,
+    /// The id of the component.
+    pub component_id: ComponentId,
+    /// How the component differs.
+    pub kind: ChangeKind,
+}
+
+/// A resource that differs between two [`WorldSnapshot`]s, see [`SnapshotDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResourceChange {
+    /// The id of the resource.
+    pub component_id: ComponentId,
+    /// How the resource differs.
+    pub kind: ChangeKind,
+}
+
+/// The type-erased snapshot of a component or resource type.
+struct TypeSnapshot {
+    component_id: ComponentId,
+    data: Box<dyn Any + Send + Sync>,
+    restore: fn(&mut World, ComponentId, &dyn Any),
+    diff: fn(ComponentId, &dyn Any, &dyn Any, &mut SnapshotDiff),
+}
+
+/// The values of a component, along with the entities holding them.
+struct ComponentColumns<T> {
+    entities: Vec<Entity>,
+    values: Vec<T>,
+}
+
+fn snapshot_component<T: Component + Clone + PartialEq>(world: &mut World) -> TypeSnapshot {
+    let component_id = world.init_component::<T>();
+    let mut columns = ComponentColumns::<T> {
+        entities: Vec::new(),
+        values: Vec::new(),
+    };
+    match T::Storage::STORAGE_TYPE {
+        StorageType::Table => {
+            for table in world.storages.tables.iter() {
+                let Some(column) = table.get_column(component_id) else {
+                    continue;
+                };
+                // SAFETY: the column stores values of type `T`.
+                let values = unsafe { column.get_data_slice::<T>() };
+                columns.entities.extend_from_slice(table.entities());
+                // SAFETY: we have shared access to the whole world, so no value is borrowed mutably.
+                columns
+                    .values
+                    .extend(values.iter().map(|value| unsafe { value.deref() }.clone()));
+            }
+        }
+        StorageType::SparseSet => {
+            if let Some(sparse_set) = world.storages.sparse_sets.get(component_id) {
+                for archetype in world.archetypes.iter() {
+                    if !archetype.contains(component_id) {
+                        continue;
+                    }
+                    for archetype_entity in archetype.entities() {
+                        let entity = archetype_entity.id();
+                        let value = sparse_set.get(entity).unwrap();
+                        // SAFETY: the sparse set stores values of type `T`.
+                        columns.values.push(unsafe { value.deref::<T>() }.clone());
+                        columns.entities.push(entity);
+                    }
+                }
+            }
+        }
+    }
+    TypeSnapshot {
+        component_id,
+        data: Box::new(columns),
+        restore: restore_component::<T>,
+        diff: diff_component::<T>,
+    }
+}
+
+fn restore_component<T: Component + Clone + PartialEq>(
+    world: &mut World,
+    component_id: ComponentId,
+    data: &dyn Any,
+) {
+    let columns = data.downcast_ref::<ComponentColumns<T>>().unwrap();
+    let snapshot_entities = columns
+        .entities
+        .iter()
+        .copied()
+        .collect::<EntityHashSet<_>>();
+    let added = world
+        .archetypes
+        .iter()
+        .filter(|archetype| archetype.contains(component_id))
+        .flat_map(|archetype| archetype.entities())
+        .map(|archetype_entity| archetype_entity.id())
+        .filter(|entity| !snapshot_entities.contains(entity))
+        .collect::<Vec<_>>();
+    for entity in added {
+        world.entity_mut(entity).remove::<T>();
+    }
+
+    let hooks = world.components.get_info(component_id).unwrap().hooks();
+    let has_hooks = hooks.on_insert.is_some() || hooks.on_replace.is_some();
+    for (&entity, value) in columns.entities.iter().zip(&columns.values) {
+        let mut entity_mut = world.entity_mut(entity);
+        if let Some(current) = entity_mut.get::<T>() {
+            if current == value {
+                continue;
+            }
+            if !has_hooks {
+                *entity_mut.get_mut::<T>().unwrap() = value.clone();
+                continue;
+            }
+        }
+        entity_mut.insert(value.clone());
+    }
+}
+
+fn diff_component<T: Component + PartialEq>(
+    component_id: ComponentId,
+    old: &dyn Any,
+    new: &dyn Any,
+    diff: &mut SnapshotDiff,
+) {
+    let old = old.downcast_ref::<ComponentColumns<T>>().unwrap();
+    let new = new.downcast_ref::<ComponentColumns<T>>().unwrap();
+    let mut old_values = old
+        .entities
+        .iter()
+        .copied()
+        .zip(&old.values)
+        .collect::<EntityHashMap<_, _>>();
+    for (&entity, value) in new.entities.iter().zip(&new.values) {
+        let kind = match old_values.remove(&entity) {
+            None => ChangeKind::Added,
+            Some(old_value) if old_value != value => ChangeKind::Changed,
+            Some(_) => continue,
+        };
+        diff.components.push(ComponentChange {
+            entity,
+            component_id,
+            kind,
+        });
+    }
+    for &entity in &old.entities {
+        if old_values.contains_key(&entity) {
+            diff.components.push(ComponentChange {
+                entity,
+                component_id,
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+}
+
+fn snapshot_resource<R: Resource + Clone + PartialEq>(world: &mut World) -> TypeSnapshot {
+    let component_id = world.initialize_resource::<R>();
+    TypeSnapshot {
+        component_id,
+        data: Box::new(world.get_resource::<R>().cloned()),
+        restore: restore_resource::<R>,
+        diff: diff_resource::<R>,
+    }
+}
+
+fn restore_resource<R: Resource + Clone + PartialEq>(
+    world: &mut World,
+    _: ComponentId,
+    data: &dyn Any,
+) {
+    match data.downcast_ref::<Option<R>>().unwrap() {
+        Some(value) => match world.get_resource_mut::<R>() {
+            Some(mut current) => {
+                if *current != *value {
+                    *current = value.clone();
+                }
+            }
+            None => world.insert_resource(value.clone()),
+        },
+        None => {
+            world.remove_resource::<R>();
+        }
+    }
+}
+
+fn diff_resource<R: Resource + PartialEq>(
+    component_id: ComponentId,
+    old: &dyn Any,
+    new: &dyn Any,
+    diff: &mut SnapshotDiff,
+) {
+    let old = old.downcast_ref::<Option<R>>().unwrap();
+    let new = new.downcast_ref::<Option<R>>().unwrap();
+    let kind = match (old, new) {
+        (None, Some(_)) => ChangeKind::Added,
+        (Some(_), None) => ChangeKind::Removed,
+        (Some(old), Some(new)) if old != new => ChangeKind::Changed,
+        _ => return,
+    };
+    diff.resources.push(ResourceChange { component_id, kind });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{query::Changed, relationship::RelationshipTarget};
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    #[component(storage = "SparseSet")]
+    struct Stunned(u32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct NotRegistered;
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Score(u32);
+
+    #[derive(Component, Clone, PartialEq)]
+    #[relationship(relationship_target = Targeted)]
+    struct Targeting(Entity);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = Targeting)]
+    struct Targeted(Vec<Entity>);
+
+    fn registry() -> SnapshotRegistry {
+        let mut registry = SnapshotRegistry::default();
+        registry
+            .register_component::<Position>()
+            .register_component::<Stunned>()
+            .register_component::<Targeting>()
+            .register_resource::<Score>();
+        registry
+    }
+
+    #[test]
+    fn restore_components_and_resources() {
+        let mut world = World::new();
+        let registry = registry();
+        let a = world.spawn((Position(1), Stunned(2), NotRegistered)).id();
+        let b = world.spawn(Position(3)).id();
+        world.insert_resource(Score(10));
+        let snapshot = registry.snapshot(&mut world);
+
+        world.get_mut::<Position>(a).unwrap().0 = 5;
+        world.entity_mut(a).remove::<(Stunned, NotRegistered)>();
+        world.entity_mut(b).insert(Stunned(4));
+        world.resource_mut::<Score>().0 = 11;
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Stunned>(a), Some(&Stunned(2)));
+        // unregistered components aren't restored
+        assert!(!world.entity(a).contains::<NotRegistered>());
+        assert_eq!(world.get::<Position>(b), Some(&Position(3)));
+        assert!(!world.entity(b).contains::<Stunned>());
+        assert_eq!(world.resource::<Score>(), &Score(10));
+
+        world.remove_resource::<Score>();
+        snapshot.restore(&mut world);
+        assert_eq!(world.resource::<Score>(), &Score(10));
+    }
+
+    #[test]
+    fn restore_entities_and_allocator() {
+        let mut world = World::new();
+        let registry = registry();
+        let kept = world.spawn(Position(0)).id();
+        let despawned = world.spawn((Position(1), Stunned(1))).id();
+        let freed = world.spawn_empty().id();
+        world.despawn(freed);
+        let snapshot = registry.snapshot(&mut world);
+
+        let simulate = |world: &mut World| {
+            world.despawn(despawned);
+            [world.spawn(Position(2)).id(), world.spawn(Position(3)).id()]
+        };
+        let spawned = simulate(&mut world);
+        snapshot.restore(&mut world);
+
+        assert!(world.get_entity(kept).is_some());
+        assert_eq!(world.get::<Position>(despawned), Some(&Position(1)));
+        assert_eq!(world.get::<Stunned>(despawned), Some(&Stunned(1)));
+        assert!(spawned
+            .iter()
+            .all(|entity| world.get_entity(*entity).is_none()));
+        assert_eq!(world.entities().len(), 2);
+
+        // resimulating allocates the same entities
+        assert_eq!(simulate(&mut world), spawned);
+    }
+
+    #[test]
+    fn restore_relationships() {
+        let mut world = World::new();
+        let registry = registry();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let source = world.spawn(Targeting(a)).id();
+        let snapshot = registry.snapshot(&mut world);
+
+        world.entity_mut(source).insert(Targeting(b));
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<Targeted>(a).unwrap().as_slice(), &[source]);
+        assert!(!world.entity(b).contains::<Targeted>());
+    }
+
+    #[test]
+    fn unchanged_values_are_not_written() {
+        let mut world = World::new();
+        let registry = registry();
+        let a = world.spawn(Position(1)).id();
+        let b = world.spawn(Position(2)).id();
+        let snapshot = registry.snapshot(&mut world);
+
+        world.get_mut::<Position>(b).unwrap().0 = 3;
+        world.clear_trackers();
+        snapshot.restore(&mut world);
+
+        let mut query = world.query_filtered::<Entity, Changed<Position>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+    }
+
+    #[test]
+    fn diff_snapshots() {
+        let mut world = World::new();
+        let registry = registry();
+        let position_id = world.init_component::<Position>();
+        let stunned_id = world.init_component::<Stunned>();
+        let a = world.spawn((Position(1), Stunned(1))).id();
+        let b = world.spawn(Position(2)).id();
+        let despawned = world.spawn(Position(3)).id();
+        let old = registry.snapshot(&mut world);
+        assert!(old.diff(&registry.snapshot(&mut world)).is_empty());
+
+        world.get_mut::<Position>(a).unwrap().0 = 4;
+        world.entity_mut(a).remove::<Stunned>();
+        world.entity_mut(b).insert(Stunned(2));
+        world.despawn(despawned);
+        let spawned = world.spawn(Position(5)).id();
+        world.insert_resource(Score(1));
+        let new = registry.snapshot(&mut world);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.spawned, vec![spawned]);
+        assert_eq!(diff.despawned, vec![despawned]);
+        let change = |entity, component_id, kind| ComponentChange {
+            entity,
+            component_id,
+            kind,
+        };
+        for expected in [
+            change(a, position_id, ChangeKind::Changed),
+            change(spawned, position_id, ChangeKind::Added),
+            change(despawned, position_id, ChangeKind::Removed),
+            change(a, stunned_id, ChangeKind::Removed),
+            change(b, stunned_id, ChangeKind::Added),
+        ] {
+            assert!(diff.components.contains(&expected), "{expected:?}");
+        }
+        assert_eq!(diff.components.len(), 5);
+        assert_eq!(
+            diff.resources,
+            vec![ResourceChange {
+                component_id: world.components().resource_id::<Score>().unwrap(),
+                kind: ChangeKind::Added,
+            }]
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use std::borrow::Cow;
 
 use super::{ReadOnlySystem, System};
-use crate::{schedule::InternedSystemSet, world::unsafe_world_cell::UnsafeWorldCell};
+use crate::{
+    schedule::InternedSystemSet,
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld},
+};
 
 /// Customizes the behavior of an [`AdapterSystem`]
 ///
@@ -126,6 +129,11 @@ where
         self.system.apply_deferred(world);
     }
 
+    #[inline]
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.system.queue_deferred(world);
+    }
+
     fn initialize(&mut self, world: &mut crate::prelude::World) {
         self.system.initialize(world);
     }
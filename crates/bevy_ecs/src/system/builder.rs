@@ -0,0 +1,356 @@
+use bevy_utils::all_tuples;
+
+use super::{
+    BuildableSystemParam, FunctionSystem, Local, Query, Res, ResMut, Resource, SystemMeta,
+    SystemParam, SystemParamFunction, SystemState,
+};
+use crate::{
+    prelude::FromWorld,
+    query::{QueryData, QueryFilter},
+    world::World,
+};
+
+/// Builder struct used to construct state for [`SystemParam`]s passed to a system.
+///
+/// Parameters are added one at a time, in the order of the arguments of the system function.
+/// Most parameters are initialized exactly as they would be for a regular function system,
+/// but parameters implementing [`BuildableSystemParam`] can be configured at runtime with
+/// [`SystemBuilder::builder`]. This is how a [`Query`] whose accesses are only known at runtime
+/// (for example, one reading a component registered from a [`ComponentDescriptor`]) is given to a
+/// system, while still letting the executor know exactly which data it accesses.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::SystemBuilder;
+/// # use bevy_ecs::world::FilteredEntityMut;
+/// #
+/// # #[derive(Component)]
+/// # struct A(usize);
+/// #
+/// # let mut world = World::new();
+/// # world.spawn(A(0));
+/// // The id of the component is only known at runtime.
+/// let component_id = world.init_component::<A>();
+///
+/// let system = SystemBuilder::<()>::new(&mut world)
+///     .builder::<Query<FilteredEntityMut>>(|query| {
+///         query.mut_id(component_id);
+///     })
+///     .build(move |mut query: Query<FilteredEntityMut>| {
+///         for mut entity in &mut query {
+///             let mut a = entity.get_mut_by_id(component_id).unwrap();
+///             // SAFETY: `component_id` is the id of `A`.
+///             unsafe { a.as_mut().deref_mut::<A>().0 += 1 };
+///         }
+///     });
+/// # let mut schedule = Schedule::default();
+/// # schedule.add_systems(system);
+/// # schedule.run(&mut world);
+/// # assert_eq!(world.query::<&A>().single(&world).0, 1);
+/// ```
+///
+/// [`ComponentDescriptor`]: crate::component::ComponentDescriptor
+pub struct SystemBuilder<'w, T: SystemParam = ()> {
+    pub(crate) meta: SystemMeta,
+    pub(crate) state: T::State,
+    pub(crate) world: &'w mut World,
+}
+
+impl<'w, T: SystemParam> SystemBuilder<'w, T> {
+    /// Returns a new builder with default state for the given `T` [`SystemParam`]
+    pub fn new(world: &'w mut World) -> Self {
+        let mut meta = SystemMeta::new::<T>();
+        Self {
+            state: T::init_state(world, &mut meta),
+            meta,
+            world,
+        }
+    }
+
+    /// Create a [`FunctionSystem`] from the current state
+    pub fn build<F, Marker>(self, func: F) -> FunctionSystem<Marker, F>
+    where
+        F: SystemParamFunction<Marker, Param = T>,
+    {
+        FunctionSystem::from_builder(self, func)
+    }
+
+    /// Create a [`SystemState`] from the current state
+    pub fn state(self) -> SystemState<T> {
+        SystemState::from_builder(self)
+    }
+}
+
+macro_rules! impl_system_builder {
+    ($($curr: ident),*) => {
+        impl<'w, $($curr: SystemParam,)*> SystemBuilder<'w, ($($curr,)*)> {
+            /// Add `T` as a parameter built from the world
+            pub fn param<T: SystemParam>(mut self) -> SystemBuilder<'w, ($($curr,)* T,)> {
+                #[allow(non_snake_case)]
+                let ($($curr,)*) = self.state;
+                SystemBuilder {
+                    state: ($($curr,)* T::init_state(self.world, &mut self.meta),),
+                    meta: self.meta,
+                    world: self.world,
+                }
+            }
+
+            /// Helper method for reading a [`Resource`] as a param, equivalent to `.param::<Res<T>>()`
+            pub fn resource<T: Resource>(self) -> SystemBuilder<'w, ($($curr,)* Res<'static, T>,)> {
+                self.param::<Res<T>>()
+            }
+
+            /// Helper method for mutably accessing a [`Resource`] as a param, equivalent to `.param::<ResMut<T>>()`
+            pub fn resource_mut<T: Resource>(self) -> SystemBuilder<'w, ($($curr,)* ResMut<'static, T>,)> {
+                self.param::<ResMut<T>>()
+            }
+
+            /// Helper method for adding a [`Local`] as a param, equivalent to `.param::<Local<T>>()`
+            pub fn local<T: Send + FromWorld>(self) -> SystemBuilder<'w, ($($curr,)* Local<'static, T>,)> {
+                self.param::<Local<T>>()
+            }
+
+            /// Helper method for adding a [`Query`] as a param, equivalent to `.param::<Query<D>>()`
+            pub fn query<D: QueryData + 'static>(self) -> SystemBuilder<'w, ($($curr,)* Query<'static, 'static, D, ()>,)> {
+                self.query_filtered::<D, ()>()
+            }
+
+            /// Helper method for adding a filtered [`Query`] as a param, equivalent to `.param::<Query<D, F>>()`
+            pub fn query_filtered<D: QueryData + 'static, F: QueryFilter + 'static>(self) -> SystemBuilder<'w, ($($curr,)* Query<'static, 'static, D, F>,)> {
+                self.param::<Query<D, F>>()
+            }
+
+            /// Add `T` as a parameter built with the given function
+            pub fn builder<T: BuildableSystemParam>(
+                mut self,
+                func: impl FnOnce(&mut T::Builder<'_>),
+            ) -> SystemBuilder<'w, ($($curr,)* T,)> {
+                #[allow(non_snake_case)]
+                let ($($curr,)*) = self.state;
+                SystemBuilder {
+                    state: ($($curr,)* T::build(self.world, &mut self.meta, func),),
+                    meta: self.meta,
+                    world: self.world,
+                }
+            }
+        }
+    };
+}
+
+all_tuples!(impl_system_builder, 0, 15, P);
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::component::{ComponentDescriptor, StorageType};
+    use crate::prelude::*;
+    use crate::system::{RunSystemOnce, SystemBuilder};
+    use crate::world::{FilteredEntityMut, FilteredEntityRef};
+    use std::alloc::Layout;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Resource, Default)]
+    struct R(usize);
+
+    fn local_system(local: Local<u64>) -> u64 {
+        *local
+    }
+
+    fn query_system(query: Query<()>) -> usize {
+        query.iter().count()
+    }
+
+    fn multi_param_system(a: Local<u64>, b: Local<u64>) -> u64 {
+        *a + *b + 1
+    }
+
+    #[test]
+    fn local_builder() {
+        let mut world = World::new();
+
+        let system = SystemBuilder::<()>::new(&mut world)
+            .param::<Local<u64>>()
+            .build(local_system);
+
+        let result = world.run_system_once(system);
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn query_builder() {
+        let mut world = World::new();
+
+        world.spawn(A);
+        world.spawn_empty();
+
+        let system = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<()>>(|query| {
+                query.with::<A>();
+            })
+            .build(query_system);
+
+        let result = world.run_system_once(system);
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn multi_param_builder() {
+        let mut world = World::new();
+
+        world.spawn(A);
+        world.spawn_empty();
+
+        let system = SystemBuilder::<()>::new(&mut world)
+            .local::<u64>()
+            .param::<Local<u64>>()
+            .build(multi_param_system);
+
+        let result = world.run_system_once(system);
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn resource_builder() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+
+        let mut state = SystemBuilder::<()>::new(&mut world)
+            .resource_mut::<R>()
+            .state();
+        let (mut r,) = state.get_mut(&mut world);
+        r.0 = 7;
+
+        assert_eq!(world.resource::<R>().0, 7);
+    }
+
+    #[test]
+    fn dynamic_component_query() {
+        let mut world = World::new();
+        // SAFETY: `u64` has no drop glue and is `Send + Sync`.
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                "Dynamic",
+                StorageType::Table,
+                Layout::new::<u64>(),
+                None,
+            )
+        };
+        let component_id = world.init_component_with_descriptor(descriptor);
+
+        let mut entity = world.spawn_empty();
+        // SAFETY: `component_id` was registered with the layout of `u64`.
+        unsafe {
+            bevy_ptr::OwningPtr::make(5u64, |ptr| {
+                entity.insert_by_id(component_id, ptr);
+            });
+        }
+        let entity = entity.id();
+
+        let system = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<FilteredEntityMut>>(|query| {
+                query.mut_id(component_id);
+            })
+            .build(move |mut query: Query<FilteredEntityMut>| {
+                for mut entity in &mut query {
+                    let value = entity.get_mut_by_id(component_id).unwrap();
+                    // SAFETY: the component was registered with the layout of `u64`.
+                    unsafe { *value.into_inner().deref_mut::<u64>() *= 2 };
+                }
+            });
+        world.run_system_once(system);
+
+        let mut state = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<FilteredEntityRef>>(|query| {
+                query.ref_id(component_id);
+            })
+            .state();
+        let (query,) = state.get(&world);
+        let entity_ref = query.get(entity).unwrap();
+        // SAFETY: the component was registered with the layout of `u64`.
+        let value = unsafe { *entity_ref.get_by_id(component_id).unwrap().deref::<u64>() };
+        assert_eq!(value, 10);
+    }
+
+    #[test]
+    fn dynamic_query_access_conflicts() {
+        let mut world = World::new();
+        world.spawn((A, B));
+        let component_id_a = world.init_component::<A>();
+        let component_id_b = world.init_component::<B>();
+
+        let mut write_a = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<FilteredEntityMut>>(|query| {
+                query.mut_id(component_id_a);
+            })
+            .build(|_: Query<FilteredEntityMut>| {});
+        let mut read_a = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<FilteredEntityRef>>(|query| {
+                query.ref_id(component_id_a);
+            })
+            .build(|_: Query<FilteredEntityRef>| {});
+        let mut read_b = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<FilteredEntityRef>>(|query| {
+                query.ref_id(component_id_b);
+            })
+            .build(|_: Query<FilteredEntityRef>| {});
+
+        let world_cell = world.as_unsafe_world_cell();
+        write_a.update_archetype_component_access(world_cell);
+        read_a.update_archetype_component_access(world_cell);
+        read_b.update_archetype_component_access(world_cell);
+
+        assert!(!write_a
+            .archetype_component_access()
+            .is_compatible(read_a.archetype_component_access()));
+        assert!(write_a
+            .archetype_component_access()
+            .is_compatible(read_b.archetype_component_access()));
+        assert!(!write_a
+            .component_access()
+            .is_compatible(read_a.component_access()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_dynamic_queries_in_one_system() {
+        let mut world = World::new();
+        let component_id = world.init_component::<A>();
+
+        SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<FilteredEntityMut>>(|query| {
+                query.mut_id(component_id);
+            })
+            .builder::<Query<FilteredEntityRef>>(|query| {
+                query.ref_id(component_id);
+            })
+            .build(|_: Query<FilteredEntityMut>, _: Query<FilteredEntityRef>| {});
+    }
+
+    #[test]
+    fn built_state_is_kept_when_initialized() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn(B);
+
+        let mut system = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<()>>(|query| {
+                query.with::<B>();
+            })
+            .build(query_system);
+        system.initialize(&mut world);
+        assert_eq!(system.run((), &mut world), 1);
+
+        let mut state = SystemBuilder::<()>::new(&mut world)
+            .builder::<Query<()>>(|query| {
+                query.with::<A>();
+            })
+            .state();
+        let (query,) = state.get(&world);
+        assert_eq!(query.iter().count(), 1);
+    }
+}
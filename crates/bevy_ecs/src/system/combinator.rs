@@ -8,7 +8,7 @@ use crate::{
     prelude::World,
     query::Access,
     schedule::InternedSystemSet,
-    world::unsafe_world_cell::UnsafeWorldCell,
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld},
 };
 
 use super::{ReadOnlySystem, System};
@@ -200,6 +200,11 @@ where
         self.b.apply_deferred(world);
     }
 
+    fn queue_deferred(&mut self, mut world: DeferredWorld) {
+        self.a.queue_deferred(world.reborrow());
+        self.b.queue_deferred(world);
+    }
+
     fn initialize(&mut self, world: &mut World) {
         self.a.initialize(world);
         self.b.initialize(world);
@@ -112,6 +112,12 @@ impl CommandQueue {
         }
     }
 
+    /// Returns `true` if there are no commands in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
     /// Execute the queued [`Command`]s in the world.
     /// This clears the queue.
     #[inline]
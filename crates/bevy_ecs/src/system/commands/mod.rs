@@ -4,15 +4,19 @@ mod parallel_scope;
 use crate::{
     self as bevy_ecs,
     bundle::Bundle,
-    entity::{Entities, Entity},
-    system::{RunSystemWithInput, SystemId},
-    world::{EntityWorldMut, FromWorld, World},
+    entity::{Entities, Entity, EntityCloner},
+    error::ErrorContext,
+    event::Event,
+    observer::{Observer, TriggerEvent, TriggerTargets},
+    system::{IntoObserverSystem, RunSystemWithInput, SystemId},
+    world::{DeferredWorld, EntityWorldMut, FromWorld, World},
 };
 use bevy_ecs_macros::SystemParam;
 use bevy_utils::tracing::{error, info};
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
+use thiserror::Error;
 
 use super::{Deferred, Resource, SystemBuffer, SystemMeta};
 
@@ -122,6 +126,13 @@ impl SystemBuffer for CommandQueue {
         let _span_guard = _system_meta.commands_span.enter();
         self.apply(world);
     }
+
+    #[inline]
+    fn queue(&mut self, _system_meta: &SystemMeta, mut world: DeferredWorld) {
+        #[cfg(feature = "trace")]
+        let _span_guard = _system_meta.commands_span.enter();
+        world.commands().append(self);
+    }
 }
 
 impl<'w, 's> Commands<'w, 's> {
@@ -610,6 +621,26 @@ impl<'w, 's> Commands<'w, 's> {
     pub fn add<C: Command>(&mut self, command: C) {
         self.queue.push(command);
     }
+
+    /// Sends a "global" [`Trigger`](crate::observer::Trigger) without any targets. This will run any [`Observer`] of the `event` that
+    /// isn't scoped to specific targets.
+    pub fn trigger(&mut self, event: impl Event) {
+        self.add(TriggerEvent { event, targets: () });
+    }
+
+    /// Sends a [`Trigger`](crate::observer::Trigger) for the given targets. This will run any [`Observer`] of the `event` that
+    /// watches those targets.
+    pub fn trigger_targets(&mut self, event: impl Event, targets: impl TriggerTargets) {
+        self.add(TriggerEvent { event, targets });
+    }
+
+    /// Spawns an [`Observer`] and returns the [`EntityCommands`] associated with the entity that stores the observer.
+    pub fn observe<E: Event, B: Bundle, M>(
+        &mut self,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> EntityCommands<'_> {
+        self.spawn(Observer::new(observer))
+    }
 }
 
 /// A [`Command`] which gets executed for a given [`Entity`].
@@ -736,11 +767,12 @@ impl EntityCommands<'_> {
     ///
     /// This will overwrite any previous value(s) of the same component type.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// If the associated entity does not exist when the command is applied, a [`NoSuchEntityError`]
+    /// is passed to the world's [`ErrorHandler`](crate::error::ErrorHandler), which panics by default.
     ///
-    /// To avoid a panic in this case, use the command [`Self::try_insert`] instead.
+    /// To silently ignore this case, use the command [`Self::try_insert`] instead.
     ///
     /// # Example
     ///
@@ -793,7 +825,7 @@ impl EntityCommands<'_> {
     ///
     /// # Note
     ///
-    /// Unlike [`Self::insert`], this will not panic if the associated entity does not exist.
+    /// Unlike [`Self::insert`], this will not report an error if the associated entity does not exist.
     ///
     /// # Example
     ///
@@ -830,7 +862,7 @@ impl EntityCommands<'_> {
     ///      .despawn();
     ///
     ///    commands.entity(player.entity)
-    ///    // This will not panic nor will it add the component
+    ///    // This will not report an error nor will it add the component
     ///      .try_insert(Defense(5));
     /// }
     /// # bevy_ecs::system::assert_is_system(add_combat_stats_system);
@@ -841,6 +873,12 @@ impl EntityCommands<'_> {
 
     /// Removes a [`Bundle`] of components from the entity.
     ///
+    /// # Errors
+    ///
+    /// If the associated entity does not exist when the command is applied, a [`NoSuchEntityError`]
+    /// is passed to the world's [`ErrorHandler`](crate::error::ErrorHandler). Without one, a warning
+    /// is logged.
+    ///
     /// # Example
     ///
     /// ```
@@ -890,9 +928,11 @@ impl EntityCommands<'_> {
     /// This won't clean up external references to the entity (such as parent-child relationships
     /// if you're using `bevy_hierarchy`), which may leave the world in an invalid state.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// The command will panic when applied if the associated entity does not exist.
+    /// If the associated entity does not exist when the command is applied, a [`NoSuchEntityError`]
+    /// is passed to the world's [`ErrorHandler`](crate::error::ErrorHandler). Without one, a warning
+    /// is logged.
     ///
     /// # Example
     ///
@@ -915,6 +955,52 @@ impl EntityCommands<'_> {
         self.add(despawn);
     }
 
+    /// Clones the entity and its components into a new entity, and returns the [`EntityCommands`]
+    /// of the clone.
+    ///
+    /// Components are cloned according to their [`ComponentCloneBehavior`](crate::component::ComponentCloneBehavior).
+    /// See [`EntityCloner`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// If the associated entity does not exist when the command is applied, a [`NoSuchEntityError`]
+    /// is passed to the world's [`ErrorHandler`](crate::error::ErrorHandler), which panics by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Resource)]
+    /// # struct Template { entity: Entity }
+    /// #[derive(Component, Clone)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// fn spawn_enemy_system(mut commands: Commands, template: Res<Template>) {
+    ///     commands.entity(template.entity).clone_and_spawn().insert(Enemy);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_enemy_system);
+    /// ```
+    pub fn clone_and_spawn(&mut self) -> EntityCommands<'_> {
+        self.clone_and_spawn_with(EntityCloner::default())
+    }
+
+    /// Clones the entity into a new entity using `cloner`, and returns the [`EntityCommands`]
+    /// of the clone.
+    ///
+    /// # Errors
+    ///
+    /// If the associated entity does not exist when the command is applied, a [`NoSuchEntityError`]
+    /// is passed to the world's [`ErrorHandler`](crate::error::ErrorHandler), which panics by default.
+    pub fn clone_and_spawn_with(&mut self, cloner: EntityCloner) -> EntityCommands<'_> {
+        let clone = self.commands.spawn_empty().id();
+        self.add(clone_entity(cloner, clone));
+        self.commands.entity(clone)
+    }
+
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
     ///
     /// # Examples
@@ -940,6 +1026,12 @@ impl EntityCommands<'_> {
     ///
     /// This can also be used to remove all the components from the entity by passing it an empty Bundle.
     ///
+    /// # Errors
+    ///
+    /// If the associated entity does not exist when the command is applied, a [`NoSuchEntityError`]
+    /// is passed to the world's [`ErrorHandler`](crate::error::ErrorHandler). Without one, a warning
+    /// is logged.
+    ///
     /// # Example
     ///
     /// ```
@@ -993,6 +1085,14 @@ impl EntityCommands<'_> {
     pub fn commands(&mut self) -> Commands {
         self.commands.reborrow()
     }
+
+    /// Creates an [`Observer`] listening for a trigger of type `T` that targets this entity.
+    pub fn observe<E: Event, B: Bundle, M>(
+        &mut self,
+        system: impl IntoObserverSystem<E, B, M>,
+    ) -> &mut Self {
+        self.add(observe(system))
+    }
 }
 
 impl<F> Command for F
@@ -1055,15 +1155,74 @@ where
     }
 }
 
+/// The error reported to the [`ErrorHandler`](crate::error::ErrorHandler) by an [`EntityCommand`]
+/// applied to an entity that doesn't exist.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("error[B0003]: Could not {action} for entity {entity:?} because it doesn't exist in this World.")]
+pub struct NoSuchEntityError {
+    /// The entity that doesn't exist.
+    pub entity: Entity,
+    /// What the command tried to do to the entity.
+    pub action: Cow<'static, str>,
+}
+
+/// Reports that the command `name` could not `action` for `entity`, which doesn't exist.
+fn no_such_entity(
+    world: &World,
+    name: &'static str,
+    entity: Entity,
+    action: impl Into<Cow<'static, str>>,
+) {
+    world.handle_error(
+        NoSuchEntityError {
+            entity,
+            action: action.into(),
+        },
+        ErrorContext::Command { name: name.into() },
+    );
+}
+
+/// Like [`no_such_entity`], for commands whose target having already been despawned is usually
+/// harmless: without an [`ErrorHandler`](crate::error::ErrorHandler), this only logs a warning.
+fn warn_no_such_entity(
+    world: &World,
+    name: &'static str,
+    entity: Entity,
+    action: impl Into<Cow<'static, str>>,
+) {
+    world.handle_warning(
+        NoSuchEntityError {
+            entity,
+            action: action.into(),
+        },
+        ErrorContext::Command { name: name.into() },
+    );
+}
+
 /// A [`Command`] that despawns a specific entity.
-/// This will emit a warning if the entity does not exist.
+/// This will report an error if the entity does not exist.
 ///
 /// # Note
 ///
 /// This won't clean up external references to the entity (such as parent-child relationships
 /// if you're using `bevy_hierarchy`), which may leave the world in an invalid state.
 fn despawn(entity: Entity, world: &mut World) {
-    world.despawn(entity);
+    if world.get_entity(entity).is_some() {
+        world.despawn(entity);
+    } else {
+        warn_no_such_entity(world, "despawn", entity, "despawn the entity");
+    }
+}
+
+/// An [`EntityCommand`] that clones an entity into `clone`.
+fn clone_entity(cloner: EntityCloner, clone: Entity) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        if world.get_entity(entity).is_some() {
+            cloner.clone_entity_into(world, entity, clone);
+        } else {
+            no_such_entity(world, "clone_and_spawn", entity, "clone the entity");
+        }
+    }
 }
 
 /// An [`EntityCommand`] that adds the components in a [`Bundle`] to an entity.
@@ -1072,7 +1231,12 @@ fn insert<T: Bundle>(bundle: T) -> impl EntityCommand {
         if let Some(mut entity) = world.get_entity_mut(entity) {
             entity.insert(bundle);
         } else {
-            panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), entity);
+            no_such_entity(
+                world,
+                "insert",
+                entity,
+                format!("insert a bundle (of type `{}`)", std::any::type_name::<T>()),
+            );
         }
     }
 }
@@ -1092,6 +1256,13 @@ fn try_insert(bundle: impl Bundle) -> impl EntityCommand {
 fn remove<T: Bundle>(entity: Entity, world: &mut World) {
     if let Some(mut entity_mut) = world.get_entity_mut(entity) {
         entity_mut.remove::<T>();
+    } else {
+        warn_no_such_entity(
+            world,
+            "remove",
+            entity,
+            format!("remove a bundle (of type `{}`)", std::any::type_name::<T>()),
+        );
     }
 }
 
@@ -1101,6 +1272,13 @@ fn remove<T: Bundle>(entity: Entity, world: &mut World) {
 fn retain<T: Bundle>(entity: Entity, world: &mut World) {
     if let Some(mut entity_mut) = world.get_entity_mut(entity) {
         entity_mut.retain::<T>();
+    } else {
+        warn_no_such_entity(
+            world,
+            "retain",
+            entity,
+            format!("retain a bundle (of type `{}`)", std::any::type_name::<T>()),
+        );
     }
 }
 
@@ -1122,6 +1300,17 @@ fn insert_resource<R: Resource>(resource: R) -> impl Command {
     }
 }
 
+/// An [`EntityCommand`] that creates an [`Observer`] listening for a trigger of type `T` that targets an entity.
+fn observe<E: Event, B: Bundle, M>(
+    observer: impl IntoObserverSystem<E, B, M>,
+) -> impl EntityCommand {
+    move |entity, world: &mut World| {
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            entity.observe(observer);
+        }
+    }
+}
+
 /// [`EntityCommand`] to log the components of a given entity. See [`EntityCommands::log_components`].
 fn log_components(entity: Entity, world: &mut World) {
     let debug_infos: Vec<_> = world
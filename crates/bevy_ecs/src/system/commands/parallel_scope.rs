@@ -7,6 +7,7 @@ use crate::{
     entity::Entities,
     prelude::World,
     system::{Deferred, SystemBuffer, SystemMeta, SystemParam},
+    world::DeferredWorld,
 };
 
 use super::{CommandQueue, Commands};
@@ -57,6 +58,15 @@ impl SystemBuffer for ParallelCommandQueue {
             cq.get_mut().apply(world);
         }
     }
+
+    #[inline]
+    fn queue(&mut self, _system_meta: &SystemMeta, mut world: DeferredWorld) {
+        #[cfg(feature = "trace")]
+        let _system_span = _system_meta.commands_span.enter();
+        for cq in &mut self.thread_local_storage {
+            world.commands().append(cq.get_mut());
+        }
+    }
 }
 
 impl<'w, 's> ParallelCommands<'w, 's> {
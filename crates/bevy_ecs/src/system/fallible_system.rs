@@ -0,0 +1,151 @@
+use std::{any::TypeId, borrow::Cow};
+
+use super::{ReadOnlySystem, System};
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick},
+    error::{handle_error, BoxedError, ErrorContext, ErrorHandler},
+    query::Access,
+    schedule::InternedSystemSet,
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+
+/// A [`System`] that runs a system returning a [`Result`], and passes its errors to the world's
+/// [`ErrorHandler`].
+///
+/// This is how systems returning a [`Result`] are added to schedules. In addition to the accesses
+/// of the wrapped system, it reads the [`ErrorHandler`] resource.
+pub struct FallibleSystem<S> {
+    system: S,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<S, E> FallibleSystem<S>
+where
+    S: System<Out = Result<(), E>>,
+    E: Into<BoxedError> + 'static,
+{
+    /// Wraps `system`, so that its errors are passed to the world's [`ErrorHandler`].
+    pub fn new(system: S) -> Self {
+        Self {
+            system,
+            component_access: Access::default(),
+            archetype_component_access: Access::default(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        ErrorContext::System {
+            name: self.system.name(),
+        }
+    }
+}
+
+impl<S, E> System for FallibleSystem<S>
+where
+    S: System<Out = Result<(), E>>,
+    E: Into<BoxedError> + 'static,
+{
+    type In = S::In;
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    fn has_deferred(&self) -> bool {
+        self.system.has_deferred()
+    }
+
+    #[inline]
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) -> Self::Out {
+        // SAFETY: `system.run_unsafe` has the same invariants as `self.run_unsafe`.
+        if let Err(error) = unsafe { self.system.run_unsafe(input, world) } {
+            // SAFETY: read access to the `ErrorHandler` resource was registered in `initialize`.
+            let handler = unsafe { world.get_resource::<ErrorHandler>() };
+            handle_error(handler, error.into(), self.context());
+        }
+    }
+
+    #[inline]
+    fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
+        if let Err(error) = self.system.run(input, world) {
+            world.handle_error(error, self.context());
+        }
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    #[inline]
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.system.queue_deferred(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+        let handler_id = world.initialize_resource::<ErrorHandler>();
+        self.component_access = self.system.component_access().clone();
+        self.component_access.add_read(handler_id);
+        let archetype_component_id = world
+            .get_resource_archetype_component_id(handler_id)
+            .unwrap();
+        self.archetype_component_access
+            .add_read(archetype_component_id);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+        self.archetype_component_access
+            .extend(self.system.archetype_component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
+
+// SAFETY: The inner system is read-only, and the `ErrorHandler` resource is only read.
+unsafe impl<S, E> ReadOnlySystem for FallibleSystem<S>
+where
+    S: ReadOnlySystem<Out = Result<(), E>>,
+    E: Into<BoxedError> + 'static,
+{
+}
@@ -5,7 +5,7 @@ use crate::{
     query::{Access, FilteredAccessSet},
     schedule::{InternedSystemSet, SystemSet},
     system::{check_system_change_tick, ReadOnlySystemParam, System, SystemParam, SystemParamItem},
-    world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World, WorldId},
 };
 
 use bevy_utils::all_tuples;
@@ -14,7 +14,7 @@ use std::{any::TypeId, borrow::Cow, marker::PhantomData};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Span};
 
-use super::{In, IntoSystem, ReadOnlySystem};
+use super::{In, IntoSystem, ReadOnlySystem, SystemBuilder};
 
 /// The metadata of a [`System`].
 #[derive(Clone)]
@@ -56,6 +56,17 @@ impl SystemMeta {
         &self.name
     }
 
+    /// Renames the system after the type `T`.
+    pub(crate) fn set_name<T>(&mut self) {
+        let name = std::any::type_name::<T>();
+        self.name = name.into();
+        #[cfg(feature = "trace")]
+        {
+            self.system_span = info_span!("system", name = name);
+            self.commands_span = info_span!("system_commands", name = name);
+        }
+    }
+
     /// Returns true if the system is [`Send`].
     #[inline]
     pub fn is_send(&self) -> bool {
@@ -202,6 +213,18 @@ impl<Param: SystemParam> SystemState<Param> {
         }
     }
 
+    /// Creates a new [`SystemState`] from the state built by a [`SystemBuilder`].
+    pub(crate) fn from_builder(builder: SystemBuilder<Param>) -> Self {
+        let mut meta = builder.meta;
+        meta.last_run = builder.world.change_tick().relative_to(Tick::MAX);
+        Self {
+            meta,
+            param_state: builder.state,
+            world_id: builder.world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+        }
+    }
+
     /// Gets the metadata for this instance.
     #[inline]
     pub fn meta(&self) -> &SystemMeta {
@@ -438,6 +461,20 @@ where
     // When lines get too long, rustfmt can sometimes refuse to format them.
     // Work around this by storing the message separately.
     const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Creates a new [`FunctionSystem`] from the state built by a [`SystemBuilder`].
+    pub(crate) fn from_builder(builder: SystemBuilder<F::Param>, func: F) -> Self {
+        let mut system_meta = builder.meta;
+        system_meta.set_name::<F>();
+        Self {
+            func,
+            param_state: Some(builder.state),
+            system_meta,
+            world_id: Some(builder.world.id()),
+            archetype_generation: ArchetypeGeneration::initial(),
+            marker: PhantomData,
+        }
+    }
 }
 
 impl<Marker, F> System for FunctionSystem<Marker, F>
@@ -512,11 +549,25 @@ where
         F::Param::apply(param_state, &self.system_meta, world);
     }
 
+    #[inline]
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        let param_state = self.param_state.as_mut().expect(Self::PARAM_MESSAGE);
+        F::Param::queue(param_state, &self.system_meta, world);
+    }
+
     #[inline]
     fn initialize(&mut self, world: &mut World) {
-        self.world_id = Some(world.id());
+        if let Some(id) = self.world_id {
+            assert_eq!(
+                id,
+                world.id(),
+                "System built with a different world than the one it was added to."
+            );
+        } else {
+            self.world_id = Some(world.id());
+            self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
+        }
         self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
-        self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
     }
 
     fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
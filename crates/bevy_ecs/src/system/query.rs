@@ -3,8 +3,8 @@ use crate::{
     entity::Entity,
     query::{
         BatchingStrategy, QueryCombinationIter, QueryComponentError, QueryData, QueryEntityError,
-        QueryFilter, QueryIter, QueryManyIter, QueryParIter, QuerySingleError, QueryState,
-        ROQueryItem, ReadOnlyQueryData,
+        QueryFilter, QueryIter, QueryJoinIter, QueryManyIter, QueryParIter, QuerySingleError,
+        QueryState, ROQueryItem, ReadOnlyQueryData,
     },
     world::{unsafe_world_cell::UnsafeWorldCell, Mut},
 };
@@ -645,6 +645,121 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the query items joined with the items of another query.
+    ///
+    /// For each query item, `key` returns the [`Entity`] to look up in `joined`.
+    /// Items whose key does not match `joined` are skipped.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `joined` was created from a different [`World`](crate::world::World).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// #[derive(Component)]
+    /// struct Name(String);
+    ///
+    /// fn system(targets: Query<&Target>, names: Query<&Name>) {
+    ///     for (_target, name) in targets.iter_join(&names, |target| target.0) {
+    ///         println!("Targeting {}", name.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_join_mut`](Self::iter_join_mut) to get mutable query items.
+    #[inline]
+    #[track_caller]
+    pub fn iter_join<'a, J: QueryData, JF: QueryFilter, K>(
+        &'a self,
+        joined: &'a Query<'_, '_, J, JF>,
+        key: K,
+    ) -> QueryJoinIter<'a, 'a, D::ReadOnly, F, J::ReadOnly, JF, K>
+    where
+        K: FnMut(&ROQueryItem<'a, D>) -> Entity,
+    {
+        joined.state.validate_world(self.world.id());
+        // SAFETY:
+        // - `self.world` has permission to access the components of both queries,
+        //   and `joined` was validated to come from the same world.
+        // - Both queries are read-only, so they can be aliased even if they were originally mutable.
+        unsafe {
+            self.state.as_readonly().iter_join_unchecked_manual(
+                joined.state.as_readonly(),
+                key,
+                self.world,
+                self.last_run,
+                self.this_run,
+            )
+        }
+    }
+
+    /// Returns an iterator over the query items joined with the items of another query.
+    ///
+    /// For each query item, `key` returns the [`Entity`] to look up in `joined`.
+    /// Items whose key does not match `joined` are skipped.
+    ///
+    /// If the joined items are mutable, the returned [`QueryJoinIter`] must be traversed with
+    /// [`QueryJoinIter::fetch_next`], since several query items may share the same key.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `joined` was created from a different [`World`](crate::world::World),
+    /// or if the accesses of the two queries conflict.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn system(mut targets: Query<&Target>, mut healths: Query<&mut Health>) {
+    ///     let mut iter = targets.iter_join_mut(&mut healths, |target| target.0);
+    ///     while let Some((_target, mut health)) = iter.fetch_next() {
+    ///         health.0 = health.0.saturating_sub(1);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn iter_join_mut<'a, J: QueryData, JF: QueryFilter, K>(
+        &'a mut self,
+        joined: &'a mut Query<'_, '_, J, JF>,
+        key: K,
+    ) -> QueryJoinIter<'a, 'a, D, F, J, JF, K>
+    where
+        K: FnMut(&D::Item<'a>) -> Entity,
+    {
+        joined.state.validate_world(self.world.id());
+        self.state
+            .validate_join(joined.state, self.world.components());
+        // SAFETY:
+        // - `self.world` has permission to access the components of both queries,
+        //   and `joined` was validated to come from the same world.
+        // - The accesses of both queries were checked to be compatible.
+        unsafe {
+            self.state.iter_join_unchecked_manual(
+                joined.state,
+                key,
+                self.world,
+                self.last_run,
+                self.this_run,
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query items.
     ///
     /// # Safety
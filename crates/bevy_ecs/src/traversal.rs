@@ -0,0 +1,36 @@
+//! A trait for traversing entity relationships, used to propagate triggered [`Event`](crate::event::Event)s.
+
+use crate::{entity::Entity, world::World};
+
+/// A way of walking from one [`Entity`] to the next, such as from a child to its parent.
+///
+/// Triggered events that target an entity use their [`Event::Traversal`](crate::event::Event::Traversal)
+/// to find the next entity to propagate to, until the traversal returns `None` or an observer stops the propagation.
+///
+/// Implementations should always eventually return `None`, otherwise propagation will never end.
+pub trait Traversal: 'static {
+    /// Returns the next entity to visit after `entity`, if any.
+    fn traverse(world: &World, entity: Entity) -> Option<Entity>;
+}
+
+impl Traversal for () {
+    fn traverse(_world: &World, _entity: Entity) -> Option<Entity> {
+        None
+    }
+}
+
+/// The [`Traversal`] used by events that do not specify one.
+///
+/// This defers to the traversal registered with [`World::set_default_traversal`], and does not
+/// propagate if none has been registered. `bevy_hierarchy` registers the traversal from an entity
+/// to its parent.
+pub struct DefaultTraversal;
+
+impl Traversal for DefaultTraversal {
+    fn traverse(world: &World, entity: Entity) -> Option<Entity> {
+        world
+            .observers
+            .default_traversal
+            .and_then(|traverse| traverse(world, entity))
+    }
+}
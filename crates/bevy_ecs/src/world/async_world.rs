@@ -0,0 +1,597 @@
+use std::{
+    future::{pending, Future},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+#[cfg(not(feature = "multi-threaded"))]
+use std::{
+    sync::atomic::AtomicBool,
+    task::{Context, Wake, Waker},
+};
+
+use async_channel::{Receiver, Sender};
+#[cfg(not(feature = "multi-threaded"))]
+use bevy_utils::synccell::SyncCell;
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{
+    self as bevy_ecs,
+    entity::Entity,
+    event::{Event, Events},
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    system::{CommandQueue, Res, Resource, SystemParam},
+    world::{Mut, World},
+};
+
+type BoxedFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Commands sent by an [`AsyncWorld`], to be applied when [`AsyncTasks::apply`] runs for
+/// `schedule`, or for any schedule if it is [`None`].
+struct Request {
+    /// The id of the [`Task`] that sent the commands, which are dropped if it is cancelled.
+    task: Option<u64>,
+    schedule: Option<InternedScheduleLabel>,
+    queue: CommandQueue,
+}
+
+/// A task spawned by an [`AsyncWorld`], cancelled if `owner` is despawned.
+///
+/// With the `multi-threaded` feature, the task runs on the
+/// [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool) and is cancelled by dropping it.
+/// Otherwise, as the single-threaded task pool can't keep tasks alive across frames, it is
+/// polled by [`AsyncTasks::apply`] whenever it is woken up.
+struct Task {
+    id: u64,
+    owner: Option<Entity>,
+    #[cfg(feature = "multi-threaded")]
+    task: bevy_tasks::Task<()>,
+    #[cfg(not(feature = "multi-threaded"))]
+    future: SyncCell<BoxedFuture>,
+    #[cfg(not(feature = "multi-threaded"))]
+    waker: Arc<TaskWaker>,
+}
+
+impl Task {
+    fn spawn(id: u64, owner: Option<Entity>, future: BoxedFuture) -> Self {
+        #[cfg(feature = "multi-threaded")]
+        {
+            let pool = bevy_tasks::AsyncComputeTaskPool::get_or_init(bevy_tasks::TaskPool::default);
+            Self {
+                id,
+                owner,
+                task: pool.spawn(future),
+            }
+        }
+        #[cfg(not(feature = "multi-threaded"))]
+        Self {
+            id,
+            owner,
+            future: SyncCell::new(future),
+            waker: Arc::new(TaskWaker(AtomicBool::new(true))),
+        }
+    }
+
+    /// Polls the task if it needs to, and returns `true` if it hasn't completed.
+    fn is_running(&mut self) -> bool {
+        #[cfg(feature = "multi-threaded")]
+        {
+            !self.task.is_finished()
+        }
+        #[cfg(not(feature = "multi-threaded"))]
+        {
+            if !self.waker.0.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+            let waker = Waker::from(self.waker.clone());
+            let mut context = Context::from_waker(&waker);
+            self.future.get().as_mut().poll(&mut context).is_pending()
+        }
+    }
+
+    /// Cancels the task, and returns its id once it is guaranteed not to send any more requests.
+    fn cancel(self) -> u64 {
+        // The future may be running on another thread, wait for it to be dropped
+        #[cfg(feature = "multi-threaded")]
+        bevy_tasks::block_on(self.task.cancel());
+        self.id
+    }
+}
+
+/// Flags a [`Task`] to be polled the next time [`AsyncTasks::apply`] runs.
+#[cfg(not(feature = "multi-threaded"))]
+struct TaskWaker(AtomicBool);
+
+#[cfg(not(feature = "multi-threaded"))]
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// A handle that async tasks use to access the [`World`], obtained from [`AsyncTasks`] or
+/// [`AsyncCommands`].
+///
+/// Each access sends a command to the world and resolves once the command has been applied by
+/// [`AsyncTasks::apply`], so tasks can `.await` the world between steps instead of being polled
+/// by a system every frame. If the [`AsyncTasks`] resource is removed or the world is dropped
+/// before the command is applied, the access never resolves.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::{AsyncTasks, AsyncWorld};
+/// # use bevy_ecs::system::RunSystemOnce;
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// #[derive(Event, Clone)]
+/// struct Defeated;
+///
+/// async fn wave(world: AsyncWorld) {
+///     let enemy = world.run(|world| world.spawn(Enemy).id()).await;
+///     world.next_event::<Defeated>().await;
+///     world.run(move |world| world.despawn(enemy)).await;
+/// }
+///
+/// fn start_wave(tasks: AsyncCommands) {
+///     tasks.spawn(wave);
+/// }
+/// #
+/// # #[derive(bevy_ecs::schedule::ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// # struct Update;
+/// # let mut world = World::new();
+/// # world.init_resource::<AsyncTasks>();
+/// # world.init_resource::<Events<Defeated>>();
+/// # world.run_system_once(start_wave);
+/// # while world.query::<&Enemy>().iter(&world).count() == 0 {
+/// #     AsyncTasks::apply(&mut world, Update);
+/// # }
+/// # while world.query::<&Enemy>().iter(&world).count() == 1 {
+/// #     world.send_event(Defeated);
+/// #     AsyncTasks::apply(&mut world, Update);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncWorld {
+    requests: Sender<Request>,
+    tasks: Sender<Task>,
+    next_task_id: Arc<AtomicU64>,
+    /// The id of the task this handle was given to, if any.
+    task: Option<u64>,
+}
+
+impl AsyncWorld {
+    /// Runs `f` against the world the next time [`AsyncTasks::apply`] runs, whatever the schedule,
+    /// and returns its result.
+    pub async fn run<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut World) -> R + Send + 'static,
+    ) -> R {
+        self.send(None, f).await
+    }
+
+    /// Runs `f` against the world the next time [`AsyncTasks::apply`] runs for the schedule
+    /// labelled `schedule`, and returns its result.
+    pub async fn run_in<R: Send + 'static>(
+        &self,
+        schedule: impl ScheduleLabel,
+        f: impl FnOnce(&mut World) -> R + Send + 'static,
+    ) -> R {
+        self.send(Some(schedule.intern()), f).await
+    }
+
+    /// Applies `queue` to the world the next time [`AsyncTasks::apply`] runs, whatever the
+    /// schedule.
+    pub async fn apply(&self, mut queue: CommandQueue) {
+        self.send(None, move |world| queue.apply(world)).await;
+    }
+
+    /// Waits for the next event of type `E` sent after this method is called, and returns a clone
+    /// of it.
+    ///
+    /// The [`Events<E>`] are read each time [`AsyncTasks::apply`] runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Events<E>`] resource doesn't exist.
+    pub async fn next_event<E: Event + Clone>(&self) -> E {
+        let mut reader = self
+            .run(|world| world.resource::<Events<E>>().get_reader_current())
+            .await;
+        loop {
+            let (event, returned) = self
+                .run(move |world| {
+                    let event = reader.read(world.resource::<Events<E>>()).next().cloned();
+                    (event, reader)
+                })
+                .await;
+            if let Some(event) = event {
+                return event;
+            }
+            reader = returned;
+        }
+    }
+
+    /// Spawns a new task, see [`AsyncCommands::spawn`].
+    pub fn spawn_task<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        self.spawn_inner(None, task);
+    }
+
+    /// Spawns a new task owned by `owner`, see [`AsyncCommands::spawn_owned`].
+    pub fn spawn_owned_task<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        owner: Entity,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        self.spawn_inner(Some(owner), task);
+    }
+
+    fn spawn_inner<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        owner: Option<Entity>,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        let id = self.next_task_id.fetch_add(1, Ordering::Relaxed);
+        let world = AsyncWorld {
+            task: Some(id),
+            ..self.clone()
+        };
+        let task = Task::spawn(id, owner, Box::pin(task(world)));
+        // The receiver only disconnects when `AsyncTasks` is dropped, and the task with it
+        let _ = self.tasks.try_send(task);
+    }
+
+    async fn send<R: Send + 'static>(
+        &self,
+        schedule: Option<InternedScheduleLabel>,
+        f: impl FnOnce(&mut World) -> R + Send + 'static,
+    ) -> R {
+        let (sender, receiver) = async_channel::bounded(1);
+        let mut queue = CommandQueue::default();
+        queue.push(move |world: &mut World| {
+            let _ = sender.try_send(f(world));
+        });
+        if self
+            .requests
+            .send(Request {
+                task: self.task,
+                schedule,
+                queue,
+            })
+            .await
+            .is_ok()
+        {
+            if let Ok(result) = receiver.recv().await {
+                return result;
+            }
+        }
+        pending().await
+    }
+}
+
+/// Resource that runs async tasks accessing the [`World`] through an [`AsyncWorld`].
+///
+/// Tasks are spawned with [`AsyncCommands`], [`AsyncTasks::spawn`] or [`AsyncWorld::spawn_task`]
+/// on the [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool). Their accesses to the world
+/// are applied by [`AsyncTasks::apply`], which should run at the points of the schedules where
+/// tasks may access the world.
+///
+/// Without the `multi-threaded` feature, tasks are instead polled on the thread calling
+/// [`AsyncTasks::apply`].
+///
+/// A task spawned with an owner entity is cancelled once that entity is despawned, along with the
+/// accesses to the world it sent that weren't applied yet.
+#[derive(Resource)]
+pub struct AsyncTasks {
+    world: AsyncWorld,
+    requests: Receiver<Request>,
+    new_tasks: Receiver<Task>,
+    pending: HashMap<Option<InternedScheduleLabel>, Vec<Request>>,
+    tasks: Vec<Task>,
+}
+
+impl Default for AsyncTasks {
+    fn default() -> Self {
+        let (request_sender, requests) = async_channel::unbounded();
+        let (task_sender, new_tasks) = async_channel::unbounded();
+        Self {
+            world: AsyncWorld {
+                requests: request_sender,
+                tasks: task_sender,
+                next_task_id: Arc::new(AtomicU64::new(0)),
+                task: None,
+            },
+            requests,
+            new_tasks,
+            pending: HashMap::default(),
+            tasks: Vec::new(),
+        }
+    }
+}
+
+impl AsyncTasks {
+    /// Returns an [`AsyncWorld`] that accesses the world holding this resource.
+    pub fn world(&self) -> AsyncWorld {
+        self.world.clone()
+    }
+
+    /// Spawns a new task, see [`AsyncCommands::spawn`].
+    pub fn spawn<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        self.world.spawn_task(task);
+    }
+
+    /// Spawns a new task owned by `owner`, see [`AsyncCommands::spawn_owned`].
+    pub fn spawn_owned<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        owner: Entity,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        self.world.spawn_owned_task(owner, task);
+    }
+
+    /// Returns the number of tasks that haven't completed or been cancelled yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len() + self.new_tasks.len()
+    }
+
+    /// Returns `true` if all tasks have completed or been cancelled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if no task is running and no access to the world is waiting to be applied,
+    /// in which case [`AsyncTasks::apply`] has nothing to do.
+    pub fn is_idle(&self) -> bool {
+        self.is_empty() && self.requests.is_empty() && self.pending.is_empty()
+    }
+
+    /// Drives the tasks of the [`AsyncTasks`] resource of `world`, if it exists.
+    ///
+    /// This cancels the tasks whose owner was despawned, dropping the commands they sent, then
+    /// applies the commands sent by
+    /// [`AsyncWorld::run`] and those sent by [`AsyncWorld::run_in`] for `schedule`. Commands sent
+    /// for other schedules are kept until this is called for their schedule.
+    ///
+    /// Without the `multi-threaded` feature, this also polls the tasks that were woken up since
+    /// the last call.
+    pub fn apply(world: &mut World, schedule: impl ScheduleLabel) {
+        if !world.contains_resource::<Self>() {
+            return;
+        }
+        let schedule = schedule.intern();
+        let requests = world.resource_scope(|world, mut tasks: Mut<Self>| {
+            let tasks = &mut *tasks;
+            tasks
+                .tasks
+                .extend(std::iter::from_fn(|| tasks.new_tasks.try_recv().ok()));
+
+            let entities = world.entities();
+            let mut cancelled = HashSet::new();
+            for mut task in std::mem::take(&mut tasks.tasks) {
+                let owner_exists = match task.owner {
+                    Some(owner) => entities.contains(owner),
+                    None => true,
+                };
+                if !owner_exists {
+                    cancelled.insert(task.cancel());
+                } else if task.is_running() {
+                    tasks.tasks.push(task);
+                }
+            }
+
+            while let Ok(request) = tasks.requests.try_recv() {
+                tasks
+                    .pending
+                    .entry(request.schedule)
+                    .or_default()
+                    .push(request);
+            }
+            if !cancelled.is_empty() {
+                tasks.pending.retain(|_, requests| {
+                    requests.retain(|request| {
+                        !request.task.is_some_and(|task| cancelled.contains(&task))
+                    });
+                    !requests.is_empty()
+                });
+            }
+            let mut requests = tasks.pending.remove(&None).unwrap_or_default();
+            requests.extend(tasks.pending.remove(&Some(schedule)).unwrap_or_default());
+            requests
+        });
+
+        for mut request in requests {
+            request.queue.apply(world);
+        }
+    }
+}
+
+/// A [`SystemParam`] to spawn async tasks that access the [`World`] through an [`AsyncWorld`].
+///
+/// Requires the [`AsyncTasks`] resource, see its documentation for how tasks are run.
+#[derive(SystemParam)]
+pub struct AsyncCommands<'w> {
+    tasks: Res<'w, AsyncTasks>,
+}
+
+impl AsyncCommands<'_> {
+    /// Spawns the task returned by `task`, which is given an [`AsyncWorld`] to access the world.
+    ///
+    /// The task starts running the next time [`AsyncTasks::apply`] runs.
+    pub fn spawn<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        self.tasks.spawn(task);
+    }
+
+    /// Spawns the task returned by `task` like [`AsyncCommands::spawn`], cancelling it once
+    /// `owner` is despawned.
+    pub fn spawn_owned<F: Future<Output = ()> + Send + 'static>(
+        &self,
+        owner: Entity,
+        task: impl FnOnce(AsyncWorld) -> F,
+    ) {
+        self.tasks.spawn_owned(owner, task);
+    }
+
+    /// Returns an [`AsyncWorld`] that accesses the world.
+    pub fn world(&self) -> AsyncWorld {
+        self.tasks.world()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, schedule::ScheduleLabel, system::RunSystemOnce};
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct First;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Last;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    #[derive(Component)]
+    struct Owner;
+
+    #[derive(Event, Clone, PartialEq, Debug)]
+    struct Loaded(u32);
+
+    fn log(world: &mut World, entry: &'static str) {
+        world.resource_mut::<Log>().0.push(entry);
+    }
+
+    fn frame(world: &mut World) {
+        AsyncTasks::apply(world, First);
+        log(world, "frame");
+        AsyncTasks::apply(world, Last);
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<AsyncTasks>();
+        world.init_resource::<Log>();
+        world.init_resource::<Events<Loaded>>();
+        world
+    }
+
+    /// Runs frames until `done` returns `true`, giving tasks on other threads time to progress.
+    fn run_until(world: &mut World, mut done: impl FnMut(&mut World) -> bool) {
+        for _ in 0..1000 {
+            if done(world) {
+                return;
+            }
+            frame(world);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("the tasks didn't progress");
+    }
+
+    #[test]
+    fn steps_run_at_their_schedule() {
+        let mut world = setup();
+        world.run_system_once(|tasks: AsyncCommands| {
+            tasks.spawn(|world| async move {
+                world.run_in(Last, |world| log(world, "last")).await;
+                world.run_in(First, |world| log(world, "first")).await;
+                let answer = world.run(|_| 42).await;
+                assert_eq!(answer, 42);
+                world.run(|world| log(world, "done")).await;
+            });
+        });
+
+        run_until(&mut world, |world| world.resource::<AsyncTasks>().is_idle());
+        let log = &world.resource::<Log>().0;
+        let steps: Vec<_> = log.iter().filter(|entry| **entry != "frame").collect();
+        assert_eq!(steps, [&"last", &"first", &"done"]);
+        let last = log.iter().position(|entry| *entry == "last").unwrap();
+        let first = log.iter().position(|entry| *entry == "first").unwrap();
+        assert_eq!(log[last - 1], "frame");
+        assert_eq!(log[first + 1], "frame");
+    }
+
+    #[test]
+    fn waits_for_events() {
+        let mut world = setup();
+        world.resource::<AsyncTasks>().spawn(|world| async move {
+            let event = world.next_event::<Loaded>().await;
+            world
+                .run(move |world| world.insert_resource(Log(vec!["loaded"; event.0 as usize])))
+                .await;
+        });
+
+        frame(&mut world);
+        frame(&mut world);
+        assert!(!world.resource::<AsyncTasks>().is_empty());
+        assert!(!world.resource::<Log>().0.contains(&"loaded"));
+
+        // Events sent before the task starts waiting are missed, so keep sending them
+        run_until(&mut world, |world| {
+            world.send_event(Loaded(2));
+            world.resource::<Log>().0.contains(&"loaded")
+        });
+        let log = &world.resource::<Log>().0;
+        let loaded: Vec<_> = log.iter().filter(|entry| **entry != "frame").collect();
+        assert_eq!(loaded, [&"loaded", &"loaded"]);
+        run_until(&mut world, |world| {
+            world.resource::<AsyncTasks>().is_empty()
+        });
+    }
+
+    #[test]
+    fn owned_tasks_are_cancelled_on_despawn() {
+        let mut world = setup();
+        let owner = world.spawn(Owner).id();
+        let tasks = world.resource::<AsyncTasks>();
+        for (owner, entry) in [(Some(owner), "owned"), (None, "free")] {
+            let spawn = move |world: AsyncWorld| async move {
+                loop {
+                    world
+                        .run(move |world| {
+                            if let Some(owner) = owner {
+                                world.entity_mut(owner);
+                            }
+                            log(world, entry);
+                        })
+                        .await;
+                }
+            };
+            match owner {
+                Some(owner) => tasks.spawn_owned(owner, spawn),
+                None => tasks.world().spawn_task(spawn),
+            }
+        }
+
+        run_until(&mut world, |world| {
+            let log = &world.resource::<Log>().0;
+            log.contains(&"owned") && log.contains(&"free")
+        });
+        assert_eq!(world.resource::<AsyncTasks>().len(), 2);
+
+        // steps sent by the owned task before it is cancelled are dropped with it
+        world.despawn(owner);
+        world.resource_mut::<Log>().0.clear();
+        frame(&mut world);
+        assert_eq!(world.resource::<AsyncTasks>().len(), 1);
+        run_until(&mut world, |world| {
+            let log = &world.resource::<Log>().0;
+            log.iter().filter(|entry| **entry == "free").count() >= 3
+        });
+        assert!(!world.resource::<Log>().0.contains(&"owned"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Internal components used by bevy with a fixed component id.
+//! Constants are used to skip [`TypeId`](std::any::TypeId) lookups in hot paths.
+
+use crate::{self as bevy_ecs, component::ComponentId, event::Event};
+
+/// [`ComponentId`] for [`OnAdd`]
+pub const ON_ADD: ComponentId = ComponentId::new(0);
+/// [`ComponentId`] for [`OnInsert`]
+pub const ON_INSERT: ComponentId = ComponentId::new(1);
+/// [`ComponentId`] for [`OnRemove`]
+pub const ON_REMOVE: ComponentId = ComponentId::new(2);
+
+/// Trigger emitted when a component is added to an entity.
+///
+/// Observers of this event run after the component's `on_add` hook.
+#[derive(Event)]
+#[event(traversal = ())]
+pub struct OnAdd;
+
+/// Trigger emitted when a component is inserted onto an entity, whether or not it was already present.
+///
+/// Observers of this event run after the component's `on_insert` hook.
+#[derive(Event)]
+#[event(traversal = ())]
+pub struct OnInsert;
+
+/// Trigger emitted when a component is removed from an entity, or the entity is despawned.
+///
+/// Observers of this event run after the component's `on_remove` hook, while the component is still present.
+#[derive(Event)]
+#[event(traversal = ())]
+pub struct OnRemove;
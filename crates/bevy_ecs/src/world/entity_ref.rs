@@ -1,13 +1,16 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
-    entity::{Entities, Entity, EntityLocation},
+    entity::{Entities, Entity, EntityCloner, EntityLocation},
+    event::Event,
+    observer::{Observer, Observers},
     query::{Access, DebugCheckedUnwrap},
     removal_detection::RemovedComponentEvents,
     storage::Storages,
-    world::{Mut, World},
+    system::IntoObserverSystem,
+    world::{Mut, World, ON_REMOVE},
 };
 use bevy_ptr::{OwningPtr, Ptr};
 use bevy_utils::tracing::debug;
@@ -392,7 +395,19 @@ pub struct EntityWorldMut<'w> {
 }
 
 impl<'w> EntityWorldMut<'w> {
+    /// Panics if a command queued by a component hook has despawned this entity.
+    #[track_caller]
+    fn assert_not_despawned(&self) {
+        if self.location.archetype_id == ArchetypeId::INVALID {
+            panic!(
+                "Entity {:?} does not exist, it was despawned by a command queued from a component hook",
+                self.entity
+            );
+        }
+    }
+
     fn as_unsafe_entity_cell_readonly(&self) -> UnsafeEntityCell<'_> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell_readonly(),
             self.entity,
@@ -400,6 +415,7 @@ impl<'w> EntityWorldMut<'w> {
         )
     }
     fn as_unsafe_entity_cell(&mut self) -> UnsafeEntityCell<'_> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell(),
             self.entity,
@@ -407,6 +423,7 @@ impl<'w> EntityWorldMut<'w> {
         )
     }
     fn into_unsafe_entity_cell(self) -> UnsafeEntityCell<'w> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell(),
             self.entity,
@@ -446,12 +463,14 @@ impl<'w> EntityWorldMut<'w> {
     /// Gets metadata indicating the location where the current entity is stored.
     #[inline]
     pub fn location(&self) -> EntityLocation {
+        self.assert_not_despawned();
         self.location
     }
 
     /// Returns the archetype that the current entity belongs to.
     #[inline]
     pub fn archetype(&self) -> &Archetype {
+        self.assert_not_despawned();
         &self.world.archetypes[self.location.archetype_id]
     }
 
@@ -569,24 +588,16 @@ impl<'w> EntityWorldMut<'w> {
     ///
     /// This will overwrite any previous value(s) of the same component type.
     pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
-        let bundle_info = self
-            .world
-            .bundles
-            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
-        let mut bundle_inserter = bundle_info.get_bundle_inserter(
-            &mut self.world.entities,
-            &mut self.world.archetypes,
-            &self.world.components,
-            &mut self.world.storages,
-            self.location.archetype_id,
-            change_tick,
-        );
+        let mut bundle_inserter =
+            BundleInserter::new::<T>(self.world, self.location.archetype_id, change_tick);
         // SAFETY: location matches current entity. `T` matches `bundle_info`
         unsafe {
             self.location = bundle_inserter.insert(self.entity, self.location, bundle);
         }
-
+        self.world.flush_commands();
+        self.update_location();
         self
     }
 
@@ -605,18 +616,18 @@ impl<'w> EntityWorldMut<'w> {
         component_id: ComponentId,
         component: OwningPtr<'_>,
     ) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
+        let bundle_id = self
+            .world
+            .bundles
+            .init_component_info(&self.world.components, component_id);
+        let storage_type = self.world.bundles.get_storage_unchecked(bundle_id);
 
-        let bundles = &mut self.world.bundles;
-        let components = &mut self.world.components;
-
-        let (bundle_info, storage_type) = bundles.init_component_info(components, component_id);
-        let bundle_inserter = bundle_info.get_bundle_inserter(
-            &mut self.world.entities,
-            &mut self.world.archetypes,
-            &self.world.components,
-            &mut self.world.storages,
+        let bundle_inserter = BundleInserter::new_with_id(
+            self.world,
             self.location.archetype_id,
+            bundle_id,
             change_tick,
         );
 
@@ -625,9 +636,10 @@ impl<'w> EntityWorldMut<'w> {
             self.entity,
             self.location,
             Some(component).into_iter(),
-            Some(storage_type).into_iter(),
+            Some(storage_type).iter().cloned(),
         );
-
+        self.world.flush_commands();
+        self.update_location();
         self
     }
 
@@ -648,18 +660,18 @@ impl<'w> EntityWorldMut<'w> {
         component_ids: &[ComponentId],
         iter_components: I,
     ) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
-
-        let bundles = &mut self.world.bundles;
-        let components = &mut self.world.components;
-
-        let (bundle_info, storage_types) = bundles.init_dynamic_info(components, component_ids);
-        let bundle_inserter = bundle_info.get_bundle_inserter(
-            &mut self.world.entities,
-            &mut self.world.archetypes,
-            &self.world.components,
-            &mut self.world.storages,
+        let bundle_id = self
+            .world
+            .bundles
+            .init_dynamic_info(&self.world.components, component_ids);
+        let mut storage_types =
+            std::mem::take(self.world.bundles.get_storages_unchecked(bundle_id));
+        let bundle_inserter = BundleInserter::new_with_id(
+            self.world,
             self.location.archetype_id,
+            bundle_id,
             change_tick,
         );
 
@@ -668,9 +680,11 @@ impl<'w> EntityWorldMut<'w> {
             self.entity,
             self.location,
             iter_components,
-            storage_types.iter().cloned(),
+            (*storage_types).iter().cloned(),
         );
-
+        *self.world.bundles.get_storages_unchecked(bundle_id) = std::mem::take(&mut storage_types);
+        self.world.flush_commands();
+        self.update_location();
         self
     }
 
@@ -681,21 +695,22 @@ impl<'w> EntityWorldMut<'w> {
     // TODO: BundleRemover?
     #[must_use]
     pub fn take<T: Bundle>(&mut self) -> Option<T> {
-        let archetypes = &mut self.world.archetypes;
-        let storages = &mut self.world.storages;
-        let components = &mut self.world.components;
-        let entities = &mut self.world.entities;
-        let removed_components = &mut self.world.removed_components;
-
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        self.assert_not_despawned();
+        let world = &mut self.world;
+        let storages = &mut world.storages;
+        let components = &mut world.components;
+        let bundle_id = world.bundles.init_info::<T>(components, storages);
+        // SAFETY: We just ensured this bundle exists
+        let bundle_info = unsafe { world.bundles.get_unchecked(bundle_id) };
         let old_location = self.location;
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
         // components exist in `bundle_info` because `Bundles::init_info` initializes a `BundleInfo` containing all components of the bundle type `T`
         let new_archetype_id = unsafe {
             remove_bundle_from_archetype(
-                archetypes,
+                &mut world.archetypes,
                 storages,
                 components,
+                &world.observers,
                 old_location.archetype_id,
                 bundle_info,
                 false,
@@ -706,8 +721,19 @@ impl<'w> EntityWorldMut<'w> {
             return None;
         }
 
-        let mut bundle_components = bundle_info.components().iter().cloned();
         let entity = self.entity;
+        // SAFETY: `bundle_id` was initialized above and `old_location` is the entity's current location
+        unsafe { trigger_on_remove_hooks(world, bundle_id, entity, old_location) };
+
+        let archetypes = &mut world.archetypes;
+        let storages = &mut world.storages;
+        let components = &mut world.components;
+        let entities = &mut world.entities;
+        let removed_components = &mut world.removed_components;
+        // SAFETY: We initialized this bundle above
+        let bundle_info = unsafe { world.bundles.get_unchecked(bundle_id) };
+
+        let mut bundle_components = bundle_info.components().iter().cloned();
         // SAFETY: bundle components are iterated in order, which guarantees that the component type
         // matches
         let result = unsafe {
@@ -741,7 +767,8 @@ impl<'w> EntityWorldMut<'w> {
                 new_archetype_id,
             );
         }
-
+        self.world.flush_commands();
+        self.update_location();
         Some(result)
     }
 
@@ -839,6 +866,7 @@ impl<'w> EntityWorldMut<'w> {
         archetypes: &mut Archetypes,
         storages: &mut Storages,
         components: &Components,
+        observers: &Observers,
         entities: &mut Entities,
         removed_components: &mut RemovedComponentEvents,
     ) {
@@ -848,6 +876,7 @@ impl<'w> EntityWorldMut<'w> {
             archetypes,
             storages,
             components,
+            observers,
             old_location.archetype_id,
             bundle_info,
             true,
@@ -894,15 +923,18 @@ impl<'w> EntityWorldMut<'w> {
     /// See [`EntityCommands::remove`](crate::system::EntityCommands::remove) for more details.
     // TODO: BundleRemover?
     pub fn remove<T: Bundle>(&mut self) -> &mut Self {
-        let archetypes = &mut self.world.archetypes;
-        let storages = &mut self.world.storages;
-        let components = &mut self.world.components;
-        let entities = &mut self.world.entities;
-        let removed_components = &mut self.world.removed_components;
-
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        self.assert_not_despawned();
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
         let old_location = self.location;
 
+        // SAFETY: `bundle_id` was initialized above and `old_location` is the entity's current location
+        unsafe { trigger_on_remove_hooks(self.world, bundle_id, self.entity, old_location) };
+
+        // SAFETY: We initialized this bundle above
+        let bundle_info = unsafe { self.world.bundles.get_unchecked(bundle_id) };
         // SAFETY: Components exist in `bundle_info` because `Bundles::init_info`
         // initializes a `BundleInfo` containing all components of the bundle type `T`.
         unsafe {
@@ -911,14 +943,16 @@ impl<'w> EntityWorldMut<'w> {
                 &mut self.location,
                 old_location,
                 bundle_info,
-                archetypes,
-                storages,
-                components,
-                entities,
-                removed_components,
+                &mut self.world.archetypes,
+                &mut self.world.storages,
+                &self.world.components,
+                &self.world.observers,
+                &mut self.world.entities,
+                &mut self.world.removed_components,
             );
         }
-
+        self.world.flush_commands();
+        self.update_location();
         self
     }
 
@@ -926,13 +960,14 @@ impl<'w> EntityWorldMut<'w> {
     ///
     /// See [`EntityCommands::retain`](crate::system::EntityCommands::retain) for more details.
     pub fn retain<T: Bundle>(&mut self) -> &mut Self {
+        self.assert_not_despawned();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
-        let entities = &mut self.world.entities;
-        let removed_components = &mut self.world.removed_components;
 
-        let retained_bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        let retained_bundle = self.world.bundles.init_info::<T>(components, storages);
+        // SAFETY: We just ensured this bundle exists
+        let retained_bundle_info = unsafe { self.world.bundles.get_unchecked(retained_bundle) };
         let old_location = self.location;
         let old_archetype = &mut archetypes[old_location.archetype_id];
 
@@ -940,12 +975,13 @@ impl<'w> EntityWorldMut<'w> {
             .components()
             .filter(|c| !retained_bundle_info.components().contains(c))
             .collect::<Vec<_>>();
-        let remove_bundle_info = self
-            .world
-            .bundles
-            .init_dynamic_info(components, to_remove)
-            .0;
+        let remove_bundle = self.world.bundles.init_dynamic_info(components, to_remove);
 
+        // SAFETY: `remove_bundle` was initialized above and `old_location` is the entity's current location
+        unsafe { trigger_on_remove_hooks(self.world, remove_bundle, self.entity, old_location) };
+
+        // SAFETY: We initialized this bundle above
+        let remove_bundle_info = unsafe { self.world.bundles.get_unchecked(remove_bundle) };
         // SAFETY: Components exist in `remove_bundle_info` because `Bundles::init_dynamic_info`
         // initializes a `BundleInfo` containing all components in the to_remove Bundle.
         unsafe {
@@ -954,23 +990,84 @@ impl<'w> EntityWorldMut<'w> {
                 &mut self.location,
                 old_location,
                 remove_bundle_info,
-                archetypes,
-                storages,
-                components,
-                entities,
-                removed_components,
+                &mut self.world.archetypes,
+                &mut self.world.storages,
+                &self.world.components,
+                &self.world.observers,
+                &mut self.world.entities,
+                &mut self.world.removed_components,
             );
         }
-
+        self.world.flush_commands();
+        self.update_location();
         self
     }
 
+    /// Clones the current entity and its components into a new entity, and returns its id.
+    ///
+    /// Components are cloned according to their [`ComponentCloneBehavior`](crate::component::ComponentCloneBehavior).
+    /// See [`EntityCloner`] for more details.
+    pub fn clone_entity(&mut self) -> Entity {
+        self.clone_entity_with(&EntityCloner::default())
+    }
+
+    /// Clones the current entity into a new entity using `cloner`, and returns its id.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::entity::EntityCloner;
+    /// #[derive(Component, Clone)]
+    /// #[relationship(relationship_target = Parts)]
+    /// struct PartOf(Entity);
+    ///
+    /// #[derive(Component)]
+    /// #[relationship_target(relationship = PartOf, clone_recursive)]
+    /// struct Parts(Vec<Entity>);
+    ///
+    /// let mut world = World::new();
+    /// let car = world.spawn_empty().id();
+    /// world.spawn(PartOf(car));
+    ///
+    /// // the parts of the car are cloned along with it
+    /// let clone = world
+    ///     .entity_mut(car)
+    ///     .clone_entity_with(&EntityCloner::default().recursive(true));
+    /// assert_eq!(world.get::<Parts>(clone).unwrap().0.len(), 1);
+    /// ```
+    pub fn clone_entity_with(&mut self, cloner: &EntityCloner) -> Entity {
+        self.assert_not_despawned();
+        let source = self.entity;
+        self.world_scope(|world| cloner.clone_entity(world, source))
+    }
+
     /// Despawns the current entity.
     ///
     /// See [`World::despawn`] for more details.
     pub fn despawn(self) {
+        self.assert_not_despawned();
         debug!("Despawning entity {:?}", self.entity);
         let world = self.world;
+        {
+            let world = world.as_unsafe_world_cell();
+            // SAFETY: `DeferredWorld` cannot make structural changes, so the archetype remains valid
+            let archetype = &world.archetypes()[self.location.archetype_id];
+            // SAFETY: We have exclusive world access and no outstanding references into it
+            let mut deferred_world = unsafe { world.into_deferred() };
+            // SAFETY: All components in the archetype exist in the world
+            unsafe {
+                deferred_world.trigger_on_despawn(archetype, self.entity, archetype.components());
+                deferred_world.trigger_on_replace(archetype, self.entity, archetype.components());
+                deferred_world.trigger_on_remove(archetype, self.entity, archetype.components());
+                if archetype.has_remove_observer() {
+                    deferred_world.trigger_observers(
+                        ON_REMOVE,
+                        self.entity,
+                        archetype.components(),
+                    );
+                }
+            }
+        }
+        // Flush entities reserved by the hooks above as well as any entities reserved before.
         world.flush();
         let location = world
             .entities
@@ -1031,6 +1128,7 @@ impl<'w> EntityWorldMut<'w> {
             world.archetypes[moved_location.archetype_id]
                 .set_entity_table_row(moved_location.archetype_row, table_row);
         }
+        world.flush_commands();
     }
 
     /// Gets read-only access to the world that the current entity belongs to.
@@ -1106,8 +1204,22 @@ impl<'w> EntityWorldMut<'w> {
     ///
     /// This is *only* required when using the unsafe function [`EntityWorldMut::world_mut`],
     /// which enables the location to change.
+    ///
+    /// If the entity has been despawned while this [`EntityWorldMut`] is still alive
+    /// (for example by a command queued from a component hook), any further use
+    /// of this [`EntityWorldMut`] other than [`id`](Self::id) and [`is_despawned`](Self::is_despawned) will panic.
     pub fn update_location(&mut self) {
-        self.location = self.world.entities().get(self.entity).unwrap();
+        self.location = self
+            .world
+            .entities()
+            .get(self.entity)
+            .unwrap_or(EntityLocation::INVALID);
+    }
+
+    /// Returns `true` if this entity has been despawned while this [`EntityWorldMut`] was alive,
+    /// for example by a command queued from a component hook.
+    pub fn is_despawned(&self) -> bool {
+        self.location.archetype_id == ArchetypeId::INVALID
     }
 
     /// Gets an Entry into the world for this entity and component for in-place manipulation.
@@ -1145,6 +1257,20 @@ impl<'w> EntityWorldMut<'w> {
             })
         }
     }
+
+    /// Creates an [`Observer`](crate::observer::Observer) listening for events of type `E` targeting this entity.
+    /// In order to trigger the callback the entity must also match the query when the event is fired.
+    pub fn observe<E: Event, B: Bundle, M>(
+        &mut self,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> &mut Self {
+        self.assert_not_despawned();
+        self.world
+            .spawn(Observer::new(observer).with_entity(self.entity));
+        self.world.flush_commands();
+        self.update_location();
+        self
+    }
 }
 
 /// A view into a single entity and component in a world, which may either be vacant or occupied.
@@ -1848,6 +1974,37 @@ impl<'w> FilteredEntityMut<'w> {
     }
 }
 
+/// Runs the `on_replace` and `on_remove` hooks and [`OnRemove`](crate::world::OnRemove) observers of every component
+/// in the given bundle that `entity` currently has.
+///
+/// # Safety
+/// - `bundle_id` must be a valid id in `world.bundles`
+/// - `location` must be the current location of `entity`
+unsafe fn trigger_on_remove_hooks(
+    world: &mut World,
+    bundle_id: BundleId,
+    entity: Entity,
+    location: EntityLocation,
+) {
+    let world = world.as_unsafe_world_cell();
+    // SAFETY: `DeferredWorld` cannot make structural changes, so the archetype and bundle remain valid
+    let archetype = &world.archetypes()[location.archetype_id];
+    if !archetype.has_on_replace() && !archetype.has_on_remove() && !archetype.has_remove_observer()
+    {
+        return;
+    }
+    let bundle_info = world.bundles().get_unchecked(bundle_id);
+    let removed = bundle_info
+        .iter_components()
+        .filter(|id| archetype.contains(*id));
+    let mut deferred_world = world.into_deferred();
+    deferred_world.trigger_on_replace(archetype, entity, removed.clone());
+    deferred_world.trigger_on_remove(archetype, entity, removed.clone());
+    if archetype.has_remove_observer() {
+        deferred_world.trigger_observers(ON_REMOVE, entity, removed);
+    }
+}
+
 /// Inserts a dynamic [`Bundle`] into the entity.
 ///
 /// # Safety
@@ -1860,7 +2017,7 @@ unsafe fn insert_dynamic_bundle<
     I: Iterator<Item = OwningPtr<'a>>,
     S: Iterator<Item = StorageType>,
 >(
-    mut bundle_inserter: BundleInserter<'_, '_>,
+    mut bundle_inserter: BundleInserter<'_>,
     entity: Entity,
     location: EntityLocation,
     components: I,
@@ -1899,6 +2056,7 @@ unsafe fn remove_bundle_from_archetype(
     archetypes: &mut Archetypes,
     storages: &mut Storages,
     components: &Components,
+    observers: &Observers,
     archetype_id: ArchetypeId,
     bundle_info: &BundleInfo,
     intersection: bool,
@@ -1966,6 +2124,8 @@ unsafe fn remove_bundle_from_archetype(
         }
 
         let new_archetype_id = archetypes.get_id_or_insert(
+            components,
+            observers,
             next_table_id,
             next_table_components,
             next_sparse_set_components,
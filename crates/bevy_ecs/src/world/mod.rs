@@ -1,5 +1,8 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod async_world;
+mod component_constants;
+mod deferred_world;
 mod entity_ref;
 pub mod error;
 mod spawn_batch;
@@ -7,6 +10,9 @@ pub mod unsafe_world_cell;
 mod world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
+pub use async_world::{AsyncCommands, AsyncTasks, AsyncWorld};
+pub use component_constants::*;
+pub use deferred_world::DeferredWorld;
 pub use entity_ref::{
     EntityMut, EntityRef, EntityWorldMut, Entry, FilteredEntityMut, FilteredEntityRef,
     OccupiedEntry, VacantEntry,
@@ -19,16 +25,17 @@ use crate::{
     bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
     change_detection::{MutUntyped, TicksMut},
     component::{
-        Component, ComponentDescriptor, ComponentId, ComponentInfo, ComponentTicks, Components,
-        Tick,
+        Component, ComponentCloneBehavior, ComponentDescriptor, ComponentHooks, ComponentId,
+        ComponentInfo, ComponentTicks, Components, Tick,
     },
     entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
     event::{Event, EventId, Events, SendBatchIds},
+    observer::Observers,
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
     storage::{ResourceData, Storages},
-    system::Resource,
+    system::{CommandQueue, Commands, Resource},
     world::error::TryRunScheduleError,
 };
 use bevy_ptr::{OwningPtr, Ptr};
@@ -71,23 +78,27 @@ pub struct World {
     pub(crate) archetypes: Archetypes,
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
+    pub(crate) observers: Observers,
     pub(crate) removed_components: RemovedComponentEvents,
     /// Access cache used by [`WorldCell`]. Is only accessed in the `Drop` impl of `WorldCell`.
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     pub(crate) change_tick: AtomicU32,
     pub(crate) last_change_tick: Tick,
     pub(crate) last_check_tick: Tick,
+    pub(crate) last_trigger_id: u32,
+    pub(crate) command_queue: CommandQueue,
 }
 
 impl Default for World {
     fn default() -> Self {
-        Self {
+        let mut world = Self {
             id: WorldId::new().expect("More `bevy` `World`s have been created than is supported"),
             entities: Entities::new(),
             components: Default::default(),
             archetypes: Archetypes::new(),
             storages: Default::default(),
             bundles: Default::default(),
+            observers: Observers::default(),
             removed_components: Default::default(),
             archetype_component_access: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
@@ -95,7 +106,11 @@ impl Default for World {
             change_tick: AtomicU32::new(1),
             last_change_tick: Tick::new(0),
             last_check_tick: Tick::new(0),
-        }
+            last_trigger_id: 0,
+            command_queue: CommandQueue::default(),
+        };
+        world.bootstrap();
+        world
     }
 }
 
@@ -112,6 +127,15 @@ impl World {
         World::default()
     }
 
+    /// Initializes components that must be present on every [`World`], such as [`OnAdd`].
+    ///
+    /// These components have fixed ids, see [`ON_ADD`], [`ON_INSERT`] and [`ON_REMOVE`].
+    fn bootstrap(&mut self) {
+        assert_eq!(ON_ADD, self.init_component::<OnAdd>());
+        assert_eq!(ON_INSERT, self.init_component::<OnInsert>());
+        assert_eq!(ON_REMOVE, self.init_component::<OnRemove>());
+    }
+
     /// Retrieves this [`World`]'s unique ID
     #[inline]
     pub fn id(&self) -> WorldId {
@@ -188,6 +212,51 @@ impl World {
         self.components.init_component::<T>(&mut self.storages)
     }
 
+    /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] type.
+    ///
+    /// Will panic if `T` exists in any archetypes.
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let index = self.init_component::<T>();
+        assert!(!self.archetypes.archetypes.iter().any(|a| a.contains(index)), "Components hooks cannot be modified if the component already exists in an archetype, use init_component if {} may already be in use", std::any::type_name::<T>());
+        // SAFETY: We just created this component
+        unsafe { self.components.get_hooks_mut(index).debug_checked_unwrap() }
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] with the given id if it exists.
+    ///
+    /// Will panic if `id` exists in any archetypes.
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        assert!(!self.archetypes.iter().any(|a| a.contains(id)), "Components hooks cannot be modified if the component already exists in an archetype, use init_component if the component with id {:?} may already be in use", id);
+        self.components.get_hooks_mut(id)
+    }
+
+    /// Sets how [`Component`]s of type `T` are cloned when their entity is cloned,
+    /// overriding [`Component::clone_behavior`].
+    pub fn set_component_clone_behavior<T: Component>(&mut self, behavior: ComponentCloneBehavior) {
+        let id = self.init_component::<T>();
+        self.set_component_clone_behavior_by_id(id, behavior);
+    }
+
+    /// Sets how the [`Component`] with the given id is cloned when its entity is cloned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no component with the given id.
+    pub fn set_component_clone_behavior_by_id(
+        &mut self,
+        id: ComponentId,
+        behavior: ComponentCloneBehavior,
+    ) {
+        *self
+            .components
+            .get_clone_behavior_mut(id)
+            .unwrap_or_else(|| panic!("No component with id {id:?} exists in this World")) =
+            behavior;
+    }
+
     /// Initializes a new [`Component`] type and returns the [`ComponentId`] created for it.
     ///
     /// This method differs from [`World::init_component`] in that it uses a [`ComponentDescriptor`]
@@ -745,22 +814,18 @@ impl World {
         self.flush();
         let change_tick = self.change_tick();
         let entity = self.entities.alloc();
-        let entity_location = {
-            let bundle_info = self
-                .bundles
-                .init_info::<B>(&mut self.components, &mut self.storages);
-            let mut spawner = bundle_info.get_bundle_spawner(
-                &mut self.entities,
-                &mut self.archetypes,
-                &self.components,
-                &mut self.storages,
-                change_tick,
-            );
-
+        let mut entity_location = {
+            let mut spawner = BundleSpawner::new::<B>(self, change_tick);
             // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
             unsafe { spawner.spawn_non_existent(entity, bundle) }
         };
 
+        // Hooks may have queued commands, which may in turn have moved the new entity.
+        self.flush_commands();
+        if let Some(location) = self.entities.get(entity) {
+            entity_location = location;
+        }
+
         // SAFETY: entity and location are valid, as they were just created above
         unsafe { EntityWorldMut::new(self, entity, entity_location) }
     }
@@ -1503,32 +1568,38 @@ impl World {
 
         let change_tick = self.change_tick();
 
-        let bundle_info = self
+        let bundle_id = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
-        enum SpawnOrInsert<'a, 'b> {
-            Spawn(BundleSpawner<'a, 'b>),
-            Insert(BundleInserter<'a, 'b>, ArchetypeId),
+        enum SpawnOrInsert<'w> {
+            Spawn(BundleSpawner<'w>),
+            Insert(BundleInserter<'w>, ArchetypeId),
         }
 
-        impl<'a, 'b> SpawnOrInsert<'a, 'b> {
+        impl<'w> SpawnOrInsert<'w> {
             fn entities(&mut self) -> &mut Entities {
                 match self {
-                    SpawnOrInsert::Spawn(spawner) => spawner.entities,
-                    SpawnOrInsert::Insert(inserter, _) => inserter.entities,
+                    SpawnOrInsert::Spawn(spawner) => spawner.entities(),
+                    SpawnOrInsert::Insert(inserter, _) => inserter.entities(),
+                }
+            }
+
+            fn flush_entities(&mut self) {
+                match self {
+                    SpawnOrInsert::Spawn(spawner) => spawner.flush_entities(),
+                    SpawnOrInsert::Insert(inserter, _) => inserter.flush_entities(),
                 }
             }
         }
-        let mut spawn_or_insert = SpawnOrInsert::Spawn(bundle_info.get_bundle_spawner(
-            &mut self.entities,
-            &mut self.archetypes,
-            &self.components,
-            &mut self.storages,
-            change_tick,
-        ));
+        // SAFETY: we initialized this bundle_id in `init_info`
+        let mut spawn_or_insert = SpawnOrInsert::Spawn(unsafe {
+            BundleSpawner::new_with_id(self, bundle_id, change_tick)
+        });
 
         let mut invalid_entities = Vec::new();
         for (entity, bundle) in iter {
+            // Hooks run for previous entities may have reserved new ones.
+            spawn_or_insert.flush_entities();
             match spawn_or_insert
                 .entities()
                 .alloc_at_without_replacement(entity)
@@ -1542,14 +1613,15 @@ impl World {
                             unsafe { inserter.insert(entity, location, bundle) };
                         }
                         _ => {
-                            let mut inserter = bundle_info.get_bundle_inserter(
-                                &mut self.entities,
-                                &mut self.archetypes,
-                                &self.components,
-                                &mut self.storages,
-                                location.archetype_id,
-                                change_tick,
-                            );
+                            // SAFETY: we initialized this bundle_id in `init_info`
+                            let mut inserter = unsafe {
+                                BundleInserter::new_with_id(
+                                    self,
+                                    location.archetype_id,
+                                    bundle_id,
+                                    change_tick,
+                                )
+                            };
                             // SAFETY: `entity` is valid, `location` matches entity, bundle matches inserter
                             unsafe { inserter.insert(entity, location, bundle) };
                             spawn_or_insert =
@@ -1562,13 +1634,9 @@ impl World {
                         // SAFETY: `entity` is allocated (but non existent), bundle matches inserter
                         unsafe { spawner.spawn_non_existent(entity, bundle) };
                     } else {
-                        let mut spawner = bundle_info.get_bundle_spawner(
-                            &mut self.entities,
-                            &mut self.archetypes,
-                            &self.components,
-                            &mut self.storages,
-                            change_tick,
-                        );
+                        // SAFETY: we initialized this bundle_id in `init_info`
+                        let mut spawner =
+                            unsafe { BundleSpawner::new_with_id(self, bundle_id, change_tick) };
                         // SAFETY: `entity` is valid, `location` matches entity, bundle matches inserter
                         unsafe { spawner.spawn_non_existent(entity, bundle) };
                         spawn_or_insert = SpawnOrInsert::Spawn(spawner);
@@ -1580,6 +1648,8 @@ impl World {
             }
         }
 
+        self.flush_commands();
+
         if invalid_entities.is_empty() {
             Ok(())
         } else {
@@ -1803,6 +1873,27 @@ impl World {
         }
     }
 
+    /// Creates a new [`Commands`] instance that writes to the world's internal command queue.
+    ///
+    /// The queued commands are applied by [`World::flush_commands`].
+    #[inline]
+    pub fn commands(&mut self) -> Commands<'_, '_> {
+        Commands::new_from_entities(&mut self.command_queue, &self.entities)
+    }
+
+    /// Applies any commands in the world's internal [`CommandQueue`].
+    ///
+    /// Commands are queued there by [`DeferredWorld::commands`], most commonly from
+    /// [component hooks](crate::component::ComponentHooks). This is called automatically
+    /// at the end of every structural change performed through [`World`] or [`EntityWorldMut`].
+    pub fn flush_commands(&mut self) {
+        // Commands applied here may queue further commands, so keep going until the queue is empty.
+        while !self.command_queue.is_empty() {
+            let mut commands = std::mem::take(&mut self.command_queue);
+            commands.apply(self);
+        }
+    }
+
     /// Increments the world's current change tick and returns the old value.
     #[inline]
     pub fn increment_change_tick(&self) -> Tick {
@@ -1870,6 +1961,7 @@ impl World {
         if let Some(mut schedules) = self.get_resource_mut::<Schedules>() {
             schedules.check_change_ticks(change_tick);
         }
+        crate::index::check_index_ticks(self, change_tick);
 
         self.last_check_tick = change_tick;
     }
@@ -11,10 +11,11 @@ use crate::{
         ComponentId, ComponentStorage, ComponentTicks, Components, StorageType, Tick, TickCells,
     },
     entity::{Entities, Entity, EntityLocation},
+    observer::Observers,
     prelude::Component,
     removal_detection::RemovedComponentEvents,
     storage::{Column, ComponentSparseSet, Storages},
-    system::Resource,
+    system::{CommandQueue, Resource},
 };
 use bevy_ptr::Ptr;
 use std::{any::TypeId, cell::UnsafeCell, fmt::Debug, marker::PhantomData};
@@ -193,6 +194,18 @@ impl<'w> UnsafeWorldCell<'w> {
         unsafe { &*self.0 }
     }
 
+    /// Retrieves this world's command queue.
+    ///
+    /// # Safety
+    /// - the caller must ensure there are no other references to the command queue
+    #[inline]
+    pub(crate) unsafe fn get_command_queue(self) -> &'w mut CommandQueue {
+        // SAFETY:
+        // - caller ensures there are no existing references to the command queue
+        // - the reference is created through a raw pointer, so no other world data is borrowed
+        unsafe { &mut *std::ptr::addr_of_mut!((*self.0).command_queue) }
+    }
+
     /// Retrieves this world's unique [ID](WorldId).
     #[inline]
     pub fn id(self) -> WorldId {
@@ -240,6 +253,24 @@ impl<'w> UnsafeWorldCell<'w> {
         &unsafe { self.world_metadata() }.bundles
     }
 
+    /// Retrieves this world's [`Observers`] collection.
+    #[inline]
+    pub(crate) fn observers(self) -> &'w Observers {
+        // SAFETY:
+        // - we only access world metadata
+        &unsafe { self.world_metadata() }.observers
+    }
+
+    /// Increments the world's trigger id, marking the start of a new trigger.
+    ///
+    /// # Safety
+    /// There must be no outstanding references to the world's trigger id.
+    #[inline]
+    pub(crate) unsafe fn increment_trigger_id(self) {
+        // SAFETY: The caller ensures the trigger id is not aliased
+        unsafe { *std::ptr::addr_of_mut!((*self.0).last_trigger_id) += 1 };
+    }
+
     /// Gets the current change tick of this world.
     #[inline]
     pub fn change_tick(self) -> Tick {
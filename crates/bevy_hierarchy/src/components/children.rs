@@ -1,3 +1,4 @@
+use crate::Parent;
 #[cfg(feature = "reflect")]
 use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
 use bevy_ecs::{
@@ -12,18 +13,27 @@ use std::ops::Deref;
 
 /// Contains references to the child entities of this entity.
 ///
-/// Each child must contain a [`Parent`] component that points back to this entity.
-/// This component rarely needs to be created manually,
-/// consider using higher level utilities like [`BuildChildren::with_children`]
-/// which are safer and easier to use.
+/// This is the [`RelationshipTarget`] side of the parent/child relationship, and is kept up to date
+/// automatically from the [`Parent`] components pointing at this entity. It should not be created
+/// manually, consider using higher level utilities like [`BuildChildren::with_children`] instead.
+///
+/// Removing this component or despawning this entity removes the [`Parent`] of its children, leaving
+/// them without a parent. Use [`DespawnRecursiveExt`] to despawn the children as well.
+///
+/// When this entity is cloned recursively with an [`EntityCloner`], its children are cloned along
+/// with it, and become the children of the clone.
 ///
 /// See [`HierarchyQueryExt`] for hierarchy related methods on [`Query`].
 ///
 /// [`HierarchyQueryExt`]: crate::query_extension::HierarchyQueryExt
 /// [`Query`]: bevy_ecs::system::Query
 /// [`Parent`]: crate::components::parent::Parent
+/// [`RelationshipTarget`]: bevy_ecs::relationship::RelationshipTarget
 /// [`BuildChildren::with_children`]: crate::child_builder::BuildChildren::with_children
+/// [`DespawnRecursiveExt`]: crate::hierarchy::DespawnRecursiveExt
+/// [`EntityCloner`]: bevy_ecs::entity::EntityCloner
 #[derive(Component, Debug)]
+#[relationship_target(relationship = Parent, clone_recursive)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 #[cfg_attr(feature = "reflect", reflect(Component, MapEntities))]
 pub struct Children(pub(crate) SmallVec<[Entity; 8]>);
@@ -48,12 +58,6 @@ impl FromWorld for Children {
 }
 
 impl Children {
-    /// Constructs a [`Children`] component with the given entities.
-    #[inline]
-    pub(crate) fn from_entities(entities: &[Entity]) -> Self {
-        Self(SmallVec::from_slice(entities))
-    }
-
     /// Swaps the child at `a_index` with the child at `b_index`.
     #[inline]
     pub fn swap(&mut self, a_index: usize, b_index: usize) {
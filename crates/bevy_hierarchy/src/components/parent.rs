@@ -1,8 +1,10 @@
+use crate::Children;
 #[cfg(feature = "reflect")]
 use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
+    traversal::Traversal,
     world::{FromWorld, World},
 };
 use std::ops::Deref;
@@ -10,17 +12,21 @@ use std::ops::Deref;
 /// Holds a reference to the parent entity of this entity.
 /// This component should only be present on entities that actually have a parent entity.
 ///
-/// Parent entity must have this entity stored in its [`Children`] component.
-/// It is hard to set up parent/child relationships manually,
-/// consider using higher level utilities like [`BuildChildren::with_children`].
+/// This is the [`Relationship`] side of the parent/child relationship: inserting it adds this entity
+/// to the [`Children`] component of the parent, and removing it or despawning this entity removes it again.
+/// It must not be mutated in place, consider using higher level utilities like
+/// [`BuildChildren::with_children`] or [`BuildChildren::set_parent`].
 ///
 /// See [`HierarchyQueryExt`] for hierarchy related methods on [`Query`].
 ///
 /// [`HierarchyQueryExt`]: crate::query_extension::HierarchyQueryExt
 /// [`Query`]: bevy_ecs::system::Query
 /// [`Children`]: super::children::Children
+/// [`Relationship`]: bevy_ecs::relationship::Relationship
 /// [`BuildChildren::with_children`]: crate::child_builder::BuildChildren::with_children
+/// [`BuildChildren::set_parent`]: crate::child_builder::BuildChildren::set_parent
 #[derive(Component, Debug, Eq, PartialEq)]
+#[relationship(relationship_target = Children)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 #[cfg_attr(feature = "reflect", reflect(Component, MapEntities, PartialEq))]
 pub struct Parent(pub(crate) Entity);
@@ -55,6 +61,16 @@ impl FromWorld for Parent {
     }
 }
 
+/// Propagates triggered events from a child to its parent.
+///
+/// [`HierarchyPlugin`](crate::HierarchyPlugin) registers this as the
+/// [default traversal](World::set_default_traversal) of the world.
+impl Traversal for Parent {
+    fn traverse(world: &World, entity: Entity) -> Option<Entity> {
+        world.get::<Parent>(entity).map(Parent::get)
+    }
+}
+
 impl MapEntities for Parent {
     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
         self.0 = entity_mapper.get_or_reserve(self.0);
@@ -1,4 +1,4 @@
-use crate::components::{Children, Parent};
+use crate::components::Children;
 use bevy_ecs::{
     entity::Entity,
     system::{Command, EntityCommands},
@@ -22,19 +22,14 @@ pub struct DespawnChildrenRecursive {
 
 /// Function for despawning an entity and all its children
 pub fn despawn_with_children_recursive(world: &mut World, entity: Entity) {
-    // first, make the entity's own parent forget about it
-    if let Some(parent) = world.get::<Parent>(entity).map(|parent| parent.0) {
-        if let Some(mut children) = world.get_mut::<Children>(parent) {
-            children.0.retain(|c| *c != entity);
-        }
-    }
-
-    // then despawn the entity and all of its children
+    // The `Parent` hooks of the entity make its own parent forget about it when it is despawned.
     despawn_with_children_recursive_inner(world, entity);
 }
 
 // Should only be called by `despawn_with_children_recursive`!
 fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
+    // Empty the `Children` first, so that neither the hooks of the `Parent` of each child
+    // nor the hooks of the `Children` itself do any bookkeeping for entities that are despawned anyway.
     if let Some(mut children) = world.get_mut::<Children>(entity) {
         for e in std::mem::take(&mut children.0) {
             despawn_with_children_recursive_inner(world, e);
@@ -47,11 +42,13 @@ fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
 }
 
 fn despawn_children_recursive(world: &mut World, entity: Entity) {
-    if let Some(children) = world.entity_mut(entity).take::<Children>() {
-        for e in children.0 {
-            despawn_with_children_recursive_inner(world, e);
-        }
+    let Some(mut children) = world.get_mut::<Children>(entity) else {
+        return;
+    };
+    for e in std::mem::take(&mut children.0) {
+        despawn_with_children_recursive_inner(world, e);
     }
+    world.entity_mut(entity).remove::<Children>();
 }
 
 impl Command for DespawnRecursive {
@@ -139,12 +136,16 @@ impl<'w> DespawnRecursiveExt for EntityWorldMut<'w> {
 mod tests {
     use bevy_ecs::{
         component::Component,
+        entity::EntityCloner,
         system::{CommandQueue, Commands},
         world::World,
     };
 
     use super::DespawnRecursiveExt;
-    use crate::{child_builder::BuildChildren, components::Children};
+    use crate::{
+        child_builder::{BuildChildren, BuildWorldChildren},
+        components::{Children, Parent},
+    };
 
     #[derive(Component, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Debug)]
     struct Idx(u32);
@@ -208,13 +209,10 @@ mod tests {
             .collect::<Vec<_>>();
         results.sort_unstable_by_key(|(_, index)| *index);
 
-        {
-            let children = world.get::<Children>(grandparent_entity).unwrap();
-            assert!(
-                !children.iter().any(|&i| i == parent_entity),
-                "grandparent should no longer know about its child which has been removed"
-            );
-        }
+        assert!(
+            world.get::<Children>(grandparent_entity).is_none(),
+            "grandparent should no longer know about its only child which has been removed"
+        );
 
         assert_eq!(
             results,
@@ -227,6 +225,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn despawn_keeps_hierarchy_valid() {
+        let mut world = World::default();
+        let [grandparent, parent, child] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(grandparent).add_child(parent);
+        world.entity_mut(parent).add_child(child);
+
+        // Despawning an entity without its descendants orphans its children.
+        world.despawn(parent);
+        assert!(world.get::<Children>(grandparent).is_none());
+        assert!(world.get::<Parent>(child).is_none());
+        assert!(world.get_entity(child).is_some());
+    }
+
     #[test]
     fn despawn_descendants() {
         let mut world = World::default();
@@ -276,4 +288,36 @@ mod tests {
         // The original child should be despawned.
         assert!(world.get_entity(child).is_none());
     }
+
+    #[test]
+    fn clone_recursive() {
+        let mut world = World::default();
+        let mut parent = world.spawn(Idx(0));
+        parent.with_children(|parent| {
+            parent.spawn(Idx(1)).with_children(|child| {
+                child.spawn(Idx(2));
+            });
+            parent.spawn(Idx(3));
+        });
+        let parent = parent.id();
+
+        let clone = world
+            .entity_mut(parent)
+            .clone_entity_with(&EntityCloner::default().recursive(true));
+
+        let children = world.get::<Children>(clone).unwrap().to_vec();
+        let original_children = world.get::<Children>(parent).unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        for (child, original) in children.iter().zip(&original_children) {
+            assert_ne!(child, original);
+            assert_eq!(world.get::<Parent>(*child).unwrap().get(), clone);
+            assert_eq!(world.get::<Idx>(*child), world.get::<Idx>(*original));
+        }
+        let grandchildren = world.get::<Children>(children[0]).unwrap();
+        assert_eq!(world.get::<Idx>(grandchildren[0]), Some(&Idx(2)));
+
+        // cloning a child without recursion gives its parent another child
+        world.entity_mut(children[1]).clone_entity();
+        assert_eq!(world.get::<Children>(clone).unwrap().len(), 3);
+    }
 }
@@ -31,12 +31,17 @@
 //! Similarly, unassigning a child in the parent
 //! will always unassign the parent in the child.
 //!
+//! [`Parent`] and [`Children`] are a [relationship]:
+//! only [`Parent`] is ever written to,
+//! and [`Children`] is kept up to date by component hooks.
+//!
 //! ## Despawning entities
 //!
 //! The commands and methods provided by `bevy_ecs` to despawn entities
-//! are not capable of automatically despawning hierarchies of entities.
-//! In most cases, these operations will invalidate the hierarchy.
-//! Instead, you should use the provided [hierarchical despawn extension methods].
+//! keep the hierarchy valid, but do not despawn hierarchies of entities:
+//! despawning a parent removes the [`Parent`] of its children, leaving them without a parent.
+//! To despawn the descendants of an entity as well,
+//! use the provided [hierarchical despawn extension methods].
 //!
 //! [command]: BuildChildren
 //! [diagnostic plugin]: ValidParentCheckPlugin
@@ -44,6 +49,7 @@
 //! [hierarchical despawn extension methods]: DespawnRecursiveExt
 //! [plugin]: HierarchyPlugin
 //! [query extension methods]: HierarchyQueryExt
+//! [relationship]: bevy_ecs::relationship
 //! [world]: BuildWorldChildren
 
 mod components;
@@ -93,5 +99,6 @@ impl Plugin for HierarchyPlugin {
             .register_type::<Parent>()
             .register_type::<SmallVec<[bevy_ecs::entity::Entity; 8]>>()
             .add_event::<HierarchyEvent>();
+        app.world.set_default_traversal::<Parent>();
     }
 }
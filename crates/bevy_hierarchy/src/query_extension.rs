@@ -1,14 +1,16 @@
-use std::collections::VecDeque;
-
 use bevy_ecs::{
     entity::Entity,
     query::{QueryData, QueryFilter, WorldQuery},
+    relationship,
     system::Query,
 };
 
 use crate::{Children, Parent};
 
 /// An extension trait for [`Query`] that adds hierarchy related methods.
+///
+/// These are the [`Children`] and [`Parent`] specific versions of the relationship methods
+/// that [`Query`] already provides, such as [`Query::iter_descendants`].
 pub trait HierarchyQueryExt<'w, 's, D: QueryData, F: QueryFilter> {
     /// Returns an [`Iterator`] of [`Entity`]s over all of `entity`s descendants.
     ///
@@ -62,96 +64,24 @@ impl<'w, 's, D: QueryData, F: QueryFilter> HierarchyQueryExt<'w, 's, D, F> for Q
     where
         D::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
     {
-        DescendantIter::new(self, entity)
+        Query::iter_descendants(self, entity)
     }
 
     fn iter_ancestors(&'w self, entity: Entity) -> AncestorIter<'w, 's, D, F>
     where
         D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>,
     {
-        AncestorIter::new(self, entity)
+        Query::iter_ancestors(self, entity)
     }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
 ///
 /// Traverses the hierarchy breadth-first.
-pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter>
-where
-    D::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
-{
-    children_query: &'w Query<'w, 's, D, F>,
-    vecdeque: VecDeque<Entity>,
-}
-
-impl<'w, 's, D: QueryData, F: QueryFilter> DescendantIter<'w, 's, D, F>
-where
-    D::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
-{
-    /// Returns a new [`DescendantIter`].
-    pub fn new(children_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
-        DescendantIter {
-            children_query,
-            vecdeque: children_query
-                .get(entity)
-                .into_iter()
-                .flatten()
-                .copied()
-                .collect(),
-        }
-    }
-}
-
-impl<'w, 's, D: QueryData, F: QueryFilter> Iterator for DescendantIter<'w, 's, D, F>
-where
-    D::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
-{
-    type Item = Entity;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let entity = self.vecdeque.pop_front()?;
-
-        if let Ok(children) = self.children_query.get(entity) {
-            self.vecdeque.extend(children);
-        }
-
-        Some(entity)
-    }
-}
+pub type DescendantIter<'w, 's, D, F> = relationship::DescendantIter<'w, 's, D, F, Children>;
 
 /// An [`Iterator`] of [`Entity`]s over the ancestors of an [`Entity`].
-pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter>
-where
-    D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>,
-{
-    parent_query: &'w Query<'w, 's, D, F>,
-    next: Option<Entity>,
-}
-
-impl<'w, 's, D: QueryData, F: QueryFilter> AncestorIter<'w, 's, D, F>
-where
-    D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>,
-{
-    /// Returns a new [`AncestorIter`].
-    pub fn new(parent_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
-        AncestorIter {
-            parent_query,
-            next: Some(entity),
-        }
-    }
-}
-
-impl<'w, 's, D: QueryData, F: QueryFilter> Iterator for AncestorIter<'w, 's, D, F>
-where
-    D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>,
-{
-    type Item = Entity;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.next = self.parent_query.get(self.next?).ok().map(|p| p.get());
-        self.next
-    }
-}
+pub type AncestorIter<'w, 's, D, F> = relationship::AncestorIter<'w, 's, D, F, Parent>;
 
 #[cfg(test)]
 mod tests {
@@ -161,7 +91,7 @@ mod tests {
         world::World,
     };
 
-    use crate::{query_extension::HierarchyQueryExt, BuildWorldChildren, Children, Parent};
+    use crate::{BuildWorldChildren, Children, Parent};
 
     #[derive(Component, PartialEq, Debug)]
     struct A(usize);
@@ -0,0 +1,779 @@
+use crate::{Enum, List, Map, Reflect, ReflectMut, ReflectRef, Struct, VariantType};
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// The changes between two [`Reflect`] values, as returned by [`diff`].
+///
+/// A diff only contains what changed: fields, elements and entries that are equal in both values
+/// are left out. It can be applied to a value with [`apply_diff`], and serialized with
+/// [`DiffSerializer`](crate::serde::DiffSerializer).
+#[derive(Debug)]
+pub enum Diff {
+    /// The values are equal.
+    Unchanged,
+    /// The value changed as a whole and is replaced by the contained value.
+    ///
+    /// This is used for value types, enums whose variant changed, and values of different types.
+    Replaced(Box<dyn Reflect>),
+    /// The named fields of a struct, or of a struct variant in a [`Diff::Enum`], that changed.
+    Struct(Vec<(String, Diff)>),
+    /// The indexed fields of a tuple struct, a tuple or a tuple variant in a [`Diff::Enum`],
+    /// or the elements of an array, that changed.
+    Tuple(Vec<(usize, Diff)>),
+    /// The fields of an enum that kept its variant: the name of the variant, and the
+    /// [`Diff::Struct`] or [`Diff::Tuple`] of its fields.
+    Enum(String, Box<Diff>),
+    /// The changes to a list, in the order they are applied.
+    List(Vec<ListChange>),
+    /// The changes to the entries of a map.
+    Map(Vec<MapChange>),
+}
+
+/// A change to a [`List`] in a [`Diff::List`].
+///
+/// Indices refer to the list as modified by the previous changes of the diff.
+#[derive(Debug)]
+pub enum ListChange {
+    /// The value is inserted at the index.
+    Insert(usize, Box<dyn Reflect>),
+    /// The element at the index is removed.
+    Remove(usize),
+    /// The element at the index changed.
+    Modify(usize, Diff),
+}
+
+/// A change to a [`Map`] in a [`Diff::Map`].
+#[derive(Debug)]
+pub enum MapChange {
+    /// The entry is inserted with the key and value.
+    Insert(Box<dyn Reflect>, Box<dyn Reflect>),
+    /// The entry with the key is removed.
+    Remove(Box<dyn Reflect>),
+    /// The value of the entry with the key changed.
+    Modify(Box<dyn Reflect>, Diff),
+}
+
+impl Diff {
+    /// Returns `true` if the diff is [`Diff::Unchanged`].
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Diff::Unchanged)
+    }
+}
+
+impl Clone for Diff {
+    fn clone(&self) -> Self {
+        match self {
+            Diff::Unchanged => Diff::Unchanged,
+            Diff::Replaced(value) => Diff::Replaced(value.clone_value()),
+            Diff::Struct(fields) => Diff::Struct(fields.clone()),
+            Diff::Tuple(fields) => Diff::Tuple(fields.clone()),
+            Diff::Enum(variant, fields) => Diff::Enum(variant.clone(), fields.clone()),
+            Diff::List(changes) => Diff::List(changes.clone()),
+            Diff::Map(changes) => Diff::Map(changes.clone()),
+        }
+    }
+}
+
+impl Clone for ListChange {
+    fn clone(&self) -> Self {
+        match self {
+            ListChange::Insert(index, value) => ListChange::Insert(*index, value.clone_value()),
+            ListChange::Remove(index) => ListChange::Remove(*index),
+            ListChange::Modify(index, diff) => ListChange::Modify(*index, diff.clone()),
+        }
+    }
+}
+
+impl Clone for MapChange {
+    fn clone(&self) -> Self {
+        match self {
+            MapChange::Insert(key, value) => {
+                MapChange::Insert(key.clone_value(), value.clone_value())
+            }
+            MapChange::Remove(key) => MapChange::Remove(key.clone_value()),
+            MapChange::Modify(key, diff) => MapChange::Modify(key.clone_value(), diff.clone()),
+        }
+    }
+}
+
+/// An error returned by [`apply_diff`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ApplyDiffError {
+    #[error("expected a value of type `{expected}` but found `{actual}`")]
+    MismatchedTypes { expected: String, actual: String },
+    #[error("a {diff} diff can't be applied to a {actual}")]
+    MismatchedKinds {
+        diff: &'static str,
+        actual: &'static str,
+    },
+    #[error("expected the `{expected}` variant of `{type_path}` but found `{actual}`")]
+    MismatchedVariants {
+        type_path: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("`{type_path}` has no field `{field}`")]
+    MissingField { type_path: String, field: String },
+    #[error("`{type_path}` has no field or element at index {index}")]
+    MissingIndex { type_path: String, index: usize },
+    #[error("`{type_path}` has no entry with the key {key}")]
+    MissingKey { type_path: String, key: String },
+}
+
+/// Returns the changes turning `old` into `new`.
+///
+/// Structs, tuple structs, tuples, arrays and enums keeping their variant are diffed field by field,
+/// lists are diffed as a sequence of insertions, removals and modifications of their elements
+/// (unless too many of them changed, in which case the list is replaced), and maps entry by entry.
+/// Value types are compared with [`Reflect::reflect_partial_eq`] and replaced as a whole when they
+/// differ, or can't be compared.
+///
+/// ```
+/// # use bevy_reflect::{apply_diff, diff, Diff, Reflect};
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Player {
+///     name: String,
+///     items: Vec<u32>,
+/// }
+///
+/// let old = Player { name: "Ferris".into(), items: vec![1, 2] };
+/// let new = Player { name: "Ferris".into(), items: vec![1, 3, 2] };
+///
+/// let diff = diff(&old, &new);
+/// let Diff::Struct(fields) = &diff else { unreachable!() };
+/// assert_eq!(fields.len(), 1);
+/// assert_eq!(fields[0].0, "items");
+///
+/// let mut target = old.clone();
+/// apply_diff(&mut target, &diff).unwrap();
+/// assert_eq!(target, new);
+/// ```
+pub fn diff(old: &dyn Reflect, new: &dyn Reflect) -> Diff {
+    if type_path(old) != type_path(new) {
+        return Diff::Replaced(new.clone_value());
+    }
+
+    let diff = match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old), ReflectRef::Struct(new)) => diff_struct(old, new),
+        (ReflectRef::TupleStruct(old), ReflectRef::TupleStruct(new))
+            if old.field_len() == new.field_len() =>
+        {
+            diff_fields((0..new.field_len()).map(|i| (old.field(i), new.field(i))))
+        }
+        (ReflectRef::Tuple(old), ReflectRef::Tuple(new)) if old.field_len() == new.field_len() => {
+            diff_fields((0..new.field_len()).map(|i| (old.field(i), new.field(i))))
+        }
+        (ReflectRef::Array(old), ReflectRef::Array(new)) if old.len() == new.len() => {
+            diff_fields((0..new.len()).map(|i| (old.get(i), new.get(i))))
+        }
+        (ReflectRef::List(old), ReflectRef::List(new)) => diff_list(old, new),
+        (ReflectRef::Map(old), ReflectRef::Map(new)) => Some(diff_map(old, new)),
+        (ReflectRef::Enum(old), ReflectRef::Enum(new)) => diff_enum(old, new),
+        (ReflectRef::Value(old), ReflectRef::Value(new)) => {
+            (old.reflect_partial_eq(new) == Some(true)).then_some(Diff::Unchanged)
+        }
+        _ => None,
+    };
+    diff.unwrap_or_else(|| Diff::Replaced(new.clone_value()))
+}
+
+/// Diffs the fields of a struct, or returns `None` if the structs have different fields.
+fn diff_struct(old: &dyn Struct, new: &dyn Struct) -> Option<Diff> {
+    if old.field_len() != new.field_len() {
+        return None;
+    }
+    let mut fields = Vec::new();
+    for (index, new_field) in new.iter_fields().enumerate() {
+        let name = new.name_at(index)?;
+        let field_diff = diff(old.field(name)?, new_field);
+        if !field_diff.is_unchanged() {
+            fields.push((name.to_string(), field_diff));
+        }
+    }
+    Some(if fields.is_empty() {
+        Diff::Unchanged
+    } else {
+        Diff::Struct(fields)
+    })
+}
+
+/// Diffs indexed fields, or returns `None` if a field is missing.
+fn diff_fields<'a>(
+    fields: impl Iterator<Item = (Option<&'a dyn Reflect>, Option<&'a dyn Reflect>)>,
+) -> Option<Diff> {
+    let mut changed = Vec::new();
+    for (index, (old, new)) in fields.enumerate() {
+        let field_diff = diff(old?, new?);
+        if !field_diff.is_unchanged() {
+            changed.push((index, field_diff));
+        }
+    }
+    Some(if changed.is_empty() {
+        Diff::Unchanged
+    } else {
+        Diff::Tuple(changed)
+    })
+}
+
+/// Diffs the fields of enums with the same variant, or returns `None` if the variant changed.
+fn diff_enum(old: &dyn Enum, new: &dyn Enum) -> Option<Diff> {
+    if old.variant_name() != new.variant_name() || old.field_len() != new.field_len() {
+        return None;
+    }
+    let fields = match new.variant_type() {
+        VariantType::Unit => Diff::Unchanged,
+        VariantType::Tuple => {
+            diff_fields((0..new.field_len()).map(|i| (old.field_at(i), new.field_at(i))))?
+        }
+        VariantType::Struct => {
+            let mut fields = Vec::new();
+            for new_field in new.iter_fields() {
+                let name = new_field.name()?;
+                let field_diff = diff(old.field(name)?, new_field.value());
+                if !field_diff.is_unchanged() {
+                    fields.push((name.to_string(), field_diff));
+                }
+            }
+            if fields.is_empty() {
+                Diff::Unchanged
+            } else {
+                Diff::Struct(fields)
+            }
+        }
+    };
+    Some(if fields.is_unchanged() {
+        Diff::Unchanged
+    } else {
+        Diff::Enum(new.variant_name().to_string(), Box::new(fields))
+    })
+}
+
+/// The maximum number of pairs of elements compared when diffing two lists, above which the list is
+/// replaced as a whole.
+const MAX_LIST_DIFF_PAIRS: usize = 1 << 16;
+
+/// Diffs two lists along their longest common subsequence of equal elements, so that inserting
+/// or removing an element doesn't modify all the elements after it.
+///
+/// Elements are compared with [`Reflect::reflect_partial_eq`]. The common prefix and suffix of the
+/// lists are skipped, and the elements left are matched in quadratic time and space, so `None` is
+/// returned if there are more than [`MAX_LIST_DIFF_PAIRS`] pairs of them.
+fn diff_list(old: &dyn List, new: &dyn List) -> Option<Diff> {
+    let equal = |i: usize, j: usize| {
+        old.get(i)
+            .unwrap()
+            .reflect_partial_eq(new.get(j).unwrap())
+            .unwrap_or(false)
+    };
+    let shortest = old.len().min(new.len());
+    let prefix = (0..shortest).take_while(|&i| equal(i, i)).count();
+    let suffix = (0..shortest - prefix)
+        .take_while(|&i| equal(old.len() - 1 - i, new.len() - 1 - i))
+        .count();
+
+    let (old_len, new_len) = (old.len() - prefix - suffix, new.len() - prefix - suffix);
+    if old_len.saturating_mul(new_len) > MAX_LIST_DIFF_PAIRS {
+        return None;
+    }
+    let old_at = |i: usize| old.get(prefix + i).unwrap();
+    let new_at = |j: usize| new.get(prefix + j).unwrap();
+    let equal: Vec<Vec<bool>> = (0..old_len)
+        .map(|i| {
+            (0..new_len)
+                .map(|j| equal(prefix + i, prefix + j))
+                .collect()
+        })
+        .collect();
+
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..],
+    // between the prefix and the suffix.
+    let mut common = vec![vec![0usize; new_len + 1]; old_len + 1];
+    for i in (0..old_len).rev() {
+        for j in (0..new_len).rev() {
+            common[i][j] = if equal[i][j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j, mut index) = (0, 0, prefix);
+    while i < old_len || j < new_len {
+        if i < old_len && j < new_len && equal[i][j] {
+            i += 1;
+            j += 1;
+            index += 1;
+        } else if i < old_len && j < new_len && common[i + 1][j + 1] == common[i][j] {
+            let element_diff = diff(old_at(i), new_at(j));
+            if !element_diff.is_unchanged() {
+                changes.push(ListChange::Modify(index, element_diff));
+            }
+            i += 1;
+            j += 1;
+            index += 1;
+        } else if j < new_len && (i == old_len || common[i][j + 1] >= common[i + 1][j]) {
+            changes.push(ListChange::Insert(index, new_at(j).clone_value()));
+            j += 1;
+            index += 1;
+        } else {
+            changes.push(ListChange::Remove(index));
+            i += 1;
+        }
+    }
+
+    Some(if changes.is_empty() {
+        Diff::Unchanged
+    } else {
+        Diff::List(changes)
+    })
+}
+
+fn diff_map(old: &dyn Map, new: &dyn Map) -> Diff {
+    let mut changes = Vec::new();
+    for (key, old_value) in old.iter() {
+        match new.get(key) {
+            Some(new_value) => {
+                let value_diff = diff(old_value, new_value);
+                if !value_diff.is_unchanged() {
+                    changes.push(MapChange::Modify(key.clone_value(), value_diff));
+                }
+            }
+            None => changes.push(MapChange::Remove(key.clone_value())),
+        }
+    }
+    for (key, new_value) in new.iter() {
+        if old.get(key).is_none() {
+            changes.push(MapChange::Insert(
+                key.clone_value(),
+                new_value.clone_value(),
+            ));
+        }
+    }
+
+    if changes.is_empty() {
+        Diff::Unchanged
+    } else {
+        Diff::Map(changes)
+    }
+}
+
+/// Applies a [`Diff`] returned by [`diff`] to `target`.
+///
+/// Applying the diff between `old` and `new` to a value equal to `old` makes it equal to `new`.
+/// The diff can also be applied to other values of the same type, in which case changes to
+/// fields, elements or entries that are missing from `target` return an error.
+/// Changes preceding the error are still applied.
+///
+/// # Panics
+///
+/// Like [`Reflect::apply`], panics if a replaced or inserted value can't be converted to the type
+/// it replaces or is inserted in.
+pub fn apply_diff(target: &mut dyn Reflect, diff: &Diff) -> Result<(), ApplyDiffError> {
+    match diff {
+        Diff::Unchanged => Ok(()),
+        Diff::Replaced(value) => {
+            if type_path(target) != type_path(&**value) {
+                return Err(ApplyDiffError::MismatchedTypes {
+                    expected: type_path(&**value).to_string(),
+                    actual: type_path(target).to_string(),
+                });
+            }
+            if let Err(value) = target.set(value.clone_value()) {
+                target.apply(&*value);
+            }
+            Ok(())
+        }
+        Diff::Struct(fields) => {
+            let type_path = target.reflect_type_path().to_string();
+            for (name, field_diff) in fields {
+                let field = match target.reflect_mut() {
+                    ReflectMut::Struct(target) => target.field_mut(name),
+                    target => return Err(mismatched_kinds("struct", target)),
+                };
+                let field = field.ok_or_else(|| ApplyDiffError::MissingField {
+                    type_path: type_path.clone(),
+                    field: name.clone(),
+                })?;
+                apply_diff(field, field_diff)?;
+            }
+            Ok(())
+        }
+        Diff::Tuple(fields) => {
+            let type_path = target.reflect_type_path().to_string();
+            for &(index, ref field_diff) in fields {
+                let field = match target.reflect_mut() {
+                    ReflectMut::TupleStruct(target) => target.field_mut(index),
+                    ReflectMut::Tuple(target) => target.field_mut(index),
+                    ReflectMut::Array(target) => target.get_mut(index),
+                    target => return Err(mismatched_kinds("tuple", target)),
+                };
+                let field = field.ok_or_else(|| ApplyDiffError::MissingIndex {
+                    type_path: type_path.clone(),
+                    index,
+                })?;
+                apply_diff(field, field_diff)?;
+            }
+            Ok(())
+        }
+        Diff::Enum(variant, fields) => {
+            let type_path = target.reflect_type_path().to_string();
+            let target = match target.reflect_mut() {
+                ReflectMut::Enum(target) => target,
+                target => return Err(mismatched_kinds("enum", target)),
+            };
+            if target.variant_name() != variant {
+                return Err(ApplyDiffError::MismatchedVariants {
+                    type_path,
+                    expected: variant.clone(),
+                    actual: target.variant_name().to_string(),
+                });
+            }
+            match &**fields {
+                Diff::Unchanged => {}
+                Diff::Struct(fields) => {
+                    for (name, field_diff) in fields {
+                        let field =
+                            target
+                                .field_mut(name)
+                                .ok_or_else(|| ApplyDiffError::MissingField {
+                                    type_path: type_path.clone(),
+                                    field: name.clone(),
+                                })?;
+                        apply_diff(field, field_diff)?;
+                    }
+                }
+                Diff::Tuple(fields) => {
+                    for &(index, ref field_diff) in fields {
+                        let field = target.field_at_mut(index).ok_or_else(|| {
+                            ApplyDiffError::MissingIndex {
+                                type_path: type_path.clone(),
+                                index,
+                            }
+                        })?;
+                        apply_diff(field, field_diff)?;
+                    }
+                }
+                _ => {
+                    return Err(ApplyDiffError::MismatchedKinds {
+                        diff: "variant",
+                        actual: "enum",
+                    })
+                }
+            }
+            Ok(())
+        }
+        Diff::List(changes) => {
+            let type_path = target.reflect_type_path().to_string();
+            let list = match target.reflect_mut() {
+                ReflectMut::List(list) => list,
+                target => return Err(mismatched_kinds("list", target)),
+            };
+            let missing_index = |index| ApplyDiffError::MissingIndex {
+                type_path: type_path.clone(),
+                index,
+            };
+            for change in changes {
+                match change {
+                    ListChange::Insert(index, value) => {
+                        if *index > list.len() {
+                            return Err(missing_index(*index));
+                        }
+                        list.insert(*index, value.clone_value());
+                    }
+                    ListChange::Remove(index) => {
+                        if *index >= list.len() {
+                            return Err(missing_index(*index));
+                        }
+                        list.remove(*index);
+                    }
+                    ListChange::Modify(index, element_diff) => {
+                        let element = list.get_mut(*index).ok_or_else(|| missing_index(*index))?;
+                        apply_diff(element, element_diff)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        Diff::Map(changes) => {
+            let type_path = target.reflect_type_path().to_string();
+            let map = match target.reflect_mut() {
+                ReflectMut::Map(map) => map,
+                target => return Err(mismatched_kinds("map", target)),
+            };
+            let missing_key = |key: &dyn Reflect| ApplyDiffError::MissingKey {
+                type_path: type_path.clone(),
+                key: format!("{key:?}"),
+            };
+            for change in changes {
+                match change {
+                    MapChange::Insert(key, value) => {
+                        map.insert_boxed(key.clone_value(), value.clone_value());
+                    }
+                    MapChange::Remove(key) => {
+                        map.remove(&**key).ok_or_else(|| missing_key(&**key))?;
+                    }
+                    MapChange::Modify(key, value_diff) => {
+                        let value = map.get_mut(&**key).ok_or_else(|| missing_key(&**key))?;
+                        apply_diff(value, value_diff)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Returns the type path of the type `value` represents, which differs from its own type path
+/// for dynamic types.
+fn type_path(value: &dyn Reflect) -> &str {
+    value
+        .get_represented_type_info()
+        .map(|info| info.type_path())
+        .unwrap_or_else(|| value.reflect_type_path())
+}
+
+fn mismatched_kinds(diff: &'static str, target: ReflectMut) -> ApplyDiffError {
+    let actual = match target {
+        ReflectMut::Struct(_) => "struct",
+        ReflectMut::TupleStruct(_) => "tuple struct",
+        ReflectMut::Tuple(_) => "tuple",
+        ReflectMut::List(_) => "list",
+        ReflectMut::Array(_) => "array",
+        ReflectMut::Map(_) => "map",
+        ReflectMut::Enum(_) => "enum",
+        ReflectMut::Value(_) => "value",
+    };
+    ApplyDiffError::MismatchedKinds { diff, actual }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, FromReflect, TypePath};
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Level {
+        name: String,
+        size: (u32, u32),
+        tiles: Vec<Tile>,
+        spawns: HashMap<String, [f32; 2]>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum Tile {
+        Empty,
+        Wall(u8),
+        Door { locked: bool, key: Option<String> },
+    }
+
+    fn level() -> Level {
+        Level {
+            name: "start".to_string(),
+            size: (4, 2),
+            tiles: vec![Tile::Empty, Tile::Wall(1), Tile::Empty],
+            spawns: HashMap::from([
+                ("player".to_string(), [0.0, 0.0]),
+                ("enemy".to_string(), [3.0, 1.0]),
+            ]),
+        }
+    }
+
+    fn assert_roundtrip<T: Reflect + Clone + PartialEq + Debug>(old: &T, new: &T) {
+        let diff = diff(old, new);
+        let mut target = old.clone();
+        apply_diff(&mut target, &diff).unwrap();
+        assert_eq!(&target, new, "{diff:#?}");
+    }
+
+    #[test]
+    fn unchanged() {
+        assert!(diff(&level(), &level()).is_unchanged());
+        assert!(diff(&vec![1, 2, 3], &vec![1, 2, 3]).is_unchanged());
+    }
+
+    #[test]
+    fn struct_fields() {
+        let old = level();
+        let mut new = level();
+        new.size.1 = 3;
+        new.name = "renamed".to_string();
+
+        let Diff::Struct(fields) = diff(&old, &new) else {
+            panic!("expected a struct diff");
+        };
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].0, "name");
+        assert!(
+            matches!(&fields[0].1, Diff::Replaced(value) if value.reflect_partial_eq(&new.name) == Some(true))
+        );
+        assert_eq!(fields[1].0, "size");
+        assert!(
+            matches!(&fields[1].1, Diff::Tuple(fields) if fields.len() == 1 && fields[0].0 == 1)
+        );
+
+        assert_roundtrip(&old, &new);
+    }
+
+    #[test]
+    fn enum_variants() {
+        let old = Tile::Door {
+            locked: true,
+            key: None,
+        };
+        let new = Tile::Door {
+            locked: true,
+            key: Some("gold".to_string()),
+        };
+        let Diff::Enum(variant, fields) = diff(&old, &new) else {
+            panic!("expected an enum diff");
+        };
+        assert_eq!(variant, "Door");
+        let Diff::Struct(fields) = *fields else {
+            panic!("expected a struct variant diff");
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "key");
+        assert_roundtrip(&old, &new);
+
+        assert!(matches!(
+            diff(&Tile::Wall(1), &Tile::Wall(2)),
+            Diff::Enum(variant, fields) if variant == "Wall" && matches!(*fields, Diff::Tuple(_))
+        ));
+        assert!(matches!(
+            diff(&Tile::Wall(1), &Tile::Empty),
+            Diff::Replaced(_)
+        ));
+        assert_roundtrip(&Tile::Wall(1), &Tile::Empty);
+        assert_roundtrip(&Tile::Empty, &new);
+    }
+
+    #[test]
+    fn list_changes() {
+        let old = vec![1, 2, 3, 4];
+        let new = vec![0, 1, 5, 3];
+        let Diff::List(changes) = diff(&old, &new) else {
+            panic!("expected a list diff");
+        };
+        assert_eq!(changes.len(), 3, "{changes:#?}");
+        assert!(matches!(changes[0], ListChange::Insert(0, _)));
+        assert!(matches!(
+            changes[1],
+            ListChange::Modify(2, Diff::Replaced(_))
+        ));
+        assert!(matches!(changes[2], ListChange::Remove(4)));
+        assert_roundtrip(&old, &new);
+
+        assert_roundtrip(&old, &vec![]);
+        assert_roundtrip(&vec![], &old);
+        assert_roundtrip(&old, &vec![4, 3, 2, 1]);
+
+        let mut new = level();
+        new.tiles.remove(0);
+        new.tiles[0] = Tile::Wall(2);
+        new.tiles.push(Tile::Door {
+            locked: false,
+            key: None,
+        });
+        assert_roundtrip(&level(), &new);
+    }
+
+    #[test]
+    fn long_list_changes() {
+        let old: Vec<u32> = (0..10_000).collect();
+        let mut new = old.clone();
+        new[5_000] = 0;
+        new.remove(5_010);
+        let Diff::List(changes) = diff(&old, &new) else {
+            panic!("expected a list diff");
+        };
+        assert_eq!(changes.len(), 2, "{changes:#?}");
+        assert!(matches!(changes[0], ListChange::Modify(5_000, _)));
+        assert!(matches!(changes[1], ListChange::Remove(5_010)));
+        assert_roundtrip(&old, &new);
+
+        let new: Vec<u32> = (10_000..20_000).collect();
+        assert!(matches!(diff(&old, &new), Diff::Replaced(_)));
+        assert_roundtrip(&old, &new);
+    }
+
+    #[test]
+    fn map_changes() {
+        let old = level();
+        let mut new = level();
+        new.spawns.remove("enemy");
+        new.spawns.get_mut("player").unwrap()[1] = 2.0;
+        new.spawns.insert("boss".to_string(), [1.0, 1.0]);
+
+        let Diff::Struct(fields) = diff(&old, &new) else {
+            panic!("expected a struct diff");
+        };
+        let Diff::Map(changes) = &fields[0].1 else {
+            panic!("expected a map diff");
+        };
+        assert_eq!(changes.len(), 3);
+        assert_roundtrip(&old, &new);
+    }
+
+    #[test]
+    fn apply_to_dynamic_and_errors() {
+        let old = level();
+        let mut new = level();
+        new.tiles.clear();
+        new.size.0 = 8;
+        let diff = diff(&old, &new);
+
+        let mut dynamic = old.clone_value();
+        apply_diff(dynamic.as_mut(), &diff).unwrap();
+        assert_eq!(Level::from_reflect(dynamic.as_ref()).unwrap(), new);
+
+        let mut other = (1u32, 2u32);
+        assert_eq!(
+            apply_diff(&mut other, &diff),
+            Err(ApplyDiffError::MismatchedKinds {
+                diff: "struct",
+                actual: "tuple"
+            })
+        );
+        assert_eq!(
+            apply_diff(
+                &mut Vec::<u32>::new(),
+                &Diff::List(vec![ListChange::Remove(0)])
+            ),
+            Err(ApplyDiffError::MissingIndex {
+                type_path: Vec::<u32>::type_path().to_string(),
+                index: 0
+            })
+        );
+        assert!(matches!(
+            apply_diff(&mut 1u32, &Diff::Replaced(Box::new(1i32))),
+            Err(ApplyDiffError::MismatchedTypes { .. })
+        ));
+
+        let wall = super::diff(&Tile::Wall(1), &Tile::Wall(2));
+        let mut door = Tile::Door {
+            locked: true,
+            key: None,
+        };
+        assert_eq!(
+            apply_diff(&mut door, &wall),
+            Err(ApplyDiffError::MismatchedVariants {
+                type_path: Tile::type_path().to_string(),
+                expected: "Wall".to_string(),
+                actual: "Door".to_string(),
+            })
+        );
+        let Diff::Enum(_, fields) = wall else {
+            panic!("expected an enum diff");
+        };
+        assert_eq!(
+            apply_diff(&mut door, &fields),
+            Err(ApplyDiffError::MismatchedKinds {
+                diff: "tuple",
+                actual: "enum"
+            })
+        );
+    }
+}
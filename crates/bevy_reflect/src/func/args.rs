@@ -0,0 +1,312 @@
+use crate::{FromReflect, Reflect, TypeInfo, Typed};
+use std::fmt;
+use thiserror::Error;
+
+/// How a value is passed to or returned from a [`DynamicFunction`](super::DynamicFunction).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// The value is owned, like `T`.
+    Owned,
+    /// The value is borrowed, like `&T`.
+    Ref,
+    /// The value is mutably borrowed, like `&mut T`.
+    Mut,
+}
+
+impl fmt::Display for Ownership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ownership::Owned => f.write_str("owned"),
+            Ownership::Ref => f.write_str("reference"),
+            Ownership::Mut => f.write_str("mutable reference"),
+        }
+    }
+}
+
+/// An argument passed to a [`DynamicFunction`](super::DynamicFunction).
+#[derive(Debug)]
+pub enum Arg<'a> {
+    /// An owned value, for parameters of type `T`.
+    Owned(Box<dyn Reflect>),
+    /// A borrowed value, for parameters of type `&T`.
+    Ref(&'a dyn Reflect),
+    /// A mutably borrowed value, for parameters of type `&mut T` or `&T`.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Arg<'a> {
+    /// Returns how the argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        match self {
+            Arg::Owned(_) => Ownership::Owned,
+            Arg::Ref(_) => Ownership::Ref,
+            Arg::Mut(_) => Ownership::Mut,
+        }
+    }
+
+    /// Returns the argument's value.
+    pub fn value(&self) -> &dyn Reflect {
+        match self {
+            Arg::Owned(value) => &**value,
+            Arg::Ref(value) => *value,
+            Arg::Mut(value) => &**value,
+        }
+    }
+}
+
+/// The ordered list of [`Arg`]s a [`DynamicFunction`](super::DynamicFunction) is called with.
+///
+/// ```
+/// # use bevy_reflect::func::ArgList;
+/// let label = "label".to_string();
+/// let mut counter = 0_u32;
+/// let args = ArgList::new()
+///     .push_owned(1_u32)
+///     .push_ref(&label)
+///     .push_mut(&mut counter);
+/// assert_eq!(args.len(), 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct ArgList<'a>(Vec<Arg<'a>>);
+
+impl<'a> ArgList<'a> {
+    /// Creates an empty argument list.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends an [`Arg`] to the list.
+    pub fn push_arg(mut self, arg: Arg<'a>) -> Self {
+        self.0.push(arg);
+        self
+    }
+
+    /// Appends an owned argument to the list.
+    pub fn push_owned(self, value: impl Reflect) -> Self {
+        self.push_arg(Arg::Owned(Box::new(value)))
+    }
+
+    /// Appends a boxed owned argument to the list.
+    pub fn push_boxed(self, value: Box<dyn Reflect>) -> Self {
+        self.push_arg(Arg::Owned(value))
+    }
+
+    /// Appends a borrowed argument to the list.
+    pub fn push_ref(self, value: &'a dyn Reflect) -> Self {
+        self.push_arg(Arg::Ref(value))
+    }
+
+    /// Appends a mutably borrowed argument to the list.
+    pub fn push_mut(self, value: &'a mut dyn Reflect) -> Self {
+        self.push_arg(Arg::Mut(value))
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the arguments.
+    pub fn iter(&self) -> impl Iterator<Item = &Arg<'a>> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for ArgList<'a> {
+    type Item = Arg<'a>;
+    type IntoIter = std::vec::IntoIter<Arg<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> FromIterator<Arg<'a>> for ArgList<'a> {
+    fn from_iter<I: IntoIterator<Item = Arg<'a>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Information about an argument of a [`DynamicFunction`](super::DynamicFunction).
+#[derive(Debug, Clone)]
+pub struct ArgInfo {
+    index: usize,
+    name: Option<String>,
+    ownership: Ownership,
+    type_info: &'static TypeInfo,
+}
+
+impl ArgInfo {
+    /// Creates the information of the argument at `index`, of type `T` passed with `ownership`.
+    pub fn new<T: Typed>(index: usize, ownership: Ownership) -> Self {
+        Self {
+            index,
+            name: None,
+            ownership,
+            type_info: T::type_info(),
+        }
+    }
+
+    /// Sets the name of the argument.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The index of the argument in the [`ArgList`].
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the argument, if it was set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// How the argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [`TypeInfo`] of the argument's type, without the reference for borrowed arguments.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type path] of the argument's type, without the reference for borrowed arguments.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_info.type_path()
+    }
+}
+
+/// An error returned when an [`Arg`] doesn't match the parameter it is passed to.
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ArgError {
+    #[error(
+        "expected a value of type `{expected}` for argument {index} but received `{received}`"
+    )]
+    UnexpectedType {
+        index: usize,
+        expected: &'static str,
+        received: String,
+    },
+    #[error("expected a {expected} value for argument {index} but received a {received} value")]
+    InvalidOwnership {
+        index: usize,
+        expected: Ownership,
+        received: Ownership,
+    },
+}
+
+/// Marker for [`FromArg`] parameters of type `T`.
+pub struct OwnedArg;
+
+/// Marker for [`FromArg`] parameters of type `&T`.
+pub struct RefArg;
+
+/// Marker for [`FromArg`] parameters of type `&mut T`.
+pub struct MutArg;
+
+/// A parameter type of a function that can be converted to a [`DynamicFunction`],
+/// which takes its value from an [`Arg`].
+///
+/// It is implemented for owned types implementing [`FromReflect`], and for references to
+/// [reflected] types. The `Marker` distinguishes these implementations.
+///
+/// [`DynamicFunction`]: super::DynamicFunction
+/// [reflected]: Reflect
+pub trait FromArg<Marker> {
+    /// The parameter type for arguments borrowed for `'a`.
+    type This<'a>;
+
+    /// Returns the [`ArgInfo`] of the parameter at `index`.
+    fn arg_info(index: usize) -> ArgInfo;
+
+    /// Takes the value of the parameter at `index` from `arg`.
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<Self::This<'_>, ArgError>;
+}
+
+impl<T: FromReflect + Typed> FromArg<OwnedArg> for T {
+    type This<'a> = T;
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Owned)
+    }
+
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<T, ArgError> {
+        match arg {
+            Arg::Owned(value) => {
+                T::take_from_reflect(value).map_err(|value| ArgError::UnexpectedType {
+                    index,
+                    expected: T::type_path(),
+                    received: value.reflect_type_path().to_string(),
+                })
+            }
+            arg => Err(ArgError::InvalidOwnership {
+                index,
+                expected: Ownership::Owned,
+                received: arg.ownership(),
+            }),
+        }
+    }
+}
+
+impl<T: Typed> FromArg<RefArg> for &T {
+    type This<'a> = &'a T;
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Ref)
+    }
+
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<&T, ArgError> {
+        let value = match arg {
+            Arg::Ref(value) => value,
+            Arg::Mut(value) => &*value,
+            Arg::Owned(_) => {
+                return Err(ArgError::InvalidOwnership {
+                    index,
+                    expected: Ownership::Ref,
+                    received: Ownership::Owned,
+                })
+            }
+        };
+        value
+            .downcast_ref::<T>()
+            .ok_or_else(|| ArgError::UnexpectedType {
+                index,
+                expected: T::type_path(),
+                received: value.reflect_type_path().to_string(),
+            })
+    }
+}
+
+impl<T: Typed> FromArg<MutArg> for &mut T {
+    type This<'a> = &'a mut T;
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Mut)
+    }
+
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<&mut T, ArgError> {
+        match arg {
+            Arg::Mut(value) if value.is::<T>() => Ok(value.downcast_mut::<T>().unwrap()),
+            Arg::Mut(value) => Err(ArgError::UnexpectedType {
+                index,
+                expected: T::type_path(),
+                received: value.reflect_type_path().to_string(),
+            }),
+            arg => Err(ArgError::InvalidOwnership {
+                index,
+                expected: Ownership::Mut,
+                received: arg.ownership(),
+            }),
+        }
+    }
+}
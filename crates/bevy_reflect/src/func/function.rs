@@ -0,0 +1,263 @@
+use crate::func::{ArgError, ArgInfo, ArgList, IntoFunction, Ownership};
+use crate::{Reflect, TypeInfo, Typed};
+use bevy_utils::HashMap;
+use std::any::TypeId;
+use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// The value returned by a [`DynamicFunction`].
+#[derive(Debug)]
+pub enum Return<'a> {
+    /// The function returned `()`.
+    Unit,
+    /// The function returned an owned value.
+    Owned(Box<dyn Reflect>),
+    /// The function returned a reference borrowed from one of its arguments.
+    Ref(&'a dyn Reflect),
+    /// The function returned a mutable reference borrowed from one of its arguments.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Return<'a> {
+    /// Wraps an owned return value, returning [`Return::Unit`] for `()`.
+    pub fn from_owned<T: Reflect>(value: T) -> Self {
+        if TypeId::of::<T>() == TypeId::of::<()>() {
+            Return::Unit
+        } else {
+            Return::Owned(Box::new(value))
+        }
+    }
+
+    /// Returns `true` if the function returned `()`.
+    pub fn is_unit(&self) -> bool {
+        matches!(self, Return::Unit)
+    }
+
+    /// Returns the owned value, or `None` if the function returned `()` or a reference.
+    pub fn into_owned(self) -> Option<Box<dyn Reflect>> {
+        match self {
+            Return::Owned(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the returned value, or `None` if the function returned `()`.
+    pub fn value(&self) -> Option<&dyn Reflect> {
+        match self {
+            Return::Unit => None,
+            Return::Owned(value) => Some(&**value),
+            Return::Ref(value) => Some(*value),
+            Return::Mut(value) => Some(&**value),
+        }
+    }
+}
+
+/// Information about the value returned by a [`DynamicFunction`].
+#[derive(Debug, Clone)]
+pub struct ReturnInfo {
+    ownership: Ownership,
+    type_info: &'static TypeInfo,
+}
+
+impl ReturnInfo {
+    /// Creates the information of a returned value of type `T`, returned with `ownership`.
+    pub fn new<T: Typed>(ownership: Ownership) -> Self {
+        Self {
+            ownership,
+            type_info: T::type_info(),
+        }
+    }
+
+    /// How the value is returned.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [`TypeInfo`] of the returned type, without the reference for borrowed values.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type path] of the returned type, without the reference for borrowed values.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_info.type_path()
+    }
+}
+
+/// Information about a [`DynamicFunction`]: its name, arguments and returned value.
+#[derive(Debug, Clone)]
+pub struct FunctionInfo {
+    name: String,
+    args: Vec<ArgInfo>,
+    return_info: ReturnInfo,
+}
+
+impl FunctionInfo {
+    /// Creates the information of a function.
+    pub fn new(name: impl Into<String>, args: Vec<ArgInfo>, return_info: ReturnInfo) -> Self {
+        Self {
+            name: name.into(),
+            args,
+            return_info,
+        }
+    }
+
+    /// The name of the function.
+    ///
+    /// Defaults to the [type name](std::any::type_name) of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The information of the function's arguments, in order.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The information of the function's returned value.
+    pub fn return_info(&self) -> &ReturnInfo {
+        &self.return_info
+    }
+}
+
+/// An error returned when calling a [`DynamicFunction`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum FunctionError {
+    #[error(transparent)]
+    Arg(#[from] ArgError),
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCount { expected: usize, received: usize },
+}
+
+/// The result of calling a [`DynamicFunction`].
+pub type FunctionResult<'a> = Result<Return<'a>, FunctionError>;
+
+type DynamicCall = dyn for<'a> Fn(ArgList<'a>) -> FunctionResult<'a> + Send + Sync;
+
+/// A function or method that can be called with reflected arguments.
+///
+/// It is created from a Rust function or closure with [`IntoFunction::into_function`], and holds
+/// the [`FunctionInfo`] describing its arguments and returned value.
+///
+/// ```
+/// # use bevy_reflect::func::{ArgList, IntoFunction};
+/// fn add(a: i32, b: &i32) -> i32 {
+///     a + *b
+/// }
+///
+/// let add = add.into_function().with_name("add");
+/// assert_eq!(add.info().args()[1].type_path(), "i32");
+///
+/// let args = ArgList::new().push_owned(2_i32).push_ref(&3_i32);
+/// let sum = add.call(args).unwrap().into_owned().unwrap();
+/// assert_eq!(sum.downcast_ref::<i32>(), Some(&5));
+/// ```
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    call: Arc<DynamicCall>,
+}
+
+impl DynamicFunction {
+    /// Creates a function from `info` and a `call` taking the arguments.
+    ///
+    /// [`IntoFunction::into_function`] should be preferred, since `info` isn't checked against
+    /// the arguments `call` expects.
+    pub fn new<F>(info: FunctionInfo, call: F) -> Self
+    where
+        F: for<'a> Fn(ArgList<'a>) -> FunctionResult<'a> + Send + Sync + 'static,
+    {
+        Self {
+            info,
+            call: Arc::new(call),
+        }
+    }
+
+    /// Sets the name of the function.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.info.name = name.into();
+        self
+    }
+
+    /// Sets the names of the function's arguments, in order.
+    pub fn with_arg_names<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        for (arg, name) in self.info.args.iter_mut().zip(names) {
+            *arg = arg.clone().with_name(name);
+        }
+        self
+    }
+
+    /// Returns the information of the function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// Calls the function with `args`.
+    ///
+    /// The returned value may borrow from the arguments.
+    pub fn call<'a>(&self, args: ArgList<'a>) -> FunctionResult<'a> {
+        let expected = self.info.args.len();
+        if args.len() != expected {
+            return Err(FunctionError::ArgCount {
+                expected,
+                received: args.len(),
+            });
+        }
+        (self.call)(args)
+    }
+}
+
+impl fmt::Debug for DynamicFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Type data holding the functions registered for a type, such as its methods.
+///
+/// Functions are added with [`TypeRegistry::register_function_for`] and named after their
+/// name in the type's `impl` block.
+///
+/// [`TypeRegistry::register_function_for`]: crate::TypeRegistry::register_function_for
+#[derive(Debug, Clone, Default)]
+pub struct ReflectFunctions {
+    functions: HashMap<String, DynamicFunction>,
+}
+
+impl ReflectFunctions {
+    /// Adds `function` with the given `name`, replacing any function with the same name.
+    pub fn insert<Marker>(&mut self, name: impl Into<String>, function: impl IntoFunction<Marker>) {
+        let name = name.into();
+        let function = function.into_function().with_name(name.clone());
+        self.functions.insert(name, function);
+    }
+
+    /// Returns the function with the given `name`.
+    pub fn get(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the functions.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
+
+    /// Returns the number of functions.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns `true` if there are no functions.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
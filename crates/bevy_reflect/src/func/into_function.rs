@@ -0,0 +1,173 @@
+use crate::func::{
+    Arg, ArgList, DynamicFunction, FromArg, FunctionInfo, MutArg, Ownership, RefArg, Return,
+    ReturnInfo,
+};
+use crate::Typed;
+use bevy_utils::all_tuples;
+
+/// A Rust function, method or closure that can be converted to a [`DynamicFunction`].
+///
+/// It is implemented for functions with up to 12 parameters that:
+/// - take owned values implementing [`FromReflect`], or references to [reflected] values,
+/// - return an owned reflected value, or `()`.
+///
+/// Functions whose first parameter is a reference, like methods taking `&self` or `&mut self`,
+/// may also return a reference borrowed from it.
+///
+/// All parameter and returned types must implement [`Typed`], which provides the [`TypeInfo`]
+/// of the [`FunctionInfo`].
+/// The `Marker` distinguishes the shapes of functions and can be ignored.
+///
+/// ```
+/// # use bevy_reflect::{func::{ArgList, IntoFunction}, Reflect};
+/// #[derive(Reflect)]
+/// struct Inventory {
+///     items: Vec<String>,
+/// }
+///
+/// impl Inventory {
+///     fn first(&self) -> &String {
+///         &self.items[0]
+///     }
+/// }
+///
+/// let inventory = Inventory { items: vec!["sword".to_string()] };
+/// let first = Inventory::first.into_function();
+///
+/// let returned = first.call(ArgList::new().push_ref(&inventory)).unwrap();
+/// assert_eq!(returned.value().unwrap().downcast_ref::<String>().unwrap(), "sword");
+/// ```
+///
+/// [`FromReflect`]: crate::FromReflect
+/// [reflected]: Reflect
+/// [`TypeInfo`]: crate::TypeInfo
+pub trait IntoFunction<Marker> {
+    /// Converts the function to a [`DynamicFunction`].
+    fn into_function(self) -> DynamicFunction;
+}
+
+impl IntoFunction<()> for DynamicFunction {
+    fn into_function(self) -> DynamicFunction {
+        self
+    }
+}
+
+/// Takes the next argument, counting its index.
+fn next_arg<'a>(args: &mut std::vec::IntoIter<Arg<'a>>, index: &mut usize) -> (Arg<'a>, usize) {
+    let arg = args.next().expect("the argument count was checked");
+    *index += 1;
+    (arg, *index - 1)
+}
+
+macro_rules! impl_into_function {
+    ($(($Arg:ident, $Marker:ident)),*) => {
+        // Functions returning owned values.
+        impl<Function, ReturnType, $($Arg, $Marker),*>
+            IntoFunction<(fn($($Arg),*) -> ReturnType, ($($Marker,)*))> for Function
+        where
+            $($Arg: FromArg<$Marker>,)*
+            ReturnType: Typed,
+            Function: Fn($($Arg),*) -> ReturnType + Send + Sync + 'static,
+            Function: for<'a> Fn($($Arg::This<'a>),*) -> ReturnType,
+        {
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn into_function(self) -> DynamicFunction {
+                let mut index = 0;
+                let args = vec![$($Arg::arg_info({ index += 1; index - 1 })),*];
+                let info = FunctionInfo::new(
+                    std::any::type_name::<Function>(),
+                    args,
+                    ReturnInfo::new::<ReturnType>(Ownership::Owned),
+                );
+                DynamicFunction::new(info, move |args: ArgList<'_>| {
+                    let (mut args, mut index) = (args.into_iter(), 0);
+                    $(let $Arg = {
+                        let (arg, index) = next_arg(&mut args, &mut index);
+                        $Arg::from_arg(arg, index)?
+                    };)*
+                    Ok(Return::from_owned((self)($($Arg),*)))
+                })
+            }
+        }
+
+        // Methods returning a reference borrowed from `&self`.
+        impl<Function, Receiver, ReturnType, $($Arg, $Marker),*>
+            IntoFunction<(fn(&Receiver, $($Arg),*) -> &ReturnType, ($($Marker,)*))> for Function
+        where
+            $($Arg: FromArg<$Marker>,)*
+            Receiver: Typed,
+            ReturnType: Typed,
+            Function: for<'a> Fn(&'a Receiver, $($Arg),*) -> &'a ReturnType + Send + Sync + 'static,
+            Function: for<'a> Fn(&'a Receiver, $($Arg::This<'a>),*) -> &'a ReturnType,
+        {
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn into_function(self) -> DynamicFunction {
+                let mut index = 1;
+                let args = vec![
+                    <&Receiver as FromArg<RefArg>>::arg_info(0),
+                    $($Arg::arg_info({ index += 1; index - 1 })),*
+                ];
+                let info = FunctionInfo::new(
+                    std::any::type_name::<Function>(),
+                    args,
+                    ReturnInfo::new::<ReturnType>(Ownership::Ref),
+                );
+                DynamicFunction::new(info, move |args: ArgList<'_>| {
+                    let (mut args, mut index) = (args.into_iter(), 0);
+                    let receiver = {
+                        let (arg, index) = next_arg(&mut args, &mut index);
+                        <&Receiver as FromArg<RefArg>>::from_arg(arg, index)?
+                    };
+                    $(let $Arg = {
+                        let (arg, index) = next_arg(&mut args, &mut index);
+                        $Arg::from_arg(arg, index)?
+                    };)*
+                    Ok(Return::Ref((self)(receiver, $($Arg),*).as_reflect()))
+                })
+            }
+        }
+
+        // Methods returning a mutable reference borrowed from `&mut self`.
+        impl<Function, Receiver, ReturnType, $($Arg, $Marker),*>
+            IntoFunction<(fn(&mut Receiver, $($Arg),*) -> &mut ReturnType, ($($Marker,)*))>
+            for Function
+        where
+            $($Arg: FromArg<$Marker>,)*
+            Receiver: Typed,
+            ReturnType: Typed,
+            Function: for<'a> Fn(&'a mut Receiver, $($Arg),*) -> &'a mut ReturnType
+                + Send
+                + Sync
+                + 'static,
+            Function: for<'a> Fn(&'a mut Receiver, $($Arg::This<'a>),*) -> &'a mut ReturnType,
+        {
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn into_function(self) -> DynamicFunction {
+                let mut index = 1;
+                let args = vec![
+                    <&mut Receiver as FromArg<MutArg>>::arg_info(0),
+                    $($Arg::arg_info({ index += 1; index - 1 })),*
+                ];
+                let info = FunctionInfo::new(
+                    std::any::type_name::<Function>(),
+                    args,
+                    ReturnInfo::new::<ReturnType>(Ownership::Mut),
+                );
+                DynamicFunction::new(info, move |args: ArgList<'_>| {
+                    let (mut args, mut index) = (args.into_iter(), 0);
+                    let receiver = {
+                        let (arg, index) = next_arg(&mut args, &mut index);
+                        <&mut Receiver as FromArg<MutArg>>::from_arg(arg, index)?
+                    };
+                    $(let $Arg = {
+                        let (arg, index) = next_arg(&mut args, &mut index);
+                        $Arg::from_arg(arg, index)?
+                    };)*
+                    Ok(Return::Mut((self)(receiver, $($Arg),*).as_reflect_mut()))
+                })
+            }
+        }
+    };
+}
+
+all_tuples!(impl_into_function, 0, 12, Arg, Marker);
@@ -0,0 +1,261 @@
+//! Reflection of functions and methods.
+//!
+//! A Rust function, method or closure can be converted to a [`DynamicFunction`] with
+//! [`IntoFunction`], if its parameters and returned value are [reflected] types or references
+//! to them. The [`DynamicFunction`] can then be called with an [`ArgList`] of reflected values,
+//! and described by its [`FunctionInfo`].
+//!
+//! Each [`Arg`] is passed as an owned value, a reference or a mutable reference, matching the
+//! [`Ownership`] of the parameter. Borrowed arguments only need to outlive the call,
+//! unless the function returns a reference borrowed from its first argument, like a method
+//! returning a reference to a field of `self`.
+//!
+//! Functions can be registered in the [`TypeRegistry`] to be looked up by name: free functions
+//! with [`TypeRegistry::register_function`], and functions associated with a type, like its
+//! methods, with [`TypeRegistry::register_function_for`] which stores them in the
+//! [`ReflectFunctions`] type data of the type.
+//!
+//! ```
+//! # use bevy_reflect::{func::{ArgList, ReflectFunctions}, Reflect, TypeRegistry};
+//! #[derive(Reflect, Default)]
+//! struct Player {
+//!     health: f32,
+//! }
+//!
+//! impl Player {
+//!     fn heal(&mut self, amount: f32) {
+//!         self.health += amount;
+//!     }
+//! }
+//!
+//! fn greet(name: String) -> String {
+//!     format!("Hello, {name}!")
+//! }
+//!
+//! let mut registry = TypeRegistry::new();
+//! registry.register::<Player>();
+//! registry.register_function_for::<Player, _>("heal", Player::heal);
+//! registry.register_function("greet", greet);
+//!
+//! let mut player = Player::default();
+//! let heal = registry
+//!     .get_type_data::<ReflectFunctions>(std::any::TypeId::of::<Player>())
+//!     .and_then(|functions| functions.get("heal"))
+//!     .unwrap();
+//! heal.call(ArgList::new().push_mut(&mut player).push_owned(5.0_f32)).unwrap();
+//! assert_eq!(player.health, 5.0);
+//!
+//! let greet = registry.get_function("greet").unwrap();
+//! assert_eq!(greet.info().args()[0].type_path(), "alloc::string::String");
+//! let greeting = greet.call(ArgList::new().push_owned("Ferris".to_string())).unwrap();
+//! assert_eq!(
+//!     greeting.value().unwrap().downcast_ref::<String>().unwrap(),
+//!     "Hello, Ferris!"
+//! );
+//! ```
+//!
+//! [reflected]: crate::Reflect
+//! [`TypeRegistry`]: crate::TypeRegistry
+//! [`TypeRegistry::register_function`]: crate::TypeRegistry::register_function
+//! [`TypeRegistry::register_function_for`]: crate::TypeRegistry::register_function_for
+
+mod args;
+mod function;
+mod into_function;
+
+pub use args::*;
+pub use function::*;
+pub use into_function::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, Reflect, TypeRegistry};
+    use std::any::TypeId;
+
+    #[derive(Reflect, Debug, Default, PartialEq)]
+    struct Player {
+        name: String,
+        health: f32,
+        inventory: Vec<String>,
+    }
+
+    impl Player {
+        fn new(name: String) -> Self {
+            Self {
+                name,
+                ..Default::default()
+            }
+        }
+
+        fn damage(&mut self, amount: f32, multiplier: &f32) -> f32 {
+            self.health -= amount * multiplier;
+            self.health
+        }
+
+        fn name(&self) -> &String {
+            &self.name
+        }
+
+        fn item_mut(&mut self, index: usize) -> &mut String {
+            &mut self.inventory[index]
+        }
+    }
+
+    #[test]
+    fn function_info() {
+        let function = Player::damage.into_function();
+        let info = function.info();
+        assert!(info.name().ends_with("Player::damage"));
+
+        let ownerships: Vec<_> = info.args().iter().map(ArgInfo::ownership).collect();
+        assert_eq!(
+            ownerships,
+            [Ownership::Mut, Ownership::Owned, Ownership::Ref]
+        );
+        assert!(info.args()[0].type_info().is::<Player>());
+        assert_eq!(info.args()[2].type_path(), "f32");
+        assert_eq!(info.return_info().ownership(), Ownership::Owned);
+        assert!(info.return_info().type_info().is::<f32>());
+
+        let info = Player::name.into_function().info().clone();
+        assert_eq!(info.return_info().ownership(), Ownership::Ref);
+        assert!(info.return_info().type_info().is::<String>());
+
+        let named = Player::damage
+            .into_function()
+            .with_name("damage")
+            .with_arg_names(["self", "amount", "multiplier"]);
+        assert_eq!(named.info().name(), "damage");
+        assert_eq!(named.info().args()[1].name(), Some("amount"));
+    }
+
+    #[test]
+    fn call_with_owned_ref_and_mut_args() {
+        let mut player = Player::new("Ferris".to_string());
+        player.health = 10.0;
+
+        let damage = Player::damage.into_function();
+        let args = ArgList::new()
+            .push_mut(&mut player)
+            .push_owned(2.0_f32)
+            .push_ref(&1.5_f32);
+        let health = damage.call(args).unwrap().into_owned().unwrap();
+        assert_eq!(health.downcast_ref::<f32>(), Some(&7.0));
+        assert_eq!(player.health, 7.0);
+
+        let new = Player::new.into_function();
+        let created = new
+            .call(ArgList::new().push_owned("Crab".to_string()))
+            .unwrap();
+        assert_eq!(
+            created
+                .value()
+                .unwrap()
+                .reflect_partial_eq(&Player::new("Crab".to_string())),
+            Some(true)
+        );
+
+        let closure = (|a: i32, b: i32| a * b).into_function();
+        let product = closure
+            .call(ArgList::new().push_owned(6).push_owned(7))
+            .unwrap();
+        assert_eq!(product.value().unwrap().downcast_ref::<i32>(), Some(&42));
+
+        let unit = (|player: &mut Player| player.health = 0.0).into_function();
+        assert!(unit
+            .call(ArgList::new().push_mut(&mut player))
+            .unwrap()
+            .is_unit());
+        assert_eq!(player.health, 0.0);
+    }
+
+    #[test]
+    fn return_borrowed_values() {
+        let mut player = Player::new("Ferris".to_string());
+        player.inventory.push("sword".to_string());
+
+        let name = Player::name.into_function();
+        let returned = name.call(ArgList::new().push_ref(&player)).unwrap();
+        let Return::Ref(value) = returned else {
+            panic!("expected a reference");
+        };
+        assert_eq!(value.downcast_ref::<String>().unwrap(), "Ferris");
+
+        let item_mut = Player::item_mut.into_function();
+        let returned = item_mut
+            .call(ArgList::new().push_mut(&mut player).push_owned(0_usize))
+            .unwrap();
+        let Return::Mut(value) = returned else {
+            panic!("expected a mutable reference");
+        };
+        value.apply(&"shield".to_string());
+        assert_eq!(player.inventory[0], "shield");
+    }
+
+    #[test]
+    fn call_errors() {
+        let damage = Player::damage.into_function();
+        let mut player = Player::default();
+
+        assert_eq!(
+            damage
+                .call(ArgList::new().push_mut(&mut player))
+                .unwrap_err(),
+            FunctionError::ArgCount {
+                expected: 3,
+                received: 1
+            }
+        );
+        assert_eq!(
+            damage
+                .call(
+                    ArgList::new()
+                        .push_mut(&mut player)
+                        .push_owned(1.0_f64)
+                        .push_ref(&1.0_f32)
+                )
+                .unwrap_err(),
+            FunctionError::Arg(ArgError::UnexpectedType {
+                index: 1,
+                expected: "f32",
+                received: "f64".to_string(),
+            })
+        );
+        assert_eq!(
+            damage
+                .call(
+                    ArgList::new()
+                        .push_ref(&Player::default())
+                        .push_owned(1.0_f32)
+                        .push_ref(&1.0_f32)
+                )
+                .unwrap_err(),
+            FunctionError::Arg(ArgError::InvalidOwnership {
+                index: 0,
+                expected: Ownership::Mut,
+                received: Ownership::Ref,
+            })
+        );
+    }
+
+    #[test]
+    fn registered_functions() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry.register_function_for::<Player, _>("new", Player::new);
+        registry.register_function_for::<Player, _>("damage", Player::damage);
+        registry.register_function("double", |value: f32| value * 2.0);
+
+        let functions = registry
+            .get_type_data::<ReflectFunctions>(TypeId::of::<Player>())
+            .unwrap();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions.get("damage").unwrap().info().name(), "damage");
+
+        let double = registry.get_function("double").unwrap();
+        let doubled = double.call(ArgList::new().push_owned(4.0_f32)).unwrap();
+        assert_eq!(doubled.value().unwrap().downcast_ref::<f32>(), Some(&8.0));
+        assert_eq!(registry.functions().count(), 1);
+    }
+}
@@ -381,6 +381,13 @@
 //! assert_eq!(original_value, converted_value);
 //! ```
 //!
+//! For binary formats, the [`CompactReflectSerializer`] and [`CompactReflectDeserializer`] write
+//! a [`TypeTable`] header mapping short ids to type paths and field names,
+//! so that values only reference their type by id.
+//!
+//! When a type changes, values serialized with an older layout can still be deserialized by
+//! registering [`ReflectMigrations`] for the type with [`TypeRegistry::register_migration`].
+//!
 //! # Limitations
 //!
 //! While this crate offers a lot in terms of adding reflection to Rust,
@@ -399,10 +406,10 @@
 //! Another limitation is the inability to fully reflect functions and methods.
 //! Most languages offer some way of calling methods dynamically,
 //! but Rust makes this very difficult to do.
-//! For non-generic methods, this can be done by registering custom [type data] that
-//! contains function pointers.
-//! For generic methods, the same can be done but will typically require manual monomorphization
-//! (i.e. manually specifying the types the generic method can take).
+//! Non-generic functions and methods can be converted to a [`DynamicFunction`] and called with
+//! reflected arguments, see the [`func`] module.
+//! Generic ones will require manual monomorphization
+//! (i.e. manually specifying the types the generic function can take).
 //!
 //! ## Manual Registration
 //!
@@ -437,7 +444,21 @@
 //! This can be useful for generating documentation for scripting language interop or
 //! for displaying tooltips in an editor.
 //!
+//! ## `json_schema`
+//!
+//! | Default | Dependencies     |
+//! | :-----: | :--------------: |
+//! | ❌      | [`serde_json`]   |
+//!
+//! This feature enables generating [JSON Schema] documents describing the output of the
+//! [reflection serializers] for the types in a [type registry],
+//! see `serde::typed_json_schema` and `serde::reflect_json_schema`.
+//! When the `documentation` feature is also enabled, doc comments are included as descriptions.
+//!
 //! [Reflection]: https://en.wikipedia.org/wiki/Reflective_programming
+//! [JSON Schema]: https://json-schema.org/
+//! [`serde_json`]: https://docs.rs/serde_json
+//! [reflection serializers]: serde::ReflectSerializer
 //! [Bevy]: https://bevyengine.org/
 //! [limitations]: #limitations
 //! [`bevy_reflect`]: crate
@@ -454,10 +475,15 @@
 //! [`TypedReflectSerializer`]: serde::TypedReflectSerializer
 //! [`UntypedReflectDeserializer`]: serde::UntypedReflectDeserializer
 //! [`TypedReflectDeserializer`]: serde::TypedReflectDeserializer
+//! [`CompactReflectSerializer`]: serde::CompactReflectSerializer
+//! [`CompactReflectDeserializer`]: serde::CompactReflectDeserializer
+//! [`TypeTable`]: serde::TypeTable
+//! [`ReflectMigrations`]: serde::ReflectMigrations
 //! [registry]: TypeRegistry
 //! [type information]: TypeInfo
 //! [type path]: TypePath
 //! [type registry]: TypeRegistry
+//! [`DynamicFunction`]: func::DynamicFunction
 //! [`bevy_math`]: https://docs.rs/bevy_math/latest/bevy_math/
 //! [`glam`]: https://docs.rs/glam/latest/glam/
 //! [`smallvec`]: https://docs.rs/smallvec/latest/smallvec/
@@ -466,6 +492,7 @@
 //! [derive `Reflect`]: derive@crate::Reflect
 
 mod array;
+mod diff;
 mod fields;
 mod from_reflect;
 mod list;
@@ -495,6 +522,7 @@ mod impls {
 }
 
 mod enums;
+pub mod func;
 pub mod serde;
 pub mod std_traits;
 pub mod utility;
@@ -510,6 +538,7 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use diff::*;
 pub use enums::*;
 pub use fields::*;
 pub use from_reflect::*;
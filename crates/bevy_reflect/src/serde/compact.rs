@@ -0,0 +1,1426 @@
+use crate::serde::ser::get_serializable;
+use crate::serde::{MigrationError, ReflectMigrations, SerializationData};
+use crate::{
+    DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, DynamicVariant, Enum, Map, Reflect, ReflectDeserialize, ReflectRef,
+    TupleStruct, TypeInfo, TypeRegistration, TypeRegistry, VariantInfo,
+};
+use bevy_utils::{HashMap, HashSet};
+use serde::de::{DeserializeSeed, Error as _, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::ser::{Error as _, SerializeMap, SerializeSeq, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::any::TypeId;
+use std::fmt::{self, Formatter};
+use thiserror::Error;
+
+/// The version of the compact format written by [`TypeTable`].
+///
+/// Tables written by a newer version of the format can't be deserialized.
+///
+/// Version 2 added the versions of the types to the table.
+pub const COMPACT_FORMAT_VERSION: u32 = 2;
+
+/// The schema of a type in a [`TypeTable`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeSchema {
+    /// The [type path] of the type.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub type_path: String,
+    /// How the values of the type are serialized.
+    pub kind: SchemaKind,
+    /// The version of the type, from its [`ReflectMigrations`].
+    ///
+    /// Serialized after the schemas by the [`TypeTable`], so that tables written before types
+    /// had versions can still be read.
+    #[serde(skip)]
+    pub version: u32,
+}
+
+/// How the values of a type in a [`TypeTable`] are serialized.
+///
+/// The types of fields, items, keys and values are referenced by their id in the table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaKind {
+    /// A struct, serialized as a tuple of these fields.
+    Struct(Vec<SchemaField>),
+    /// A tuple struct, serialized as a tuple of fields of these types.
+    TupleStruct(Vec<u32>),
+    /// A tuple, serialized as a tuple of fields of these types.
+    Tuple(Vec<u32>),
+    /// A list, serialized as a sequence of items of this type.
+    List(u32),
+    /// An array, serialized as a tuple of the given length of items of this type.
+    Array(u32, usize),
+    /// A map, serialized as a map from keys of the first type to values of the second type.
+    Map(u32, u32),
+    /// An enum, serialized as a tuple of the index of the variant and of its fields.
+    Enum(Vec<SchemaVariant>),
+    /// A value type, serialized with its [`ReflectSerialize`](crate::ReflectSerialize).
+    Value,
+}
+
+/// A named field of a [`SchemaKind::Struct`] or of a [`SchemaVariantKind::Struct`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaField {
+    /// The name of the field.
+    pub name: String,
+    /// The id of the type of the field.
+    pub id: u32,
+}
+
+/// A variant of a [`SchemaKind::Enum`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaVariant {
+    /// The name of the variant.
+    pub name: String,
+    /// The fields of the variant.
+    pub kind: SchemaVariantKind,
+}
+
+/// The fields of a [`SchemaVariant`], serialized as a tuple.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaVariantKind {
+    /// A struct variant with these fields.
+    Struct(Vec<SchemaField>),
+    /// A tuple variant with fields of these types.
+    Tuple(Vec<u32>),
+    /// A unit variant.
+    Unit,
+}
+
+/// A change made to a type since a [`TypeTable`] was written.
+///
+/// See [`TypeTable::changes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// A field was added to a struct, or to a struct variant of an enum.
+    FieldAdded {
+        type_path: String,
+        variant: Option<String>,
+        field: String,
+    },
+    /// A field was removed from a struct, or from a struct variant of an enum.
+    FieldRemoved {
+        type_path: String,
+        variant: Option<String>,
+        field: String,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (type_path, variant, field, change) = match self {
+            SchemaChange::FieldAdded {
+                type_path,
+                variant,
+                field,
+            } => (type_path, variant, field, "added to"),
+            SchemaChange::FieldRemoved {
+                type_path,
+                variant,
+                field,
+            } => (type_path, variant, field, "removed from"),
+        };
+        match variant {
+            Some(variant) => write!(f, "field `{field}` was {change} `{type_path}::{variant}`"),
+            None => write!(f, "field `{field}` was {change} `{type_path}`"),
+        }
+    }
+}
+
+/// An error returned when adding a value to a [`TypeTable`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum TypeTableError {
+    #[error("cannot describe dynamic value without represented type: `{0}`")]
+    MissingTypeInfo(String),
+}
+
+/// The header of the compact serialization format, mapping short numeric ids to the schemas of
+/// the types of the serialized values.
+///
+/// Values serialized with a [`CompactValueSerializer`] reference their type by its id instead of
+/// its type path, and are serialized without field names, making binary formats like `bincode`
+/// much smaller than with a [`ReflectSerializer`](crate::serde::ReflectSerializer).
+///
+/// The table records the fields of each struct as they were when it was written. A value whose
+/// type has gained or lost fields since can still be deserialized: the removed fields are
+/// dropped and the added fields are left out of the returned dynamic value. These changes can
+/// be listed with [`TypeTable::changes`].
+///
+/// The table also records the version of each type with [`ReflectMigrations`]. Values of a type
+/// written at an older version, including values nested in other values, are deserialized with
+/// the layout of that version and migrated to the current version.
+///
+/// A table is serialized as its [`COMPACT_FORMAT_VERSION`] followed by its schemas in id order,
+/// and by the ids and versions of the types that aren't at version 0.
+#[derive(Debug, Clone, Default)]
+pub struct TypeTable {
+    types: Vec<TypeSchema>,
+    ids: HashMap<String, u32>,
+    /// Types missing from the registry, described once one of their values is added.
+    unresolved: HashSet<u32>,
+}
+
+impl TypeTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the type of `value`, and the types of the values it contains, to the table.
+    ///
+    /// Returns the id of the type of `value`.
+    pub fn register_value(
+        &mut self,
+        value: &dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Result<u32, TypeTableError> {
+        let info = value.get_represented_type_info().ok_or_else(|| {
+            TypeTableError::MissingTypeInfo(value.reflect_type_path().to_string())
+        })?;
+        let id = self.register_type_info(info, registry);
+
+        let data = registry.get_type_data::<SerializationData>(info.type_id());
+        let skipped = |index: usize| data.is_some_and(|data| data.is_field_skipped(index));
+        match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    if !skipped(index) {
+                        self.register_value(field, registry)?;
+                    }
+                }
+            }
+            ReflectRef::TupleStruct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    if !skipped(index) {
+                        self.register_value(field, registry)?;
+                    }
+                }
+            }
+            ReflectRef::Tuple(value) => {
+                for field in value.iter_fields() {
+                    self.register_value(field, registry)?;
+                }
+            }
+            ReflectRef::List(value) => {
+                for item in value.iter() {
+                    self.register_value(item, registry)?;
+                }
+            }
+            ReflectRef::Array(value) => {
+                for item in value.iter() {
+                    self.register_value(item, registry)?;
+                }
+            }
+            ReflectRef::Map(value) => {
+                for (key, value) in value.iter() {
+                    self.register_value(key, registry)?;
+                    self.register_value(value, registry)?;
+                }
+            }
+            ReflectRef::Enum(value) => {
+                for field in value.iter_fields() {
+                    self.register_value(field.value(), registry)?;
+                }
+            }
+            ReflectRef::Value(_) => {}
+        }
+
+        Ok(id)
+    }
+
+    /// Adds a type, and the types it contains, to the table.
+    ///
+    /// Contained types that aren't registered in `registry` are only described once one of
+    /// their values is added with [`TypeTable::register_value`].
+    ///
+    /// Returns the id of the type.
+    pub fn register_type_info(&mut self, info: &'static TypeInfo, registry: &TypeRegistry) -> u32 {
+        let id = match self.ids.get(info.type_path()) {
+            Some(&id) if !self.unresolved.remove(&id) => return id,
+            Some(&id) => id,
+            None => self.push(info.type_path()),
+        };
+        self.types[id as usize].kind = self.schema_kind(info, registry);
+        self.types[id as usize].version = registry
+            .get_type_data::<ReflectMigrations>(info.type_id())
+            .map_or(0, ReflectMigrations::version);
+        id
+    }
+
+    /// Returns the id of the type with the given [type path].
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn id(&self, type_path: &str) -> Option<u32> {
+        self.ids.get(type_path).copied()
+    }
+
+    /// Returns the schema of the type with the given id.
+    pub fn get(&self, id: u32) -> Option<&TypeSchema> {
+        self.types.get(id as usize)
+    }
+
+    /// Returns an iterator over the schemas of the table, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &TypeSchema> {
+        self.types.iter()
+    }
+
+    /// Returns the number of types in the table.
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns `true` if the table has no types.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Returns the fields added to and removed from the types of the table since it was written,
+    /// comparing it to the types registered in `registry`.
+    ///
+    /// Types written at an older version are compared to the layout of that version, which is
+    /// migrated rather than changed. Fields skipped during serialization are ignored.
+    pub fn changes(&self, registry: &TypeRegistry) -> Vec<SchemaChange> {
+        let mut changes = Vec::new();
+        for schema in &self.types {
+            let Some(registration) = registry
+                .get_with_type_path(&schema.type_path)
+                .and_then(|registration| schema_layout(schema, registration).ok())
+            else {
+                continue;
+            };
+            match (&schema.kind, registration.type_info()) {
+                (SchemaKind::Struct(fields), TypeInfo::Struct(info)) => {
+                    let data = registration.data::<SerializationData>();
+                    let current = info
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| {
+                            !data.is_some_and(|data| data.is_field_skipped(*index))
+                        })
+                        .map(|(_, field)| field.name());
+                    compare_fields(schema, None, fields, current, &mut changes);
+                }
+                (SchemaKind::Enum(variants), TypeInfo::Enum(info)) => {
+                    for variant in variants {
+                        if let (
+                            SchemaVariantKind::Struct(fields),
+                            Some(VariantInfo::Struct(info)),
+                        ) = (&variant.kind, info.variant(&variant.name))
+                        {
+                            let current = info.iter().map(|field| field.name());
+                            compare_fields(
+                                schema,
+                                Some(&variant.name),
+                                fields,
+                                current,
+                                &mut changes,
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        changes
+    }
+
+    fn push(&mut self, type_path: &str) -> u32 {
+        let id = self.types.len() as u32;
+        self.types.push(TypeSchema {
+            type_path: type_path.to_string(),
+            kind: SchemaKind::Value,
+            version: 0,
+        });
+        self.ids.insert(type_path.to_string(), id);
+        id
+    }
+
+    fn register_type(&mut self, type_id: TypeId, type_path: &str, registry: &TypeRegistry) -> u32 {
+        if let Some(&id) = self.ids.get(type_path) {
+            return id;
+        }
+        match registry.get_type_info(type_id) {
+            Some(info) => self.register_type_info(info, registry),
+            None => {
+                let id = self.push(type_path);
+                self.unresolved.insert(id);
+                id
+            }
+        }
+    }
+
+    fn schema_field(
+        &mut self,
+        name: &str,
+        type_id: TypeId,
+        type_path: &str,
+        registry: &TypeRegistry,
+    ) -> SchemaField {
+        SchemaField {
+            name: name.to_string(),
+            id: self.register_type(type_id, type_path, registry),
+        }
+    }
+
+    fn schema_kind(&mut self, info: &'static TypeInfo, registry: &TypeRegistry) -> SchemaKind {
+        let data = registry.get_type_data::<SerializationData>(info.type_id());
+        let skipped = |index: usize| data.is_some_and(|data| data.is_field_skipped(index));
+        match info {
+            TypeInfo::Struct(info) => SchemaKind::Struct(
+                info.iter()
+                    .enumerate()
+                    .filter(|(index, _)| !skipped(*index))
+                    .map(|(_, field)| {
+                        self.schema_field(
+                            field.name(),
+                            field.type_id(),
+                            field.type_path(),
+                            registry,
+                        )
+                    })
+                    .collect(),
+            ),
+            TypeInfo::TupleStruct(info) => SchemaKind::TupleStruct(
+                info.iter()
+                    .enumerate()
+                    .filter(|(index, _)| !skipped(*index))
+                    .map(|(_, field)| {
+                        self.register_type(field.type_id(), field.type_path(), registry)
+                    })
+                    .collect(),
+            ),
+            TypeInfo::Tuple(info) => SchemaKind::Tuple(
+                info.iter()
+                    .map(|field| self.register_type(field.type_id(), field.type_path(), registry))
+                    .collect(),
+            ),
+            TypeInfo::List(info) => SchemaKind::List(self.register_type(
+                info.item_type_id(),
+                info.item_type_path_table().path(),
+                registry,
+            )),
+            TypeInfo::Array(info) => SchemaKind::Array(
+                self.register_type(
+                    info.item_type_id(),
+                    info.item_type_path_table().path(),
+                    registry,
+                ),
+                info.capacity(),
+            ),
+            TypeInfo::Map(info) => SchemaKind::Map(
+                self.register_type(
+                    info.key_type_id(),
+                    info.key_type_path_table().path(),
+                    registry,
+                ),
+                self.register_type(
+                    info.value_type_id(),
+                    info.value_type_path_table().path(),
+                    registry,
+                ),
+            ),
+            TypeInfo::Enum(info) => SchemaKind::Enum(
+                info.iter()
+                    .map(|variant| SchemaVariant {
+                        name: variant.name().to_string(),
+                        kind: match variant {
+                            VariantInfo::Struct(variant) => SchemaVariantKind::Struct(
+                                variant
+                                    .iter()
+                                    .map(|field| {
+                                        self.schema_field(
+                                            field.name(),
+                                            field.type_id(),
+                                            field.type_path(),
+                                            registry,
+                                        )
+                                    })
+                                    .collect(),
+                            ),
+                            VariantInfo::Tuple(variant) => SchemaVariantKind::Tuple(
+                                variant
+                                    .iter()
+                                    .map(|field| {
+                                        self.register_type(
+                                            field.type_id(),
+                                            field.type_path(),
+                                            registry,
+                                        )
+                                    })
+                                    .collect(),
+                            ),
+                            VariantInfo::Unit(_) => SchemaVariantKind::Unit,
+                        },
+                    })
+                    .collect(),
+            ),
+            TypeInfo::Value(_) => SchemaKind::Value,
+        }
+    }
+}
+
+/// Returns the registration describing the layout of the values of `schema`, which is the
+/// registration of a previous version of the type if the schema is at an older version.
+fn schema_layout<'a>(
+    schema: &TypeSchema,
+    registration: &'a TypeRegistration,
+) -> Result<&'a TypeRegistration, MigrationError> {
+    let migrations = registration.data::<ReflectMigrations>();
+    let current = migrations.map_or(0, ReflectMigrations::version);
+    if schema.version == current {
+        return Ok(registration);
+    }
+    migrations
+        .and_then(|migrations| migrations.previous(schema.version))
+        .ok_or_else(|| MigrationError::UnknownVersion {
+            type_path: schema.type_path.clone(),
+            version: schema.version,
+            current,
+        })
+}
+
+fn compare_fields<'a>(
+    schema: &TypeSchema,
+    variant: Option<&str>,
+    saved: &[SchemaField],
+    current: impl Iterator<Item = &'a str>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let current: Vec<&str> = current.collect();
+    let variant = variant.map(str::to_string);
+    for &name in &current {
+        if !saved.iter().any(|field| field.name == name) {
+            changes.push(SchemaChange::FieldAdded {
+                type_path: schema.type_path.clone(),
+                variant: variant.clone(),
+                field: name.to_string(),
+            });
+        }
+    }
+    for field in saved {
+        if !current.contains(&field.name.as_str()) {
+            changes.push(SchemaChange::FieldRemoved {
+                type_path: schema.type_path.clone(),
+                variant: variant.clone(),
+                field: field.name.clone(),
+            });
+        }
+    }
+}
+
+impl Serialize for TypeTable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let versions: Vec<(u32, u32)> = (0..)
+            .zip(&self.types)
+            .filter(|(_, schema)| schema.version != 0)
+            .map(|(id, schema)| (id, schema.version))
+            .collect();
+        (COMPACT_FORMAT_VERSION, &self.types, versions).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeTable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TypeTableVisitor;
+
+        impl<'de> Visitor<'de> for TypeTableVisitor {
+            type Value = Vec<TypeSchema>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("type table")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let version: u32 = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                if version > COMPACT_FORMAT_VERSION {
+                    return Err(A::Error::custom(format_args!(
+                        "unsupported compact format version {version}, expected at most {COMPACT_FORMAT_VERSION}"
+                    )));
+                }
+                let mut types: Vec<TypeSchema> = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+                // Tables of version 1 don't have versions, all their types are at version 0.
+                if version >= 2 {
+                    let versions: Vec<(u32, u32)> = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+                    for (id, version) in versions {
+                        let schema = types.get_mut(id as usize).ok_or_else(|| {
+                            A::Error::custom(format_args!("unknown type id {id}"))
+                        })?;
+                        schema.version = version;
+                    }
+                }
+                Ok(types)
+            }
+        }
+
+        let types = deserializer.deserialize_tuple(3, TypeTableVisitor)?;
+        let ids = types
+            .iter()
+            .enumerate()
+            .map(|(id, schema)| (schema.type_path.clone(), id as u32))
+            .collect();
+        Ok(Self {
+            types,
+            ids,
+            unresolved: HashSet::default(),
+        })
+    }
+}
+
+/// A serializer for a reflected value, following the schema of its type in a [`TypeTable`].
+///
+/// The value must have been added to the table with [`TypeTable::register_value`].
+pub struct CompactValueSerializer<'a> {
+    value: &'a dyn Reflect,
+    id: u32,
+    table: &'a TypeTable,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> CompactValueSerializer<'a> {
+    /// Creates a serializer for `value`, whose type has the given id in `table`.
+    pub fn new(
+        value: &'a dyn Reflect,
+        id: u32,
+        table: &'a TypeTable,
+        registry: &'a TypeRegistry,
+    ) -> Self {
+        Self {
+            value,
+            id,
+            table,
+            registry,
+        }
+    }
+
+    fn with(&self, value: &'a dyn Reflect, id: u32) -> Self {
+        Self::new(value, id, self.table, self.registry)
+    }
+
+    fn serialize_fields<S>(
+        &self,
+        serializer: S,
+        schema: &TypeSchema,
+        ids: &[u32],
+        fields: Vec<&'a dyn Reflect>,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if fields.len() != ids.len() {
+            return Err(S::Error::custom(format_args!(
+                "expected {} fields for `{}` but received {}",
+                ids.len(),
+                schema.type_path,
+                fields.len()
+            )));
+        }
+        let mut state = serializer.serialize_tuple(ids.len())?;
+        for (field, &id) in fields.into_iter().zip(ids) {
+            state.serialize_element(&self.with(field, id))?;
+        }
+        state.end()
+    }
+}
+
+impl<'a> Serialize for CompactValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let schema = self
+            .table
+            .get(self.id)
+            .ok_or_else(|| S::Error::custom(format_args!("unknown type id {}", self.id)))?;
+        let data = self
+            .registry
+            .get_with_type_path(&schema.type_path)
+            .and_then(TypeRegistration::data::<SerializationData>);
+        let skipped = |index: usize| data.is_some_and(|data| data.is_field_skipped(index));
+
+        match (&schema.kind, self.value.reflect_ref()) {
+            (SchemaKind::Struct(fields), ReflectRef::Struct(value)) => {
+                let mut state = serializer.serialize_tuple(fields.len())?;
+                for field in fields {
+                    let field_value = value.field(&field.name).ok_or_else(|| {
+                        S::Error::custom(format_args!(
+                            "missing field `{}` of `{}`",
+                            field.name, schema.type_path
+                        ))
+                    })?;
+                    state.serialize_element(&self.with(field_value, field.id))?;
+                }
+                state.end()
+            }
+            (SchemaKind::TupleStruct(ids), ReflectRef::TupleStruct(value)) => {
+                let fields = value
+                    .iter_fields()
+                    .enumerate()
+                    .filter(|(index, _)| !skipped(*index))
+                    .map(|(_, field)| field)
+                    .collect();
+                self.serialize_fields(serializer, schema, ids, fields)
+            }
+            (SchemaKind::Tuple(ids), ReflectRef::Tuple(value)) => {
+                self.serialize_fields(serializer, schema, ids, value.iter_fields().collect())
+            }
+            (&SchemaKind::List(item), ReflectRef::List(value)) => {
+                let mut state = serializer.serialize_seq(Some(value.len()))?;
+                for value in value.iter() {
+                    state.serialize_element(&self.with(value, item))?;
+                }
+                state.end()
+            }
+            (&SchemaKind::Array(item, len), ReflectRef::Array(value)) => {
+                let ids = vec![item; len];
+                self.serialize_fields(serializer, schema, &ids, value.iter().collect())
+            }
+            (&SchemaKind::Map(key, value_id), ReflectRef::Map(value)) => {
+                let mut state = serializer.serialize_map(Some(value.len()))?;
+                for (key_value, value) in value.iter() {
+                    state
+                        .serialize_entry(&self.with(key_value, key), &self.with(value, value_id))?;
+                }
+                state.end()
+            }
+            (SchemaKind::Enum(variants), ReflectRef::Enum(value)) => {
+                let (index, variant) = variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, variant)| variant.name == value.variant_name())
+                    .ok_or_else(|| {
+                        S::Error::custom(format_args!(
+                            "unknown variant `{}` of `{}`",
+                            value.variant_name(),
+                            schema.type_path
+                        ))
+                    })?;
+                let mut state = serializer.serialize_tuple(2)?;
+                state.serialize_element(&(index as u32))?;
+                state.serialize_element(&VariantSerializer {
+                    value,
+                    variant,
+                    parent: self,
+                })?;
+                state.end()
+            }
+            (SchemaKind::Value, ReflectRef::Value(value)) => {
+                get_serializable::<S::Error>(value, self.registry)?
+                    .borrow()
+                    .serialize(serializer)
+            }
+            _ => Err(S::Error::custom(format_args!(
+                "value of `{}` doesn't match its schema",
+                schema.type_path
+            ))),
+        }
+    }
+}
+
+struct VariantSerializer<'a, 'b> {
+    value: &'a dyn Enum,
+    variant: &'b SchemaVariant,
+    parent: &'b CompactValueSerializer<'a>,
+}
+
+impl<'a, 'b> Serialize for VariantSerializer<'a, 'b> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.variant.kind {
+            SchemaVariantKind::Struct(fields) => {
+                let mut state = serializer.serialize_tuple(fields.len())?;
+                for field in fields {
+                    let field_value = self.value.field(&field.name).ok_or_else(|| {
+                        S::Error::custom(format_args!(
+                            "missing field `{}` of variant `{}`",
+                            field.name, self.variant.name
+                        ))
+                    })?;
+                    state.serialize_element(&self.parent.with(field_value, field.id))?;
+                }
+                state.end()
+            }
+            SchemaVariantKind::Tuple(ids) => {
+                let mut state = serializer.serialize_tuple(ids.len())?;
+                for (index, &id) in ids.iter().enumerate() {
+                    let field_value = self.value.field_at(index).ok_or_else(|| {
+                        S::Error::custom(format_args!(
+                            "missing field {index} of variant `{}`",
+                            self.variant.name
+                        ))
+                    })?;
+                    state.serialize_element(&self.parent.with(field_value, id))?;
+                }
+                state.end()
+            }
+            SchemaVariantKind::Unit => serializer.serialize_tuple(0)?.end(),
+        }
+    }
+}
+
+/// A deserializer for reflected values serialized with a [`CompactValueSerializer`].
+///
+/// The values are deserialized as dynamic types, like [`DynamicStruct`], representing their type
+/// if it is registered with the same kind.
+/// Value types must be registered with [`ReflectDeserialize`].
+///
+/// Values of types written at an older version are migrated to the current version with the
+/// [`ReflectMigrations`] of their type.
+#[derive(Clone, Copy)]
+pub struct CompactValueDeserializer<'a> {
+    id: u32,
+    table: &'a TypeTable,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> CompactValueDeserializer<'a> {
+    /// Creates a deserializer for a value whose type has the given id in `table`.
+    pub fn new(id: u32, table: &'a TypeTable, registry: &'a TypeRegistry) -> Self {
+        Self {
+            id,
+            table,
+            registry,
+        }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactValueDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let schema = self
+            .table
+            .get(self.id)
+            .ok_or_else(|| D::Error::custom(format_args!("unknown type id {}", self.id)))?;
+        let registration = self.registry.get_with_type_path(&schema.type_path);
+        // Values of older versions are deserialized with the layout of their version.
+        let layout = registration
+            .map(|registration| schema_layout(schema, registration))
+            .transpose()
+            .map_err(D::Error::custom)?;
+        let visitor = SchemaVisitor {
+            schema,
+            registration: layout,
+            seed: self,
+        };
+        let value = match &schema.kind {
+            SchemaKind::Struct(fields) => deserializer.deserialize_tuple(fields.len(), visitor),
+            SchemaKind::TupleStruct(ids) | SchemaKind::Tuple(ids) => {
+                deserializer.deserialize_tuple(ids.len(), visitor)
+            }
+            SchemaKind::List(_) => deserializer.deserialize_seq(visitor),
+            &SchemaKind::Array(_, len) => deserializer.deserialize_tuple(len, visitor),
+            SchemaKind::Map(..) => deserializer.deserialize_map(visitor),
+            SchemaKind::Enum(_) => deserializer.deserialize_tuple(2, visitor),
+            SchemaKind::Value => {
+                let reflect_deserialize = layout
+                    .and_then(TypeRegistration::data::<ReflectDeserialize>)
+                    .ok_or_else(|| {
+                        D::Error::custom(format_args!(
+                            "type `{}` is not registered with ReflectDeserialize",
+                            schema.type_path
+                        ))
+                    })?;
+                reflect_deserialize.deserialize(deserializer)
+            }
+        }?;
+        if let Some(registration) = registration {
+            if let Some(migrations) = registration.data::<ReflectMigrations>() {
+                return migrations
+                    .migrate(value, schema.version, registration)
+                    .map_err(D::Error::custom);
+            }
+        }
+        Ok(value)
+    }
+}
+
+fn next_value<'de, A>(
+    seq: &mut A,
+    seed: CompactValueDeserializer,
+    type_path: &str,
+) -> Result<Box<dyn Reflect>, A::Error>
+where
+    A: SeqAccess<'de>,
+{
+    seq.next_element_seed(seed)?
+        .ok_or_else(|| A::Error::custom(format_args!("missing fields of `{type_path}`")))
+}
+
+struct SchemaVisitor<'a> {
+    schema: &'a TypeSchema,
+    registration: Option<&'a TypeRegistration>,
+    seed: CompactValueDeserializer<'a>,
+}
+
+impl<'a> SchemaVisitor<'a> {
+    fn seed(&self, id: u32) -> CompactValueDeserializer<'a> {
+        CompactValueDeserializer { id, ..self.seed }
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SchemaVisitor<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "compact value of `{}`", self.schema.type_path)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let type_path = self.schema.type_path.as_str();
+        let info = self.registration.map(TypeRegistration::type_info);
+        let data = self
+            .registration
+            .and_then(TypeRegistration::data::<SerializationData>);
+
+        Ok(match &self.schema.kind {
+            SchemaKind::Struct(fields) => {
+                let current = match info {
+                    Some(TypeInfo::Struct(current)) => Some(current),
+                    _ => None,
+                };
+                let mut value = DynamicStruct::default();
+                for field in fields {
+                    let field_value = next_value(&mut seq, self.seed(field.id), type_path)?;
+                    // Fields removed from the type since the value was serialized are dropped.
+                    let is_current = match current {
+                        Some(current) => current.field(&field.name).is_some(),
+                        None => true,
+                    };
+                    if is_current {
+                        value.insert_boxed(&field.name, field_value);
+                    }
+                }
+                if let Some(current) = current {
+                    for (index, skipped_field) in data.iter().flat_map(|data| data.iter_skipped()) {
+                        if let Some(field) = current.field_at(*index) {
+                            value.insert_boxed(field.name(), skipped_field.generate_default());
+                        }
+                    }
+                    value.set_represented_type(info);
+                }
+                Box::new(value)
+            }
+            SchemaKind::TupleStruct(ids) => {
+                let is_current = matches!(info, Some(TypeInfo::TupleStruct(_)));
+                let data = data.filter(|_| is_current);
+                let skipped_default =
+                    |index: usize| data.and_then(|data| data.generate_default(index));
+                let mut value = DynamicTupleStruct::default();
+                for &id in ids {
+                    while let Some(default) = skipped_default(value.field_len()) {
+                        value.insert_boxed(default);
+                    }
+                    value.insert_boxed(next_value(&mut seq, self.seed(id), type_path)?);
+                }
+                while let Some(default) = skipped_default(value.field_len()) {
+                    value.insert_boxed(default);
+                }
+                if is_current {
+                    value.set_represented_type(info);
+                }
+                Box::new(value)
+            }
+            SchemaKind::Tuple(ids) => {
+                let mut value = DynamicTuple::default();
+                for &id in ids {
+                    value.insert_boxed(next_value(&mut seq, self.seed(id), type_path)?);
+                }
+                if matches!(info, Some(TypeInfo::Tuple(_))) {
+                    value.set_represented_type(info);
+                }
+                Box::new(value)
+            }
+            &SchemaKind::List(item) => {
+                let mut value = DynamicList::default();
+                while let Some(item) = seq.next_element_seed(self.seed(item))? {
+                    value.push_box(item);
+                }
+                if matches!(info, Some(TypeInfo::List(_))) {
+                    value.set_represented_type(info);
+                }
+                Box::new(value)
+            }
+            &SchemaKind::Array(item, len) => {
+                let items = (0..len)
+                    .map(|_| next_value(&mut seq, self.seed(item), type_path))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut value = DynamicArray::new(items.into_boxed_slice());
+                if matches!(info, Some(TypeInfo::Array(_))) {
+                    value.set_represented_type(info);
+                }
+                Box::new(value)
+            }
+            SchemaKind::Enum(variants) => {
+                let index: u32 = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                let variant = variants.get(index as usize).ok_or_else(|| {
+                    A::Error::custom(format_args!("no variant at index {index} of `{type_path}`"))
+                })?;
+                let current = match info {
+                    Some(TypeInfo::Enum(current)) => Some(current),
+                    _ => None,
+                };
+                let dynamic_variant = seq
+                    .next_element_seed(VariantDeserializer {
+                        variant,
+                        current: current.and_then(|current| current.variant(&variant.name)),
+                        visitor: &self,
+                    })?
+                    .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+                let mut value = DynamicEnum::new(&variant.name, dynamic_variant);
+                if current.is_some() {
+                    value.set_represented_type(info);
+                }
+                Box::new(value)
+            }
+            SchemaKind::Map(..) | SchemaKind::Value => {
+                return Err(A::Error::invalid_type(Unexpected::Seq, &self));
+            }
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let &SchemaKind::Map(key, value_id) = &self.schema.kind else {
+            return Err(A::Error::invalid_type(Unexpected::Map, &self));
+        };
+        let mut value = DynamicMap::default();
+        while let Some(key) = map.next_key_seed(self.seed(key))? {
+            value.insert_boxed(key, map.next_value_seed(self.seed(value_id))?);
+        }
+        if let Some(info @ TypeInfo::Map(_)) = self.registration.map(TypeRegistration::type_info) {
+            value.set_represented_type(Some(info));
+        }
+        Ok(Box::new(value))
+    }
+}
+
+struct VariantDeserializer<'a, 'b> {
+    variant: &'a SchemaVariant,
+    current: Option<&'a VariantInfo>,
+    visitor: &'b SchemaVisitor<'a>,
+}
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for VariantDeserializer<'a, 'b> {
+    type Value = DynamicVariant;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let len = match &self.variant.kind {
+            SchemaVariantKind::Struct(fields) => fields.len(),
+            SchemaVariantKind::Tuple(ids) => ids.len(),
+            SchemaVariantKind::Unit => 0,
+        };
+        deserializer.deserialize_tuple(len, self)
+    }
+}
+
+impl<'a, 'b, 'de> Visitor<'de> for VariantDeserializer<'a, 'b> {
+    type Value = DynamicVariant;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "fields of variant `{}`", self.variant.name)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let type_path = self.visitor.schema.type_path.as_str();
+        Ok(match &self.variant.kind {
+            SchemaVariantKind::Struct(fields) => {
+                let mut value = DynamicStruct::default();
+                for field in fields {
+                    let field_value = next_value(&mut seq, self.visitor.seed(field.id), type_path)?;
+                    let is_current = match self.current {
+                        Some(VariantInfo::Struct(current)) => current.field(&field.name).is_some(),
+                        _ => true,
+                    };
+                    if is_current {
+                        value.insert_boxed(&field.name, field_value);
+                    }
+                }
+                DynamicVariant::Struct(value)
+            }
+            SchemaVariantKind::Tuple(ids) => {
+                let mut value = DynamicTuple::default();
+                for &id in ids {
+                    value.insert_boxed(next_value(&mut seq, self.visitor.seed(id), type_path)?);
+                }
+                DynamicVariant::Tuple(value)
+            }
+            SchemaVariantKind::Unit => DynamicVariant::Unit,
+        })
+    }
+}
+
+/// A general purpose serializer for reflected values in the compact format.
+///
+/// The serialized data is a tuple of:
+/// 1. The [`TypeTable`] of the type of the value and of the types it contains
+/// 2. The id of the type of the value in the table
+/// 3. The value, serialized with a [`CompactValueSerializer`]
+///
+/// Since the table describes every type the value contains, it pays off for large values, like
+/// long lists. Collections of many small values, like scenes, should rather share a single table
+/// and serialize each value with a [`CompactValueSerializer`].
+///
+/// ```
+/// # use bevy_reflect::{serde::{CompactReflectDeserializer, CompactReflectSerializer}, Reflect, TypeRegistry};
+/// # use bincode::Options;
+/// #[derive(Reflect, Debug, PartialEq)]
+/// struct Inventory {
+///     items: Vec<Item>,
+/// }
+///
+/// #[derive(Reflect, Debug, PartialEq)]
+/// struct Item {
+///     name: String,
+///     count: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Inventory>();
+///
+/// let inventory = Inventory {
+///     items: vec![
+///         Item { name: "sword".to_string(), count: 1 },
+///         Item { name: "arrow".to_string(), count: 20 },
+///     ],
+/// };
+///
+/// let bytes = bincode::serialize(&CompactReflectSerializer::new(&inventory, &registry)).unwrap();
+///
+/// let value = bincode::DefaultOptions::new()
+///     .with_fixint_encoding()
+///     .deserialize_seed(CompactReflectDeserializer::new(&registry), &bytes)
+///     .unwrap();
+/// assert!(value.reflect_partial_eq(&inventory).unwrap());
+/// ```
+pub struct CompactReflectSerializer<'a> {
+    pub value: &'a dyn Reflect,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> CompactReflectSerializer<'a> {
+    pub fn new(value: &'a dyn Reflect, registry: &'a TypeRegistry) -> Self {
+        CompactReflectSerializer { value, registry }
+    }
+}
+
+impl<'a> Serialize for CompactReflectSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut table = TypeTable::new();
+        let id = table
+            .register_value(self.value, self.registry)
+            .map_err(S::Error::custom)?;
+        let mut state = serializer.serialize_tuple(3)?;
+        state.serialize_element(&table)?;
+        state.serialize_element(&id)?;
+        state.serialize_element(&CompactValueSerializer::new(
+            self.value,
+            id,
+            &table,
+            self.registry,
+        ))?;
+        state.end()
+    }
+}
+
+/// A general purpose deserializer for reflected values serialized with a
+/// [`CompactReflectSerializer`].
+///
+/// The value is deserialized with a [`CompactValueDeserializer`], using the [`TypeTable`]
+/// serialized with it.
+pub struct CompactReflectDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> CompactReflectDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(3, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for CompactReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("compact reflected value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let table: TypeTable = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let id: u32 = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        seq.next_element_seed(CompactValueDeserializer::new(id, &table, self.registry))?
+            .ok_or_else(|| A::Error::invalid_length(2, &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::ReflectSerializer;
+    use crate::{self as bevy_reflect, FromReflect, TypePath};
+    use bincode::Options;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        inventory: Vec<Item>,
+        stats: HashMap<String, u32>,
+        #[reflect(skip_serializing)]
+        #[reflect(default = "default_health")]
+        health: u32,
+        state: State,
+    }
+
+    fn default_health() -> u32 {
+        100
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Item(String, [u8; 2]);
+
+    #[derive(Reflect, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Walking(f32),
+        Attacking { target: Option<u32> },
+    }
+
+    fn player(state: State) -> Player {
+        Player {
+            name: "Ferris".to_string(),
+            position: (1.0, -2.5),
+            inventory: vec![
+                Item("sword".to_string(), [1, 2]),
+                Item("shield".to_string(), [3, 4]),
+            ],
+            stats: HashMap::from([("strength".to_string(), 12)]),
+            health: 42,
+            state,
+        }
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry.register::<State>();
+        registry
+    }
+
+    #[test]
+    fn should_roundtrip_bincode() {
+        let registry = registry();
+        let states = [
+            State::Idle,
+            State::Walking(0.5),
+            State::Attacking { target: Some(7) },
+        ];
+        for state in states {
+            let value = player(state);
+            let bytes =
+                bincode::serialize(&CompactReflectSerializer::new(&value, &registry)).unwrap();
+            let deserialized = bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .deserialize_seed(CompactReflectDeserializer::new(&registry), &bytes)
+                .unwrap();
+
+            let mut expected = value;
+            expected.health = default_health();
+            assert_eq!(
+                Some(expected),
+                <Player as FromReflect>::from_reflect(&*deserialized)
+            );
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_ron() {
+        let registry = registry();
+        let value = player(State::Attacking { target: None });
+        let ron = ron::to_string(&CompactReflectSerializer::new(&value, &registry)).unwrap();
+
+        let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+        let deserialized = CompactReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert!(deserialized.represents::<Player>());
+        assert_eq!(
+            Some(player(State::Attacking { target: None })),
+            <Player as FromReflect>::from_reflect(&*deserialized).map(|mut player| {
+                player.health = 42;
+                player
+            })
+        );
+    }
+
+    #[test]
+    fn should_reference_types_by_id() {
+        let registry = registry();
+        let value = player(State::Idle);
+        let mut table = TypeTable::new();
+        let id = table.register_value(&value, &registry).unwrap();
+
+        assert_eq!(Some(id), table.id(Player::type_path()));
+        let Some(TypeSchema {
+            kind: SchemaKind::List(item),
+            ..
+        }) = table.get(table.id(Vec::<Item>::type_path()).unwrap())
+        else {
+            panic!("expected a list schema");
+        };
+        assert_eq!(Some(*item), table.id(Item::type_path()));
+
+        let Some(TypeSchema {
+            kind: SchemaKind::Struct(fields),
+            ..
+        }) = table.get(id)
+        else {
+            panic!("expected a struct schema");
+        };
+        let names: Vec<_> = fields.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(names, ["name", "position", "inventory", "stats", "state"]);
+
+        let compact =
+            bincode::serialize(&CompactValueSerializer::new(&value, id, &table, &registry))
+                .unwrap();
+        let bytes = bincode::serialize(&ReflectSerializer::new(&value, &registry)).unwrap();
+        assert!(compact.len() < bytes.len());
+    }
+
+    #[test]
+    fn should_detect_schema_changes() {
+        mod v1 {
+            use crate::{self as bevy_reflect, Reflect};
+
+            #[derive(Reflect)]
+            pub struct Save {
+                pub level: u32,
+                pub score: u64,
+            }
+        }
+
+        mod v2 {
+            use crate::{self as bevy_reflect, Reflect};
+
+            #[derive(Reflect, Debug, PartialEq)]
+            pub struct Save {
+                pub level: u32,
+                pub checkpoint: String,
+            }
+        }
+
+        let mut old_registry = TypeRegistry::new();
+        old_registry.register::<v1::Save>();
+        let mut registry = TypeRegistry::new();
+        registry.register::<v2::Save>();
+
+        let save = v1::Save {
+            level: 3,
+            score: 1200,
+        };
+        let ron = ron::to_string(&CompactReflectSerializer::new(&save, &old_registry))
+            .unwrap()
+            .replace("::v1::", "::v2::");
+
+        let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+        let (table, _, _): (TypeTable, u32, ron::Value) =
+            serde::Deserialize::deserialize(&mut deserializer).unwrap();
+        let type_path = v2::Save::type_path().to_string();
+        assert_eq!(
+            table.changes(&registry),
+            [
+                SchemaChange::FieldAdded {
+                    type_path: type_path.clone(),
+                    variant: None,
+                    field: "checkpoint".to_string(),
+                },
+                SchemaChange::FieldRemoved {
+                    type_path,
+                    variant: None,
+                    field: "score".to_string(),
+                },
+            ]
+        );
+
+        let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+        let deserialized = CompactReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let mut current = v2::Save {
+            level: 1,
+            checkpoint: "start".to_string(),
+        };
+        current.apply(&*deserialized);
+        assert_eq!(
+            v2::Save {
+                level: 3,
+                checkpoint: "start".to_string(),
+            },
+            current
+        );
+    }
+
+    #[test]
+    fn should_reject_newer_versions() {
+        let ron = format!("({}, [])", COMPACT_FORMAT_VERSION + 1);
+        assert!(ron::from_str::<TypeTable>(&ron).is_err());
+        assert!(ron::from_str::<TypeTable>(&format!("({COMPACT_FORMAT_VERSION}, [], [])")).is_ok());
+        // Tables of version 1 have no versions.
+        assert!(ron::from_str::<TypeTable>("(1, [])").is_ok());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::serde::SerializationData;
+use crate::serde::{
+    ReflectMigrations, SerializationData, TypeVersions, VersionedReflectDeserializer,
+    VersionedTypeRegistrationDeserializer,
+};
 use crate::{
     ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
     DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField, Reflect,
@@ -305,15 +308,19 @@ impl<'de> Visitor<'de> for U32Visitor {
 ///
 /// Because the type isn't known ahead of time, the serialized data must take the form of
 /// a map containing the following entries (in order):
-/// 1. `type`: The _full_ [type path]
+/// 1. `type`: The _full_ [type path], optionally followed by `@` and the version of the type
 /// 2. `value`: The serialized value of the reflected type
 ///
+/// Values of older versions of a type are migrated to the current version with the
+/// [`ReflectMigrations`] registered for the type.
+///
 /// If the type is already known and the [`TypeInfo`] for it can be retrieved,
 /// [`TypedReflectDeserializer`] may be used instead to avoid requiring these entries.
 ///
 /// [`Box<dyn Reflect>`]: crate::Reflect
 /// [`FromReflect`]: crate::FromReflect
 /// [type path]: crate::TypePath::type_path
+/// [`ReflectMigrations`]: crate::serde::ReflectMigrations
 pub struct UntypedReflectDeserializer<'a> {
     registry: &'a TypeRegistry,
 }
@@ -400,14 +407,15 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
     where
         A: MapAccess<'de>,
     {
-        let registration = map
-            .next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        let (registration, version) = map
+            .next_key_seed(VersionedTypeRegistrationDeserializer::new(self.registry))?
             .ok_or_else(|| Error::invalid_length(0, &"a single entry"))?;
 
-        let value = map.next_value_seed(TypedReflectDeserializer {
+        let value = map.next_value_seed(VersionedReflectDeserializer::new(
             registration,
-            registry: self.registry,
-        })?;
+            version,
+            self.registry,
+        ))?;
 
         if map.next_key::<IgnoredAny>()?.is_some() {
             return Err(Error::invalid_length(2, &"a single entry"));
@@ -435,6 +443,7 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
 pub struct TypedReflectDeserializer<'a> {
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a> TypedReflectDeserializer<'a> {
@@ -442,14 +451,27 @@ impl<'a> TypedReflectDeserializer<'a> {
         Self {
             registration,
             registry,
+            versions: None,
         }
     }
-}
-
-impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
-    type Value = Box<dyn Reflect>;
 
-    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    /// Deserializes the value and the values nested in it as serialized at the versions recorded
+    /// in `versions`, returning `self`.
+    ///
+    /// Values of types recorded at an older version than their current one are deserialized
+    /// with the layout of that version and migrated, like with a
+    /// [`VersionedReflectDeserializer`]. Types missing from `versions` are at version 0.
+    /// Without versions, all values are deserialized with the current layout of their type.
+    pub fn with_versions(mut self, versions: &'a TypeVersions) -> Self {
+        self.versions = Some(versions);
+        self
+    }
+
+    /// Deserializes the value with the current layout of its type, whatever its version.
+    pub(super) fn deserialize_current<'de, D>(
+        self,
+        deserializer: D,
+    ) -> Result<Box<dyn Reflect>, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
@@ -470,6 +492,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                         struct_info,
                         registration: self.registration,
                         registry: self.registry,
+                        versions: self.versions,
                     },
                 )?;
                 dynamic_struct.set_represented_type(Some(self.registration.type_info()));
@@ -482,6 +505,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     TupleStructVisitor {
                         tuple_struct_info,
                         registry: self.registry,
+                        versions: self.versions,
                         registration: self.registration,
                     },
                 )?;
@@ -492,6 +516,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                 let mut dynamic_list = deserializer.deserialize_seq(ListVisitor {
                     list_info,
                     registry: self.registry,
+                    versions: self.versions,
                 })?;
                 dynamic_list.set_represented_type(Some(self.registration.type_info()));
                 Ok(Box::new(dynamic_list))
@@ -502,6 +527,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     ArrayVisitor {
                         array_info,
                         registry: self.registry,
+                        versions: self.versions,
                     },
                 )?;
                 dynamic_array.set_represented_type(Some(self.registration.type_info()));
@@ -511,6 +537,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                 let mut dynamic_map = deserializer.deserialize_map(MapVisitor {
                     map_info,
                     registry: self.registry,
+                    versions: self.versions,
                 })?;
                 dynamic_map.set_represented_type(Some(self.registration.type_info()));
                 Ok(Box::new(dynamic_map))
@@ -522,6 +549,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                         tuple_info,
                         registration: self.registration,
                         registry: self.registry,
+                        versions: self.versions,
                     },
                 )?;
                 dynamic_tuple.set_represented_type(Some(self.registration.type_info()));
@@ -535,6 +563,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     deserializer.deserialize_option(OptionVisitor {
                         enum_info,
                         registry: self.registry,
+                        versions: self.versions,
                     })?
                 } else {
                     deserializer.deserialize_enum(
@@ -544,6 +573,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                             enum_info,
                             registration: self.registration,
                             registry: self.registry,
+                            versions: self.versions,
                         },
                     )?
                 };
@@ -560,10 +590,38 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
     }
 }
 
+impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if let Some(versions) = self.versions {
+            let version = versions.get(self.registration.type_info().type_path());
+            let current = self
+                .registration
+                .data::<ReflectMigrations>()
+                .map_or(0, ReflectMigrations::version);
+            if version != current {
+                return VersionedReflectDeserializer::new(
+                    self.registration,
+                    version,
+                    self.registry,
+                )
+                .with_versions(versions)
+                .deserialize(deserializer);
+            }
+        }
+        self.deserialize_current(deserializer)
+    }
+}
+
 struct StructVisitor<'a> {
     struct_info: &'static StructInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
@@ -577,20 +635,33 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
-        visit_struct_seq(&mut seq, self.struct_info, self.registration, self.registry)
+        visit_struct_seq(
+            &mut seq,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.versions,
+        )
     }
 
     fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
     where
         V: MapAccess<'de>,
     {
-        visit_struct(&mut map, self.struct_info, self.registration, self.registry)
+        visit_struct(
+            &mut map,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.versions,
+        )
     }
 }
 
 struct TupleStructVisitor<'a> {
     tuple_struct_info: &'static TupleStructInfo,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
     registration: &'a TypeRegistration,
 }
 
@@ -610,6 +681,7 @@ impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
             self.tuple_struct_info,
             self.registration,
             self.registry,
+            self.versions,
         )
         .map(DynamicTupleStruct::from)
     }
@@ -619,6 +691,7 @@ struct TupleVisitor<'a> {
     tuple_info: &'static TupleInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
@@ -632,13 +705,20 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
     where
         V: SeqAccess<'de>,
     {
-        visit_tuple(&mut seq, self.tuple_info, self.registration, self.registry)
+        visit_tuple(
+            &mut seq,
+            self.tuple_info,
+            self.registration,
+            self.registry,
+            self.versions,
+        )
     }
 }
 
 struct ArrayVisitor<'a> {
     array_info: &'static ArrayInfo,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
@@ -661,6 +741,7 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
         while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
             registration,
             registry: self.registry,
+            versions: self.versions,
         })? {
             vec.push(value);
         }
@@ -679,6 +760,7 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
 struct ListVisitor<'a> {
     list_info: &'static ListInfo,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
@@ -701,6 +783,7 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
         while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
             registration,
             registry: self.registry,
+            versions: self.versions,
         })? {
             list.push_box(value);
         }
@@ -711,6 +794,7 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
 struct MapVisitor<'a> {
     map_info: &'static MapInfo,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
@@ -738,10 +822,12 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
         while let Some(key) = map.next_key_seed(TypedReflectDeserializer {
             registration: key_registration,
             registry: self.registry,
+            versions: self.versions,
         })? {
             let value = map.next_value_seed(TypedReflectDeserializer {
                 registration: value_registration,
                 registry: self.registry,
+                versions: self.versions,
             })?;
             dynamic_map.insert_boxed(key, value);
         }
@@ -754,6 +840,7 @@ struct EnumVisitor<'a> {
     enum_info: &'static EnumInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
@@ -781,6 +868,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                         struct_info,
                         registration: self.registration,
                         registry: self.registry,
+                        versions: self.versions,
                     },
                 )?
                 .into(),
@@ -789,6 +877,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                 let value = variant.newtype_variant_seed(TypedReflectDeserializer {
                     registration,
                     registry: self.registry,
+                    versions: self.versions,
                 })?;
                 let mut dynamic_tuple = DynamicTuple::default();
                 dynamic_tuple.insert_boxed(value);
@@ -801,6 +890,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                         tuple_info,
                         registration: self.registration,
                         registry: self.registry,
+                        versions: self.versions,
                     },
                 )?
                 .into(),
@@ -867,6 +957,7 @@ struct StructVariantVisitor<'a> {
     struct_info: &'static StructVariantInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
@@ -880,14 +971,26 @@ impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
-        visit_struct_seq(&mut seq, self.struct_info, self.registration, self.registry)
+        visit_struct_seq(
+            &mut seq,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.versions,
+        )
     }
 
     fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
     where
         V: MapAccess<'de>,
     {
-        visit_struct(&mut map, self.struct_info, self.registration, self.registry)
+        visit_struct(
+            &mut map,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.versions,
+        )
     }
 }
 
@@ -895,6 +998,7 @@ struct TupleVariantVisitor<'a> {
     tuple_info: &'static TupleVariantInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for TupleVariantVisitor<'a> {
@@ -908,13 +1012,20 @@ impl<'a, 'de> Visitor<'de> for TupleVariantVisitor<'a> {
     where
         V: SeqAccess<'de>,
     {
-        visit_tuple(&mut seq, self.tuple_info, self.registration, self.registry)
+        visit_tuple(
+            &mut seq,
+            self.tuple_info,
+            self.registration,
+            self.registry,
+            self.versions,
+        )
     }
 }
 
 struct OptionVisitor<'a> {
     enum_info: &'static EnumInfo,
     registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
 }
 
 impl<'a, 'de> Visitor<'de> for OptionVisitor<'a> {
@@ -947,6 +1058,7 @@ impl<'a, 'de> Visitor<'de> for OptionVisitor<'a> {
                 let de = TypedReflectDeserializer {
                     registration,
                     registry: self.registry,
+                    versions: self.versions,
                 };
                 let mut value = DynamicTuple::default();
                 value.insert_boxed(de.deserialize(deserializer)?);
@@ -967,6 +1079,7 @@ fn visit_struct<'de, T, V>(
     info: &'static T,
     registration: &TypeRegistration,
     registry: &TypeRegistry,
+    versions: Option<&TypeVersions>,
 ) -> Result<DynamicStruct, V::Error>
 where
     T: StructLikeInfo,
@@ -986,6 +1099,7 @@ where
         let value = map.next_value_seed(TypedReflectDeserializer {
             registration,
             registry,
+            versions,
         })?;
         dynamic_struct.insert_boxed(&key, value);
     }
@@ -1007,6 +1121,7 @@ fn visit_tuple<'de, T, V>(
     info: &T,
     registration: &TypeRegistration,
     registry: &TypeRegistry,
+    versions: Option<&TypeVersions>,
 ) -> Result<DynamicTuple, V::Error>
 where
     T: TupleLikeInfo + Container,
@@ -1033,6 +1148,7 @@ where
             .next_element_seed(TypedReflectDeserializer {
                 registration: info.get_field_registration(index, registry)?,
                 registry,
+                versions,
             })?
             .ok_or_else(|| Error::invalid_length(index, &len.to_string().as_str()))?;
         tuple.insert_boxed(value);
@@ -1046,6 +1162,7 @@ fn visit_struct_seq<'de, T, V>(
     info: &T,
     registration: &TypeRegistration,
     registry: &TypeRegistry,
+    versions: Option<&TypeVersions>,
 ) -> Result<DynamicStruct, V::Error>
 where
     T: StructLikeInfo + Container,
@@ -1079,6 +1196,7 @@ where
             .next_element_seed(TypedReflectDeserializer {
                 registration: info.get_field_registration(index, registry)?,
                 registry,
+                versions,
             })?
             .ok_or_else(|| Error::invalid_length(index, &len.to_string().as_str()))?;
         dynamic_struct.insert_boxed(name, value);
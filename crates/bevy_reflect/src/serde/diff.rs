@@ -0,0 +1,621 @@
+use crate::serde::{ReflectSerializer, UntypedReflectDeserializer};
+use crate::{Diff, ListChange, MapChange, Reflect, TypeRegistry};
+use serde::de::{DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{SerializeTupleVariant, Serializer};
+use serde::Serialize;
+use std::fmt::{self, Formatter};
+use std::marker::PhantomData;
+
+const DIFF_VARIANTS: &[&str] = &[
+    "Unchanged",
+    "Replaced",
+    "Struct",
+    "Tuple",
+    "List",
+    "Map",
+    "Enum",
+];
+const CHANGE_VARIANTS: &[&str] = &["Insert", "Remove", "Modify"];
+
+/// A serializer for [`Diff`]s.
+///
+/// Diffs are serialized as externally tagged enums. Replaced and inserted values, as well as map
+/// keys, are serialized with a [`ReflectSerializer`], so that [`DiffDeserializer`] can deserialize
+/// them without knowing the type of the diffed value.
+///
+/// ```
+/// # use bevy_reflect::{diff, serde::{DiffDeserializer, DiffSerializer}, Diff, Reflect, TypeRegistry};
+/// # use serde::de::DeserializeSeed;
+/// #[derive(Reflect)]
+/// struct Health(f32);
+///
+/// let registry = TypeRegistry::new();
+/// let diff = diff(&Health(1.0), &Health(0.5));
+///
+/// let ron = ron::to_string(&DiffSerializer::new(&diff, &registry)).unwrap();
+/// assert_eq!(ron, r#"Tuple([(0,Replaced({"f32":0.5}))])"#);
+///
+/// let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+/// let deserialized = DiffDeserializer::new(&registry).deserialize(&mut deserializer).unwrap();
+/// assert!(matches!(deserialized, Diff::Tuple(_)));
+/// ```
+pub struct DiffSerializer<'a> {
+    diff: &'a Diff,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> DiffSerializer<'a> {
+    pub fn new(diff: &'a Diff, registry: &'a TypeRegistry) -> Self {
+        DiffSerializer { diff, registry }
+    }
+}
+
+impl<'a> Serialize for DiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let index = match self.diff {
+            Diff::Unchanged => {
+                return serializer.serialize_unit_variant("Diff", 0, DIFF_VARIANTS[0]);
+            }
+            Diff::Replaced(value) => {
+                return serializer.serialize_newtype_variant(
+                    "Diff",
+                    1,
+                    DIFF_VARIANTS[1],
+                    &ReflectSerializer::new(&**value, self.registry),
+                );
+            }
+            Diff::Enum(variant, fields) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("Diff", 6, DIFF_VARIANTS[6], 2)?;
+                state.serialize_field(variant)?;
+                state.serialize_field(&DiffSerializer::new(fields, self.registry))?;
+                return state.end();
+            }
+            Diff::Struct(_) => 2,
+            Diff::Tuple(_) => 3,
+            Diff::List(_) => 4,
+            Diff::Map(_) => 5,
+        };
+        serializer.serialize_newtype_variant(
+            "Diff",
+            index,
+            DIFF_VARIANTS[index as usize],
+            &DiffContentsSerializer {
+                diff: self.diff,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+/// Serializes the changes contained in a [`Diff::Struct`], [`Diff::Tuple`], [`Diff::List`] or
+/// [`Diff::Map`].
+struct DiffContentsSerializer<'a> {
+    diff: &'a Diff,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for DiffContentsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry;
+        match self.diff {
+            Diff::Struct(fields) => serializer.collect_map(
+                fields
+                    .iter()
+                    .map(|(name, diff)| (name, DiffSerializer::new(diff, registry))),
+            ),
+            Diff::Tuple(fields) => serializer.collect_seq(
+                fields
+                    .iter()
+                    .map(|(index, diff)| (index, DiffSerializer::new(diff, registry))),
+            ),
+            Diff::List(changes) => serializer.collect_seq(
+                changes
+                    .iter()
+                    .map(|change| ListChangeSerializer { change, registry }),
+            ),
+            Diff::Map(changes) => serializer.collect_seq(
+                changes
+                    .iter()
+                    .map(|change| MapChangeSerializer { change, registry }),
+            ),
+            Diff::Unchanged | Diff::Replaced(_) | Diff::Enum(..) => {
+                unreachable!("the diff has no contents")
+            }
+        }
+    }
+}
+
+struct ListChangeSerializer<'a> {
+    change: &'a ListChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ListChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.change {
+            ListChange::Insert(index, value) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("ListChange", 0, CHANGE_VARIANTS[0], 2)?;
+                state.serialize_field(index)?;
+                state.serialize_field(&ReflectSerializer::new(&**value, self.registry))?;
+                state.end()
+            }
+            ListChange::Remove(index) => {
+                serializer.serialize_newtype_variant("ListChange", 1, CHANGE_VARIANTS[1], index)
+            }
+            ListChange::Modify(index, diff) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("ListChange", 2, CHANGE_VARIANTS[2], 2)?;
+                state.serialize_field(index)?;
+                state.serialize_field(&DiffSerializer::new(diff, self.registry))?;
+                state.end()
+            }
+        }
+    }
+}
+
+struct MapChangeSerializer<'a> {
+    change: &'a MapChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for MapChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.change {
+            MapChange::Insert(key, value) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("MapChange", 0, CHANGE_VARIANTS[0], 2)?;
+                state.serialize_field(&ReflectSerializer::new(&**key, self.registry))?;
+                state.serialize_field(&ReflectSerializer::new(&**value, self.registry))?;
+                state.end()
+            }
+            MapChange::Remove(key) => serializer.serialize_newtype_variant(
+                "MapChange",
+                1,
+                CHANGE_VARIANTS[1],
+                &ReflectSerializer::new(&**key, self.registry),
+            ),
+            MapChange::Modify(key, diff) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("MapChange", 2, CHANGE_VARIANTS[2], 2)?;
+                state.serialize_field(&ReflectSerializer::new(&**key, self.registry))?;
+                state.serialize_field(&DiffSerializer::new(diff, self.registry))?;
+                state.end()
+            }
+        }
+    }
+}
+
+/// A deserializer for [`Diff`]s serialized with a [`DiffSerializer`].
+///
+/// Replaced and inserted values, as well as map keys, are deserialized with an
+/// [`UntypedReflectDeserializer`], so they usually are dynamic values. Their types must be
+/// registered in the [`TypeRegistry`].
+#[derive(Clone, Copy)]
+pub struct DiffDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> DiffDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        DiffDeserializer { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for DiffDeserializer<'a> {
+    type Value = Diff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("Diff", DIFF_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for DiffDeserializer<'a> {
+    type Value = Diff;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("reflected diff")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let value = ValueDeserializer(self.registry);
+        let (variant, access) = data.variant_seed(VariantDeserializer(DIFF_VARIANTS))?;
+        Ok(match variant {
+            0 => {
+                access.unit_variant()?;
+                Diff::Unchanged
+            }
+            1 => Diff::Replaced(access.newtype_variant_seed(value)?),
+            2 => Diff::Struct(access.newtype_variant_seed(StructDiffDeserializer(self))?),
+            3 => Diff::Tuple(
+                access.newtype_variant_seed(SeqDeserializer(PairDeserializer(
+                    PhantomData::<usize>,
+                    self,
+                )))?,
+            ),
+            4 => Diff::List(
+                access
+                    .newtype_variant_seed(SeqDeserializer(ListChangeDeserializer(self.registry)))?,
+            ),
+            5 => Diff::Map(
+                access
+                    .newtype_variant_seed(SeqDeserializer(MapChangeDeserializer(self.registry)))?,
+            ),
+            _ => {
+                let pair = PairDeserializer(PhantomData::<String>, self);
+                let (variant, fields) = access.tuple_variant(2, pair)?;
+                Diff::Enum(variant, Box::new(fields))
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ListChangeDeserializer<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for ListChangeDeserializer<'a> {
+    type Value = ListChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("ListChange", CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ListChangeDeserializer<'a> {
+    type Value = ListChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("reflected list change")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let index = PhantomData::<usize>;
+        let (variant, access) = data.variant_seed(VariantDeserializer(CHANGE_VARIANTS))?;
+        Ok(match variant {
+            0 => {
+                let pair = PairDeserializer(index, ValueDeserializer(self.0));
+                let (index, value) = access.tuple_variant(2, pair)?;
+                ListChange::Insert(index, value)
+            }
+            1 => ListChange::Remove(access.newtype_variant_seed(index)?),
+            _ => {
+                let pair = PairDeserializer(index, DiffDeserializer::new(self.0));
+                let (index, diff) = access.tuple_variant(2, pair)?;
+                ListChange::Modify(index, diff)
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MapChangeDeserializer<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for MapChangeDeserializer<'a> {
+    type Value = MapChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("MapChange", CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MapChangeDeserializer<'a> {
+    type Value = MapChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("reflected map change")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let key = ValueDeserializer(self.0);
+        let (variant, access) = data.variant_seed(VariantDeserializer(CHANGE_VARIANTS))?;
+        Ok(match variant {
+            0 => {
+                let (key, value) = access.tuple_variant(2, PairDeserializer(key, key))?;
+                MapChange::Insert(key, value)
+            }
+            1 => MapChange::Remove(access.newtype_variant_seed(key)?),
+            _ => {
+                let pair = PairDeserializer(key, DiffDeserializer::new(self.0));
+                let (key, diff) = access.tuple_variant(2, pair)?;
+                MapChange::Modify(key, diff)
+            }
+        })
+    }
+}
+
+/// Deserializes a value serialized with a [`ReflectSerializer`].
+#[derive(Clone, Copy)]
+struct ValueDeserializer<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for ValueDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        UntypedReflectDeserializer::new(self.0).deserialize(deserializer)
+    }
+}
+
+/// Deserializes the index of a variant from its index or name.
+struct VariantDeserializer(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for VariantDeserializer {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for VariantDeserializer {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("expected either a variant index or variant name")
+    }
+
+    fn visit_u64<E>(self, index: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        usize::try_from(index)
+            .ok()
+            .filter(|&index| index < self.0.len())
+            .ok_or_else(|| Error::custom(format_args!("no variant found at index `{index}`")))
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.0
+            .iter()
+            .position(|variant| *variant == name)
+            .ok_or_else(|| Error::unknown_variant(name, self.0))
+    }
+}
+
+/// Deserializes the fields of a [`Diff::Struct`].
+struct StructDiffDeserializer<'a>(DiffDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for StructDiffDeserializer<'a> {
+    type Value = Vec<(String, Diff)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for StructDiffDeserializer<'a> {
+    type Value = Vec<(String, Diff)>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("map of field names to diffs")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(name) = map.next_key::<String>()? {
+            fields.push((name, map.next_value_seed(self.0)?));
+        }
+        Ok(fields)
+    }
+}
+
+/// Deserializes a sequence of values with the same seed.
+struct SeqDeserializer<T>(T);
+
+impl<'de, T: DeserializeSeed<'de> + Copy> DeserializeSeed<'de> for SeqDeserializer<T> {
+    type Value = Vec<T::Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: DeserializeSeed<'de> + Copy> Visitor<'de> for SeqDeserializer<T> {
+    type Value = Vec<T::Value>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(self.0)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+/// Deserializes a pair of values with their own seeds.
+#[derive(Clone, Copy)]
+struct PairDeserializer<A, B>(A, B);
+
+impl<'de, A: DeserializeSeed<'de>, B: DeserializeSeed<'de>> DeserializeSeed<'de>
+    for PairDeserializer<A, B>
+{
+    type Value = (A::Value, B::Value);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de, A: DeserializeSeed<'de>, B: DeserializeSeed<'de>> Visitor<'de>
+    for PairDeserializer<A, B>
+{
+    type Value = (A::Value, B::Value);
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("pair")
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let first = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| Error::invalid_length(0, &"a pair"))?;
+        let second = seq
+            .next_element_seed(self.1)?
+            .ok_or_else(|| Error::invalid_length(1, &"a pair"))?;
+        Ok((first, second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serde::{DiffDeserializer, DiffSerializer};
+    use crate::{self as bevy_reflect, apply_diff, diff, Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Inventory {
+        slots: Vec<Item>,
+        counts: HashMap<String, u32>,
+        selected: Option<usize>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum Item {
+        Sword(u8),
+        Shield { durability: f32 },
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Inventory>();
+        registry.register::<Item>();
+        registry.register::<Option<usize>>();
+        registry
+    }
+
+    fn diffs() -> (Inventory, Inventory) {
+        let old = Inventory {
+            slots: vec![Item::Sword(1), Item::Shield { durability: 1.0 }],
+            counts: HashMap::from([("arrow".to_string(), 10), ("bomb".to_string(), 2)]),
+            selected: None,
+        };
+        let new = Inventory {
+            slots: vec![
+                Item::Shield { durability: 0.5 },
+                Item::Sword(2),
+                Item::Sword(3),
+            ],
+            counts: HashMap::from([("arrow".to_string(), 8), ("potion".to_string(), 1)]),
+            selected: Some(1),
+        };
+        (old, new)
+    }
+
+    #[test]
+    fn diff_roundtrip_ron() {
+        let registry = registry();
+        let (old, new) = diffs();
+        let diff = diff(&old, &new);
+
+        let ron = ron::to_string(&DiffSerializer::new(&diff, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+        let deserialized = DiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut target = old.clone();
+        apply_diff(&mut target, &deserialized).unwrap();
+        assert_eq!(target, new, "{ron}");
+    }
+
+    #[test]
+    fn diff_roundtrip_bincode() {
+        let registry = registry();
+        let (old, new) = diffs();
+        let diff = diff(&old, &new);
+
+        let bytes = bincode::serialize(&DiffSerializer::new(&diff, &registry)).unwrap();
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(DiffDeserializer::new(&registry), &bytes)
+            .unwrap();
+
+        let mut target = old.clone();
+        apply_diff(&mut target, &deserialized).unwrap();
+        assert_eq!(target, new);
+    }
+
+    #[test]
+    fn diff_json_format() {
+        let registry = registry();
+        assert_eq!(
+            diff_json(&Item::Sword(1), &Item::Sword(2), &registry),
+            r#"{"Enum":["Sword",{"Tuple":[[0,{"Replaced":{"u8":2}}]]}]}"#
+        );
+        assert_eq!(
+            diff_json(&Item::Sword(1), &Item::Sword(1), &registry),
+            r#""Unchanged""#
+        );
+        assert_eq!(
+            diff_json(&vec![1u8, 2], &vec![2u8], &registry),
+            r#"{"List":[{"Remove":0}]}"#
+        );
+    }
+
+    fn diff_json(old: &dyn Reflect, new: &dyn Reflect, registry: &TypeRegistry) -> String {
+        serde_json::to_string(&DiffSerializer::new(&diff(old, new), registry)).unwrap()
+    }
+}
@@ -0,0 +1,794 @@
+use crate::serde::{SerializationData, TypedReflectDeserializer};
+use crate::{
+    DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, GetTypeRegistration, Reflect, ReflectFromReflect, ReflectRef, TypeInfo,
+    TypeRegistration, TypeRegistry,
+};
+use serde::de::{DeserializeSeed, Error, Visitor};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Separates the type path from the version in [`versioned_type_path`].
+const VERSION_SEPARATOR: char = '@';
+
+/// The error returned by a migration function of [`ReflectMigrations`].
+pub type BoxedMigrationError = Box<dyn std::error::Error + Send + Sync>;
+
+type MigrateFn =
+    dyn Fn(Box<dyn Reflect>) -> Result<Box<dyn Reflect>, BoxedMigrationError> + Send + Sync;
+
+#[derive(Clone)]
+struct Migration {
+    /// The registration of the type describing the layout of the version migrated from.
+    previous: TypeRegistration,
+    migrate: Arc<MigrateFn>,
+}
+
+/// An error returned when migrating a value to the current version of its type.
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("`{type_path}` has no version {version}, its current version is {current}")]
+    UnknownVersion {
+        /// The type path of the type.
+        type_path: String,
+        /// The version of the value, newer than the current version.
+        version: u32,
+        /// The current version of the type.
+        current: u32,
+    },
+    #[error("failed to migrate `{type_path}` from version {version}: {error}")]
+    Failed {
+        /// The type path of the type.
+        type_path: String,
+        /// The version the failed migration converts from.
+        version: u32,
+        /// The error returned by the migration function.
+        error: BoxedMigrationError,
+    },
+    #[error("migrating `{type_path}` from version {version} didn't produce a valid `{type_path}`")]
+    InvalidValue {
+        /// The type path of the type.
+        type_path: String,
+        /// The version the value was migrated from.
+        version: u32,
+    },
+}
+
+/// Type data holding the version of a type and the migrations from its previous versions.
+///
+/// Types start at version 0, and each migration bumps the version by one. Values serialized by
+/// a [`ReflectSerializer`] have the version of their type appended to their type path, like
+/// `my_game::Player@2`, unless it is 0. Values serialized before migrations were added to their
+/// type are thus of version 0.
+///
+/// The migration from version `n` is given a type describing the layout of version `n`, like an
+/// older copy of the type. An [`UntypedReflectDeserializer`] deserializes a value of version `n`
+/// as this type, which gives a dynamic value like a [`DynamicStruct`], and passes it to the
+/// migration functions from version `n` to the current version in turn.
+///
+/// Values serialized along with their type carry a version: the values of a [`ReflectSerializer`],
+/// and the components and resources of scenes. The versions of the values nested in them, like
+/// their fields, are recorded in a [`TypeVersions`] map, which scenes serialize alongside their
+/// values, and a [`TypedReflectDeserializer`] given this map migrates these nested values too.
+/// The compact format of a [`TypeTable`] records the version of each type in the table, and
+/// migrates all values. The nested values of a [`ReflectSerializer`] don't carry their version,
+/// and are deserialized with the current layout of their type.
+///
+/// ```
+/// # use bevy_reflect::{serde::UntypedReflectDeserializer, DynamicStruct, FromReflect, Reflect, TypePath, TypeRegistry};
+/// # use serde::de::DeserializeSeed;
+/// mod v0 {
+///     # use bevy_reflect::Reflect;
+///     #[derive(Reflect)]
+///     pub struct Player {
+///         pub hp: u32,
+///     }
+/// }
+///
+/// #[derive(Reflect, PartialEq, Debug)]
+/// struct Player {
+///     health: f32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+/// registry.register_migration::<Player, v0::Player, _>(|old| {
+///     let old = v0::Player::take_from_reflect(old).map_err(|_| "expected a player")?;
+///     let mut player = DynamicStruct::default();
+///     player.insert("health", old.hp as f32);
+///     Ok(Box::new(player))
+/// });
+///
+/// // Serialized before `hp` was renamed, so without version.
+/// let ron = format!(r#"{{ "{}": (hp: 20) }}"#, Player::type_path());
+/// let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+/// let value = UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(Player::from_reflect(&*value), Some(Player { health: 20.0 }));
+/// ```
+///
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+/// [`UntypedReflectDeserializer`]: crate::serde::UntypedReflectDeserializer
+/// [`TypeTable`]: crate::serde::TypeTable
+#[derive(Clone, Default)]
+pub struct ReflectMigrations {
+    migrations: Vec<Migration>,
+}
+
+impl ReflectMigrations {
+    /// Creates migrations for a type at version 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a migration from the current version to the next one, returning `self`.
+    ///
+    /// See [`ReflectMigrations::add_migration`].
+    pub fn with_migration<Previous, F>(mut self, migrate: F) -> Self
+    where
+        Previous: GetTypeRegistration,
+        F: Fn(Box<dyn Reflect>) -> Result<Box<dyn Reflect>, BoxedMigrationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.add_migration::<Previous, F>(migrate);
+        self
+    }
+
+    /// Adds a migration from the current version to the next one.
+    ///
+    /// Values of the current version are deserialized as `Previous`, and `migrate` converts them
+    /// to values of the next version. The converted value may be a dynamic value, which
+    /// represents the type of the next version if it doesn't represent any type.
+    pub fn add_migration<Previous, F>(&mut self, migrate: F)
+    where
+        Previous: GetTypeRegistration,
+        F: Fn(Box<dyn Reflect>) -> Result<Box<dyn Reflect>, BoxedMigrationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.migrations.push(Migration {
+            previous: Previous::get_type_registration(),
+            migrate: Arc::new(migrate),
+        });
+    }
+
+    /// Returns the current version of the type.
+    pub fn version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    /// Returns the registration of the type describing the layout of the given version,
+    /// or `None` if it is the current version or doesn't exist.
+    pub fn previous(&self, version: u32) -> Option<&TypeRegistration> {
+        self.migrations
+            .get(version as usize)
+            .map(|migration| &migration.previous)
+    }
+
+    /// Migrates a value of the type of `registration` from the given version to the current
+    /// version.
+    ///
+    /// If the type registers [`ReflectFromReflect`], the migrated value is checked to convert to
+    /// the type.
+    pub fn migrate(
+        &self,
+        mut value: Box<dyn Reflect>,
+        version: u32,
+        registration: &TypeRegistration,
+    ) -> Result<Box<dyn Reflect>, MigrationError> {
+        let type_path = registration.type_info().type_path();
+        if version > self.version() {
+            return Err(MigrationError::UnknownVersion {
+                type_path: type_path.to_string(),
+                version,
+                current: self.version(),
+            });
+        }
+        if version == self.version() {
+            return Ok(value);
+        }
+
+        for (step, migration) in self.migrations.iter().enumerate().skip(version as usize) {
+            value = (migration.migrate)(value).map_err(|error| MigrationError::Failed {
+                type_path: type_path.to_string(),
+                version: step as u32,
+                error,
+            })?;
+            let next = self
+                .migrations
+                .get(step + 1)
+                .map_or(registration, |migration| &migration.previous);
+            represent(&mut *value, next.type_info());
+        }
+
+        if let Some(from_reflect) = registration.data::<ReflectFromReflect>() {
+            if from_reflect.from_reflect(&*value).is_none() {
+                return Err(MigrationError::InvalidValue {
+                    type_path: type_path.to_string(),
+                    version,
+                });
+            }
+        }
+        Ok(value)
+    }
+}
+
+impl fmt::Debug for ReflectMigrations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReflectMigrations")
+            .field("version", &self.version())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Makes a dynamic `value` represent the type of `info`, if it doesn't represent any type yet.
+fn represent(value: &mut dyn Reflect, info: &'static TypeInfo) {
+    if value.get_represented_type_info().is_some() {
+        return;
+    }
+    let info = Some(info);
+    let value = value.as_any_mut();
+    match info {
+        Some(TypeInfo::Struct(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicStruct>() {
+                value.set_represented_type(info);
+            }
+        }
+        Some(TypeInfo::TupleStruct(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicTupleStruct>() {
+                value.set_represented_type(info);
+            }
+        }
+        Some(TypeInfo::Tuple(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicTuple>() {
+                value.set_represented_type(info);
+            }
+        }
+        Some(TypeInfo::List(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicList>() {
+                value.set_represented_type(info);
+            }
+        }
+        Some(TypeInfo::Array(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicArray>() {
+                value.set_represented_type(info);
+            }
+        }
+        Some(TypeInfo::Map(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicMap>() {
+                value.set_represented_type(info);
+            }
+        }
+        Some(TypeInfo::Enum(_)) => {
+            if let Some(value) = value.downcast_mut::<DynamicEnum>() {
+                value.set_represented_type(info);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the [type path] of `type_info`, followed by `@` and the version of the type if it has
+/// [`ReflectMigrations`] and isn't at version 0.
+///
+/// This is how [`ReflectSerializer`] serializes the type of values, and how
+/// [`VersionedTypeRegistrationDeserializer`] deserializes them.
+///
+/// [type path]: crate::TypePath::type_path
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+pub fn versioned_type_path(
+    type_info: &'static TypeInfo,
+    registry: &TypeRegistry,
+) -> Cow<'static, str> {
+    let version = registry
+        .get_type_data::<ReflectMigrations>(type_info.type_id())
+        .map_or(0, ReflectMigrations::version);
+    if version == 0 {
+        Cow::Borrowed(type_info.type_path())
+    } else {
+        Cow::Owned(format!(
+            "{}{VERSION_SEPARATOR}{version}",
+            type_info.type_path()
+        ))
+    }
+}
+
+/// A deserializer for type registrations and versions.
+///
+/// This expects a string containing the _full_ [type path] of the type, optionally followed by
+/// `@` and a version as written by [`versioned_type_path`], and returns the
+/// [`&TypeRegistration`] of the type with the version, which is 0 if it is omitted.
+///
+/// [type path]: crate::TypePath::type_path
+/// [`&TypeRegistration`]: TypeRegistration
+pub struct VersionedTypeRegistrationDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> VersionedTypeRegistrationDeserializer<'a> {
+    /// Creates a deserializer looking up the types in `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for VersionedTypeRegistrationDeserializer<'a> {
+    type Value = (&'a TypeRegistration, u32);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct VersionedTypeRegistrationVisitor<'a>(&'a TypeRegistry);
+
+        impl<'de, 'a> Visitor<'de> for VersionedTypeRegistrationVisitor<'a> {
+            type Value = (&'a TypeRegistration, u32);
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("string containing `type` entry for the reflected value")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                let (type_path, version) = match value.rsplit_once(VERSION_SEPARATOR) {
+                    Some((type_path, version)) => {
+                        let version = version.parse().map_err(|_| {
+                            Error::custom(format_args!(
+                                "invalid version `{version}` of `{type_path}`"
+                            ))
+                        })?;
+                        (type_path, version)
+                    }
+                    None => (value, 0),
+                };
+                let registration = self.0.get_with_type_path(type_path).ok_or_else(|| {
+                    Error::custom(format_args!("No registration found for `{type_path}`"))
+                })?;
+                Ok((registration, version))
+            }
+        }
+
+        deserializer.deserialize_str(VersionedTypeRegistrationVisitor(self.registry))
+    }
+}
+
+/// The versions of the types of serialized values, by [type path].
+///
+/// Types missing from the map are at version 0, so only the types with [`ReflectMigrations`] at
+/// a later version are recorded.
+///
+/// Scenes record the versions of the types of the values nested in their components and
+/// resources, to migrate them with [`TypedReflectDeserializer::with_versions`].
+///
+/// [type path]: crate::TypePath::type_path
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TypeVersions(BTreeMap<String, u32>);
+
+impl TypeVersions {
+    /// Creates an empty map, where all types are at version 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the current versions of the types of the values nested in `value`, like its
+    /// fields, items and their own fields, but not of the type of `value` itself.
+    ///
+    /// Fields skipped during serialization are ignored.
+    pub fn add_nested(&mut self, value: &dyn Reflect, registry: &TypeRegistry) {
+        let data = value
+            .get_represented_type_info()
+            .and_then(|info| registry.get_type_data::<SerializationData>(info.type_id()));
+        let skipped = |index: usize| data.is_some_and(|data| data.is_field_skipped(index));
+        match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    if !skipped(index) {
+                        self.add(field, registry);
+                    }
+                }
+            }
+            ReflectRef::TupleStruct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    if !skipped(index) {
+                        self.add(field, registry);
+                    }
+                }
+            }
+            ReflectRef::Tuple(value) => {
+                for field in value.iter_fields() {
+                    self.add(field, registry);
+                }
+            }
+            ReflectRef::List(value) => {
+                for item in value.iter() {
+                    self.add(item, registry);
+                }
+            }
+            ReflectRef::Array(value) => {
+                for item in value.iter() {
+                    self.add(item, registry);
+                }
+            }
+            ReflectRef::Map(value) => {
+                for (key, value) in value.iter() {
+                    self.add(key, registry);
+                    self.add(value, registry);
+                }
+            }
+            ReflectRef::Enum(value) => {
+                for field in value.iter_fields() {
+                    self.add(field.value(), registry);
+                }
+            }
+            ReflectRef::Value(_) => {}
+        }
+    }
+
+    /// Returns the version of the type with the given [type path], which is 0 if it isn't
+    /// recorded.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn get(&self, type_path: &str) -> u32 {
+        self.0.get(type_path).copied().unwrap_or(0)
+    }
+
+    /// Records the version of the type with the given [type path].
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn insert(&mut self, type_path: impl Into<String>, version: u32) {
+        self.0.insert(type_path.into(), version);
+    }
+
+    /// Returns `true` if no version is recorded, so that all types are at version 0.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn add(&mut self, value: &dyn Reflect, registry: &TypeRegistry) {
+        if let Some(info) = value.get_represented_type_info() {
+            let version = registry
+                .get_type_data::<ReflectMigrations>(info.type_id())
+                .map_or(0, ReflectMigrations::version);
+            if version != 0 {
+                self.insert(info.type_path(), version);
+            }
+        }
+        self.add_nested(value, registry);
+    }
+}
+
+/// A deserializer for reflected values of a known type, serialized at the given version of
+/// the type.
+///
+/// Values of the current version are deserialized like with a [`TypedReflectDeserializer`].
+/// Values of older versions are deserialized with the layout of that version, then migrated to
+/// the current version with the [`ReflectMigrations`] of the type. The values nested in it are
+/// only migrated if their versions are given with [`VersionedReflectDeserializer::with_versions`],
+/// and are otherwise deserialized with the current layout of their types.
+pub struct VersionedReflectDeserializer<'a> {
+    registration: &'a TypeRegistration,
+    version: u32,
+    registry: &'a TypeRegistry,
+    versions: Option<&'a TypeVersions>,
+}
+
+impl<'a> VersionedReflectDeserializer<'a> {
+    /// Creates a deserializer for a value of the type of `registration`, serialized at `version`
+    /// of the type.
+    pub fn new(
+        registration: &'a TypeRegistration,
+        version: u32,
+        registry: &'a TypeRegistry,
+    ) -> Self {
+        Self {
+            registration,
+            version,
+            registry,
+            versions: None,
+        }
+    }
+
+    /// Deserializes the values nested in the value as serialized at the versions recorded in
+    /// `versions`, returning `self`.
+    ///
+    /// See [`TypedReflectDeserializer::with_versions`].
+    pub fn with_versions(mut self, versions: &'a TypeVersions) -> Self {
+        self.versions = Some(versions);
+        self
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for VersionedReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let layout = |registration| {
+            let deserializer = TypedReflectDeserializer::new(registration, self.registry);
+            match self.versions {
+                Some(versions) => deserializer.with_versions(versions),
+                None => deserializer,
+            }
+        };
+        let migrations = self.registration.data::<ReflectMigrations>();
+        let current = migrations.map_or(0, ReflectMigrations::version);
+        let Some(migrations) = migrations.filter(|_| self.version != current) else {
+            if self.version > current {
+                return Err(Error::custom(MigrationError::UnknownVersion {
+                    type_path: self.registration.type_info().type_path().to_string(),
+                    version: self.version,
+                    current,
+                }));
+            }
+            return layout(self.registration).deserialize_current(deserializer);
+        };
+
+        let previous = migrations.previous(self.version).ok_or_else(|| {
+            Error::custom(MigrationError::UnknownVersion {
+                type_path: self.registration.type_info().type_path().to_string(),
+                version: self.version,
+                current,
+            })
+        })?;
+        let value = layout(previous).deserialize_current(deserializer)?;
+        migrations
+            .migrate(value, self.version, self.registration)
+            .map_err(Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::{
+        CompactReflectDeserializer, CompactReflectSerializer, ReflectSerializer,
+        UntypedReflectDeserializer,
+    };
+    use crate::{self as bevy_reflect, Enum, FromReflect, TypePath};
+    use bincode::Options;
+
+    mod v0 {
+        use crate::{self as bevy_reflect, Reflect};
+
+        #[derive(Reflect)]
+        pub struct Player {
+            pub hp: u32,
+        }
+
+        #[derive(Reflect)]
+        pub enum Mode {
+            Easy,
+            Hard,
+        }
+
+        #[derive(Reflect)]
+        pub struct Team {
+            pub leader: Player,
+        }
+    }
+
+    mod v1 {
+        use crate::{self as bevy_reflect, Reflect};
+
+        #[derive(Reflect)]
+        pub struct Player {
+            pub health: u32,
+        }
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Player {
+        health: f32,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    enum Mode {
+        Casual,
+        Hard,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Team {
+        leader: Player,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Team>();
+        registry.register::<Mode>();
+        registry.register_migration::<Player, v0::Player, _>(|old| {
+            let old = v0::Player::take_from_reflect(old).map_err(|_| "expected a player")?;
+            Ok(Box::new(v1::Player { health: old.hp }))
+        });
+        registry.register_migration::<Player, v1::Player, _>(|old| {
+            let old = v1::Player::take_from_reflect(old).map_err(|_| "expected a player")?;
+            let mut player = DynamicStruct::default();
+            player.insert("health", old.health as f32);
+            Ok(Box::new(player))
+        });
+        registry.register_migration::<Mode, v0::Mode, _>(|old| {
+            let variant = match old.downcast_ref::<DynamicEnum>().unwrap().variant_name() {
+                "Easy" => "Casual",
+                name => name,
+            };
+            Ok(Box::new(DynamicEnum::new(variant, ())))
+        });
+        registry
+    }
+
+    fn deserialize(ron: &str, registry: &TypeRegistry) -> Result<Box<dyn Reflect>, ron::Error> {
+        let mut deserializer = ron::Deserializer::from_str(ron).unwrap();
+        UntypedReflectDeserializer::new(registry).deserialize(&mut deserializer)
+    }
+
+    #[test]
+    fn should_migrate_old_versions() {
+        let registry = registry();
+
+        let unversioned = format!(r#"{{ "{}": (hp: 20) }}"#, Player::type_path());
+        let value = deserialize(&unversioned, &registry).unwrap();
+        assert!(value.represents::<Player>());
+        assert_eq!(Player::from_reflect(&*value), Some(Player { health: 20.0 }));
+
+        let version_1 = format!(r#"{{ "{}@1": (health: 15) }}"#, Player::type_path());
+        let value = deserialize(&version_1, &registry).unwrap();
+        assert_eq!(Player::from_reflect(&*value), Some(Player { health: 15.0 }));
+
+        let mode = format!(r#"{{ "{}": Easy }}"#, Mode::type_path());
+        let value = deserialize(&mode, &registry).unwrap();
+        assert_eq!(Mode::from_reflect(&*value), Some(Mode::Casual));
+    }
+
+    #[test]
+    fn should_serialize_current_version() {
+        let registry = registry();
+        let player = Player { health: 7.5 };
+
+        let serializer = ReflectSerializer::new(&player, &registry);
+        let ron = ron::to_string(&serializer).unwrap();
+        assert_eq!(
+            ron,
+            format!(r#"{{"{}@2":(health:7.5)}}"#, Player::type_path())
+        );
+
+        let value = deserialize(&ron, &registry).unwrap();
+        assert_eq!(Player::from_reflect(&*value), Some(player));
+
+        let serializer = ReflectSerializer::new(&Mode::Hard, &registry);
+        let ron = ron::to_string(&serializer).unwrap();
+        assert_eq!(ron, format!(r#"{{"{}@1":Hard}}"#, Mode::type_path()));
+    }
+
+    #[test]
+    fn should_report_migration_errors() {
+        let mut registry = registry();
+
+        let newer = format!(r#"{{ "{}@3": (health: 1.0) }}"#, Player::type_path());
+        let error = deserialize(&newer, &registry).unwrap_err().to_string();
+        assert!(error.contains(&format!(
+            "`{}` has no version 3, its current version is 2",
+            Player::type_path()
+        )));
+
+        let invalid = format!(r#"{{ "{}@x": (health: 1.0) }}"#, Player::type_path());
+        let error = deserialize(&invalid, &registry).unwrap_err().to_string();
+        assert!(error.contains("invalid version `x`"));
+
+        registry.register_migration::<Mode, Mode, _>(|_| Err("no longer supported".into()));
+        let mode = format!(r#"{{ "{}@1": Hard }}"#, Mode::type_path());
+        let error = deserialize(&mode, &registry).unwrap_err().to_string();
+        assert!(error.contains(&format!(
+            "failed to migrate `{}` from version 1: no longer supported",
+            Mode::type_path()
+        )));
+
+        registry.register_migration::<Player, Player, _>(|_| Ok(Box::new(Mode::Hard)));
+        let player = format!(r#"{{ "{}": (hp: 20) }}"#, Player::type_path());
+        let error = deserialize(&player, &registry).unwrap_err().to_string();
+        assert!(error.contains(&format!(
+            "migrating `{0}` from version 0 didn't produce a valid `{0}`",
+            Player::type_path()
+        )));
+    }
+
+    #[test]
+    fn should_migrate_nested_values() {
+        let registry = registry();
+        let team = Team {
+            leader: Player { health: 7.5 },
+        };
+        let typed = |ron: &str, versions: &TypeVersions| {
+            let registration = registry.get(std::any::TypeId::of::<Team>()).unwrap();
+            let mut deserializer = ron::Deserializer::from_str(ron).unwrap();
+            TypedReflectDeserializer::new(registration, &registry)
+                .with_versions(versions)
+                .deserialize(&mut deserializer)
+                .map(|value| Team::from_reflect(&*value))
+        };
+
+        // The version of `Player` isn't serialized within `Team` by a `ReflectSerializer`.
+        let ron = ron::to_string(&ReflectSerializer::new(&team, &registry)).unwrap();
+        assert_eq!(
+            ron,
+            format!(r#"{{"{}":(leader:(health:7.5))}}"#, Team::type_path())
+        );
+        let value = deserialize(&ron, &registry).unwrap();
+        assert_eq!(Team::from_reflect(&*value).as_ref(), Some(&team));
+        let old = format!(r#"{{ "{}": (leader: (hp: 20)) }}"#, Team::type_path());
+        assert!(deserialize(&old, &registry).is_err());
+
+        // Versions are recorded separately instead.
+        let mut versions = TypeVersions::new();
+        versions.add_nested(&team, &registry);
+        assert_eq!(versions.get(Player::type_path()), 2);
+        assert_eq!(versions.get(Team::type_path()), 0);
+        let value = typed("(leader: (health: 7.5))", &versions).unwrap();
+        assert_eq!(value, Some(team));
+
+        let value = typed("(leader: (hp: 20))", &TypeVersions::new()).unwrap();
+        assert_eq!(
+            value,
+            Some(Team {
+                leader: Player { health: 20.0 }
+            })
+        );
+
+        let mut versions = TypeVersions::new();
+        versions.insert(Player::type_path(), 1);
+        let value = typed("(leader: (health: 15))", &versions).unwrap();
+        assert_eq!(
+            value,
+            Some(Team {
+                leader: Player { health: 15.0 }
+            })
+        );
+    }
+
+    #[test]
+    fn should_migrate_compact_values() {
+        let registry = registry();
+        let team = Team {
+            leader: Player { health: 7.5 },
+        };
+
+        let bytes = bincode::serialize(&CompactReflectSerializer::new(&team, &registry)).unwrap();
+        let value = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(CompactReflectDeserializer::new(&registry), &bytes)
+            .unwrap();
+        assert_eq!(Team::from_reflect(&*value), Some(team));
+
+        // Written before `Player` had migrations, at version 0.
+        let mut old_registry = TypeRegistry::default();
+        old_registry.register::<v0::Team>();
+        old_registry.register::<v0::Player>();
+        let old = v0::Team {
+            leader: v0::Player { hp: 20 },
+        };
+        let ron = ron::to_string(&CompactReflectSerializer::new(&old, &old_registry))
+            .unwrap()
+            .replace("::v0::", "::");
+        let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+        let value = CompactReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(
+            Team::from_reflect(&*value),
+            Some(Team {
+                leader: Player { health: 20.0 }
+            })
+        );
+    }
+}
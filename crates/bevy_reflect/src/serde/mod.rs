@@ -1,8 +1,18 @@
+mod compact;
 mod de;
+mod diff;
+mod migration;
+#[cfg(feature = "json_schema")]
+mod schema;
 mod ser;
 mod type_data;
 
+pub use compact::*;
 pub use de::*;
+pub use diff::*;
+pub use migration::*;
+#[cfg(feature = "json_schema")]
+pub use schema::*;
 pub use ser::*;
 pub use type_data::*;
 
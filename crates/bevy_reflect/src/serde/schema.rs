@@ -0,0 +1,695 @@
+use crate::{
+    serde::SerializationData, ArrayInfo, EnumInfo, ListInfo, MapInfo, NamedField, ReflectSerialize,
+    StructInfo, TupleInfo, TupleStructInfo, TypeInfo, TypeRegistry, UnnamedField, VariantInfo,
+};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+/// The JSON Schema dialect of the generated documents.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generates a [JSON Schema] describing the output of a [`TypedReflectSerializer`] for the
+/// registered type with the given [`TypeId`], when serialized to JSON.
+///
+/// The root of the document references the schema of the type, which is stored with the schemas
+/// of all the types it is made of under `$defs`, keyed by [type path].
+/// Returns `None` if the type isn't registered in the `registry`.
+///
+/// The schema follows the shapes used by the serializer:
+/// - structs are objects of their serialized fields, and tuple structs, tuples, lists and arrays
+///   are arrays,
+/// - maps are objects, since JSON only supports string and numeric keys,
+/// - `Option`s are either `null` or their value,
+/// - other enums are externally tagged: unit variants are strings holding the variant name,
+///   and other variants are objects with the variant name as their only key.
+///
+/// Primitive types get precise schemas, while other types that register [`ReflectSerialize`]
+/// accept any value, since their serialized form is defined by their own `Serialize` implementation.
+/// Value types that can't be serialized get the `false` schema.
+///
+/// With the `documentation` feature, doc comments of types, fields and variants are included as
+/// descriptions.
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypePath, TypeRegistry, serde::typed_json_schema};
+/// # use std::any::TypeId;
+/// #[derive(Reflect)]
+/// struct Player {
+///     name: String,
+///     lives: u8,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Player>();
+///
+/// let schema = typed_json_schema(TypeId::of::<Player>(), &registry).unwrap();
+/// let player = &schema["$defs"][Player::type_path()];
+/// assert_eq!(player["type"], "object");
+/// assert_eq!(player["properties"]["lives"]["$ref"], "#/$defs/u8");
+/// ```
+///
+/// [JSON Schema]: https://json-schema.org/
+/// [`TypedReflectSerializer`]: crate::serde::TypedReflectSerializer
+/// [type path]: crate::TypePath
+pub fn typed_json_schema(type_id: TypeId, registry: &TypeRegistry) -> Option<Value> {
+    registry.get(type_id)?;
+    let mut generator = SchemaGenerator::new(registry);
+    let root = generator.reference(type_id);
+    Some(generator.finish(root))
+}
+
+/// Generates a [JSON Schema] describing the output of a [`ReflectSerializer`] for any of the types
+/// registered in the `registry`, when serialized to JSON.
+///
+/// The document accepts objects with a single key, the [type path] of a registered type, whose
+/// value matches the schema [`typed_json_schema`] generates for that type.
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypePath, TypeRegistry, serde::reflect_json_schema};
+/// #[derive(Reflect)]
+/// struct Score(u32);
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Score>();
+///
+/// let schema = reflect_json_schema(&registry);
+/// assert!(schema["properties"][Score::type_path()]["$ref"].is_string());
+/// assert_eq!(schema["$defs"][Score::type_path()]["type"], "array");
+/// ```
+///
+/// [JSON Schema]: https://json-schema.org/
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+/// [type path]: crate::TypePath
+pub fn reflect_json_schema(registry: &TypeRegistry) -> Value {
+    let mut generator = SchemaGenerator::new(registry);
+    let properties: Map<String, Value> = registry
+        .iter()
+        .map(|registration| {
+            let type_path = registration.type_info().type_path().to_string();
+            (type_path, generator.reference(registration.type_id()))
+        })
+        .collect();
+    generator.finish(json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+        "minProperties": 1,
+        "maxProperties": 1,
+    }))
+}
+
+struct SchemaGenerator<'a> {
+    registry: &'a TypeRegistry,
+    defs: Map<String, Value>,
+}
+
+impl<'a> SchemaGenerator<'a> {
+    fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            registry,
+            defs: Map::new(),
+        }
+    }
+
+    /// Wraps `root` in a document holding the schemas of all the referenced types.
+    fn finish(self, root: Value) -> Value {
+        let mut document = Map::new();
+        document.insert("$schema".to_string(), JSON_SCHEMA_DIALECT.into());
+        match root {
+            Value::Object(root) => document.extend(root),
+            root => {
+                document.insert("allOf".to_string(), json!([root]));
+            }
+        }
+        document.insert("$defs".to_string(), Value::Object(self.defs));
+        Value::Object(document)
+    }
+
+    /// Returns a reference to the schema of the type with the given [`TypeId`], generating it if
+    /// it hasn't been yet.
+    ///
+    /// Types that aren't registered accept any value, since their type information is unknown.
+    fn reference(&mut self, type_id: TypeId) -> Value {
+        let Some(registration) = self.registry.get(type_id) else {
+            return json!({});
+        };
+        let type_path = registration.type_info().type_path();
+        if !self.defs.contains_key(type_path) {
+            // Insert a placeholder first, so that recursive types terminate.
+            self.defs.insert(type_path.to_string(), Value::Null);
+            let schema = self.type_schema(type_id, registration.type_info());
+            self.defs.insert(type_path.to_string(), schema);
+        }
+        json!({ "$ref": format!("#/$defs/{}", pointer_fragment(type_path)) })
+    }
+
+    fn type_schema(&mut self, type_id: TypeId, type_info: &TypeInfo) -> Value {
+        let mut schema = if let Some(schema) = primitive_schema(type_id) {
+            schema
+        } else if self
+            .registry
+            .get_type_data::<ReflectSerialize>(type_id)
+            .is_some()
+        {
+            json!({})
+        } else {
+            match type_info {
+                TypeInfo::Struct(info) => self.struct_schema(info),
+                TypeInfo::TupleStruct(info) => self.tuple_struct_schema(info),
+                TypeInfo::Tuple(info) => self.tuple_schema(info),
+                TypeInfo::List(info) => self.list_schema(info),
+                TypeInfo::Array(info) => self.array_schema(info),
+                TypeInfo::Map(info) => self.map_schema(info),
+                TypeInfo::Enum(info) => self.enum_schema(info),
+                TypeInfo::Value(_) => return Value::Bool(false),
+            }
+        };
+        schema["title"] = type_info.type_path().into();
+        #[cfg(feature = "documentation")]
+        with_description(&mut schema, type_info.docs());
+        schema
+    }
+
+    fn struct_schema(&mut self, info: &StructInfo) -> Value {
+        let serialization_data = self
+            .registry
+            .get(info.type_id())
+            .and_then(|registration| registration.data::<SerializationData>());
+        let fields = info.iter().enumerate().filter(|(index, _)| {
+            !serialization_data.is_some_and(|data| data.is_field_skipped(*index))
+        });
+        self.object_schema(fields.map(|(_, field)| field))
+    }
+
+    fn tuple_struct_schema(&mut self, info: &TupleStructInfo) -> Value {
+        let serialization_data = self
+            .registry
+            .get(info.type_id())
+            .and_then(|registration| registration.data::<SerializationData>());
+        let fields = info.iter().enumerate().filter(|(index, _)| {
+            !serialization_data.is_some_and(|data| data.is_field_skipped(*index))
+        });
+        self.tuple_schema_of(fields.map(|(_, field)| field))
+    }
+
+    fn tuple_schema(&mut self, info: &TupleInfo) -> Value {
+        self.tuple_schema_of(info.iter())
+    }
+
+    fn list_schema(&mut self, info: &ListInfo) -> Value {
+        json!({
+            "type": "array",
+            "items": self.reference(info.item_type_id()),
+        })
+    }
+
+    fn array_schema(&mut self, info: &ArrayInfo) -> Value {
+        json!({
+            "type": "array",
+            "items": self.reference(info.item_type_id()),
+            "minItems": info.capacity(),
+            "maxItems": info.capacity(),
+        })
+    }
+
+    fn map_schema(&mut self, info: &MapInfo) -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "additionalProperties": self.reference(info.value_type_id()),
+        });
+        // Integer keys are serialized as strings, since JSON only allows strings as keys.
+        if primitive_schema(info.key_type_id()).is_some_and(|key| key["type"] == "integer") {
+            schema["propertyNames"] = json!({ "pattern": "^-?[0-9]+$" });
+        }
+        schema
+    }
+
+    fn enum_schema(&mut self, info: &EnumInfo) -> Value {
+        let path_table = info.type_path_table();
+        if path_table.module_path() == Some("core::option") && path_table.ident() == Some("Option")
+        {
+            let some = info
+                .variant("Some")
+                .and_then(|variant| match variant {
+                    VariantInfo::Tuple(variant) => variant.field_at(0),
+                    _ => None,
+                })
+                .map(|field| self.reference(field.type_id()))
+                .unwrap_or_else(|| json!({}));
+            return json!({ "anyOf": [some, { "type": "null" }] });
+        }
+
+        let variants: Vec<Value> = info
+            .iter()
+            .map(|variant| {
+                let (name, value) = match variant {
+                    VariantInfo::Unit(variant) => {
+                        #[allow(unused_mut)]
+                        let mut schema = json!({ "const": variant.name() });
+                        #[cfg(feature = "documentation")]
+                        with_description(&mut schema, variant.docs());
+                        return schema;
+                    }
+                    VariantInfo::Tuple(variant) if variant.field_len() == 1 => {
+                        let field = variant.field_at(0).unwrap();
+                        (variant.name(), self.field_reference(field.type_id(), field))
+                    }
+                    VariantInfo::Tuple(variant) => {
+                        (variant.name(), self.tuple_schema_of(variant.iter()))
+                    }
+                    VariantInfo::Struct(variant) => {
+                        (variant.name(), self.object_schema(variant.iter()))
+                    }
+                };
+                #[allow(unused_mut)]
+                let mut schema = json!({
+                    "type": "object",
+                    "properties": { name: value },
+                    "required": [name],
+                    "additionalProperties": false,
+                });
+                #[cfg(feature = "documentation")]
+                with_description(&mut schema, variant.docs());
+                schema
+            })
+            .collect();
+        json!({ "oneOf": variants })
+    }
+
+    fn object_schema<'f>(&mut self, fields: impl Iterator<Item = &'f NamedField>) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            properties.insert(
+                field.name().to_string(),
+                self.field_reference(field.type_id(), field),
+            );
+            required.push(Value::from(field.name()));
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    fn tuple_schema_of<'f>(&mut self, fields: impl Iterator<Item = &'f UnnamedField>) -> Value {
+        let items: Vec<Value> = fields
+            .map(|field| self.field_reference(field.type_id(), field))
+            .collect();
+        json!({
+            "type": "array",
+            "minItems": items.len(),
+            "maxItems": items.len(),
+            "prefixItems": items,
+            "items": false,
+        })
+    }
+
+    /// Returns a reference to the schema of a field's type, described by the field's docs.
+    #[allow(unused_variables)]
+    fn field_reference(&mut self, type_id: TypeId, field: &impl FieldDocs) -> Value {
+        #[allow(unused_mut)]
+        let mut schema = self.reference(type_id);
+        #[cfg(feature = "documentation")]
+        with_description(&mut schema, field.docs());
+        schema
+    }
+}
+
+/// Access to the docs of [`NamedField`] and [`UnnamedField`].
+trait FieldDocs {
+    #[cfg(feature = "documentation")]
+    fn docs(&self) -> Option<&'static str>;
+}
+
+impl FieldDocs for NamedField {
+    #[cfg(feature = "documentation")]
+    fn docs(&self) -> Option<&'static str> {
+        NamedField::docs(self)
+    }
+}
+
+impl FieldDocs for UnnamedField {
+    #[cfg(feature = "documentation")]
+    fn docs(&self) -> Option<&'static str> {
+        UnnamedField::docs(self)
+    }
+}
+
+#[cfg(feature = "documentation")]
+fn with_description(schema: &mut Value, docs: Option<&str>) {
+    if let (Some(docs), Value::Object(schema)) = (docs, schema) {
+        schema.insert("description".to_string(), docs.trim().into());
+    }
+}
+
+/// Returns the schema of the primitive type with the given [`TypeId`], if it is one.
+fn primitive_schema(type_id: TypeId) -> Option<Value> {
+    macro_rules! integer {
+        ($($ty:ty),*) => {
+            $(if type_id == TypeId::of::<$ty>() {
+                return Some(json!({ "type": "integer", "minimum": <$ty>::MIN, "maximum": <$ty>::MAX }));
+            })*
+        };
+    }
+
+    integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    let schema = if type_id == TypeId::of::<bool>() {
+        json!({ "type": "boolean" })
+    } else if type_id == TypeId::of::<u128>() {
+        json!({ "type": "integer", "minimum": 0 })
+    } else if type_id == TypeId::of::<i128>() {
+        json!({ "type": "integer" })
+    } else if type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>() {
+        // Non-finite floats are serialized as `null`.
+        json!({ "type": ["number", "null"] })
+    } else if type_id == TypeId::of::<char>() {
+        json!({ "type": "string", "minLength": 1, "maxLength": 1 })
+    } else if type_id == TypeId::of::<String>()
+        || type_id == TypeId::of::<&'static str>()
+        || type_id == TypeId::of::<Cow<'static, str>>()
+        || type_id == TypeId::of::<PathBuf>()
+    {
+        json!({ "type": "string" })
+    } else {
+        return None;
+    };
+    Some(schema)
+}
+
+/// Escapes a `$defs` key into a JSON Pointer usable as a URI fragment.
+fn pointer_fragment(key: &str) -> String {
+    let mut fragment = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'~' => fragment.push_str("~0"),
+            b'/' => fragment.push_str("~1"),
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@' => fragment.push(byte as char),
+            _ => fragment.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    fragment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, serde::TypedReflectSerializer, Reflect, TypePath};
+    use bevy_utils::HashMap;
+
+    /// Serializes `value` with a [`TypedReflectSerializer`] and checks that the output has the
+    /// shape of the generated schema.
+    fn assert_matches_schema(value: &dyn Reflect, registry: &TypeRegistry) {
+        let schema = typed_json_schema(value.type_id(), registry).unwrap();
+        let json = serde_json::to_value(TypedReflectSerializer::new(value, registry)).unwrap();
+        assert!(
+            validate(&json, &schema, &schema),
+            "{json} doesn't match the schema {schema:#}"
+        );
+    }
+
+    /// Validates `value` against the subset of JSON Schema the generator uses.
+    fn validate(value: &Value, schema: &Value, document: &Value) -> bool {
+        let schema = match schema {
+            Value::Bool(accept) => return *accept,
+            Value::Object(schema) => schema,
+            _ => panic!("invalid schema {schema}"),
+        };
+        if let Some(Value::String(reference)) = schema.get("$ref") {
+            let pointer = reference.strip_prefix('#').unwrap();
+            let pointer = pointer
+                .replace("%3C", "<")
+                .replace("%3E", ">")
+                .replace("%20", " ")
+                .replace("%5B", "[")
+                .replace("%5D", "]");
+            if !validate(value, document.pointer(&pointer).unwrap(), document) {
+                return false;
+            }
+        }
+        let types = match schema.get("type") {
+            Some(Value::String(kind)) => vec![kind.as_str()],
+            Some(Value::Array(kinds)) => kinds.iter().map(|kind| kind.as_str().unwrap()).collect(),
+            _ => Vec::new(),
+        };
+        let has_type = |kind| match (kind, value) {
+            ("null", Value::Null)
+            | ("boolean", Value::Bool(_))
+            | ("number", Value::Number(_))
+            | ("string", Value::String(_))
+            | ("array", Value::Array(_))
+            | ("object", Value::Object(_)) => true,
+            ("integer", Value::Number(number)) => number.is_i64() || number.is_u64(),
+            _ => false,
+        };
+        if !types.is_empty() && !types.into_iter().any(has_type) {
+            return false;
+        }
+        if let Some(constant) = schema.get("const") {
+            if value != constant {
+                return false;
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            if !schemas
+                .iter()
+                .any(|schema| validate(value, schema, document))
+            {
+                return false;
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+            let matching = schemas
+                .iter()
+                .filter(|schema| validate(value, schema, document))
+                .count();
+            if matching != 1 {
+                return false;
+            }
+        }
+        if let Value::Array(items) = value {
+            let prefix = match schema.get("prefixItems") {
+                Some(Value::Array(prefix)) => prefix.as_slice(),
+                _ => &[],
+            };
+            for (index, item) in items.iter().enumerate() {
+                let item_schema = prefix.get(index).or(schema.get("items"));
+                if item_schema.is_some_and(|schema| !validate(item, schema, document)) {
+                    return false;
+                }
+            }
+            let len = items.len() as u64;
+            if schema
+                .get("minItems")
+                .is_some_and(|min| len < min.as_u64().unwrap())
+                || schema
+                    .get("maxItems")
+                    .is_some_and(|max| len > max.as_u64().unwrap())
+            {
+                return false;
+            }
+        }
+        if let Value::Object(object) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, value) in object {
+                let property_schema = properties
+                    .and_then(|properties| properties.get(key))
+                    .or(schema.get("additionalProperties"));
+                if property_schema.is_some_and(|schema| !validate(value, schema, document)) {
+                    return false;
+                }
+            }
+            if let Some(Value::Array(required)) = schema.get("required") {
+                if !required
+                    .iter()
+                    .all(|key| object.contains_key(key.as_str().unwrap()))
+                {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    #[derive(Reflect)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        #[reflect(skip_serializing)]
+        _cached: u32,
+        inventory: Vec<Item>,
+        stats: HashMap<u8, Stat>,
+        pet: Option<Pet>,
+        slots: [Option<Item>; 2],
+    }
+
+    #[derive(Reflect, Clone)]
+    enum Item {
+        Empty,
+        Coins(u64),
+        Potion(String, u8),
+        Weapon { damage: f32, broken: bool },
+    }
+
+    #[derive(Reflect)]
+    struct Stat(i16);
+
+    #[derive(Reflect)]
+    struct Pet {
+        name: Option<String>,
+        best_stat: Option<Stat>,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry.register::<Item>();
+        registry.register::<Stat>();
+        registry.register::<Pet>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<u8, Stat>>();
+        registry.register::<Option<Pet>>();
+        registry.register::<Option<Item>>();
+        registry.register::<[Option<Item>; 2]>();
+        registry.register::<Option<String>>();
+        registry
+    }
+
+    #[test]
+    fn schema_matches_serialized_values() {
+        let registry = registry();
+        let player = Player {
+            name: "Ferris".to_string(),
+            position: (1.5, -2.0),
+            _cached: 7,
+            inventory: vec![
+                Item::Empty,
+                Item::Coins(12),
+                Item::Potion("healing".to_string(), 3),
+                Item::Weapon {
+                    damage: 4.5,
+                    broken: false,
+                },
+            ],
+            stats: HashMap::from([(0, Stat(-3)), (1, Stat(10))]),
+            pet: Some(Pet {
+                name: None,
+                best_stat: Some(Stat(2)),
+            }),
+            slots: [None, Some(Item::Coins(1))],
+        };
+        assert_matches_schema(&player, &registry);
+        assert_matches_schema(&Item::Potion("mana".to_string(), 1), &registry);
+        assert_matches_schema(&Stat(1), &registry);
+
+        let schema = typed_json_schema(TypeId::of::<Item>(), &registry).unwrap();
+        assert!(!validate(&json!("Coins"), &schema, &schema));
+        assert!(!validate(&json!({ "Potion": ["mana"] }), &schema, &schema));
+    }
+
+    #[test]
+    fn schema_shapes() {
+        let registry = registry();
+        let schema = typed_json_schema(TypeId::of::<Player>(), &registry).unwrap();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        let defs = &schema["$defs"];
+
+        let player = &defs[Player::type_path()];
+        assert_eq!(
+            player["required"],
+            json!(["name", "position", "inventory", "stats", "pet", "slots"])
+        );
+        assert!(player["properties"].get("_cached").is_none());
+        assert_eq!(defs[Stat::type_path()]["maxItems"], 1);
+        assert_eq!(
+            defs[HashMap::<u8, Stat>::type_path()]["propertyNames"]["pattern"],
+            "^-?[0-9]+$"
+        );
+        assert_eq!(
+            defs[Option::<Pet>::type_path()]["anyOf"][1],
+            json!({ "type": "null" })
+        );
+
+        let item = &defs[Item::type_path()]["oneOf"];
+        assert_eq!(item[0]["const"], "Empty");
+        assert_eq!(item[1]["properties"]["Coins"]["$ref"], "#/$defs/u64");
+        assert_eq!(item[2]["properties"]["Potion"]["maxItems"], 2);
+        assert_eq!(
+            item[3]["properties"]["Weapon"]["required"],
+            json!(["damage", "broken"])
+        );
+
+        // `Option<Stat>` isn't registered, so it accepts any value.
+        let pet = &defs[Pet::type_path()];
+        assert_eq!(pet["properties"]["best_stat"], json!({}));
+    }
+
+    #[test]
+    fn reflect_schema_includes_registered_types() {
+        let registry = registry();
+        let schema = reflect_json_schema(&registry);
+        assert_eq!(schema["maxProperties"], 1);
+        assert_eq!(
+            schema["properties"].as_object().unwrap().len(),
+            registry.iter().count()
+        );
+        assert_eq!(
+            schema["properties"]
+                ["[core::option::Option<bevy_reflect::serde::schema::tests::Item>; 2]"]["$ref"],
+            "#/$defs/%5Bcore::option::Option%3Cbevy_reflect::serde::schema::tests::Item%3E;%202%5D"
+        );
+        assert_eq!(
+            schema["$defs"]["i8"],
+            json!({ "type": "integer", "minimum": -128, "maximum": 127, "title": "i8" })
+        );
+    }
+
+    #[cfg(feature = "documentation")]
+    #[test]
+    fn schema_includes_docs() {
+        /// A collectible.
+        #[derive(Reflect)]
+        enum Collectible {
+            /// A shiny coin.
+            Coin,
+            Gem {
+                /// The gem's value.
+                value: u32,
+            },
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Collectible>();
+        let schema = typed_json_schema(TypeId::of::<Collectible>(), &registry).unwrap();
+        let collectible = &schema["$defs"][Collectible::type_path()];
+        assert_eq!(collectible["description"], "A collectible.");
+        assert_eq!(collectible["oneOf"][0]["description"], "A shiny coin.");
+        assert_eq!(
+            collectible["oneOf"][1]["properties"]["Gem"]["properties"]["value"]["description"],
+            "The gem's value."
+        );
+    }
+}
@@ -11,7 +11,7 @@ use serde::{
     Serialize,
 };
 
-use super::SerializationData;
+use super::{versioned_type_path, SerializationData};
 
 pub enum Serializable<'a> {
     Owned(Box<dyn erased_serde::Serialize + 'a>),
@@ -28,7 +28,7 @@ impl<'a> Serializable<'a> {
     }
 }
 
-fn get_serializable<'a, E: Error>(
+pub(super) fn get_serializable<'a, E: Error>(
     reflect_value: &'a dyn Reflect,
     type_registry: &TypeRegistry,
 ) -> Result<Serializable<'a>, E> {
@@ -68,7 +68,8 @@ impl<'a> Serialize for ReflectSerializer<'a> {
     {
         let mut state = serializer.serialize_map(Some(1))?;
         state.serialize_entry(
-            self.value
+            &self
+                .value
                 .get_represented_type_info()
                 .ok_or_else(|| {
                     if self.value.is_dynamic() {
@@ -82,8 +83,8 @@ impl<'a> Serialize for ReflectSerializer<'a> {
                             self.value.reflect_type_path()
                         ))
                     }
-                })?
-                .type_path(),
+                })
+                .map(|type_info| versioned_type_path(type_info, self.registry))?,
             &TypedReflectSerializer::new(self.value, self.registry),
         )?;
         state.end()
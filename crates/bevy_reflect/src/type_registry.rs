@@ -1,9 +1,14 @@
-use crate::{serde::Serializable, Reflect, TypeInfo, TypePath, Typed};
+use crate::{
+    func::{DynamicFunction, IntoFunction, ReflectFunctions},
+    serde::{BoxedMigrationError, ReflectMigrations, Serializable},
+    Reflect, TypeInfo, TypePath, Typed,
+};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use serde::Deserialize;
 use std::{
+    alloc::Layout,
     any::TypeId,
     fmt::Debug,
     sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
@@ -26,6 +31,7 @@ pub struct TypeRegistry {
     short_path_to_id: HashMap<&'static str, TypeId>,
     type_path_to_id: HashMap<&'static str, TypeId>,
     ambiguous_names: HashSet<&'static str>,
+    functions: HashMap<String, DynamicFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -74,6 +80,7 @@ impl TypeRegistry {
             short_path_to_id: Default::default(),
             type_path_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            functions: Default::default(),
         }
     }
 
@@ -276,6 +283,143 @@ impl TypeRegistry {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut TypeRegistration> {
         self.registrations.values_mut()
     }
+
+    /// Registers a free function under the given `name`, replacing any function with the same name.
+    ///
+    /// The function can then be retrieved with [`TypeRegistry::get_function`] and called with
+    /// reflected arguments. See the [`func`](crate::func) module for more information.
+    pub fn register_function<Marker>(
+        &mut self,
+        name: impl Into<String>,
+        function: impl IntoFunction<Marker>,
+    ) {
+        let name = name.into();
+        let function = function.into_function().with_name(name.clone());
+        self.functions.insert(name, function);
+    }
+
+    /// Registers a function associated with type `T`, like one of its methods, under the given
+    /// `name` in the [`ReflectFunctions`] type data of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` hasn't been registered.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_reflect::{func::ReflectFunctions, Reflect, TypeRegistry};
+    /// # use std::any::TypeId;
+    ///
+    /// #[derive(Reflect)]
+    /// struct Counter(u32);
+    ///
+    /// impl Counter {
+    ///     fn increment(&mut self) {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Counter>();
+    /// type_registry.register_function_for::<Counter, _>("increment", Counter::increment);
+    ///
+    /// let functions = type_registry.get_type_data::<ReflectFunctions>(TypeId::of::<Counter>());
+    /// assert!(functions.unwrap().get("increment").is_some());
+    /// ```
+    pub fn register_function_for<T: Reflect + TypePath, Marker>(
+        &mut self,
+        name: impl Into<String>,
+        function: impl IntoFunction<Marker>,
+    ) {
+        let registration = self.get_mut(TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "attempted to call `TypeRegistry::register_function_for` for type `{T}` without registering `{T}` first",
+                T = T::type_path(),
+            )
+        });
+        if registration.data::<ReflectFunctions>().is_none() {
+            registration.insert(ReflectFunctions::default());
+        }
+        registration
+            .data_mut::<ReflectFunctions>()
+            .unwrap()
+            .insert(name, function);
+    }
+
+    /// Registers a migration of type `T` from its current version to the next one in the
+    /// [`ReflectMigrations`] type data of `T`.
+    ///
+    /// Values serialized at the current version are deserialized with the layout of `Previous`,
+    /// usually an older copy of `T`, and converted by `migrate`.
+    /// See [`ReflectMigrations`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` hasn't been registered.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_reflect::{serde::ReflectMigrations, DynamicEnum, Enum, Reflect, TypeRegistry};
+    /// # use std::any::TypeId;
+    ///
+    /// #[derive(Reflect)]
+    /// enum OldDifficulty {
+    ///     Easy,
+    ///     Normal,
+    /// }
+    ///
+    /// #[derive(Reflect)]
+    /// enum Difficulty {
+    ///     Casual,
+    ///     Normal,
+    /// }
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Difficulty>();
+    /// type_registry.register_migration::<Difficulty, OldDifficulty, _>(|old| {
+    ///     let variant = match old.downcast_ref::<DynamicEnum>().unwrap().variant_name() {
+    ///         "Easy" => "Casual",
+    ///         name => name,
+    ///     };
+    ///     Ok(Box::new(DynamicEnum::new(variant, ())))
+    /// });
+    ///
+    /// let migrations = type_registry.get_type_data::<ReflectMigrations>(TypeId::of::<Difficulty>());
+    /// assert_eq!(migrations.unwrap().version(), 1);
+    /// ```
+    pub fn register_migration<T, Previous, F>(&mut self, migrate: F)
+    where
+        T: Reflect + TypePath,
+        Previous: GetTypeRegistration,
+        F: Fn(Box<dyn Reflect>) -> Result<Box<dyn Reflect>, BoxedMigrationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let registration = self.get_mut(TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "attempted to call `TypeRegistry::register_migration` for type `{T}` without registering `{T}` first",
+                T = T::type_path(),
+            )
+        });
+        if registration.data::<ReflectMigrations>().is_none() {
+            registration.insert(ReflectMigrations::default());
+        }
+        registration
+            .data_mut::<ReflectMigrations>()
+            .unwrap()
+            .add_migration::<Previous, F>(migrate);
+    }
+
+    /// Returns the free function registered under the given `name`.
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the registered free functions.
+    pub fn functions(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
 }
 
 impl TypeRegistryArc {
@@ -320,6 +464,7 @@ impl TypeRegistryArc {
 pub struct TypeRegistration {
     data: HashMap<TypeId, Box<dyn TypeData>>,
     type_info: &'static TypeInfo,
+    layout: Layout,
 }
 
 impl Debug for TypeRegistration {
@@ -363,6 +508,11 @@ impl TypeRegistration {
         self.type_info
     }
 
+    /// Returns the size and alignment of the type, as seen by the code that registered it.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
     /// Inserts an instance of `T` into this registration's type data.
     ///
     /// If another instance of `T` was previously inserted, it is replaced.
@@ -370,11 +520,23 @@ impl TypeRegistration {
         self.data.insert(TypeId::of::<T>(), Box::new(data));
     }
 
+    /// Inserts a copy of the type data of `other` that this registration doesn't have.
+    ///
+    /// Type data already present in this registration is kept.
+    pub fn insert_missing_data(&mut self, other: &TypeRegistration) {
+        for (id, type_data) in &other.data {
+            self.data
+                .entry(*id)
+                .or_insert_with(|| (**type_data).clone_type_data());
+        }
+    }
+
     /// Creates type registration information for `T`.
     pub fn of<T: Reflect + Typed + TypePath>() -> Self {
         Self {
             data: HashMap::default(),
             type_info: T::type_info(),
+            layout: Layout::new::<T>(),
         }
     }
 }
@@ -389,6 +551,7 @@ impl Clone for TypeRegistration {
         TypeRegistration {
             data,
             type_info: self.type_info,
+            layout: self.layout,
         }
     }
 }
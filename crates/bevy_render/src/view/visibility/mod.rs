@@ -29,8 +29,12 @@ use crate::{
 ///
 /// This is done by the `visibility_propagate_system` which uses the entity hierarchy and
 /// `Visibility` to set the values of each entity's [`InheritedVisibility`] component.
+///
+/// [`InheritedVisibility`] and [`ViewVisibility`] are required components of `Visibility`,
+/// and are inserted with their default values if the entity doesn't have them yet.
 #[derive(Component, Clone, Copy, Reflect, Debug, PartialEq, Eq, Default)]
 #[reflect(Component, Default)]
+#[require(InheritedVisibility, ViewVisibility)]
 pub enum Visibility {
     /// An entity with `Visibility::Inherited` will inherit the Visibility of its [`Parent`].
     ///
@@ -1,18 +1,20 @@
 use crate::{ron, DynamicSceneBuilder, Scene, SceneSpawnError};
 use bevy_ecs::{
-    entity::Entity,
+    entity::{Entity, StableId, StableIds},
     reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
     world::World,
 };
 use bevy_reflect::{Reflect, TypePath, TypeRegistryArc};
 use bevy_utils::{EntityHashMap, HashMap};
-use std::any::TypeId;
+use std::{any::TypeId, collections::BTreeMap};
 
 #[cfg(feature = "serialize")]
-use crate::serde::SceneSerializer;
+use crate::serde::{CompactSceneSerializer, SceneSerializer};
 use bevy_asset::Asset;
 use bevy_ecs::reflect::ReflectResource;
 #[cfg(feature = "serialize")]
+use bincode::Options;
+#[cfg(feature = "serialize")]
 use serde::Serialize;
 
 /// A collection of serializable resources and dynamic entities.
@@ -31,6 +33,12 @@ pub struct DynamicScene {
     pub resources: Vec<Box<dyn Reflect>>,
     /// Entities contained in the dynamic scene.
     pub entities: Vec<DynamicEntity>,
+    /// The [`StableId`]s of the entities of the scene, and of the entities outside of it that its
+    /// components reference.
+    ///
+    /// When the scene is written to a world, each of these entities resolves to the entity of that
+    /// world with the same [`StableId`], if there is one, instead of a newly spawned entity.
+    pub stable_ids: BTreeMap<Entity, StableId>,
 }
 
 /// A reflection-powered serializable representation of an entity and its components.
@@ -63,6 +71,9 @@ impl DynamicScene {
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
     /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
     /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::prelude::Resource) trait.
+    ///
+    /// Entities of the scene with a [`StableId`] found in the world's [`StableIds`] are written to the
+    /// entity with that id, and references to them are mapped to it, regardless of `entity_map`.
     pub fn write_to_world_with(
         &self,
         world: &mut World,
@@ -93,20 +104,36 @@ impl DynamicScene {
             reflect_resource.apply_or_insert(world, &**resource);
         }
 
-        // For each component types that reference other entities, we keep track
-        // of which entities in the scene use that component.
+        // For each component types that reference other entities but could not be
+        // mapped before being inserted, we keep track of which entities in the scene
+        // use that component.
         // This is so we can update the scene-internal references to references
         // of the actual entities in the world.
         let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
 
+        // Entities with a stable id that already exists in the world resolve to the entity holding
+        // it, whether they are part of the scene or only referenced by it.
+        if let Some(stable_ids) = world.get_resource::<StableIds>() {
+            for (&scene_entity, &stable_id) in &self.stable_ids {
+                if let Some(entity) = stable_ids.get(stable_id) {
+                    entity_map.insert(scene_entity, entity);
+                }
+            }
+        }
+
+        // Fetch the entity with the given entity id from the `entity_map`
+        // or spawn a new entity with a transiently unique id if there is
+        // no corresponding entry.
+        // This is done for all entities up front, so that references between
+        // entities of the scene can be mapped before their components are inserted.
         for scene_entity in &self.entities {
-            // Fetch the entity with the given entity id from the `entity_map`
-            // or spawn a new entity with a transiently unique id if there is
-            // no corresponding entry.
-            let entity = *entity_map
+            entity_map
                 .entry(scene_entity.entity)
                 .or_insert_with(|| world.spawn_empty().id());
-            let entity_mut = &mut world.entity_mut(entity);
+        }
+
+        for scene_entity in &self.entities {
+            let entity = entity_map[&scene_entity.entity];
 
             // Apply/ add each component to the given entity.
             for component in &scene_entity.components {
@@ -127,9 +154,25 @@ impl DynamicScene {
                         }
                     })?;
 
-                // If this component references entities in the scene, track it
-                // so we can update it to the entity in the world.
-                if registration.data::<ReflectMapEntities>().is_some() {
+                // If this component references entities in the scene, map it to the
+                // entities in the world before inserting it, so that its hooks only
+                // ever see the mapped entities.
+                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                    if let Some(mapped) = map_entities_reflect.map_reflect(
+                        world,
+                        entity_map,
+                        &**component,
+                        &type_registry,
+                    ) {
+                        reflect_component.insert(
+                            &mut world.entity_mut(entity),
+                            &*mapped,
+                            &type_registry,
+                        );
+                        continue;
+                    }
+
+                    // Otherwise track it so we can update it to the entity in the world.
                     scene_mappings
                         .entry(registration.type_id())
                         .or_insert(Vec::new())
@@ -139,7 +182,11 @@ impl DynamicScene {
                 // If the entity already has the given component attached,
                 // just apply the (possibly) new value, otherwise add the
                 // component to the entity.
-                reflect_component.apply_or_insert(entity_mut, &**component, &type_registry);
+                reflect_component.apply_or_insert(
+                    &mut world.entity_mut(entity),
+                    &**component,
+                    &type_registry,
+                );
             }
         }
 
@@ -176,6 +223,16 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    // TODO: move to AssetSaver when it is implemented
+    /// Serialize this dynamic scene into the compact binary format loaded by
+    /// [`SceneLoader`](crate::SceneLoader) from `.scn.bin` files.
+    ///
+    /// See [`CompactSceneSerializer`] for the layout of the format.
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(&self, registry: &TypeRegistryArc) -> Result<Vec<u8>, bincode::Error> {
+        serialize_binary(CompactSceneSerializer::new(self, registry))
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -190,10 +247,25 @@ where
     ron::ser::to_string_pretty(&serialize, pretty_config)
 }
 
+/// Serialize a given Rust data structure into the binary format of
+/// [`DynamicScene::serialize_binary`].
+#[cfg(feature = "serialize")]
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, bincode::Error>
+where
+    S: Serialize,
+{
+    bincode::DefaultOptions::new().serialize(&serialize)
+}
+
 #[cfg(test)]
 mod tests {
-    use bevy_ecs::{reflect::AppTypeRegistry, system::Command, world::World};
-    use bevy_hierarchy::{Parent, PushChild};
+    use bevy_ecs::{
+        entity::{StableId, StableIds},
+        reflect::AppTypeRegistry,
+        system::Command,
+        world::World,
+    };
+    use bevy_hierarchy::{Children, Parent, PushChild};
     use bevy_utils::EntityHashMap;
 
     use crate::dynamic_scene_builder::DynamicSceneBuilder;
@@ -274,4 +346,96 @@ mod tests {
             "something is wrong with the this test or the code reloading scenes since the relationship between scene entities is broken"
         );
     }
+
+    #[test]
+    fn scene_hierarchy_is_mapped_before_insertion() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let type_registry = world.resource::<AppTypeRegistry>();
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Parent>();
+            type_registry.register::<Children>();
+        }
+        let original_parent_entity = world.spawn_empty().id();
+        let original_child_entity = world.spawn_empty().id();
+        PushChild {
+            parent: original_parent_entity,
+            child: original_child_entity,
+        }
+        .apply(&mut world);
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entity(original_parent_entity)
+            .extract_entity(original_child_entity)
+            .build();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        // Writing the scene again must not duplicate or corrupt the relationship.
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let &from_scene_parent_entity = entity_map.get(&original_parent_entity).unwrap();
+        let &from_scene_child_entity = entity_map.get(&original_child_entity).unwrap();
+
+        assert_eq!(
+            &**world.get::<Children>(from_scene_parent_entity).unwrap(),
+            &[from_scene_child_entity]
+        );
+        assert_eq!(
+            &**world.get::<Children>(original_parent_entity).unwrap(),
+            &[original_child_entity]
+        );
+        assert_eq!(
+            world.get::<Parent>(from_scene_child_entity).unwrap().get(),
+            from_scene_parent_entity
+        );
+    }
+
+    #[test]
+    fn stable_ids_resolve_to_existing_entities() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let type_registry = world.resource::<AppTypeRegistry>();
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Parent>();
+            type_registry.register::<StableId>();
+        }
+        world.init_resource::<StableIds>();
+        let parent_id = StableId::new();
+        let child_id = StableId::new();
+        let parent = world.spawn(parent_id).id();
+        let child = world.spawn(child_id).id();
+        PushChild { parent, child }.apply(&mut world);
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entity(child)
+            .build();
+
+        // Respawn both entities with their identities, as a game reloading a save would.
+        world.despawn(parent);
+        world.despawn(child);
+        let respawned_parent = world.spawn(parent_id).id();
+
+        scene
+            .write_to_world(&mut world, &mut EntityHashMap::default())
+            .unwrap();
+        let respawned_child = world.resource::<StableIds>().get(child_id).unwrap();
+        assert_eq!(
+            world.get::<Parent>(respawned_child).unwrap().get(),
+            respawned_parent,
+            "the reference to an entity outside of the scene should resolve through its stable id"
+        );
+
+        // Writing the scene again updates the same entity instead of spawning a new one.
+        let entity_count = world.entities().len();
+        scene
+            .write_to_world(&mut world, &mut EntityHashMap::default())
+            .unwrap();
+        assert_eq!(world.entities().len(), entity_count);
+        assert_eq!(
+            world.resource::<StableIds>().get(child_id),
+            Some(respawned_child)
+        );
+    }
 }
@@ -2,8 +2,9 @@ use crate::{DynamicEntity, DynamicScene, SceneFilter};
 use bevy_ecs::component::{Component, ComponentId};
 use bevy_ecs::system::Resource;
 use bevy_ecs::{
+    entity::StableId,
     prelude::Entity,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
 use bevy_reflect::Reflect;
@@ -36,6 +37,13 @@ use std::collections::BTreeMap;
 /// This means that inserting `Entity(1v0)` then `Entity(0v0)` will always result in the entities
 /// being ordered as `[Entity(0v0), Entity(1v0)]`.
 ///
+/// # Stable Ids
+///
+/// The [`StableId`] of every extracted entity, and of every entity referenced by an extracted component
+/// whose type registration has [`ReflectMapEntities`] type data, is recorded in the scene's
+/// [`stable_ids`](DynamicScene::stable_ids). When the scene is written back to a world, these entities
+/// resolve to the entities of that world with the same [`StableId`], if any.
+///
 /// # Example
 /// ```
 /// # use bevy_scene::DynamicSceneBuilder;
@@ -55,6 +63,7 @@ use std::collections::BTreeMap;
 pub struct DynamicSceneBuilder<'w> {
     extracted_resources: BTreeMap<ComponentId, Box<dyn Reflect>>,
     extracted_scene: BTreeMap<Entity, DynamicEntity>,
+    stable_ids: BTreeMap<Entity, StableId>,
     component_filter: SceneFilter,
     resource_filter: SceneFilter,
     original_world: &'w World,
@@ -66,6 +75,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         Self {
             extracted_resources: default(),
             extracted_scene: default(),
+            stable_ids: default(),
             component_filter: SceneFilter::default(),
             resource_filter: SceneFilter::default(),
             original_world: world,
@@ -187,6 +197,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         DynamicScene {
             resources: self.extracted_resources.into_values().collect(),
             entities: self.extracted_scene.into_values().collect(),
+            stable_ids: self.stable_ids,
         }
     }
 
@@ -257,6 +268,10 @@ impl<'w> DynamicSceneBuilder<'w> {
             };
 
             let original_entity = self.original_world.entity(entity);
+            if let Some(&stable_id) = original_entity.get::<StableId>() {
+                self.stable_ids.insert(entity, stable_id);
+            }
+
             for component_id in original_entity.archetype().components() {
                 let mut extract_and_push = || {
                     let type_id = self
@@ -272,10 +287,31 @@ impl<'w> DynamicSceneBuilder<'w> {
                         return None;
                     }
 
-                    let component = type_registry
-                        .get(type_id)?
+                    let registration = type_registry.get(type_id)?;
+                    let component = registration
                         .data::<ReflectComponent>()?
                         .reflect(original_entity)?;
+
+                    // Record the stable ids of the entities this component references, so that
+                    // references to entities outside of the scene can be resolved when it is loaded.
+                    if let Some(referenced) =
+                        registration
+                            .data::<ReflectMapEntities>()
+                            .and_then(|map_entities| {
+                                map_entities.referenced_entities(component, &type_registry)
+                            })
+                    {
+                        for referenced in referenced {
+                            if let Some(&stable_id) = self
+                                .original_world
+                                .get_entity(referenced)
+                                .and_then(|entity| entity.get::<StableId>())
+                            {
+                                self.stable_ids.insert(referenced, stable_id);
+                            }
+                        }
+                    }
+
                     entry.components.push(component.clone_value());
                     Some(())
                 };
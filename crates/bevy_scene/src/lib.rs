@@ -20,7 +20,7 @@ pub mod serde;
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy scenes.
 pub use bevy_asset::ron;
 
-use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::{entity::StableIds, schedule::IntoSystemConfigs};
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
@@ -42,6 +42,9 @@ use bevy_app::prelude::*;
 use bevy_asset::AssetApp;
 
 /// Plugin that provides scene functionality to an [`App`].
+///
+/// It adds the [`StableIds`] resource, so that scenes can resolve their entities through their
+/// [`StableId`](bevy_ecs::entity::StableId).
 #[derive(Default)]
 pub struct ScenePlugin;
 
@@ -53,6 +56,7 @@ impl Plugin for ScenePlugin {
             .init_asset_loader::<SceneLoader>()
             .add_event::<SceneInstanceReady>()
             .init_resource::<SceneSpawner>()
+            .init_resource::<StableIds>()
             .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
     }
 }
@@ -1,11 +1,13 @@
 use crate::{DynamicScene, InstanceInfo, SceneSpawnError};
 use bevy_asset::Asset;
 use bevy_ecs::{
+    entity::Entity,
     reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
 use bevy_reflect::TypePath;
-use bevy_utils::EntityHashMap;
+use bevy_utils::{EntityHashMap, HashMap};
+use std::any::TypeId;
 
 /// To spawn a scene, you can use either:
 /// * [`SceneSpawner::spawn`](crate::SceneSpawner::spawn)
@@ -92,12 +94,24 @@ impl Scene {
             reflect_resource.copy(&self.world, world);
         }
 
+        // Spawn all entities up front, so that references between entities of the scene
+        // can be mapped before their components are inserted.
         for archetype in self.world.archetypes().iter() {
             for scene_entity in archetype.entities() {
-                let entity = *instance_info
+                instance_info
                     .entity_map
                     .entry(scene_entity.id())
                     .or_insert_with(|| world.spawn_empty().id());
+            }
+        }
+
+        // Components that reference other entities but could not be mapped before being
+        // inserted, along with the entities that use them.
+        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+
+        for archetype in self.world.archetypes().iter() {
+            for scene_entity in archetype.entities() {
+                let entity = instance_info.entity_map[&scene_entity.id()];
                 for component_id in archetype.components() {
                     let component_info = self
                         .world
@@ -105,18 +119,46 @@ impl Scene {
                         .get_info(component_id)
                         .expect("component_ids in archetypes should have ComponentInfo");
 
-                    let reflect_component = type_registry
+                    let registration = type_registry
                         .get(component_info.type_id().unwrap())
                         .ok_or_else(|| SceneSpawnError::UnregisteredType {
                             std_type_name: component_info.name().to_string(),
-                        })
-                        .and_then(|registration| {
-                            registration.data::<ReflectComponent>().ok_or_else(|| {
-                                SceneSpawnError::UnregisteredComponent {
-                                    type_path: registration.type_info().type_path().to_string(),
-                                }
-                            })
                         })?;
+                    let reflect_component =
+                        registration.data::<ReflectComponent>().ok_or_else(|| {
+                            SceneSpawnError::UnregisteredComponent {
+                                type_path: registration.type_info().type_path().to_string(),
+                            }
+                        })?;
+
+                    // If this component references entities in the scene, map it to the
+                    // entities in the world before inserting it, so that its hooks only
+                    // ever see the mapped entities.
+                    if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                        let mapped = reflect_component
+                            .reflect(self.world.entity(scene_entity.id()))
+                            .and_then(|component| {
+                                map_entities_reflect.map_reflect(
+                                    world,
+                                    &mut instance_info.entity_map,
+                                    component,
+                                    &type_registry,
+                                )
+                            });
+                        if let Some(mapped) = mapped {
+                            reflect_component.insert(
+                                &mut world.entity_mut(entity),
+                                &*mapped,
+                                &type_registry,
+                            );
+                            continue;
+                        }
+                        scene_mappings
+                            .entry(registration.type_id())
+                            .or_default()
+                            .push(entity);
+                    }
+
                     reflect_component.copy(
                         &self.world,
                         world,
@@ -128,9 +170,13 @@ impl Scene {
             }
         }
 
-        for registration in type_registry.iter() {
+        // Updates references to entities in the scene to entities in the world
+        for (type_id, entities) in scene_mappings.into_iter() {
+            let registration = type_registry.get(type_id).expect(
+                "we should be getting TypeId from this TypeRegistration in the first place",
+            );
             if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect.map_all_entities(world, &mut instance_info.entity_map);
+                map_entities_reflect.map_entities(world, &mut instance_info.entity_map, &entities);
             }
         }
 
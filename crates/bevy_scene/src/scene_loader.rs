@@ -1,6 +1,6 @@
 use crate::ron;
 #[cfg(feature = "serialize")]
-use crate::serde::SceneDeserializer;
+use crate::serde::{CompactSceneDeserializer, SceneDeserializer};
 use crate::DynamicScene;
 use bevy_asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
 use bevy_ecs::reflect::AppTypeRegistry;
@@ -8,10 +8,15 @@ use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
 #[cfg(feature = "serialize")]
+use bincode::Options;
+#[cfg(feature = "serialize")]
 use serde::de::DeserializeSeed;
 use thiserror::Error;
 
 /// [`AssetLoader`] for loading serialized Bevy scene files as [`DynamicScene`].
+///
+/// Scenes are read as RON, except `.scn.bin` files which are read in the compact binary format
+/// written by [`DynamicScene::serialize_binary`].
 #[derive(Debug)]
 pub struct SceneLoader {
     type_registry: TypeRegistryArc,
@@ -36,6 +41,10 @@ pub enum SceneLoaderError {
     /// A [RON Error](ron::error::SpannedError)
     #[error("Could not parse RON: {0}")]
     RonSpannedError(#[from] ron::error::SpannedError),
+    /// A [Bincode Error](bincode::Error)
+    #[cfg(feature = "serialize")]
+    #[error("Could not parse binary scene: {0}")]
+    Bincode(#[from] bincode::Error),
 }
 
 #[cfg(feature = "serialize")]
@@ -48,11 +57,19 @@ impl AssetLoader for SceneLoader {
         &'a self,
         reader: &'a mut Reader,
         _settings: &'a (),
-        _load_context: &'a mut LoadContext,
+        load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
+            if load_context.path().extension() == Some("bin".as_ref()) {
+                let scene_deserializer = CompactSceneDeserializer {
+                    type_registry: &self.type_registry.read(),
+                };
+                return Ok(
+                    bincode::DefaultOptions::new().deserialize_seed(scene_deserializer, &bytes)?
+                );
+            }
             let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
             let scene_deserializer = SceneDeserializer {
                 type_registry: &self.type_registry.read(),
@@ -64,6 +81,6 @@ impl AssetLoader for SceneLoader {
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", "scn.bin"]
     }
 }
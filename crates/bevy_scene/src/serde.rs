@@ -1,20 +1,20 @@
 //! `serde` serialization and deserialization implementation for Bevy scenes.
 
 use crate::{DynamicEntity, DynamicScene};
-use bevy_ecs::entity::Entity;
-use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
-use bevy_reflect::{
-    serde::{TypeRegistrationDeserializer, UntypedReflectDeserializer},
-    Reflect, TypeRegistry, TypeRegistryArc,
+use bevy_ecs::entity::{Entity, StableId};
+use bevy_reflect::serde::{
+    versioned_type_path, CompactValueDeserializer, CompactValueSerializer, TypeTable, TypeVersions,
+    TypedReflectSerializer, VersionedReflectDeserializer, VersionedTypeRegistrationDeserializer,
 };
-use bevy_utils::HashSet;
-use serde::ser::SerializeMap;
+use bevy_reflect::{serde::UntypedReflectDeserializer, Reflect, TypeRegistry, TypeRegistryArc};
+use bevy_utils::{tracing::warn, HashSet};
+use serde::ser::{SerializeMap, SerializeSeq, SerializeTuple};
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::fmt::Formatter;
+use std::{collections::BTreeMap, fmt::Formatter};
 
 /// Name of the serialized scene struct type.
 pub const SCENE_STRUCT: &str = "Scene";
@@ -22,6 +22,16 @@ pub const SCENE_STRUCT: &str = "Scene";
 pub const SCENE_RESOURCES: &str = "resources";
 /// Name of the serialized entities field in a scene struct.
 pub const SCENE_ENTITIES: &str = "entities";
+/// Name of the serialized stable ids field in a scene struct.
+///
+/// This field is optional, and only used by human-readable formats.
+pub const SCENE_STABLE_IDS: &str = "stable_ids";
+/// Name of the serialized versions field in a scene struct, holding the [`TypeVersions`] of the
+/// values nested in the components and resources of the scene.
+///
+/// This field is optional, and only used by human-readable formats. It must come before the
+/// resources and entities.
+pub const SCENE_VERSIONS: &str = "versions";
 
 /// Name of the serialized entity struct type.
 pub const ENTITY_STRUCT: &str = "Entity";
@@ -30,6 +40,18 @@ pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
 /// Handles serialization of a scene as a struct containing its entities and resources.
 ///
+/// The [stable ids](DynamicScene::stable_ids) of the scene are only serialized in human-readable
+/// formats, like RON, and left out if there are none. Binary formats read fields in order and can't
+/// tell whether an optional field is present, so they keep the layout scenes had before stable ids
+/// were added, and serializing a scene with stable ids to them is an error:
+/// [`CompactSceneSerializer`] should be used to keep stable ids in binary scenes.
+///
+/// Components and resources are serialized with the version of their type. The versions of the
+/// types of the values nested in them are serialized as a map of [`TypeVersions`], before the
+/// resources and entities, so that these values are migrated too when deserialized after their
+/// type changed. Like stable ids, versions are left out if all types are at version 0, and can't
+/// be serialized to binary formats.
+///
 /// # Examples
 ///
 /// ```
@@ -90,7 +112,37 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        let stable_ids = !self.scene.stable_ids.is_empty();
+        if stable_ids && !serializer.is_human_readable() {
+            return Err(serde::ser::Error::custom(
+                "the stable ids of a scene can't be serialized to a binary format by `SceneSerializer`, use `CompactSceneSerializer` instead",
+            ));
+        }
+        let versions = {
+            let registry = self.registry.read();
+            let mut versions = TypeVersions::new();
+            let components = self
+                .scene
+                .entities
+                .iter()
+                .flat_map(|entity| &entity.components);
+            for value in self.scene.resources.iter().chain(components) {
+                versions.add_nested(&**value, &registry);
+            }
+            versions
+        };
+        if !versions.is_empty() && !serializer.is_human_readable() {
+            return Err(serde::ser::Error::custom(
+                "the versions of the nested values of a scene can't be serialized to a binary format by `SceneSerializer`, use `CompactSceneSerializer` instead",
+            ));
+        }
+        let len = 2 + stable_ids as usize + !versions.is_empty() as usize;
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, len)?;
+        if versions.is_empty() {
+            state.skip_field(SCENE_VERSIONS)?;
+        } else {
+            state.serialize_field(SCENE_VERSIONS, &versions)?;
+        }
         state.serialize_field(
             SCENE_RESOURCES,
             &SceneMapSerializer {
@@ -105,6 +157,11 @@ impl<'a> Serialize for SceneSerializer<'a> {
                 registry: self.registry,
             },
         )?;
+        if stable_ids {
+            state.serialize_field(SCENE_STABLE_IDS, &self.scene.stable_ids)?;
+        } else {
+            state.skip_field(SCENE_STABLE_IDS)?;
+        }
         state.end()
     }
 }
@@ -178,11 +235,12 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
     where
         S: Serializer,
     {
+        let registry = self.registry.read();
         let mut state = serializer.serialize_map(Some(self.entries.len()))?;
         for reflect in self.entries {
             state.serialize_entry(
-                reflect.get_represented_type_info().unwrap().type_path(),
-                &TypedReflectSerializer::new(&**reflect, &self.registry.read()),
+                &versioned_type_path(reflect.get_represented_type_info().unwrap(), &registry),
+                &TypedReflectSerializer::new(&**reflect, &registry),
             )?;
         }
         state.end()
@@ -194,6 +252,9 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
 enum SceneField {
     Resources,
     Entities,
+    #[serde(rename = "stable_ids")]
+    StableIds,
+    Versions,
 }
 
 #[derive(Deserialize)]
@@ -215,9 +276,21 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: Deserializer<'de>,
     {
+        // Binary formats read the fields in order, and don't have the optional stable ids and
+        // versions.
+        let fields: &'static [&'static str] = if deserializer.is_human_readable() {
+            &[
+                SCENE_VERSIONS,
+                SCENE_RESOURCES,
+                SCENE_ENTITIES,
+                SCENE_STABLE_IDS,
+            ]
+        } else {
+            &[SCENE_RESOURCES, SCENE_ENTITIES]
+        };
         deserializer.deserialize_struct(
             SCENE_STRUCT,
-            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            fields,
             SceneVisitor {
                 type_registry: self.type_registry,
             },
@@ -240,21 +313,29 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
+        // Without versions, the nested values are of version 0.
+        let versions = TypeVersions::new();
         let resources = seq
             .next_element_seed(SceneMapDeserializer {
                 registry: self.type_registry,
+                versions: &versions,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
 
         let entities = seq
             .next_element_seed(SceneEntitiesDeserializer {
                 type_registry: self.type_registry,
+                versions: &versions,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
 
+        // Stable ids are optional, and never present in binary formats.
+        let stable_ids = seq.next_element()?.unwrap_or_default();
+
         Ok(DynamicScene {
             resources,
             entities,
+            stable_ids,
         })
     }
 
@@ -264,6 +345,10 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     {
         let mut resources = None;
         let mut entities = None;
+        let mut stable_ids: Option<BTreeMap<Entity, StableId>> = None;
+        // Scenes without versions, like those saved before versions were introduced, have all
+        // their nested values at version 0.
+        let mut versions: Option<TypeVersions> = None;
         while let Some(key) = map.next_key()? {
             match key {
                 SceneField::Resources => {
@@ -272,6 +357,7 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                     }
                     resources = Some(map.next_value_seed(SceneMapDeserializer {
                         registry: self.type_registry,
+                        versions: versions.get_or_insert_with(TypeVersions::new),
                     })?);
                 }
                 SceneField::Entities => {
@@ -280,17 +366,38 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                     }
                     entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
                         type_registry: self.type_registry,
+                        versions: versions.get_or_insert_with(TypeVersions::new),
                     })?);
                 }
+                SceneField::Versions => {
+                    if resources.is_some() || entities.is_some() {
+                        return Err(Error::custom(format_args!(
+                            "`{SCENE_VERSIONS}` must come before `{SCENE_RESOURCES}` and `{SCENE_ENTITIES}`"
+                        )));
+                    }
+                    if versions.is_some() {
+                        return Err(Error::duplicate_field(SCENE_VERSIONS));
+                    }
+                    versions = Some(map.next_value()?);
+                }
+                SceneField::StableIds => {
+                    if stable_ids.is_some() {
+                        return Err(Error::duplicate_field(SCENE_STABLE_IDS));
+                    }
+                    stable_ids = Some(map.next_value()?);
+                }
             }
         }
 
         let resources = resources.ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
         let entities = entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+        // Stable ids are optional, and left out of scenes saved before they were introduced.
+        let stable_ids = stable_ids.unwrap_or_default();
 
         Ok(DynamicScene {
             resources,
             entities,
+            stable_ids,
         })
     }
 }
@@ -299,6 +406,8 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
 pub struct SceneEntitiesDeserializer<'a> {
     /// Type registry in which the component types used by the entities to deserialize are registered.
     pub type_registry: &'a TypeRegistry,
+    /// Versions of the types of the values nested in the components.
+    pub versions: &'a TypeVersions,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
@@ -310,12 +419,14 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
     {
         deserializer.deserialize_map(SceneEntitiesVisitor {
             type_registry: self.type_registry,
+            versions: self.versions,
         })
     }
 }
 
 struct SceneEntitiesVisitor<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub versions: &'a TypeVersions,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
@@ -334,6 +445,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
             let entity = map.next_value_seed(SceneEntityDeserializer {
                 entity,
                 type_registry: self.type_registry,
+                versions: self.versions,
             })?;
             entities.push(entity);
         }
@@ -348,6 +460,8 @@ pub struct SceneEntityDeserializer<'a> {
     pub entity: Entity,
     /// Type registry in which the component types used by the entity to deserialize are registered.
     pub type_registry: &'a TypeRegistry,
+    /// Versions of the types of the values nested in the components.
+    pub versions: &'a TypeVersions,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
@@ -363,6 +477,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
             SceneEntityVisitor {
                 entity: self.entity,
                 registry: self.type_registry,
+                versions: self.versions,
             },
         )
     }
@@ -371,6 +486,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
 struct SceneEntityVisitor<'a> {
     pub entity: Entity,
     pub registry: &'a TypeRegistry,
+    pub versions: &'a TypeVersions,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
@@ -387,6 +503,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         let components = seq
             .next_element_seed(SceneMapDeserializer {
                 registry: self.registry,
+                versions: self.versions,
             })?
             .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
 
@@ -410,6 +527,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 
                     components = Some(map.next_value_seed(SceneMapDeserializer {
                         registry: self.registry,
+                        versions: self.versions,
                     })?);
                 }
             }
@@ -429,6 +547,8 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 pub struct SceneMapDeserializer<'a> {
     /// Type registry in which the types of the values to deserialize are registered.
     pub registry: &'a TypeRegistry,
+    /// Versions of the types of the values nested in the values to deserialize.
+    pub versions: &'a TypeVersions,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneMapDeserializer<'a> {
@@ -440,12 +560,14 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneMapDeserializer<'a> {
     {
         deserializer.deserialize_map(SceneMapVisitor {
             registry: self.registry,
+            versions: self.versions,
         })
     }
 }
 
 struct SceneMapVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    pub versions: &'a TypeVersions,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
@@ -475,8 +597,8 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     {
         let mut added = HashSet::new();
         let mut entries = Vec::new();
-        while let Some(registration) =
-            map.next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        while let Some((registration, version)) =
+            map.next_key_seed(VersionedTypeRegistrationDeserializer::new(self.registry))?
         {
             if !added.insert(registration.type_id()) {
                 return Err(Error::custom(format_args!(
@@ -486,7 +608,10 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
             }
 
             entries.push(
-                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?,
+                map.next_value_seed(
+                    VersionedReflectDeserializer::new(registration, version, self.registry)
+                        .with_versions(self.versions),
+                )?,
             );
         }
 
@@ -494,17 +619,358 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     }
 }
 
+/// Handles serialization of a scene in the compact format of [`TypeTable`], meant for binary
+/// formats like `bincode`.
+///
+/// The scene is serialized as a tuple of:
+/// 1. The [`TypeTable`] of the types of its resources and components
+/// 2. Its resources, as a sequence of type ids and values
+/// 3. Its entities, as a sequence of entity ids and their components, serialized like resources
+/// 4. Its stable ids
+///
+/// Type paths and field names are only written once, in the table, which makes scenes much
+/// smaller than with a [`SceneSerializer`].
+/// The table also records the fields and versions of the types of the scene, so that
+/// [`CompactSceneDeserializer`] can load the scene after fields were added or removed, and
+/// migrate the values of older versions.
+pub struct CompactSceneSerializer<'a> {
+    /// The scene to serialize.
+    pub scene: &'a DynamicScene,
+    /// Type registry in which the components and resources types used in the scene are registered.
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> CompactSceneSerializer<'a> {
+    /// Creates a compact scene serializer.
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistryArc) -> Self {
+        CompactSceneSerializer { scene, registry }
+    }
+}
+
+impl<'a> Serialize for CompactSceneSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry.read();
+        let mut table = TypeTable::new();
+        let components = self
+            .scene
+            .entities
+            .iter()
+            .flat_map(|entity| &entity.components);
+        for value in self.scene.resources.iter().chain(components) {
+            table
+                .register_value(&**value, &registry)
+                .map_err(serde::ser::Error::custom)?;
+        }
+
+        let mut state = serializer.serialize_tuple(4)?;
+        state.serialize_element(&table)?;
+        state.serialize_element(&CompactEntriesSerializer {
+            entries: &self.scene.resources,
+            table: &table,
+            registry: &registry,
+        })?;
+        state.serialize_element(&CompactEntitiesSerializer {
+            entities: &self.scene.entities,
+            table: &table,
+            registry: &registry,
+        })?;
+        state.serialize_element(&self.scene.stable_ids)?;
+        state.end()
+    }
+}
+
+struct CompactEntitiesSerializer<'a> {
+    entities: &'a [DynamicEntity],
+    table: &'a TypeTable,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for CompactEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_element(&(
+                entity.entity,
+                CompactEntriesSerializer {
+                    entries: &entity.components,
+                    table: self.table,
+                    registry: self.registry,
+                },
+            ))?;
+        }
+        state.end()
+    }
+}
+
+struct CompactEntriesSerializer<'a> {
+    entries: &'a [Box<dyn Reflect>],
+    table: &'a TypeTable,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for CompactEntriesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entries.len()))?;
+        for value in self.entries {
+            let type_path = value.get_represented_type_info().unwrap().type_path();
+            let id = self.table.id(type_path).ok_or_else(|| {
+                serde::ser::Error::custom(format_args!("type `{type_path}` is not in the table"))
+            })?;
+            state.serialize_element(&(
+                id,
+                CompactValueSerializer::new(&**value, id, self.table, self.registry),
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Handles deserialization of scenes serialized with a [`CompactSceneSerializer`].
+///
+/// The fields added to or removed from the types of the scene since it was serialized are
+/// logged as warnings. Removed fields are dropped, and added fields are left out of the
+/// deserialized components and resources.
+pub struct CompactSceneDeserializer<'a> {
+    /// Type registry in which the components and resources types used in the scene to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactSceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(4, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for CompactSceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("compact scene")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let table: TypeTable = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        for change in table.changes(self.type_registry) {
+            warn!("{change} since the scene was saved");
+        }
+
+        let entries = CompactEntriesDeserializer {
+            table: &table,
+            registry: self.type_registry,
+        };
+        let resources = seq
+            .next_element_seed(entries)?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        let entities = seq
+            .next_element_seed(CompactEntitiesDeserializer { entries })?
+            .ok_or_else(|| Error::invalid_length(2, &self))?;
+        let stable_ids = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(3, &self))?;
+
+        Ok(DynamicScene {
+            resources,
+            entities,
+            stable_ids,
+        })
+    }
+}
+
+struct CompactEntitiesDeserializer<'a> {
+    entries: CompactEntriesDeserializer<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for CompactEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = seq.next_element_seed(CompactEntityDeserializer {
+            entries: self.entries,
+        })? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+struct CompactEntityDeserializer<'a> {
+    entries: CompactEntriesDeserializer<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for CompactEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("entity and its components")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity: Entity = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let components = seq
+            .next_element_seed(self.entries)?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        Ok(DynamicEntity { entity, components })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct CompactEntriesDeserializer<'a> {
+    table: &'a TypeTable,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactEntriesDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for CompactEntriesDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of reflect types")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut added = HashSet::new();
+        let mut entries = Vec::new();
+        while let Some(entry) = seq.next_element_seed(CompactEntryDeserializer(self))? {
+            let type_path = entry.get_represented_type_info().unwrap().type_path();
+            if !added.insert(type_path) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{type_path}`"
+                )));
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+struct CompactEntryDeserializer<'a>(CompactEntriesDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for CompactEntryDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for CompactEntryDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("type id and value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let CompactEntriesDeserializer { table, registry } = self.0;
+        let id: u32 = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let schema = table
+            .get(id)
+            .ok_or_else(|| Error::custom(format_args!("unknown type id {id}")))?;
+        if registry.get_with_type_path(&schema.type_path).is_none() {
+            return Err(Error::custom(format_args!(
+                "No registration found for `{}`",
+                schema.type_path
+            )));
+        }
+        let value = seq
+            .next_element_seed(CompactValueDeserializer::new(id, table, registry))?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        if value.get_represented_type_info().is_none() {
+            return Err(Error::custom(format_args!(
+                "`{}` is no longer of the kind it was serialized as",
+                schema.type_path
+            )));
+        }
+        Ok(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ron;
-    use crate::serde::{SceneDeserializer, SceneSerializer};
+    use crate::serde::{CompactSceneDeserializer, SceneDeserializer, SceneSerializer};
     use crate::{DynamicScene, DynamicSceneBuilder};
-    use bevy_ecs::entity::{Entity, EntityMapper, MapEntities};
+    use bevy_ecs::entity::{Entity, EntityMapper, MapEntities, StableId, StableIds};
     use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
     use bevy_ecs::query::{With, Without};
     use bevy_ecs::reflect::{AppTypeRegistry, ReflectMapEntities};
     use bevy_ecs::world::FromWorld;
-    use bevy_reflect::{Reflect, ReflectSerialize};
+    use bevy_reflect::{DynamicStruct, FromReflect, Reflect, ReflectSerialize, TypePath};
     use bevy_utils::EntityHashMap;
     use bincode::Options;
     use serde::de::DeserializeSeed;
@@ -577,6 +1043,7 @@ mod tests {
             registry.register::<(f32, f32)>();
             registry.register::<MyEntityRef>();
             registry.register::<Entity>();
+            registry.register::<StableId>();
             registry.register::<MyResource>();
         }
         world.insert_resource(registry);
@@ -744,6 +1211,51 @@ mod tests {
             .all(|r| world.get_entity(r.0).is_none()));
     }
 
+    #[test]
+    fn should_roundtrip_stable_ids() {
+        let mut world = create_world();
+
+        let target_id = StableId::new();
+        let target = world.spawn((target_id, Foo(123))).id();
+        let referrer_id = StableId::new();
+        let referrer = world.spawn((referrer_id, MyEntityRef(target))).id();
+
+        // only the referrer is part of the scene, but the stable id of its target is kept
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entity(referrer)
+            .build();
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let serialized = scene.serialize_ron(&registry.0).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.0.read(),
+        };
+        let deserialized_scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        assert_scene_eq(&scene, &deserialized_scene);
+        assert_eq!(
+            deserialized_scene
+                .stable_ids
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![(target, target_id), (referrer, referrer_id)]
+        );
+
+        let mut dst_world = create_world();
+        dst_world.init_resource::<StableIds>();
+        let dst_target = dst_world.spawn(target_id).id();
+        scene
+            .write_to_world(&mut dst_world, &mut EntityHashMap::default())
+            .unwrap();
+
+        let dst_referrer = dst_world.resource::<StableIds>().get(referrer_id).unwrap();
+        assert_eq!(
+            dst_world.get::<MyEntityRef>(dst_referrer).unwrap().0,
+            dst_target
+        );
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
@@ -782,6 +1294,44 @@ mod tests {
         assert_scene_eq(&scene, &deserialized_scene);
     }
 
+    #[test]
+    fn should_deserialize_binary_scenes_saved_before_stable_ids() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>();
+
+        // A postcard scene with a single `MyComponent`, saved before stable ids were added.
+        let serialized_scene = [
+            0, 1, 128, 128, 128, 128, 16, 1, 37, 98, 101, 118, 121, 95, 115, 99, 101, 110, 101, 58,
+            58, 115, 101, 114, 100, 101, 58, 58, 116, 101, 115, 116, 115, 58, 58, 77, 121, 67, 111,
+            109, 112, 111, 110, 101, 110, 116, 1, 2, 3, 102, 102, 166, 63, 205, 204, 108, 64, 1,
+            12, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33,
+        ];
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.0.read(),
+        };
+        let deserialized_scene = scene_deserializer
+            .deserialize(&mut postcard::Deserializer::from_bytes(&serialized_scene))
+            .unwrap();
+
+        assert_eq!(1, deserialized_scene.entities.len());
+        assert_eq!(1, deserialized_scene.entities[0].components.len());
+        assert!(deserialized_scene.stable_ids.is_empty());
+    }
+
+    #[test]
+    fn should_not_serialize_stable_ids_to_binary_formats() {
+        let mut world = create_world();
+        let entity = world.spawn((StableId::new(), Foo(123))).id();
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entity(entity)
+            .build();
+        let scene_serializer = SceneSerializer::new(&scene, &registry.0);
+
+        assert!(postcard::to_allocvec(&scene_serializer).is_err());
+    }
+
     #[test]
     fn should_roundtrip_messagepack() {
         let mut world = create_world();
@@ -867,6 +1417,287 @@ mod tests {
         assert_scene_eq(&scene, &deserialized_scene);
     }
 
+    #[test]
+    fn should_roundtrip_compact() {
+        let mut world = create_world();
+
+        // Compact scenes are smaller once types repeat, as the type table is only written once.
+        for i in 0..10 {
+            let foo = world.spawn((Foo(i), Bar(345))).id();
+            world.spawn((
+                MyComponent {
+                    foo: [1, 2, 3],
+                    bar: (1.3, 3.7),
+                    baz: MyEnum::Struct { value: i as u32 },
+                },
+                MyEntityRef(foo),
+            ));
+        }
+        world.insert_resource(MyResource { foo: 7 });
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicScene::from_world(&world);
+
+        let compact = scene.serialize_binary(&registry.0).unwrap();
+        let verbose = bincode::DefaultOptions::new()
+            .serialize(&SceneSerializer::new(&scene, &registry.0))
+            .unwrap();
+        assert!(compact.len() < verbose.len());
+
+        let scene_deserializer = CompactSceneDeserializer {
+            type_registry: &registry.0.read(),
+        };
+        let deserialized_scene = bincode::DefaultOptions::new()
+            .deserialize_seed(scene_deserializer, &compact)
+            .unwrap();
+
+        assert_eq!(1, deserialized_scene.resources.len());
+        assert_eq!(
+            Some(true),
+            scene.resources[0].reflect_partial_eq(&*deserialized_scene.resources[0])
+        );
+        assert_eq!(scene.stable_ids, deserialized_scene.stable_ids);
+        assert_scene_eq(&scene, &deserialized_scene);
+
+        let mut dst_world = create_world();
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut EntityHashMap::default())
+            .unwrap();
+        assert_eq!(7, dst_world.resource::<MyResource>().foo);
+        assert_eq!(
+            10,
+            dst_world.query::<&MyComponent>().iter(&dst_world).count()
+        );
+    }
+
+    #[test]
+    fn should_load_compact_after_fields_changed() {
+        mod v1 {
+            use bevy_ecs::prelude::{Component, ReflectComponent};
+            use bevy_reflect::Reflect;
+
+            #[derive(Component, Reflect, Default)]
+            #[reflect(Component)]
+            pub struct Health {
+                pub current: f32,
+                pub regeneration: f32,
+            }
+        }
+
+        mod v2 {
+            use bevy_ecs::prelude::{Component, ReflectComponent};
+            use bevy_reflect::Reflect;
+
+            #[derive(Component, Reflect, Default)]
+            #[reflect(Component)]
+            pub struct Health {
+                pub current: f32,
+                pub max: f32,
+            }
+        }
+
+        let mut world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<v1::Health>();
+        world.spawn(v1::Health {
+            current: 50.0,
+            regeneration: 1.0,
+        });
+        let scene = DynamicScene::from_world(&world);
+        let bytes = scene
+            .serialize_binary(&world.resource::<AppTypeRegistry>().0)
+            .unwrap();
+
+        // The type paths have the same length, keeping the rest of the file valid.
+        let (from, to) = (b"::v1::Health", b"::v2::Health");
+        let position = bytes
+            .windows(from.len())
+            .position(|window| window == from)
+            .unwrap();
+        let mut bytes = bytes;
+        bytes[position..position + to.len()].copy_from_slice(to);
+
+        let mut dst_world = create_world();
+        let registry = dst_world.resource::<AppTypeRegistry>().clone();
+        registry.write().register::<v2::Health>();
+        let scene_deserializer = CompactSceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let deserialized_scene = bincode::DefaultOptions::new()
+            .deserialize_seed(scene_deserializer, &bytes)
+            .unwrap();
+
+        let entity = dst_world
+            .spawn(v2::Health {
+                current: 0.0,
+                max: 100.0,
+            })
+            .id();
+        let mut entity_map = EntityHashMap::default();
+        entity_map.insert(deserialized_scene.entities[0].entity, entity);
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut entity_map)
+            .unwrap();
+
+        let health = dst_world.get::<v2::Health>(entity).unwrap();
+        assert_eq!((50.0, 100.0), (health.current, health.max));
+    }
+
+    #[test]
+    fn should_migrate_components_of_old_versions() {
+        mod v0 {
+            use bevy_reflect::Reflect;
+
+            #[derive(Reflect)]
+            pub struct Health {
+                pub hp: u32,
+            }
+        }
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        struct Health {
+            current: f32,
+        }
+
+        let mut world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register_migration::<Health, v0::Health, _>(|old| {
+                let old = v0::Health::take_from_reflect(old).map_err(|_| "expected health")?;
+                let mut health = DynamicStruct::default();
+                health.insert("current", old.hp as f32);
+                Ok(Box::new(health))
+            });
+        }
+
+        // Saved before the migration was registered, so without version.
+        let input = format!(
+            r#"(
+  resources: {{}},
+  entities: {{
+    4294967296: (
+      components: {{
+        "{}": (hp: 80),
+      }},
+    ),
+  }},
+)"#,
+            Health::type_path()
+        );
+        let mut deserializer = ron::de::Deserializer::from_str(&input).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let entity = entity_map[&Entity::from_raw(0)];
+        assert_eq!(80.0, world.get::<Health>(entity).unwrap().current);
+
+        let output = DynamicScene::from_world(&world)
+            .serialize_ron(&registry.0)
+            .unwrap();
+        assert!(output.contains(&format!(r#""{}@1": ("#, Health::type_path())));
+
+        let newer = output.replace("@1", "@2");
+        let mut deserializer = ron::de::Deserializer::from_str(&newer).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let Err(error) = scene_deserializer.deserialize(&mut deserializer) else {
+            panic!("expected a newer version to fail to deserialize");
+        };
+        assert!(error
+            .to_string()
+            .contains("has no version 2, its current version is 1"));
+    }
+
+    #[test]
+    fn should_migrate_nested_values_of_old_versions() {
+        mod v0 {
+            use bevy_reflect::Reflect;
+
+            #[derive(Reflect)]
+            pub struct Health {
+                pub hp: u32,
+            }
+        }
+
+        #[derive(Reflect, Default)]
+        struct Health {
+            current: f32,
+        }
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        struct Stats {
+            health: Health,
+        }
+
+        let mut world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        {
+            let mut registry = registry.write();
+            registry.register::<Stats>();
+            registry.register::<Health>();
+            registry.register_migration::<Health, v0::Health, _>(|old| {
+                let old = v0::Health::take_from_reflect(old).map_err(|_| "expected health")?;
+                let mut health = DynamicStruct::default();
+                health.insert("current", old.hp as f32);
+                Ok(Box::new(health))
+            });
+        }
+
+        // Saved before the migration was registered, so without versions.
+        let input = format!(
+            r#"(
+  resources: {{}},
+  entities: {{
+    4294967296: (
+      components: {{
+        "{}": (health: (hp: 80)),
+      }},
+    ),
+  }},
+)"#,
+            Stats::type_path()
+        );
+        let mut deserializer = ron::de::Deserializer::from_str(&input).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let entity = entity_map[&Entity::from_raw(0)];
+        assert_eq!(80.0, world.get::<Stats>(entity).unwrap().health.current);
+
+        let scene = DynamicScene::from_world(&world);
+        let output = scene.serialize_ron(&registry.0).unwrap();
+        assert!(output.starts_with("(\n  versions: {"));
+        assert!(output.contains(&format!(r#""{}": 1,"#, Health::type_path())));
+        assert!(postcard::to_allocvec(&SceneSerializer::new(&scene, &registry.0)).is_err());
+
+        let mut deserializer = ron::de::Deserializer::from_str(&output).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+        let mut dst_world = create_world();
+        scene
+            .write_to_world_with(&mut dst_world, &mut EntityHashMap::default(), &registry)
+            .unwrap();
+        let mut query = dst_world.query::<&Stats>();
+        assert_eq!(80.0, query.single(&dst_world).health.current);
+    }
+
     /// A crude equality checker for [`DynamicScene`], used solely for testing purposes.
     fn assert_scene_eq(expected: &DynamicScene, received: &DynamicScene) {
         assert_eq!(
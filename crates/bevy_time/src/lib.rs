@@ -6,6 +6,7 @@ pub mod common_conditions;
 mod fixed;
 mod real;
 mod stopwatch;
+mod test_app;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
@@ -14,6 +15,7 @@ mod virt;
 pub use fixed::*;
 pub use real::*;
 pub use stopwatch::*;
+pub use test_app::*;
 pub use time::*;
 pub use timer::*;
 pub use virt::*;
@@ -142,3 +144,58 @@ fn time_system(
         TimeUpdateStrategy::ManualDuration(duration) => time.update_with_duration(*duration),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fixed, TestAppTimeExt, Time, TimePlugin, TimeUpdateStrategy, Virtual};
+    use bevy_app::{FixedUpdate, TestApp};
+    use bevy_ecs::system::{ResMut, Resource};
+    use bevy_utils::Duration;
+
+    #[derive(Resource, Default)]
+    struct FixedUpdateCount(u32);
+
+    fn count_fixed_updates(mut count: ResMut<FixedUpdateCount>) {
+        count.0 += 1;
+    }
+
+    #[test]
+    fn test_app_frame_time_is_deterministic() {
+        let mut app = TestApp::new();
+        app.with_frame_time(Duration::from_millis(100))
+            .add_plugins(TimePlugin)
+            .init_resource::<FixedUpdateCount>()
+            .add_systems(FixedUpdate, count_fixed_updates);
+
+        // the first update only starts the clock
+        app.run_frames(11);
+
+        assert_eq!(
+            app.world.resource::<Time<Virtual>>().elapsed(),
+            Duration::from_secs(1)
+        );
+        let timestep = app.world.resource::<Time<Fixed>>().timestep();
+        assert_eq!(
+            app.world.resource::<FixedUpdateCount>().0,
+            (Duration::from_secs(1).as_secs_f64() / timestep.as_secs_f64()) as u32
+        );
+    }
+
+    #[test]
+    fn test_app_frame_time_after_time_plugin() {
+        let mut app = TestApp::new();
+        app.add_plugins(TimePlugin);
+        app.with_frame_time(Duration::from_millis(100));
+
+        app.run_frames(3);
+
+        assert!(matches!(
+            app.world.resource::<TimeUpdateStrategy>(),
+            TimeUpdateStrategy::ManualDuration(_)
+        ));
+        assert_eq!(
+            app.world.resource::<Time<Virtual>>().elapsed(),
+            Duration::from_millis(200)
+        );
+    }
+}
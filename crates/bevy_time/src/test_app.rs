@@ -0,0 +1,22 @@
+use bevy_app::TestApp;
+use bevy_utils::Duration;
+
+use crate::TimeUpdateStrategy;
+
+/// Adds deterministic time to [`TestApp`].
+pub trait TestAppTimeExt {
+    /// Makes time advance by exactly `frame_time` on every frame, instead of using the system
+    /// clock, by inserting [`TimeUpdateStrategy::ManualDuration`].
+    ///
+    /// This makes anything that depends on time, such as timers or the number of times
+    /// `FixedUpdate` runs, deterministic. It can be called before or after adding
+    /// [`TimePlugin`](crate::TimePlugin), which keeps an existing [`TimeUpdateStrategy`].
+    fn with_frame_time(&mut self, frame_time: Duration) -> &mut Self;
+}
+
+impl TestAppTimeExt for TestApp {
+    fn with_frame_time(&mut self, frame_time: Duration) -> &mut Self {
+        self.insert_resource(TimeUpdateStrategy::ManualDuration(frame_time));
+        self
+    }
+}